@@ -7,5 +7,8 @@ use crate::single_app;
 
 installed_apps! {
     ("/subscriptions", subscription_service, "../subscription_service/migrations"),
-    ("/auth", auth_service, "../auth_service/migrations")
+    ("/auth", auth_service, "../auth_service/migrations"),
+    ("/media", media_service, "../media_service/migrations"),
+    ("/content", content_service, "../content_service/migrations"),
+    ("/admin", auth_service => create_admin_router)
 }
@@ -0,0 +1,18 @@
+use std::time::Duration;
+use utils::state::AppState;
+
+// Permanently removes deleted accounts once their grace period is over.
+pub fn spawn_account_purge(state: AppState) {
+    let period = Duration::from_secs(state.settings.account.purge_interval_minutes * 60);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            match auth_service::helpers::account::purge_deleted_accounts(&state).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Purged {} deleted accounts", count),
+                Err(e) => tracing::error!("Failed to purge deleted accounts: {:?}", e),
+            }
+        }
+    });
+}
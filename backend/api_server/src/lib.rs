@@ -4,6 +4,7 @@ use tokio::net::TcpListener;
 
 mod apps;
 mod handlers;
+pub mod jobs;
 pub mod macros;
 pub mod migrate;
 pub mod routes;
@@ -12,5 +13,10 @@ pub mod telemetry;
 pub async fn run(app: Router, addr: SocketAddr) {
     tracing::info!("Starting server in http://{}", addr);
     let listener = TcpListener::bind(addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .unwrap();
 }
@@ -41,6 +41,12 @@ macro_rules! single_app {
             router: $name::router::create_router,
         });
     };
+    (($url:literal, $name:ident => $router:ident), $pool:expr, $app_lists:expr) => {
+        $app_lists.push(AppConfig {
+            url: $url,
+            router: $name::router::$router,
+        });
+    };
     (($url:literal, $name:ident), $pool:expr, $app_lists:expr) => {
         $app_lists.push(AppConfig {
             url: $url,
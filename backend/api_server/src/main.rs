@@ -1,5 +1,8 @@
 use api_server::routes;
+use auth_service::extractors::audit::{AuditEventType, NewAuditEvent};
+use auth_service::helpers::audit::record_audit_event;
 use once_cell::sync::Lazy;
+use serde_json::json;
 use std::env;
 use utils::configuration::Settings;
 use utils::state::AppState;
@@ -21,6 +24,40 @@ async fn main() {
         return;
     }
 
+    if let Some(position) = args.iter().position(|arg| arg == "make-admin") {
+        let username = args
+            .get(position + 1)
+            .expect("Usage: make-admin <username>");
+        let app_state = AppState::init(configuration).await;
+        let mut transaction = app_state
+            .connection
+            .begin()
+            .await
+            .expect("Failed to connect to Postgres.");
+        let user_id =
+            auth_service::helpers::admin::set_user_admin(&mut transaction, username, true)
+                .await
+                .expect("Failed to update the user");
+        match user_id {
+            None => println!("No user found with username {}", username),
+            Some(user_id) => {
+                // The role change and its audit record are saved together.
+                let event = NewAuditEvent::new(AuditEventType::RoleChanged, &Default::default())
+                    .target(user_id)
+                    .details(json!({"is_admin": true, "source": "cli"}));
+                record_audit_event(&mut transaction, event)
+                    .await
+                    .expect("Failed to record the role change");
+                transaction
+                    .commit()
+                    .await
+                    .expect("Failed to save the role change");
+                println!("{} is now an admin", username);
+            }
+        }
+        return;
+    }
+
     let addr = configuration.application.url().parse().unwrap();
 
     Lazy::force(&TRACING);
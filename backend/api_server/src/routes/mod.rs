@@ -1,13 +1,22 @@
 use crate::apps::applications;
 use crate::handlers;
+use crate::jobs;
+use auth_service::middleware::csrf_protection;
+use axum::middleware;
 use axum::routing::method_routing::get;
 use axum::routing::Router;
 
+use content_service::middleware::seo_head;
+use content_service::user_data::{CommentData, PostData};
+use media_service::storage::storage_from_settings;
+use media_service::user_data::MediaData;
+use subscription_service::user_data::SubscriptionData;
 use tower::ServiceBuilder;
 use tower_http::request_id::MakeRequestUuid;
 use tower_http::trace::{DefaultMakeSpan, DefaultOnResponse, TraceLayer};
 use tower_http::ServiceBuilderExt;
 use utils::configuration::Settings;
+use utils::i18n::localize_errors;
 use utils::state::AppState;
 
 use dioxus::prelude::*;
@@ -19,8 +28,17 @@ pub fn base_routes() -> Router<AppState> {
 pub async fn create_router() -> Router {
     let settings = Settings::new().expect("Failed to read configuration");
     let serve_dir_path = settings.frontend.assets.clone();
-    let app_state = AppState::init(settings).await;
+    let media_storage =
+        storage_from_settings(&settings.media).expect("Invalid media storage configuration");
+    let app_state = AppState::init(settings)
+        .await
+        .with_user_data(SubscriptionData)
+        .with_user_data(CommentData)
+        .with_user_data(PostData)
+        .with_user_data(MediaData::new(media_storage.clone()))
+        .with_media_storage(media_storage);
     let apps = applications(&app_state.connection).await;
+    jobs::spawn_account_purge(app_state.clone());
 
     let svc = ServiceBuilder::new()
         // make sure to set request ids before the request reaches `TraceLayer`
@@ -38,6 +56,15 @@ pub async fn create_router() -> Router {
     for app in apps {
         api_router = app.add_routes(api_router);
     }
+    let api_router = api_router
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            csrf_protection,
+        ))
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            localize_errors,
+        ));
 
     tracing::info!(
         "{}/index.html from {:?}",
@@ -50,7 +77,11 @@ pub async fn create_router() -> Router {
 
     let router = Router::new()
         .nest("/api", api_router.fallback(handlers::not_found))
+        .nest("/media", media_service::router::create_serve_router())
+        .nest("/feeds", content_service::router::create_feed_router())
+        .merge(content_service::router::create_sitemap_router())
         .serve_dioxus_application(serve_config, || VirtualDom::new(frontend::App))
-        .await;
+        .await
+        .layer(middleware::from_fn_with_state(app_state.clone(), seo_head));
     router.with_state(app_state).layer(svc)
 }
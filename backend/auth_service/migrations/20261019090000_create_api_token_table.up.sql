@@ -0,0 +1,19 @@
+create table api_tokens
+(
+    identifier    uuid         not null
+        constraint api_tokens_pk
+            primary key,
+    name          varchar(100) not null,
+    verifier_hash varchar(256) not null unique,
+    user_id       integer      not null
+        constraint api_tokens_users_id_fk
+            references users on delete cascade,
+    scopes        text[]       not null default '{}',
+    created_at    timestamptz  not null default now(),
+    expires_at    timestamptz,
+    last_used_at  timestamptz
+);
+
+create index api_tokens_user_id_index on api_tokens (user_id);
+
+comment on table api_tokens is 'Personal access tokens used by scripts and CI';
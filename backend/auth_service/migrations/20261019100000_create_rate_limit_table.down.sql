@@ -0,0 +1,2 @@
+-- Add down migration script here
+drop table if exists rate_limits;
\ No newline at end of file
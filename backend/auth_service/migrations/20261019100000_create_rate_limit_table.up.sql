@@ -0,0 +1,11 @@
+create table rate_limits
+(
+    key             varchar(320) not null
+        constraint rate_limits_pk
+            primary key,
+    attempts        integer      not null default 0,
+    last_attempt_at timestamptz  not null,
+    locked_until    timestamptz
+);
+
+comment on table rate_limits is 'Attempt counters for login throttling, keyed by ip or username';
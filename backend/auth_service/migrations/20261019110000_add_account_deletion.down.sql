@@ -0,0 +1,5 @@
+-- Add down migration script here
+drop table if exists data_exports;
+
+alter table users
+    drop column if exists deleted_at;
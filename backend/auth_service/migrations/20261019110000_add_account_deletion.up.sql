@@ -0,0 +1,21 @@
+alter table users
+    add deleted_at timestamptz;
+
+create table data_exports
+(
+    identifier uuid        not null
+        constraint data_exports_pk
+            primary key,
+    user_id    integer     not null
+        constraint data_exports_users_id_fk
+            references users on delete cascade,
+    content    jsonb,
+    created_at timestamptz not null default now(),
+    ready_at   timestamptz,
+    expires_at timestamptz not null
+);
+
+create index data_exports_user_id_index on data_exports (user_id);
+
+comment on column users.deleted_at is 'Set when the user deletes the account, purged after the grace period';
+comment on table data_exports is 'Archives of user data prepared for download';
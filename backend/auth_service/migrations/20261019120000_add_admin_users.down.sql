@@ -0,0 +1,5 @@
+-- Add down migration script here
+drop table if exists admin_audit_log;
+
+alter table users
+    drop column if exists is_admin;
@@ -0,0 +1,20 @@
+alter table users
+    add is_admin bool default false not null;
+
+create table admin_audit_log
+(
+    id         serial primary key,
+    actor_id   integer
+        constraint admin_audit_log_actor_fk
+            references users on delete set null,
+    target_id  integer
+        constraint admin_audit_log_target_fk
+            references users on delete set null,
+    action     varchar(50) not null,
+    details    json        not null default '{}'::json,
+    created_at timestamptz not null default now()
+);
+
+create index admin_audit_log_target_id_index on admin_audit_log (target_id);
+
+comment on table admin_audit_log is 'Actions performed by staff on user accounts';
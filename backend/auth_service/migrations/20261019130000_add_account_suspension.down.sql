@@ -0,0 +1,7 @@
+-- Add down migration script here
+alter table users
+    drop column if exists suspended_until;
+alter table users
+    drop column if exists suspension_reason;
+alter table users
+    drop column if exists suspended_at;
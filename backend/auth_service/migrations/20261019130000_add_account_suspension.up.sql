@@ -0,0 +1,8 @@
+alter table users
+    add suspended_at timestamptz;
+alter table users
+    add suspension_reason text;
+alter table users
+    add suspended_until timestamptz;
+
+comment on column users.suspended_until is 'End of the suspension, null means until lifted by an admin';
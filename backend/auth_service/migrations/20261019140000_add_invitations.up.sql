@@ -0,0 +1,20 @@
+create table invitations
+(
+    identifier    uuid primary key,
+    verifier_hash varchar(255) not null,
+    email         varchar(255) not null,
+    role          varchar(20)  not null default 'user',
+    inviter_id    integer
+        constraint invitations_inviter_fk
+            references users on delete set null,
+    created_at    timestamptz  not null default now(),
+    expires_at    timestamptz  not null,
+    accepted_at   timestamptz,
+    accepted_by   integer
+        constraint invitations_accepted_by_fk
+            references users on delete set null
+);
+
+create index invitations_email_index on invitations (lower(email));
+
+comment on table invitations is 'Pending and accepted invitations to register';
@@ -0,0 +1,23 @@
+create table audit_events
+(
+    id         bigserial primary key,
+    event_type varchar(50) not null,
+    actor_id   integer
+        constraint audit_events_actor_fk
+            references users on delete set null,
+    target_id  integer
+        constraint audit_events_target_fk
+            references users on delete set null,
+    ip         varchar(64),
+    user_agent text,
+    -- Plain json keeps the stored text exactly as hashed.
+    details    json        not null default '{}'::json,
+    created_at timestamptz not null,
+    prev_hash  varchar(64),
+    hash       varchar(64) not null
+);
+
+create index audit_events_target_id_index on audit_events (target_id, created_at);
+create index audit_events_event_type_index on audit_events (event_type);
+
+comment on table audit_events is 'Security events, each row hashes the previous one to detect tampering';
@@ -0,0 +1,19 @@
+create table refresh_tokens
+(
+    identifier    uuid primary key,
+    -- Every rotation keeps the family of the first token issued at sign in.
+    family_id     uuid        not null,
+    verifier_hash varchar(64) not null,
+    user_id       integer     not null
+        constraint refresh_tokens_user_fk
+            references users on delete cascade,
+    created_at    timestamptz not null,
+    expires_at    timestamptz not null,
+    used_at       timestamptz,
+    revoked_at    timestamptz
+);
+
+create index refresh_tokens_family_id_index on refresh_tokens (family_id);
+create index refresh_tokens_user_id_index on refresh_tokens (user_id);
+
+comment on table refresh_tokens is 'Single use refresh tokens for api clients, reusing one revokes its family';
@@ -0,0 +1,6 @@
+-- Add down migration script here
+update audit_events set actor_id = null where actor_id not in (select id from users);
+update audit_events set target_id = null where target_id not in (select id from users);
+alter table audit_events
+    add constraint audit_events_actor_fk foreign key (actor_id) references users on delete set null,
+    add constraint audit_events_target_fk foreign key (target_id) references users on delete set null;
@@ -0,0 +1,4 @@
+-- Actor and target ids are part of the hash, nulling them when a user is purged would break the chain.
+alter table audit_events
+    drop constraint audit_events_actor_fk,
+    drop constraint audit_events_target_fk;
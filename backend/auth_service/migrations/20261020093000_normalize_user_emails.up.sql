@@ -0,0 +1,14 @@
+-- Emails are normalized on write, older rows are brought in line here. Rows that would collide with
+-- another account are left as they are for an admin to resolve.
+update users u
+set email = lower(trim(normalize(u.email, NFKC)))
+where u.email <> lower(trim(normalize(u.email, NFKC)))
+  and not exists (select 1
+                  from users o
+                  where o.id <> u.id
+                    and (o.email = lower(trim(normalize(u.email, NFKC)))
+                      or lower(trim(normalize(o.email, NFKC))) = lower(trim(normalize(u.email, NFKC)))));
+
+update invitations
+set email = lower(trim(normalize(email, NFKC)))
+where accepted_at is null;
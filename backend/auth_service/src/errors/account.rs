@@ -0,0 +1,38 @@
+use util_macros::ErrorPayloadMacro;
+use utils::errors::{ErrorPayload, ErrorReport};
+
+#[derive(Debug, thiserror::Error, ErrorPayloadMacro)]
+pub enum AccountError {
+    #[error("Failed to acquire a Postgres connection from the pool")]
+    Pool(#[source] sqlx::Error),
+    #[error("Account database failed")]
+    DatabaseError(#[source] sqlx::Error),
+    #[error("Account is not scheduled for deletion")]
+    NotDeleted,
+    #[error("Data export not found")]
+    ExportNotFound,
+    #[error("Data export is still being prepared")]
+    ExportNotReady,
+    #[error("Failed to collect {0} data")]
+    ExportFailed(String, #[source] sqlx::Error),
+    #[error("Failed to purge {0} data")]
+    PurgeFailed(String, #[source] sqlx::Error),
+}
+
+impl ErrorReport for AccountError {
+    fn message(&self) -> String {
+        self.to_string()
+    }
+
+    fn status(&self) -> u16 {
+        match self {
+            AccountError::Pool(_) => 500,
+            AccountError::DatabaseError(_) => 500,
+            AccountError::NotDeleted => 400,
+            AccountError::ExportNotFound => 404,
+            AccountError::ExportNotReady => 409,
+            AccountError::ExportFailed(_, _) => 500,
+            AccountError::PurgeFailed(_, _) => 500,
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use util_macros::ErrorPayloadMacro;
+use utils::errors::{ErrorPayload, ErrorReport};
+
+#[derive(Debug, thiserror::Error, ErrorPayloadMacro)]
+pub enum AdminError {
+    #[error("Failed to acquire a Postgres connection from the pool")]
+    Pool(#[source] sqlx::Error),
+    #[error("Admin database failed")]
+    DatabaseError(#[source] sqlx::Error),
+    #[error("insufficient permission: admin access required")]
+    AdminRequired,
+    #[error("User not found")]
+    UserNotFound,
+    #[error("Admins cannot perform this action on their own account")]
+    SelfTarget,
+    #[error("Suspension end must be in the future")]
+    SuspensionEndInPast,
+}
+
+impl ErrorReport for AdminError {
+    fn message(&self) -> String {
+        self.to_string()
+    }
+
+    fn status(&self) -> u16 {
+        match self {
+            AdminError::Pool(_) => 500,
+            AdminError::DatabaseError(_) => 500,
+            AdminError::AdminRequired => 403,
+            AdminError::UserNotFound => 404,
+            AdminError::SelfTarget => 400,
+            AdminError::SuspensionEndInPast => 400,
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use util_macros::ErrorPayloadMacro;
+use utils::errors::{ErrorPayload, ErrorReport};
+
+#[derive(Debug, thiserror::Error, ErrorPayloadMacro)]
+pub enum AuditError {
+    #[error("Failed to acquire a Postgres connection from the pool")]
+    Pool(#[source] sqlx::Error),
+    #[error("Audit log database failed")]
+    DatabaseError(#[source] sqlx::Error),
+}
+
+impl ErrorReport for AuditError {
+    fn message(&self) -> String {
+        self.to_string()
+    }
+
+    fn status(&self) -> u16 {
+        500
+    }
+}
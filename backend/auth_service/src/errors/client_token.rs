@@ -0,0 +1,29 @@
+use util_macros::ErrorPayloadMacro;
+use utils::errors::{ErrorPayload, ErrorReport};
+
+#[derive(Debug, thiserror::Error, ErrorPayloadMacro)]
+pub enum ClientTokenError {
+    #[error("Failed to acquire a Postgres connection from the pool")]
+    Pool(#[source] sqlx::Error),
+    #[error("Refresh token database failed")]
+    DatabaseError(#[source] sqlx::Error),
+    #[error("Refresh token is invalid or expired")]
+    InvalidRefreshToken,
+    #[error("Refresh token was already used, please sign in again")]
+    RefreshTokenReused,
+}
+
+impl ErrorReport for ClientTokenError {
+    fn message(&self) -> String {
+        self.to_string()
+    }
+
+    fn status(&self) -> u16 {
+        match self {
+            ClientTokenError::Pool(_) => 500,
+            ClientTokenError::DatabaseError(_) => 500,
+            ClientTokenError::InvalidRefreshToken => 401,
+            ClientTokenError::RefreshTokenReused => 401,
+        }
+    }
+}
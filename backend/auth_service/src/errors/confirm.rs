@@ -18,6 +18,8 @@ pub enum ConfirmUserError {
     FetchUserFailed(#[source] FetchUserError),
     #[error("User already verified")]
     UserAlreadyVerified,
+    #[error("Email already registered")]
+    EmailNotAvailable,
 }
 
 impl ErrorReport for ConfirmUserError {
@@ -0,0 +1,20 @@
+use util_macros::ErrorPayloadMacro;
+use utils::errors::{ErrorPayload, ErrorReport};
+
+#[derive(Debug, thiserror::Error, ErrorPayloadMacro)]
+pub enum CsrfError {
+    #[error("Request origin is missing")]
+    MissingOrigin,
+    #[error("Request origin is not allowed: {0}")]
+    UntrustedOrigin(String),
+}
+
+impl ErrorReport for CsrfError {
+    fn message(&self) -> String {
+        self.to_string()
+    }
+
+    fn status(&self) -> u16 {
+        403
+    }
+}
@@ -0,0 +1,35 @@
+use util_macros::ErrorPayloadMacro;
+use utils::errors::{ErrorPayload, ErrorReport};
+
+#[derive(Debug, thiserror::Error, ErrorPayloadMacro)]
+pub enum InvitationError {
+    #[error("Failed to acquire a Postgres connection from the pool")]
+    Pool(#[source] sqlx::Error),
+    #[error("Invitation database failed")]
+    DatabaseError(#[source] sqlx::Error),
+    #[error("Registration is closed")]
+    RegistrationClosed,
+    #[error("Registration requires an invitation")]
+    InvitationRequired,
+    #[error("Invitation is invalid or expired")]
+    InvalidInvitation,
+    #[error("Email does not match the invitation")]
+    EmailMismatch,
+    #[error("Invitation not found")]
+    NotFound,
+}
+
+impl ErrorReport for InvitationError {
+    fn message(&self) -> String {
+        self.to_string()
+    }
+
+    fn status(&self) -> u16 {
+        match self {
+            InvitationError::Pool(_) => 500,
+            InvitationError::DatabaseError(_) => 500,
+            InvitationError::NotFound => 404,
+            _ => 400,
+        }
+    }
+}
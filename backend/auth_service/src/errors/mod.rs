@@ -1,3 +1,10 @@
+pub mod account;
+pub mod admin;
+pub mod audit;
 pub mod auth;
+pub mod client_token;
 pub mod confirm;
+pub mod csrf;
+pub mod invitation;
+pub mod token;
 pub mod user;
@@ -0,0 +1,32 @@
+use util_macros::ErrorPayloadMacro;
+use utils::errors::{ErrorPayload, ErrorReport};
+
+#[derive(Debug, thiserror::Error, ErrorPayloadMacro)]
+pub enum ApiTokenError {
+    #[error("Failed to acquire a Postgres connection from the pool")]
+    Pool(#[source] sqlx::Error),
+    #[error("Api token database failed")]
+    DatabaseError(#[source] sqlx::Error),
+    #[error("Api token not found")]
+    NotFound,
+    #[error("Api token does not have the required scope: {0}")]
+    InsufficientScope(String),
+    #[error("Api tokens can only be managed from a login session")]
+    SessionRequired,
+}
+
+impl ErrorReport for ApiTokenError {
+    fn message(&self) -> String {
+        self.to_string()
+    }
+
+    fn status(&self) -> u16 {
+        match self {
+            ApiTokenError::Pool(_) => 500,
+            ApiTokenError::DatabaseError(_) => 500,
+            ApiTokenError::NotFound => 404,
+            ApiTokenError::InsufficientScope(_) => 403,
+            ApiTokenError::SessionRequired => 403,
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::errors::auth::FetchUserError;
+use chrono::{DateTime, Utc};
 use email_clients::errors::EmailError;
 use serde_json::{json, Value};
 use util_macros::ErrorPayloadMacro;
@@ -13,8 +14,15 @@ pub enum UserError {
     NormalizeUserFailed(String),
     #[error("failed to check password strength")]
     PasswordCheckFailed(#[source] ZxcvbnError),
+    #[error("Password must contain {min}-{max} characters")]
+    PasswordLength { min: usize, max: usize },
     #[error("Weak password")]
-    WeakPassword,
+    WeakPassword {
+        warning: Option<String>,
+        suggestions: Vec<String>,
+    },
+    #[error("Password has appeared in a data breach")]
+    BreachedPassword,
     #[error("Failed to send confirmation email: {0}")]
     ConfirmationEmailError(#[source] EmailError),
     #[error("User not verified")]
@@ -25,6 +33,15 @@ pub enum UserError {
     SessionError(#[source] sqlx::Error),
     #[error("user fetch error")]
     UserFetchError(#[source] FetchUserError),
+    #[error("Current password is incorrect")]
+    IncorrectPassword,
+    #[error("Update user failed: {0}")]
+    UpdateUserFailed(#[source] sqlx::Error),
+    #[error("Account suspended: {reason}")]
+    AccountSuspended {
+        reason: String,
+        until: Option<DateTime<Utc>>,
+    },
     #[error("Unexpected error")]
     UnexpectedError,
 }
@@ -37,6 +54,7 @@ impl ErrorReport for UserError {
     fn status(&self) -> u16 {
         match self {
             UserError::UserNotVerified => 403,
+            UserError::AccountSuspended { .. } => 403,
             UserError::ConfirmationEmailError(_) => 500,
             UserError::PasswordCheckFailed(_) => 500,
             UserError::AuthorizationTokenInvalid(_) => 401,
@@ -58,12 +76,51 @@ impl ErrorReport for UserError {
                 "Failed to check password strength",
                 None,
             ),
-            UserError::WeakPassword => {
-                ErrorPayload::form_details("password", "weak_password", "Weak password", None)
+            UserError::PasswordLength { min, max } => json!({
+                "password": [{
+                    "code": "password_length",
+                    "message": self.to_string(),
+                    "params": {"min": min, "max": max}
+                }]
+            }),
+            UserError::WeakPassword {
+                warning,
+                suggestions,
+            } => {
+                let mut message = warning.clone().unwrap_or("Weak password.".to_string());
+                for suggestion in suggestions {
+                    message.push(' ');
+                    message.push_str(suggestion);
+                }
+                json!({
+                    "password": [{
+                        "code": "weak_password",
+                        "message": message,
+                        "params": {"warning": warning, "suggestions": suggestions}
+                    }]
+                })
             }
+            UserError::BreachedPassword => ErrorPayload::form_details(
+                "password",
+                "breached_password",
+                "This password has appeared in a known data breach, please choose another one.",
+                None,
+            ),
             UserError::UserNotVerified => {
                 ErrorPayload::form_details("auth", "user_not_verified", "User not verified", None)
             }
+            UserError::IncorrectPassword => ErrorPayload::form_details(
+                "current_password",
+                "incorrect_password",
+                "Current password is incorrect",
+                None,
+            ),
+            UserError::AccountSuspended { reason, until } => ErrorPayload::form_details(
+                "auth",
+                "account_suspended",
+                reason,
+                until.map(|until| until.to_rfc3339()).as_deref(),
+            ),
             _ => {
                 json!({})
             }
@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AdminAction {
+    Activate,
+    Deactivate,
+    Verify,
+    ForcePasswordReset,
+    RevokeSessions,
+    Impersonate,
+    Suspend,
+    Unsuspend,
+}
+
+impl From<AdminAction> for String {
+    fn from(value: AdminAction) -> Self {
+        match value {
+            AdminAction::Activate => "activate".to_string(),
+            AdminAction::Deactivate => "deactivate".to_string(),
+            AdminAction::Verify => "verify".to_string(),
+            AdminAction::ForcePasswordReset => "force_password_reset".to_string(),
+            AdminAction::RevokeSessions => "revoke_sessions".to_string(),
+            AdminAction::Impersonate => "impersonate".to_string(),
+            AdminAction::Suspend => "suspend".to_string(),
+            AdminAction::Unsuspend => "unsuspend".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct AdminAuditEntry {
+    pub id: i32,
+    pub actor_id: Option<i32>,
+    pub target_id: Option<i32>,
+    pub action: String,
+    pub details: Value,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,124 @@
+use axum::http::Method;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub static API_TOKEN_PREFIX: &str = "pat_";
+
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenScope {
+    Read,
+    Write,
+}
+
+impl From<&TokenScope> for String {
+    fn from(value: &TokenScope) -> Self {
+        match value {
+            TokenScope::Read => "read".to_string(),
+            TokenScope::Write => "write".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[serde(transparent)]
+pub struct TokenScopes(pub Vec<TokenScope>);
+
+impl From<Vec<String>> for TokenScopes {
+    fn from(value: Vec<String>) -> Self {
+        let scopes = value
+            .iter()
+            .filter_map(|scope| match scope.to_lowercase().as_str() {
+                "read" => Some(TokenScope::Read),
+                "write" => Some(TokenScope::Write),
+                _ => None,
+            })
+            .collect();
+        Self(scopes)
+    }
+}
+
+impl TokenScopes {
+    pub fn to_vec(&self) -> Vec<String> {
+        self.0.iter().map(String::from).collect()
+    }
+
+    /// Write scope implies read, read scope only allows safe methods.
+    ///
+    /// ```
+    /// use auth_service::extractors::api_token::{TokenScope, TokenScopes};
+    /// use axum::http::Method;
+    ///
+    /// let read = TokenScopes(vec![TokenScope::Read]);
+    /// assert!(read.allows(&Method::GET));
+    /// assert!(!read.allows(&Method::POST));
+    ///
+    /// let write = TokenScopes(vec![TokenScope::Write]);
+    /// assert!(write.allows(&Method::GET));
+    /// assert!(write.allows(&Method::DELETE));
+    /// ```
+    pub fn allows(&self, method: &Method) -> bool {
+        if self.0.contains(&TokenScope::Write) {
+            return true;
+        }
+        self.0.contains(&TokenScope::Read)
+            && matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+    }
+}
+
+#[derive(Debug, FromRow, Deserialize, Serialize, Clone)]
+pub struct ApiToken {
+    pub identifier: Uuid,
+    pub name: String,
+    #[serde(skip_serializing)]
+    pub verifier_hash: String,
+    #[serde(skip_serializing)]
+    pub user_id: i32,
+    pub scopes: TokenScopes,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl ApiToken {
+    pub fn new(
+        user_id: i32,
+        name: String,
+        scopes: TokenScopes,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> (Self, String) {
+        let identifier = Uuid::new_v4();
+        let verifier = Uuid::new_v4();
+
+        let mut hasher = Sha256::new();
+        hasher.update(verifier.to_string().as_bytes());
+        let verifier_hash = format!("{:x}", hasher.finalize());
+        let token = format!("{}{}.{}", API_TOKEN_PREFIX, identifier, verifier);
+
+        (
+            Self {
+                identifier,
+                name,
+                verifier_hash,
+                user_id,
+                scopes,
+                created_at: Utc::now(),
+                expires_at,
+                last_used_at: None,
+            },
+            token,
+        )
+    }
+
+    pub fn is_api_token(token: &str) -> bool {
+        token.starts_with(API_TOKEN_PREFIX)
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at < Utc::now())
+    }
+}
@@ -0,0 +1,195 @@
+use axum::async_trait;
+use axum::extract::FromRequestParts;
+use axum::http::header::USER_AGENT;
+use axum::http::request::Parts;
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use sqlx::FromRow;
+use std::convert::Infallible;
+use utils::client_ip::ClientIp;
+use utils::state::AppState;
+
+#[derive(Debug, Serialize, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEventType {
+    LoginSucceeded,
+    LoginFailed,
+    Logout,
+    PasswordResetRequested,
+    PasswordResetCompleted,
+    PasswordChanged,
+    EmailVerified,
+    EmailChanged,
+    SessionsRevoked,
+    RoleChanged,
+}
+
+impl From<AuditEventType> for String {
+    fn from(value: AuditEventType) -> Self {
+        match value {
+            AuditEventType::LoginSucceeded => "login_succeeded".to_string(),
+            AuditEventType::LoginFailed => "login_failed".to_string(),
+            AuditEventType::Logout => "logout".to_string(),
+            AuditEventType::PasswordResetRequested => "password_reset_requested".to_string(),
+            AuditEventType::PasswordResetCompleted => "password_reset_completed".to_string(),
+            AuditEventType::PasswordChanged => "password_changed".to_string(),
+            AuditEventType::EmailVerified => "email_verified".to_string(),
+            AuditEventType::EmailChanged => "email_changed".to_string(),
+            AuditEventType::SessionsRevoked => "sessions_revoked".to_string(),
+            AuditEventType::RoleChanged => "role_changed".to_string(),
+        }
+    }
+}
+
+// Where a request came from, recorded alongside each event.
+#[derive(Debug, Clone, Default)]
+pub struct RequestContext {
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for RequestContext {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let ClientIp(ip) = ClientIp::from_request_parts(parts, state).await?;
+        let user_agent = parts
+            .headers
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.chars().take(512).collect());
+        Ok(RequestContext { ip, user_agent })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NewAuditEvent {
+    pub event_type: AuditEventType,
+    pub actor_id: Option<i32>,
+    pub target_id: Option<i32>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub details: Value,
+}
+
+impl NewAuditEvent {
+    pub fn new(event_type: AuditEventType, context: &RequestContext) -> Self {
+        Self {
+            event_type,
+            actor_id: None,
+            target_id: None,
+            ip: context.ip.clone(),
+            user_agent: context.user_agent.clone(),
+            details: json!({}),
+        }
+    }
+
+    // Events on a user's own account.
+    pub fn for_user(event_type: AuditEventType, context: &RequestContext, user_id: i32) -> Self {
+        Self::new(event_type, context)
+            .actor(user_id)
+            .target(user_id)
+    }
+
+    pub fn actor(mut self, actor_id: i32) -> Self {
+        self.actor_id = Some(actor_id);
+        self
+    }
+
+    pub fn target(mut self, target_id: i32) -> Self {
+        self.target_id = Some(target_id);
+        self
+    }
+
+    pub fn details(mut self, details: Value) -> Self {
+        self.details = details;
+        self
+    }
+}
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct AuditEvent {
+    pub id: i64,
+    pub event_type: String,
+    pub actor_id: Option<i32>,
+    pub target_id: Option<i32>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub details: Value,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing)]
+    pub prev_hash: Option<String>,
+    #[serde(skip_serializing)]
+    pub hash: String,
+}
+
+impl AuditEvent {
+    // Postgres keeps microseconds, so the hashed timestamp must not be finer than that.
+    pub fn timestamp() -> DateTime<Utc> {
+        Utc::now()
+            .duration_trunc(TimeDelta::microseconds(1))
+            .unwrap_or_else(|_| Utc::now())
+    }
+
+    pub fn chain_hash(
+        prev_hash: Option<&str>,
+        event: &NewAuditEvent,
+        created_at: &DateTime<Utc>,
+    ) -> String {
+        Self::compute_hash(
+            prev_hash,
+            &String::from(event.event_type),
+            event.actor_id,
+            event.target_id,
+            event.ip.as_deref(),
+            event.user_agent.as_deref(),
+            &event.details,
+            created_at,
+        )
+    }
+
+    pub fn expected_hash(&self) -> String {
+        Self::compute_hash(
+            self.prev_hash.as_deref(),
+            &self.event_type,
+            self.actor_id,
+            self.target_id,
+            self.ip.as_deref(),
+            self.user_agent.as_deref(),
+            &self.details,
+            &self.created_at,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn compute_hash(
+        prev_hash: Option<&str>,
+        event_type: &str,
+        actor_id: Option<i32>,
+        target_id: Option<i32>,
+        ip: Option<&str>,
+        user_agent: Option<&str>,
+        details: &Value,
+        created_at: &DateTime<Utc>,
+    ) -> String {
+        let content = json!([
+            prev_hash,
+            event_type,
+            actor_id,
+            target_id,
+            ip,
+            user_agent,
+            details.to_string(),
+            created_at.timestamp_micros(),
+        ]);
+        let mut hasher = Sha256::new();
+        hasher.update(content.to_string().as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+}
@@ -1,12 +1,19 @@
+use crate::errors::admin::AdminError;
 use crate::errors::auth::UserRegistrationError;
+use crate::errors::token::ApiTokenError;
 use crate::errors::user::UserError;
+use crate::extractors::api_token::{ApiToken, TokenScopes};
+use crate::extractors::client_token::AccessTokenClaims;
 use crate::extractors::session::SESSION_TOKEN_COOKIE;
 use crate::extractors::user::User;
+use crate::helpers::api_tokens::user_from_api_token;
+use crate::helpers::client_tokens::is_family_active;
 use crate::helpers::sessions::user_from_session;
+use crate::helpers::user::fetch_user;
 use axum::http::header::AUTHORIZATION;
 use axum::{
     async_trait,
-    extract::FromRequestParts,
+    extract::{FromRef, FromRequestParts},
     http::{request::Parts, HeaderMap},
     response::{IntoResponse, Response},
 };
@@ -16,28 +23,62 @@ use uuid::Uuid;
 use utils::errors::ErrorPayload;
 use utils::state::AppState;
 
+// How the request was authenticated.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Credential {
+    Session,
+    // Short lived token issued to api clients, the session id is its refresh token family.
+    AccessToken,
+    ApiToken(TokenScopes),
+}
+
+impl Credential {
+    pub fn require_session(&self) -> Result<(), ApiTokenError> {
+        match self {
+            Credential::Session | Credential::AccessToken => Ok(()),
+            Credential::ApiToken(_) => Err(ApiTokenError::SessionRequired),
+        }
+    }
+}
+
 // Simple login.
 pub struct LoggedInUser {
     pub session: Uuid,
     pub user: User,
+    pub credential: Credential,
 }
 
 // This checks for verification as well.
 pub struct AuthenticatedUser {
     pub session: Uuid,
     pub user: User,
+    pub credential: Credential,
 }
 
 // This checks for header only.
 pub struct AuthenticationHeaderUser {
     pub session: Uuid,
     pub user: User,
+    pub credential: Credential,
+}
+
+// Visitors without any credentials pass as `None`, invalid credentials are still refused.
+pub struct OptionalAuthenticatedUser(pub Option<AuthenticatedUser>);
+
+// Verified user with the admin flag, only reachable with a login session.
+pub struct AdminUser {
+    pub session: Uuid,
+    pub user: User,
 }
 
 impl AuthenticatedUser {
-    pub fn new(user: User, session: Uuid) -> Result<Self, UserError> {
+    pub fn new(user: User, session: Uuid, credential: Credential) -> Result<Self, UserError> {
         if user.is_confirmed {
-            return Ok(Self { user, session });
+            return Ok(Self {
+                user,
+                session,
+                credential,
+            });
         }
         Err(UserError::UserNotVerified)
     }
@@ -55,14 +96,56 @@ impl FromRequestParts<AppState> for AuthenticatedUser {
         if let Err(err) = result {
             return Err(err.into_response());
         }
-        let (user, session) = result.unwrap();
-        match AuthenticatedUser::new(user, session) {
+        let (user, session, credential) = result.unwrap();
+        match AuthenticatedUser::new(user, session, credential) {
             Ok(auth_user) => Ok(auth_user),
             Err(err) => Err(ErrorPayload::from_error(err).into_response()),
         }
     }
 }
 
+#[async_trait]
+impl FromRequestParts<AppState> for OptionalAuthenticatedUser {
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let jar = SignedCookieJar::<Key>::from_request_parts(parts, state)
+            .await
+            .map_err(|never| match never {})?;
+        if !parts.headers.contains_key(AUTHORIZATION) && jar.get(SESSION_TOKEN_COOKIE).is_none() {
+            return Ok(OptionalAuthenticatedUser(None));
+        }
+        let user = AuthenticatedUser::from_request_parts(parts, state).await?;
+        Ok(OptionalAuthenticatedUser(Some(user)))
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for AdminUser {
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let AuthenticatedUser {
+            user,
+            session,
+            credential,
+        } = AuthenticatedUser::from_request_parts(parts, state).await?;
+        if let Err(err) = credential.require_session() {
+            return Err(ErrorPayload::from_error(err).into_response());
+        }
+        if !user.is_admin {
+            return Err(ErrorPayload::from_error(AdminError::AdminRequired).into_response());
+        }
+        Ok(AdminUser { session, user })
+    }
+}
+
 #[async_trait]
 impl FromRequestParts<AppState> for LoggedInUser {
     type Rejection = Response;
@@ -73,7 +156,11 @@ impl FromRequestParts<AppState> for LoggedInUser {
     ) -> Result<Self, Self::Rejection> {
         let user = process_session_from_parts(parts, state, true).await;
         match user {
-            Ok((user, session)) => Ok(LoggedInUser { session, user }),
+            Ok((user, session, credential)) => Ok(LoggedInUser {
+                session,
+                user,
+                credential,
+            }),
             Err(err) => Err(err.into_response()),
         }
     }
@@ -89,7 +176,11 @@ impl FromRequestParts<AppState> for AuthenticationHeaderUser {
     ) -> Result<Self, Self::Rejection> {
         let result = process_session_from_parts(parts, state, false).await;
         match result {
-            Ok((user, session)) => Ok(AuthenticationHeaderUser { session, user }),
+            Ok((user, session, credential)) => Ok(AuthenticationHeaderUser {
+                session,
+                user,
+                credential,
+            }),
             Err(err) => Err(err.into_response()),
         }
     }
@@ -99,12 +190,12 @@ async fn process_session_from_parts(
     parts: &mut Parts,
     state: &AppState,
     cookie: bool,
-) -> Result<(User, Uuid), ErrorPayload> {
+) -> Result<(User, Uuid, Credential), ErrorPayload> {
     let headers = HeaderMap::from_request_parts(parts, state).await?;
     let mut token = "".to_string();
     if let Some(header) = headers.get(AUTHORIZATION) {
         if let Ok(header) = header.to_str() {
-            token = header.to_string();
+            token = header.strip_prefix("Bearer ").unwrap_or(header).to_string();
         }
     }
 
@@ -128,6 +219,61 @@ async fn process_session_from_parts(
 
     let pool = &state.connection;
     let mut transaction = pool.begin().await.map_err(UserRegistrationError::Pool)?;
-    let result = user_from_session(&mut transaction, token).await?;
-    Ok(result)
+
+    if AccessTokenClaims::is_access_token(&token) {
+        let claims = AccessTokenClaims::verify(&token, &Key::from_ref(state))?;
+        if !is_family_active(&mut transaction, claims.fam).await? {
+            Err(UserError::AuthorizationTokenInvalid("token revoked".into()))?;
+        }
+        let user = fetch_user(&mut transaction, claims.sub)
+            .await
+            .map_err(UserError::UserFetchError)?;
+        check_active(&user)?;
+        transaction
+            .commit()
+            .await
+            .map_err(UserRegistrationError::TransactionCommitError)?;
+        return Ok((user, claims.fam, Credential::AccessToken));
+    }
+
+    if ApiToken::is_api_token(&token) {
+        let (user, api_token) = user_from_api_token(&mut transaction, &token).await?;
+        check_active(&user)?;
+        if !api_token.scopes.allows(&parts.method) {
+            Err(ApiTokenError::InsufficientScope(parts.method.to_string()))?;
+        }
+        transaction
+            .commit()
+            .await
+            .map_err(UserRegistrationError::TransactionCommitError)?;
+        return Ok((
+            user,
+            api_token.identifier,
+            Credential::ApiToken(api_token.scopes),
+        ));
+    }
+
+    let (user, session) = user_from_session(&mut transaction, token).await?;
+    check_active(&user)?;
+    transaction
+        .commit()
+        .await
+        .map_err(UserRegistrationError::TransactionCommitError)?;
+    Ok((user, session, Credential::Session))
+}
+
+// Deleted and deactivated accounts keep their rows but must not authenticate, suspended
+// accounts get a distinct error so the client can explain why.
+pub(crate) fn check_active(user: &User) -> Result<(), UserError> {
+    if !user.is_active {
+        let reason = match user.deleted_at {
+            Some(_) => "account deleted",
+            None => "account deactivated",
+        };
+        Err(UserError::AuthorizationTokenInvalid(reason.into()))?;
+    }
+    if let Some(err) = user.suspension_error() {
+        Err(err)?;
+    }
+    Ok(())
 }
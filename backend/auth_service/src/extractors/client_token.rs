@@ -0,0 +1,143 @@
+use crate::errors::user::UserError;
+use axum_extra::extract::cookie::Key;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub static ACCESS_TOKEN_PREFIX: &str = "at_";
+pub static REFRESH_TOKEN_PREFIX: &str = "rt_";
+
+type HmacSha256 = Hmac<Sha256>;
+
+// Signed and checked without a database lookup, apart from the family still being active.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AccessTokenClaims {
+    pub sub: i32,
+    pub fam: Uuid,
+    pub exp: i64,
+}
+
+impl AccessTokenClaims {
+    pub fn new(user_id: i32, family_id: Uuid, lifetime: Duration) -> Self {
+        Self {
+            sub: user_id,
+            fam: family_id,
+            exp: (Utc::now() + lifetime).timestamp(),
+        }
+    }
+
+    pub fn is_access_token(token: &str) -> bool {
+        token.starts_with(ACCESS_TOKEN_PREFIX)
+    }
+
+    /// ```
+    /// use auth_service::extractors::client_token::AccessTokenClaims;
+    /// use axum_extra::extract::cookie::Key;
+    /// use chrono::Duration;
+    /// use uuid::Uuid;
+    ///
+    /// let key = Key::generate();
+    /// let claims = AccessTokenClaims::new(1, Uuid::new_v4(), Duration::try_minutes(5).unwrap());
+    /// let token = claims.sign(&key);
+    /// assert_eq!(AccessTokenClaims::verify(&token, &key).unwrap(), claims);
+    /// assert!(AccessTokenClaims::verify(&token, &Key::generate()).is_err());
+    ///
+    /// let expired = AccessTokenClaims::new(1, Uuid::new_v4(), Duration::try_minutes(-1).unwrap());
+    /// assert!(AccessTokenClaims::verify(&expired.sign(&key), &key).is_err());
+    /// ```
+    pub fn sign(&self, key: &Key) -> String {
+        let payload = URL_SAFE_NO_PAD
+            .encode(serde_json::to_vec(self).expect("claims always serialize to json"));
+        let signature = URL_SAFE_NO_PAD.encode(Self::mac(key, &payload).finalize().into_bytes());
+        format!("{}{}.{}", ACCESS_TOKEN_PREFIX, payload, signature)
+    }
+
+    pub fn verify(token: &str, key: &Key) -> Result<Self, UserError> {
+        let invalid = |reason: &str| UserError::AuthorizationTokenInvalid(reason.into());
+        let (payload, signature) = token
+            .strip_prefix(ACCESS_TOKEN_PREFIX)
+            .and_then(|token| token.split_once('.'))
+            .ok_or(invalid("incomplete token"))?;
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| invalid("invalid token"))?;
+        Self::mac(key, payload)
+            .verify_slice(&signature)
+            .map_err(|_| invalid("invalid token signature"))?;
+
+        let claims: Self = URL_SAFE_NO_PAD
+            .decode(payload)
+            .ok()
+            .and_then(|payload| serde_json::from_slice(&payload).ok())
+            .ok_or(invalid("invalid token"))?;
+        if claims.exp < Utc::now().timestamp() {
+            return Err(invalid("token expired"));
+        }
+        Ok(claims)
+    }
+
+    fn mac(key: &Key, payload: &str) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(key.signing()).expect("hmac accepts keys of any size");
+        mac.update(payload.as_bytes());
+        mac
+    }
+}
+
+#[derive(Debug, FromRow, Clone)]
+pub struct RefreshToken {
+    pub identifier: Uuid,
+    pub family_id: Uuid,
+    pub verifier_hash: String,
+    pub user_id: i32,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl RefreshToken {
+    pub fn new(user_id: i32, family_id: Uuid, lifetime: Duration) -> (Self, String) {
+        let identifier = Uuid::new_v4();
+        let verifier = Uuid::new_v4();
+        let token = format!("{}{}.{}", REFRESH_TOKEN_PREFIX, identifier, verifier);
+
+        (
+            Self {
+                identifier,
+                family_id,
+                verifier_hash: Self::hash_verifier(&verifier.to_string()),
+                user_id,
+                created_at: Utc::now(),
+                expires_at: Utc::now() + lifetime,
+                used_at: None,
+                revoked_at: None,
+            },
+            token,
+        )
+    }
+
+    pub fn hash_verifier(verifier: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(verifier.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at < Utc::now()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClientTokens {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub token_type: String,
+    // Seconds until the access token expires.
+    pub expires_in: i64,
+}
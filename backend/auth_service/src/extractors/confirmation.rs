@@ -10,6 +10,9 @@ use uuid::Uuid;
 pub enum ConfirmationActionType {
     UserVerification,
     PasswordReset,
+    AccountUnlock,
+    EmailChange,
+    AccountRestore,
     Invalid,
 }
 
@@ -18,6 +21,9 @@ impl From<String> for ConfirmationActionType {
         match value.to_lowercase().as_str() {
             "userverification" => ConfirmationActionType::UserVerification,
             "passwordreset" => ConfirmationActionType::PasswordReset,
+            "accountunlock" => ConfirmationActionType::AccountUnlock,
+            "emailchange" => ConfirmationActionType::EmailChange,
+            "accountrestore" => ConfirmationActionType::AccountRestore,
             _ => ConfirmationActionType::Invalid,
         }
     }
@@ -29,6 +35,9 @@ impl From<ConfirmationActionType> for String {
             ConfirmationActionType::UserVerification => "userverification".to_string(),
             ConfirmationActionType::Invalid => "invalid".to_string(),
             ConfirmationActionType::PasswordReset => "passwordreset".to_string(),
+            ConfirmationActionType::AccountUnlock => "accountunlock".to_string(),
+            ConfirmationActionType::EmailChange => "emailchange".to_string(),
+            ConfirmationActionType::AccountRestore => "accountrestore".to_string(),
         }
     }
 }
@@ -84,6 +93,15 @@ impl Confirmation {
             ConfirmationActionType::PasswordReset => {
                 "Please proceed to reset the password".to_string()
             }
+            ConfirmationActionType::AccountUnlock => {
+                "Your account was locked after failed login attempts".to_string()
+            }
+            ConfirmationActionType::EmailChange => {
+                "Please confirm your new email address".to_string()
+            }
+            ConfirmationActionType::AccountRestore => {
+                "Your account is scheduled for deletion".to_string()
+            }
             ConfirmationActionType::Invalid => {
                 unreachable!()
             }
@@ -95,6 +113,12 @@ impl Confirmation {
             ConfirmationActionType::PasswordReset => {
                 format!("{}/auth/reset-password/{}", full_url, token.expose_secret())
             }
+            ConfirmationActionType::AccountUnlock => {
+                format!("{}/auth/unlock/{}", full_url, token.expose_secret())
+            }
+            ConfirmationActionType::AccountRestore => {
+                format!("{}/auth/restore/{}", full_url, token.expose_secret())
+            }
             _ => {
                 format!("{}/auth/confirm/{}", full_url, token.expose_secret())
             }
@@ -137,6 +161,58 @@ impl Confirmation {
                     ),
                 )
             }
+            ConfirmationActionType::AccountUnlock => (
+                format!(
+                    "Your account was temporarily locked after several failed login attempts. \
+                    If this was you, please visit {} to unlock it. If not, consider resetting \
+                    your password.",
+                    { confirmation_link }
+                ),
+                format!(
+                    "<b>Your account was temporarily locked after several failed login attempts.</b>\
+                 If this was you, please click <a href='{}' target='_blank'>here </a>\
+                  or copy the link below to unlock it. If not, consider resetting your password.<br>\
+                 \
+                 {}
+                 ",
+                    { confirmation_link },
+                    { confirmation_link }
+                ),
+            ),
+            ConfirmationActionType::EmailChange => (
+                format!(
+                    "You have requested to use this address for your account. \
+                    Please visit {} to confirm the change",
+                    { confirmation_link }
+                ),
+                format!(
+                    "<b>You have requested to use this address for your account.</b>\
+                 Please click <a href='{}' target='_blank'>here </a>\
+                  or copy the link below to confirm the change.<br>\
+                 \
+                 {}
+                 ",
+                    { confirmation_link },
+                    { confirmation_link }
+                ),
+            ),
+            ConfirmationActionType::AccountRestore => (
+                format!(
+                    "Your account was deleted and will be removed permanently once the grace \
+                    period ends. If you change your mind, please visit {} to restore it.",
+                    { confirmation_link }
+                ),
+                format!(
+                    "<b>Your account was deleted and will be removed permanently once the grace period ends.</b>\
+                 If you change your mind, please click <a href='{}' target='_blank'>here </a>\
+                  or copy the link below to restore it.<br>\
+                 \
+                 {}
+                 ",
+                    { confirmation_link },
+                    { confirmation_link }
+                ),
+            ),
             ConfirmationActionType::Invalid => {
                 unreachable!()
             }
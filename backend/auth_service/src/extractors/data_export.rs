@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct DataExport {
+    pub identifier: Uuid,
+    pub user_id: i32,
+    #[serde(skip_serializing)]
+    pub content: Option<Value>,
+    pub created_at: DateTime<Utc>,
+    pub ready_at: Option<DateTime<Utc>>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl DataExport {
+    pub fn new(user_id: i32, expires_at: DateTime<Utc>) -> Self {
+        Self {
+            identifier: Uuid::new_v4(),
+            user_id,
+            content: None,
+            created_at: Utc::now(),
+            ready_at: None,
+            expires_at,
+        }
+    }
+
+    pub fn download_url(&self, full_url: &str) -> String {
+        format!("{}/api/auth/export/{}", full_url, self.identifier)
+    }
+
+    pub fn file_name(&self) -> String {
+        format!("export-{}.json", self.created_at.format("%Y%m%d%H%M%S"))
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at < Utc::now()
+    }
+}
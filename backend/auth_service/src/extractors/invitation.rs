@@ -0,0 +1,133 @@
+use crate::extractors::user::User;
+use chrono::{DateTime, Duration, Utc};
+use secrecy::{ExposeSecret, Secret};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum InviteRole {
+    #[default]
+    User,
+    Admin,
+}
+
+impl From<String> for InviteRole {
+    fn from(value: String) -> Self {
+        match value.to_lowercase().as_str() {
+            "admin" => InviteRole::Admin,
+            _ => InviteRole::User,
+        }
+    }
+}
+
+impl From<InviteRole> for String {
+    fn from(value: InviteRole) -> Self {
+        match value {
+            InviteRole::User => "user".to_string(),
+            InviteRole::Admin => "admin".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct Invitation {
+    pub identifier: Uuid,
+    #[serde(skip_serializing)]
+    pub verifier_hash: String,
+    pub email: String,
+    pub role: InviteRole,
+    pub inviter_id: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub accepted_at: Option<DateTime<Utc>>,
+    pub accepted_by: Option<i32>,
+}
+
+impl Invitation {
+    pub fn new(
+        email: &str,
+        role: InviteRole,
+        inviter_id: i32,
+        expires_in: Duration,
+    ) -> (Self, String) {
+        let identifier = Uuid::new_v4();
+        let (verifier_hash, token) = Self::new_verifier(identifier);
+        (
+            Self {
+                identifier,
+                verifier_hash,
+                email: User::normalize_email(email),
+                role,
+                inviter_id: Some(inviter_id),
+                created_at: Utc::now(),
+                expires_at: Utc::now() + expires_in,
+                accepted_at: None,
+                accepted_by: None,
+            },
+            token,
+        )
+    }
+
+    // Resending rotates the verifier so that earlier links stop working.
+    pub fn renew(&mut self, expires_in: Duration) -> String {
+        let (verifier_hash, token) = Self::new_verifier(self.identifier);
+        self.verifier_hash = verifier_hash;
+        self.expires_at = Utc::now() + expires_in;
+        token
+    }
+
+    fn new_verifier(identifier: Uuid) -> (String, String) {
+        let verifier = Uuid::new_v4();
+        let mut hasher = Sha256::new();
+        hasher.update(verifier.to_string().as_bytes());
+        let verifier_hash = format!("{:x}", hasher.finalize());
+        (verifier_hash, format!("{}.{}", identifier, verifier))
+    }
+
+    pub fn matches_verifier(&self, verifier: &str) -> bool {
+        let mut hasher = Sha256::new();
+        hasher.update(verifier.as_bytes());
+        format!("{:x}", hasher.finalize()) == self.verifier_hash
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at < Utc::now()
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.accepted_at.is_none() && !self.is_expired()
+    }
+
+    pub fn subject(&self, application_name: &str) -> String {
+        format!("You have been invited to join {}", application_name)
+    }
+
+    pub fn accept_url(&self, full_url: &str, token: Secret<String>) -> String {
+        format!("{}/auth/invite/{}", full_url, token.expose_secret())
+    }
+
+    pub fn email_contents(&self, invite_link: &str) -> (String, String) {
+        (
+            format!(
+                "You have been invited to create an account. Please visit {} to sign up. \
+                The invitation expires on {}.",
+                invite_link,
+                self.expires_at.format("%Y-%m-%d %H:%M UTC")
+            ),
+            format!(
+                "<b>You have been invited to create an account.</b>\
+             Please click <a href='{}' target='_blank'>here </a>\
+              or copy the link below to sign up. The invitation expires on {}.<br>\
+             \
+             {}
+             ",
+                invite_link,
+                self.expires_at.format("%Y-%m-%d %H:%M UTC"),
+                invite_link
+            ),
+        )
+    }
+}
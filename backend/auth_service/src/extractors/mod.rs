@@ -1,4 +1,10 @@
+pub mod admin;
+pub mod api_token;
+pub mod audit;
 pub mod authentication;
+pub mod client_token;
 pub mod confirmation;
+pub mod data_export;
+pub mod invitation;
 pub mod session;
 pub mod user;
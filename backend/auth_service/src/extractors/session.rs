@@ -1,12 +1,31 @@
+use axum_extra::extract::cookie::{Cookie, SameSite};
 use chrono::{DateTime, Duration, Utc};
 use serde::Deserialize;
 use serde_json::Value;
 use sha2::{Digest, Sha256};
 use sqlx::FromRow;
+use utils::configuration::{CookieSameSite, CookieSettings};
 use uuid::Uuid;
 
 pub static SESSION_TOKEN_COOKIE: &str = "session_token";
 
+pub fn session_cookie(token: String, settings: &CookieSettings) -> Cookie<'static> {
+    let same_site = match settings.same_site {
+        CookieSameSite::Strict => SameSite::Strict,
+        CookieSameSite::Lax => SameSite::Lax,
+        CookieSameSite::None => SameSite::None,
+    };
+    let mut cookie = Cookie::build((SESSION_TOKEN_COOKIE, token))
+        .path(settings.path.clone())
+        .secure(settings.secure)
+        .http_only(settings.http_only)
+        .same_site(same_site);
+    if let Some(domain) = &settings.domain {
+        cookie = cookie.domain(domain.clone());
+    }
+    cookie.build()
+}
+
 #[derive(Debug, FromRow, Deserialize)]
 pub struct UserSession {
     pub identifier: Uuid,
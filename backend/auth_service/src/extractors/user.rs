@@ -1,11 +1,17 @@
 use crate::errors::user::UserError;
 use argon2::password_hash::rand_core::OsRng;
 use argon2::password_hash::SaltString;
-use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
+use argon2::{
+    Algorithm, Argon2, KeyId, Params, ParamsBuilder, PasswordHash, PasswordHasher,
+    PasswordVerifier, Version,
+};
 use chrono::{DateTime, Utc};
 use secrecy::{ExposeSecret, Secret};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use sqlx::FromRow;
+use unicode_normalization::UnicodeNormalization;
+use utils::configuration::{PasswordHashSettings, PasswordPolicy};
 
 #[derive(Debug, FromRow, Deserialize, Serialize)]
 pub struct User {
@@ -21,6 +27,11 @@ pub struct User {
     pub updated_at: DateTime<Utc>,
     pub is_active: bool,
     pub is_confirmed: bool,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub is_admin: bool,
+    pub suspended_at: Option<DateTime<Utc>>,
+    pub suspension_reason: Option<String>,
+    pub suspended_until: Option<DateTime<Utc>>,
 }
 
 impl Default for User {
@@ -36,6 +47,11 @@ impl Default for User {
             updated_at: Utc::now(),
             is_active: true,
             is_confirmed: false,
+            deleted_at: None,
+            is_admin: false,
+            suspended_at: None,
+            suspension_reason: None,
+            suspended_until: None,
         }
     }
 }
@@ -89,9 +105,28 @@ impl User {
         Ok(normalized_username.to_lowercase())
     }
 
-    pub fn hash_password(password: &str) -> Result<String, UserError> {
+    /// # Examples
+    ///
+    /// ```
+    /// use auth_service::extractors::user::User;
+    ///
+    /// assert_eq!(User::normalize_email(" Bob@Example.COM "), "bob@example.com");
+    /// assert_eq!(User::normalize_email("ｂｏｂ@example.com"), "bob@example.com");
+    /// ```
+    pub fn normalize_email(email: &str) -> String {
+        email.trim().nfkc().collect::<String>().to_lowercase()
+    }
+
+    pub fn hash_password(
+        password: &str,
+        settings: &PasswordHashSettings,
+    ) -> Result<String, UserError> {
         let salt = SaltString::generate(&mut OsRng);
-        let argon2 = Argon2::default();
+        let pepper = settings
+            .pepper
+            .as_ref()
+            .map(|pepper| pepper.expose_secret());
+        let argon2 = Self::hasher(settings, pepper)?;
         let result = argon2
             .hash_password(password.as_ref(), &salt)
             .map_err(|_| UserError::PasswordHashError("Failed to hash password".to_string()))?;
@@ -99,23 +134,149 @@ impl User {
         Ok(result.to_string())
     }
 
-    pub fn check_acceptable_password(password: &str, inputs: &[&str]) -> Result<(), UserError> {
-        let estimator = zxcvbn::zxcvbn(password, inputs).map_err(UserError::PasswordCheckFailed)?;
-        if estimator.score() < 3 {
-            return Err(UserError::WeakPassword);
+    fn hasher<'a>(
+        settings: &PasswordHashSettings,
+        pepper: Option<&'a String>,
+    ) -> Result<Argon2<'a>, UserError> {
+        let hash_error = |_| UserError::PasswordHashError("Invalid hash parameters".to_string());
+        let mut builder = ParamsBuilder::new();
+        builder
+            .m_cost(settings.memory_kib)
+            .t_cost(settings.iterations)
+            .p_cost(settings.parallelism);
+        match pepper {
+            Some(pepper) => {
+                builder.keyid(Self::pepper_id(pepper));
+                let params = builder.build().map_err(hash_error)?;
+                Argon2::new_with_secret(
+                    pepper.as_bytes(),
+                    Algorithm::Argon2id,
+                    Version::V0x13,
+                    params,
+                )
+                .map_err(hash_error)
+            }
+            None => {
+                let params = builder.build().map_err(hash_error)?;
+                Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+            }
+        }
+    }
+
+    // Recorded as the hash's keyid, so hashes from before a pepper was set can still be told apart.
+    fn pepper_id(pepper: &str) -> KeyId {
+        let digest = Sha256::digest(pepper.as_bytes());
+        KeyId::new(&digest[..4]).expect("four bytes fit in a key id")
+    }
+
+    pub fn check_acceptable_password(
+        password: &str,
+        inputs: &[&str],
+        policy: &PasswordPolicy,
+    ) -> Result<(), UserError> {
+        let length = password.chars().count();
+        if length < policy.min_length || length > policy.max_length {
+            return Err(UserError::PasswordLength {
+                min: policy.min_length,
+                max: policy.max_length,
+            });
+        }
+        let mut inputs = inputs.to_vec();
+        inputs.extend(policy.forbidden_inputs.iter().map(String::as_str));
+        let estimator =
+            zxcvbn::zxcvbn(password, &inputs).map_err(UserError::PasswordCheckFailed)?;
+        if estimator.score() < policy.min_score {
+            let feedback = estimator.feedback().as_ref();
+            return Err(UserError::WeakPassword {
+                warning: feedback
+                    .and_then(|feedback| feedback.warning())
+                    .map(|warning| warning.to_string()),
+                suggestions: feedback
+                    .map(|feedback| {
+                        feedback
+                            .suggestions()
+                            .iter()
+                            .map(|suggestion| suggestion.to_string())
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            });
         }
         Ok(())
     }
 }
 
 impl User {
-    pub fn check_password(&self, password: &str) -> bool {
-        let parsed_hash = PasswordHash::new(self.password_hash.expose_secret());
-        if let Ok(parsed_hash) = parsed_hash {
-            return Argon2::default()
+    pub fn check_password(&self, password: &str, settings: &PasswordHashSettings) -> bool {
+        let Ok(parsed_hash) = PasswordHash::new(self.password_hash.expose_secret()) else {
+            return false;
+        };
+        let Ok(params) = Params::try_from(&parsed_hash) else {
+            return false;
+        };
+        let pepper = match (params.keyid().is_empty(), &settings.pepper) {
+            (true, _) => None,
+            (false, Some(pepper))
+                if params.keyid() == Self::pepper_id(pepper.expose_secret()).as_bytes() =>
+            {
+                Some(pepper.expose_secret())
+            }
+            (false, _) => return false,
+        };
+        // The cost stored in the hash is used for verifying, only the pepper comes from settings.
+        let argon2 = match pepper {
+            Some(pepper) => Argon2::new_with_secret(
+                pepper.as_bytes(),
+                Algorithm::Argon2id,
+                Version::V0x13,
+                Params::default(),
+            ),
+            None => Ok(Argon2::default()),
+        };
+        argon2.is_ok_and(|argon2| {
+            argon2
                 .verify_password(password.as_ref(), &parsed_hash)
-                .is_ok();
+                .is_ok()
+        })
+    }
+
+    // True when the stored hash was made with a different cost or pepper than configured now.
+    pub fn needs_rehash(&self, settings: &PasswordHashSettings) -> bool {
+        let Ok(parsed_hash) = PasswordHash::new(self.password_hash.expose_secret()) else {
+            return true;
+        };
+        let Ok(params) = Params::try_from(&parsed_hash) else {
+            return true;
+        };
+        let pepper_id = settings
+            .pepper
+            .as_ref()
+            .map(|pepper| Self::pepper_id(pepper.expose_secret()));
+        parsed_hash.algorithm != Algorithm::Argon2id.ident()
+            || parsed_hash.version != Some(Version::V0x13.into())
+            || params.m_cost() != settings.memory_kib
+            || params.t_cost() != settings.iterations
+            || params.p_cost() != settings.parallelism
+            || params.keyid() != pepper_id.as_ref().map_or(&[][..], |id| id.as_bytes())
+    }
+
+    // Hashing costs the same as verifying, so unknown logins take as long as wrong passwords.
+    pub fn dummy_password_check(password: &str, settings: &PasswordHashSettings) {
+        let _ = Self::hash_password(password, settings);
+    }
+
+    // A suspension without an end date lasts until an admin lifts it.
+    pub fn is_suspended(&self) -> bool {
+        self.suspended_at.is_some() && self.suspended_until.is_none_or(|until| until > Utc::now())
+    }
+
+    pub fn suspension_error(&self) -> Option<UserError> {
+        if !self.is_suspended() {
+            return None;
         }
-        false
+        Some(UserError::AccountSuspended {
+            reason: self.suspension_reason.clone().unwrap_or_default(),
+            until: self.suspended_until,
+        })
     }
 }
@@ -0,0 +1,146 @@
+use crate::errors::account::AccountError;
+use crate::errors::auth::UserRegistrationError;
+use crate::errors::confirm::ConfirmUserError;
+use crate::errors::user::UserError;
+use crate::extractors::authentication::LoggedInUser;
+use crate::extractors::confirmation::{Confirmation, ConfirmationActionType};
+use crate::extractors::data_export::DataExport;
+use crate::helpers::account::{
+    fetch_data_export, insert_data_export, mark_account_deleted, prepare_data_export,
+    restore_deleted_account,
+};
+use crate::helpers::api_tokens::revoke_all_api_tokens;
+use crate::helpers::confirmation;
+use crate::helpers::confirmation::{
+    add_confirmation, clear_confirmation_action_type, send_verification_link,
+};
+use crate::helpers::sessions::clear_sessions;
+use crate::helpers::user::fetch_user;
+use crate::payload::DeleteAccountPayload;
+use axum::extract::{Path, State};
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::Utc;
+use serde_json::json;
+use utils::errors::ErrorPayload;
+use utils::state::AppState;
+use utils::validation::ValidatedForm;
+use uuid::Uuid;
+
+#[tracing::instrument(name = "Deleting account", skip(state, user, payload), fields(username = % user.user.username))]
+pub async fn delete_account(
+    State(state): State<AppState>,
+    user: LoggedInUser,
+    ValidatedForm(payload): ValidatedForm<DeleteAccountPayload>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    user.credential.require_session()?;
+    let LoggedInUser { user, .. } = user;
+    let pool = &state.connection;
+
+    if !user.check_password(&payload.current_password, &state.settings.password_hash) {
+        Err(UserError::IncorrectPassword)?;
+    }
+
+    let mut transaction = pool.begin().await.map_err(AccountError::Pool)?;
+    let deleted_at = mark_account_deleted(&mut transaction, user.id).await?;
+    clear_sessions(&mut transaction, user.id).await?;
+    revoke_all_api_tokens(&mut transaction, user.id).await?;
+
+    // The restore link stays valid for the whole grace period.
+    let (mut confirmation, confirmation_token) =
+        Confirmation::new(user.id, ConfirmationActionType::AccountRestore, json!({}));
+    let purge_at = deleted_at + state.settings.account.deletion_grace();
+    confirmation.expires_at = purge_at;
+    add_confirmation(&mut transaction, &confirmation).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(AccountError::DatabaseError)?;
+
+    send_verification_link(&state, &user, &confirmation, confirmation_token).await?;
+
+    Ok(Json(json!({"purge_at": purge_at})))
+}
+
+pub async fn restore_account(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+
+    tracing::info!("starting account restore");
+    let mut transaction = pool.begin().await.map_err(UserRegistrationError::Pool)?;
+
+    let confirmation = confirmation::check_confirmation(token, &mut transaction).await?;
+    if let ConfirmationActionType::AccountRestore = confirmation.action_type {
+        let user = fetch_user(&mut transaction, confirmation.user_id).await?;
+        restore_deleted_account(&mut transaction, user.id).await?;
+        clear_confirmation_action_type(
+            &mut transaction,
+            user.id,
+            ConfirmationActionType::AccountRestore,
+        )
+        .await?;
+        transaction
+            .commit()
+            .await
+            .map_err(UserRegistrationError::TransactionCommitError)?;
+        Ok(Json(json!({
+            "username": user.username
+        })))
+    } else {
+        Err(ConfirmUserError::InvalidActionType)?
+    }
+}
+
+#[tracing::instrument(name = "Requesting data export", skip(state, user), fields(username = % user.user.username))]
+pub async fn request_export(
+    State(state): State<AppState>,
+    user: LoggedInUser,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    user.credential.require_session()?;
+    let LoggedInUser { user, .. } = user;
+    let pool = &state.connection;
+
+    let export = DataExport::new(user.id, Utc::now() + state.settings.account.export_expiry());
+    let mut transaction = pool.begin().await.map_err(AccountError::Pool)?;
+    insert_data_export(&mut transaction, &export).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(AccountError::DatabaseError)?;
+
+    let response = Json(json!(export));
+    prepare_data_export(&state, user, export);
+    Ok(response)
+}
+
+#[tracing::instrument(name = "Downloading data export", skip(state, user), fields(username = % user.user.username))]
+pub async fn download_export(
+    State(state): State<AppState>,
+    user: LoggedInUser,
+    Path(identifier): Path<Uuid>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    user.credential.require_session()?;
+    let pool = &state.connection;
+
+    let mut connection = pool.acquire().await.map_err(AccountError::Pool)?;
+    let export = fetch_data_export(&mut connection, user.user.id, identifier)
+        .await?
+        .filter(|export| !export.is_expired())
+        .ok_or(AccountError::ExportNotFound)?;
+    let file_name = export.file_name();
+    let content = export.content.ok_or(AccountError::ExportNotReady)?;
+
+    Ok((
+        [
+            (CONTENT_TYPE, "application/json".to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", file_name),
+            ),
+        ],
+        Json(content),
+    ))
+}
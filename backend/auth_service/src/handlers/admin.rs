@@ -0,0 +1,368 @@
+use crate::errors::admin::AdminError;
+use crate::errors::auth::UserRegistrationError;
+use crate::extractors::admin::AdminAction;
+use crate::extractors::audit::{AuditEventType, NewAuditEvent, RequestContext};
+use crate::extractors::authentication::AdminUser;
+use crate::extractors::confirmation::{Confirmation, ConfirmationActionType};
+use crate::extractors::session::session_cookie;
+use crate::helpers::admin::{
+    clear_user_suspension, count_sessions, count_users, fetch_target_user, list_admin_actions,
+    record_admin_action, search_users, set_user_active, set_user_suspension,
+};
+use crate::helpers::audit::record_audit_event;
+use crate::helpers::confirmation::{
+    add_confirmation, clear_confirmation_action_type, mark_user_as_confirmed,
+    send_verification_link,
+};
+use crate::helpers::sessions::{clear_sessions, create_new_session};
+use crate::helpers::user::update_password;
+use crate::payload::{SuspendPayload, UserListQuery};
+use axum::extract::{Path, Query, State};
+use axum::http::header::AUTHORIZATION;
+use axum::http::HeaderValue;
+use axum::response::IntoResponse;
+use axum::Json;
+use axum_extra::extract::SignedCookieJar;
+use chrono::Utc;
+use secrecy::Secret;
+use serde_json::json;
+use utils::errors::ErrorPayload;
+use utils::state::AppState;
+use utils::validation::ValidatedForm;
+use uuid::Uuid;
+
+#[tracing::instrument(name = "Listing users", skip(admin, state), fields(admin = % admin.user.username))]
+pub async fn list_users(
+    admin: AdminUser,
+    State(state): State<AppState>,
+    Query(query): Query<UserListQuery>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(AdminError::Pool)?;
+    let users = search_users(
+        &mut connection,
+        query.pattern(),
+        query.per_page(),
+        query.offset(),
+    )
+    .await?;
+    let total = count_users(&mut connection, query.pattern()).await?;
+
+    Ok(Json(json!({
+        "users": users,
+        "total": total,
+        "page": query.page(),
+        "per_page": query.per_page(),
+    })))
+}
+
+#[tracing::instrument(name = "Viewing user", skip(admin, state), fields(admin = % admin.user.username))]
+pub async fn user_detail(
+    admin: AdminUser,
+    State(state): State<AppState>,
+    Path(user_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(AdminError::Pool)?;
+    let user = fetch_target_user(&mut connection, user_id).await?;
+    let sessions = count_sessions(&mut connection, user_id).await?;
+    let audit = list_admin_actions(&mut connection, user_id).await?;
+
+    Ok(Json(json!({
+        "user": user,
+        "sessions": sessions,
+        "audit": audit,
+    })))
+}
+
+#[tracing::instrument(name = "Activating user", skip(admin, state), fields(admin = % admin.user.username))]
+pub async fn activate_user(
+    admin: AdminUser,
+    State(state): State<AppState>,
+    Path(user_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(AdminError::Pool)?;
+    let user = fetch_target_user(&mut transaction, user_id).await?;
+    set_user_active(&mut transaction, user.id, true).await?;
+    clear_confirmation_action_type(
+        &mut transaction,
+        user.id,
+        ConfirmationActionType::AccountRestore,
+    )
+    .await?;
+    record_admin_action(
+        &mut transaction,
+        admin.user.id,
+        user.id,
+        AdminAction::Activate,
+        json!({}),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(AdminError::DatabaseError)?;
+
+    Ok(Json(json!({"ok": true})))
+}
+
+#[tracing::instrument(name = "Deactivating user", skip(admin, state), fields(admin = % admin.user.username))]
+pub async fn deactivate_user(
+    admin: AdminUser,
+    State(state): State<AppState>,
+    context: RequestContext,
+    Path(user_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    if admin.user.id == user_id {
+        Err(AdminError::SelfTarget)?;
+    }
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(AdminError::Pool)?;
+    let user = fetch_target_user(&mut transaction, user_id).await?;
+    set_user_active(&mut transaction, user.id, false).await?;
+    clear_sessions(&mut transaction, user.id).await?;
+    record_admin_action(
+        &mut transaction,
+        admin.user.id,
+        user.id,
+        AdminAction::Deactivate,
+        json!({}),
+    )
+    .await?;
+    record_audit_event(
+        &mut transaction,
+        NewAuditEvent::new(AuditEventType::SessionsRevoked, &context)
+            .actor(admin.user.id)
+            .target(user.id)
+            .details(json!({"reason": "deactivate"})),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(AdminError::DatabaseError)?;
+
+    Ok(Json(json!({"ok": true})))
+}
+
+#[tracing::instrument(name = "Force verifying user", skip(admin, state), fields(admin = % admin.user.username))]
+pub async fn verify_user(
+    admin: AdminUser,
+    State(state): State<AppState>,
+    Path(user_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(AdminError::Pool)?;
+    let user = fetch_target_user(&mut transaction, user_id).await?;
+    mark_user_as_confirmed(&mut transaction, user.id).await?;
+    record_admin_action(
+        &mut transaction,
+        admin.user.id,
+        user.id,
+        AdminAction::Verify,
+        json!({}),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(AdminError::DatabaseError)?;
+
+    Ok(Json(json!({"ok": true})))
+}
+
+// Existing sessions are kept but rejected until the suspension ends or is lifted.
+#[tracing::instrument(name = "Suspending user", skip(admin, state, payload), fields(admin = % admin.user.username))]
+pub async fn suspend_user(
+    admin: AdminUser,
+    State(state): State<AppState>,
+    Path(user_id): Path<i32>,
+    ValidatedForm(payload): ValidatedForm<SuspendPayload>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    if admin.user.id == user_id {
+        Err(AdminError::SelfTarget)?;
+    }
+    if payload.until.is_some_and(|until| until <= Utc::now()) {
+        Err(AdminError::SuspensionEndInPast)?;
+    }
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(AdminError::Pool)?;
+    let user = fetch_target_user(&mut transaction, user_id).await?;
+    set_user_suspension(&mut transaction, user.id, &payload.reason, payload.until).await?;
+    record_admin_action(
+        &mut transaction,
+        admin.user.id,
+        user.id,
+        AdminAction::Suspend,
+        json!({"reason": payload.reason, "until": payload.until}),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(AdminError::DatabaseError)?;
+
+    Ok(Json(json!({"ok": true})))
+}
+
+#[tracing::instrument(name = "Lifting suspension", skip(admin, state), fields(admin = % admin.user.username))]
+pub async fn unsuspend_user(
+    admin: AdminUser,
+    State(state): State<AppState>,
+    Path(user_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(AdminError::Pool)?;
+    let user = fetch_target_user(&mut transaction, user_id).await?;
+    clear_user_suspension(&mut transaction, user.id).await?;
+    record_admin_action(
+        &mut transaction,
+        admin.user.id,
+        user.id,
+        AdminAction::Unsuspend,
+        json!({}),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(AdminError::DatabaseError)?;
+
+    Ok(Json(json!({"ok": true})))
+}
+
+// The current password stops working right away, the user has to pick a new one from the link.
+#[tracing::instrument(name = "Forcing password reset", skip(admin, state), fields(admin = % admin.user.username))]
+pub async fn force_password_reset(
+    admin: AdminUser,
+    State(state): State<AppState>,
+    context: RequestContext,
+    Path(user_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(AdminError::Pool)?;
+    let user = fetch_target_user(&mut transaction, user_id).await?;
+    let placeholder = format!("{}{}", Uuid::new_v4(), Uuid::new_v4());
+    update_password(
+        &mut transaction,
+        user.id,
+        Secret::from(placeholder),
+        &state.settings.password_hash,
+    )
+    .await?;
+    clear_sessions(&mut transaction, user.id).await?;
+    clear_confirmation_action_type(
+        &mut transaction,
+        user.id,
+        ConfirmationActionType::PasswordReset,
+    )
+    .await?;
+    let (confirmation, confirmation_token) =
+        Confirmation::new(user.id, ConfirmationActionType::PasswordReset, json!({}));
+    add_confirmation(&mut transaction, &confirmation).await?;
+    record_admin_action(
+        &mut transaction,
+        admin.user.id,
+        user.id,
+        AdminAction::ForcePasswordReset,
+        json!({}),
+    )
+    .await?;
+    record_audit_event(
+        &mut transaction,
+        NewAuditEvent::new(AuditEventType::SessionsRevoked, &context)
+            .actor(admin.user.id)
+            .target(user.id)
+            .details(json!({"reason": "force_password_reset"})),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(AdminError::DatabaseError)?;
+
+    send_verification_link(&state, &user, &confirmation, confirmation_token).await?;
+
+    Ok(Json(json!({"ok": true})))
+}
+
+#[tracing::instrument(name = "Revoking user sessions", skip(admin, state), fields(admin = % admin.user.username))]
+pub async fn revoke_user_sessions(
+    admin: AdminUser,
+    State(state): State<AppState>,
+    context: RequestContext,
+    Path(user_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(AdminError::Pool)?;
+    let user = fetch_target_user(&mut transaction, user_id).await?;
+    clear_sessions(&mut transaction, user.id).await?;
+    record_admin_action(
+        &mut transaction,
+        admin.user.id,
+        user.id,
+        AdminAction::RevokeSessions,
+        json!({}),
+    )
+    .await?;
+    record_audit_event(
+        &mut transaction,
+        NewAuditEvent::new(AuditEventType::SessionsRevoked, &context)
+            .actor(admin.user.id)
+            .target(user.id)
+            .details(json!({"reason": "revoke_sessions"})),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(AdminError::DatabaseError)?;
+
+    Ok(Json(json!({"ok": true})))
+}
+
+// Replaces the admin's session cookie with one for the target user, tagged with who started it.
+#[tracing::instrument(name = "Impersonating user", skip(admin, state, jar), fields(admin = % admin.user.username))]
+pub async fn impersonate_user(
+    admin: AdminUser,
+    jar: SignedCookieJar,
+    State(state): State<AppState>,
+    Path(user_id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    if admin.user.id == user_id {
+        Err(AdminError::SelfTarget)?;
+    }
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(AdminError::Pool)?;
+    let user = fetch_target_user(&mut transaction, user_id).await?;
+    if !user.is_active {
+        Err(AdminError::UserNotFound)?;
+    }
+    let session_token = create_new_session(
+        &mut transaction,
+        user.id,
+        json!({"impersonated_by": admin.user.id}),
+    )
+    .await?;
+    record_admin_action(
+        &mut transaction,
+        admin.user.id,
+        user.id,
+        AdminAction::Impersonate,
+        json!({"admin_session": admin.session}),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(AdminError::DatabaseError)?;
+
+    let session_header =
+        HeaderValue::from_str(&session_token).map_err(UserRegistrationError::HeaderError)?;
+    let jar = jar.add(session_cookie(session_token, &state.settings.cookie));
+
+    let mut response = (jar, Json(user)).into_response();
+    response.headers_mut().insert(AUTHORIZATION, session_header);
+    Ok(response)
+}
@@ -0,0 +1,91 @@
+use crate::errors::token::ApiTokenError;
+use crate::extractors::api_token::{ApiToken, TokenScope, TokenScopes};
+use crate::extractors::authentication::AuthenticatedUser;
+use crate::helpers::api_tokens::{insert_api_token, list_api_tokens, revoke_api_token};
+use axum::extract::{Path, State};
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use serde_json::json;
+use utils::errors::ErrorPayload;
+use utils::state::AppState;
+use utils::validation::ValidatedForm;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateApiTokenPayload {
+    #[validate(length(min = 1, max = 100, message = "Name must contain 1-100 characters"))]
+    pub name: String,
+    #[validate(length(min = 1, message = "At least one scope is required"))]
+    pub scopes: Vec<TokenScope>,
+    #[validate(range(min = 1, max = 365, message = "Expiry must be between 1 and 365 days"))]
+    pub expires_in_days: Option<i64>,
+}
+
+#[tracing::instrument(name = "Listing api tokens", skip(user, state), fields(username = % user.user.username))]
+pub async fn list_tokens(
+    user: AuthenticatedUser,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ApiTokenError::Pool)?;
+    let tokens = list_api_tokens(&mut connection, user.user.id).await?;
+    Ok(Json(tokens))
+}
+
+#[tracing::instrument(name = "Creating api token", skip(user, state, payload), fields(username = % user.user.username))]
+pub async fn create_token(
+    user: AuthenticatedUser,
+    State(state): State<AppState>,
+    ValidatedForm(payload): ValidatedForm<CreateApiTokenPayload>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    user.credential.require_session()?;
+    let pool = &state.connection;
+
+    let expires_at = payload
+        .expires_in_days
+        .map(|days| Utc::now() + Duration::try_days(days).unwrap());
+    let (api_token, token) = ApiToken::new(
+        user.user.id,
+        payload.name,
+        TokenScopes(payload.scopes),
+        expires_at,
+    );
+
+    let mut transaction = pool.begin().await.map_err(ApiTokenError::Pool)?;
+    insert_api_token(&mut transaction, &api_token).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(ApiTokenError::DatabaseError)?;
+
+    // The raw token is only ever returned here, we store the verifier hash.
+    Ok(Json(json!({
+        "token": token,
+        "api_token": api_token
+    })))
+}
+
+#[tracing::instrument(name = "Revoking api token", skip(user, state), fields(username = % user.user.username))]
+pub async fn revoke_token(
+    user: AuthenticatedUser,
+    State(state): State<AppState>,
+    Path(identifier): Path<Uuid>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    user.credential.require_session()?;
+    let pool = &state.connection;
+
+    let mut transaction = pool.begin().await.map_err(ApiTokenError::Pool)?;
+    let count = revoke_api_token(&mut transaction, user.user.id, identifier).await?;
+    if count < 1 {
+        Err(ApiTokenError::NotFound)?;
+    }
+    transaction
+        .commit()
+        .await
+        .map_err(ApiTokenError::DatabaseError)?;
+
+    Ok(Json(json!({"ok": true})))
+}
@@ -0,0 +1,52 @@
+use crate::errors::audit::AuditError;
+use crate::extractors::authentication::{AdminUser, LoggedInUser};
+use crate::helpers::audit::{recent_security_activity, search_audit_events, verify_audit_chain};
+use crate::payload::AuditQuery;
+use axum::extract::{Query, State};
+use axum::response::IntoResponse;
+use axum::Json;
+use serde_json::json;
+use utils::errors::ErrorPayload;
+use utils::state::AppState;
+
+const SECURITY_ACTIVITY_LIMIT: i64 = 20;
+
+#[tracing::instrument(name = "Searching audit log", skip(admin, state), fields(admin = % admin.user.username))]
+pub async fn list_audit_events(
+    admin: AdminUser,
+    State(state): State<AppState>,
+    Query(query): Query<AuditQuery>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(AuditError::Pool)?;
+    let events = search_audit_events(&mut connection, &query).await?;
+
+    Ok(Json(json!({
+        "events": events,
+        "page": query.page(),
+        "per_page": query.per_page(),
+    })))
+}
+
+#[tracing::instrument(name = "Verifying audit log", skip(admin, state), fields(admin = % admin.user.username))]
+pub async fn verify_audit_log(
+    admin: AdminUser,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(AuditError::Pool)?;
+    let report = verify_audit_chain(&mut connection).await?;
+    Ok(Json(report))
+}
+
+#[tracing::instrument(name = "Listing security activity", skip(user, state), fields(username = % user.user.username))]
+pub async fn security_activity(
+    user: LoggedInUser,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(AuditError::Pool)?;
+    let events =
+        recent_security_activity(&mut connection, user.user.id, SECURITY_ACTIVITY_LIMIT).await?;
+    Ok(Json(events))
+}
@@ -0,0 +1,119 @@
+use crate::errors::auth::UserLoginError;
+use crate::errors::client_token::ClientTokenError;
+use crate::errors::user::UserError;
+use crate::extractors::audit::{AuditEventType, NewAuditEvent, RequestContext};
+use crate::extractors::authentication::check_active;
+use crate::handlers::login::{authenticate, LoginForm};
+use crate::helpers::audit::record_audit_event;
+use crate::helpers::client_tokens::{
+    fetch_refresh_token, issue_client_tokens, mark_refresh_token_used, revoke_token_family,
+};
+use crate::helpers::user::fetch_user;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::Deserialize;
+use serde_json::json;
+use utils::client_ip::ClientIp;
+use utils::errors::ErrorPayload;
+use utils::state::AppState;
+use utils::validation::ValidatedForm;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RefreshTokenPayload {
+    #[validate(length(min = 1, message = "Refresh token cannot be empty"))]
+    pub refresh_token: String,
+}
+
+// Password grant for api clients, the web frontend keeps using the cookie session from /login.
+#[tracing::instrument(name = "Issuing client tokens",
+skip(state, payload), fields(
+username = % payload.username,
+)
+)]
+pub async fn issue_token(
+    ClientIp(ip): ClientIp,
+    context: RequestContext,
+    State(state): State<AppState>,
+    ValidatedForm(payload): ValidatedForm<LoginForm>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let (user, mut transaction) = authenticate(&state, ip.as_deref(), &context, &payload).await?;
+    let tokens =
+        issue_client_tokens(&mut transaction, &state.settings, user.id, Uuid::new_v4()).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(UserLoginError::DatabaseError)?;
+    Ok(Json(tokens))
+}
+
+// Each refresh token works once. Presenting a used one means it leaked, so the whole family
+// is revoked and the client has to sign in again.
+#[tracing::instrument(name = "Refreshing client tokens", skip(state, payload))]
+pub async fn refresh_token(
+    context: RequestContext,
+    State(state): State<AppState>,
+    ValidatedForm(payload): ValidatedForm<RefreshTokenPayload>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(ClientTokenError::Pool)?;
+    let refresh_token = fetch_refresh_token(&mut transaction, &payload.refresh_token).await?;
+    if refresh_token.revoked_at.is_some() || refresh_token.is_expired() {
+        return Err(ClientTokenError::InvalidRefreshToken.into());
+    }
+
+    if !mark_refresh_token_used(&mut transaction, refresh_token.identifier).await? {
+        revoke_token_family(&mut transaction, refresh_token.family_id).await?;
+        record_audit_event(
+            &mut transaction,
+            NewAuditEvent::new(AuditEventType::SessionsRevoked, &context)
+                .target(refresh_token.user_id)
+                .details(json!({"reason": "refresh_token_reuse"})),
+        )
+        .await?;
+        transaction
+            .commit()
+            .await
+            .map_err(ClientTokenError::DatabaseError)?;
+        return Err(ClientTokenError::RefreshTokenReused.into());
+    }
+
+    let user = fetch_user(&mut transaction, refresh_token.user_id)
+        .await
+        .map_err(UserError::UserFetchError)?;
+    check_active(&user)?;
+    let tokens = issue_client_tokens(
+        &mut transaction,
+        &state.settings,
+        user.id,
+        refresh_token.family_id,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(ClientTokenError::DatabaseError)?;
+    Ok(Json(tokens))
+}
+
+// Unknown tokens are accepted too, the client only needs to know the token is gone.
+#[tracing::instrument(name = "Revoking client tokens", skip(state, payload))]
+pub async fn revoke_refresh_token(
+    State(state): State<AppState>,
+    ValidatedForm(payload): ValidatedForm<RefreshTokenPayload>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(ClientTokenError::Pool)?;
+    match fetch_refresh_token(&mut transaction, &payload.refresh_token).await {
+        Ok(refresh_token) => revoke_token_family(&mut transaction, refresh_token.family_id).await?,
+        Err(ClientTokenError::InvalidRefreshToken) => {}
+        Err(err) => return Err(err.into()),
+    }
+    transaction
+        .commit()
+        .await
+        .map_err(ClientTokenError::DatabaseError)?;
+    Ok(Json(json!({"ok": true})))
+}
@@ -1,17 +1,20 @@
 use crate::errors::auth::UserRegistrationError;
 use crate::errors::confirm::ConfirmUserError;
 use crate::errors::confirm::ConfirmUserError::UserAlreadyVerified;
+use crate::extractors::audit::{AuditEventType, NewAuditEvent, RequestContext};
 use crate::extractors::authentication::LoggedInUser;
 use crate::extractors::confirmation::{Confirmation, ConfirmationActionType};
 use crate::extractors::user::User;
+use crate::helpers::audit::record_audit_event;
 use crate::helpers::confirmation;
 use crate::helpers::confirmation::{
-    add_confirmation, delete_confirmation, mark_user_as_confirmed, send_verification_link,
+    add_confirmation, change_user_email, delete_confirmation, mark_user_as_confirmed,
+    send_verification_link,
 };
 use axum::extract::{Path, State};
 use axum::response::IntoResponse;
 use axum::Json;
-use serde_json::json;
+use serde_json::{json, Value};
 use sqlx::PgConnection;
 use utils::errors::ErrorPayload;
 use utils::state::AppState;
@@ -19,6 +22,7 @@ use utils::state::AppState;
 pub async fn confirm(
     State(state): State<AppState>,
     Path(token): Path<String>,
+    context: RequestContext,
     user: LoggedInUser,
 ) -> Result<impl IntoResponse, ErrorPayload> {
     let pool = &state.connection;
@@ -35,29 +39,44 @@ pub async fn confirm(
         ))?;
     }
 
-    let response = match confirmation.action_type {
-        ConfirmationActionType::UserVerification => {
-            Ok(verify_user(&mut transaction, &confirmation, user).await?)
-        }
+    let user_id = user.id;
+    let (response, event_type) = match confirmation.action_type {
+        ConfirmationActionType::UserVerification => (
+            verify_user(&mut transaction, &confirmation, user).await?,
+            AuditEventType::EmailVerified,
+        ),
         ConfirmationActionType::Invalid => {
             Err(ConfirmUserError::InvalidToken("invalid token type".into()))?
         }
         ConfirmationActionType::PasswordReset => Err(ConfirmUserError::InvalidToken(
             "password reset not supported here".into(),
         ))?,
+        ConfirmationActionType::AccountUnlock => Err(ConfirmUserError::InvalidToken(
+            "account unlock not supported here".into(),
+        ))?,
+        ConfirmationActionType::AccountRestore => Err(ConfirmUserError::InvalidToken(
+            "account restore not supported here".into(),
+        ))?,
+        ConfirmationActionType::EmailChange => (
+            apply_email_change(&mut transaction, &confirmation, user).await?,
+            AuditEventType::EmailChanged,
+        ),
     };
+    record_audit_event(
+        &mut transaction,
+        NewAuditEvent::for_user(event_type, &context, user_id).details(json!({
+            "email": confirmation_email(&confirmation)?,
+        })),
+    )
+    .await?;
     transaction
         .commit()
         .await
         .map_err(UserRegistrationError::TransactionCommitError)?;
-    response
+    Ok(response)
 }
 
-async fn verify_user(
-    transaction: &mut PgConnection,
-    confirmation: &Confirmation,
-    user: User,
-) -> Result<impl IntoResponse, ConfirmUserError> {
+fn confirmation_email(confirmation: &Confirmation) -> Result<String, ConfirmUserError> {
     let confirmation_email = confirmation
         .details
         .clone()
@@ -73,6 +92,15 @@ async fn verify_user(
     let confirmation_email = confirmation_email
         .as_str()
         .ok_or(ConfirmUserError::InvalidToken("invalid email set".into()))?;
+    Ok(confirmation_email.to_string())
+}
+
+async fn verify_user(
+    transaction: &mut PgConnection,
+    confirmation: &Confirmation,
+    user: User,
+) -> Result<Json<Value>, ConfirmUserError> {
+    let confirmation_email = confirmation_email(confirmation)?;
     if user.email.ne(&confirmation_email) {
         delete_confirmation(transaction, &confirmation.confirmation_id.to_string()).await?;
         Err(ConfirmUserError::InvalidToken("email mismatch".into()))?;
@@ -81,6 +109,16 @@ async fn verify_user(
     Ok(Json(json!({})))
 }
 
+async fn apply_email_change(
+    transaction: &mut PgConnection,
+    confirmation: &Confirmation,
+    user: User,
+) -> Result<Json<Value>, ConfirmUserError> {
+    let confirmation_email = confirmation_email(confirmation)?;
+    change_user_email(transaction, user.id, &confirmation_email).await?;
+    Ok(Json(json!({"email": confirmation_email})))
+}
+
 pub async fn resend_verification(
     State(state): State<AppState>,
     user: LoggedInUser,
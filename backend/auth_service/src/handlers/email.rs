@@ -0,0 +1,98 @@
+use crate::errors::auth::UserRegistrationError;
+use crate::errors::user::UserError;
+use crate::extractors::authentication::LoggedInUser;
+use crate::extractors::confirmation::{Confirmation, ConfirmationActionType};
+use crate::extractors::user::User;
+use crate::helpers::confirmation::{
+    add_confirmation, clear_confirmation_action_type, send_confirmation_link_to, send_notice_email,
+};
+use crate::helpers::user::fetch_by_email;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use email_clients::email::EmailAddress;
+use serde::Deserialize;
+use serde_json::json;
+use utils::errors::ErrorPayload;
+use utils::state::AppState;
+use utils::validation::ValidatedForm;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ChangeEmailPayload {
+    #[validate(length(min = 1, max = 255, message = "Email cannot be empty"), email)]
+    pub email: String,
+    #[validate(length(min = 1, max = 1024, message = "Password cannot be empty"))]
+    pub password: String,
+}
+
+#[tracing::instrument(name = "Starting an email change", skip(state, user, payload), fields(username = % user.user.username))]
+pub async fn change_email(
+    State(state): State<AppState>,
+    user: LoggedInUser,
+    ValidatedForm(payload): ValidatedForm<ChangeEmailPayload>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    user.credential.require_session()?;
+    let LoggedInUser { user, .. } = user;
+    let pool = &state.connection;
+
+    if !user.check_password(&payload.password, &state.settings.password_hash) {
+        Err(UserError::IncorrectPassword)?;
+    }
+    let email = User::normalize_email(&payload.email);
+    let mut transaction = pool.begin().await.map_err(UserRegistrationError::Pool)?;
+    let existing = fetch_by_email(&mut transaction, &email)
+        .await
+        .map_err(UserRegistrationError::EmailCheckError)?;
+    if existing.is_some() {
+        Err(UserRegistrationError::EmailNotAvailable)?;
+    }
+
+    // Only the latest requested address can be confirmed.
+    clear_confirmation_action_type(
+        &mut transaction,
+        user.id,
+        ConfirmationActionType::EmailChange,
+    )
+    .await?;
+    let (confirmation, confirmation_token) = Confirmation::new(
+        user.id,
+        ConfirmationActionType::EmailChange,
+        json!({"email": email}),
+    );
+    add_confirmation(&mut transaction, &confirmation).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(UserRegistrationError::TransactionCommitError)?;
+
+    let recipient = EmailAddress {
+        name: user.name.clone(),
+        email: email.clone(),
+    };
+    send_confirmation_link_to(&state, recipient, &confirmation, confirmation_token).await?;
+
+    let recipient = EmailAddress {
+        name: user.name.clone(),
+        email: user.email.clone(),
+    };
+    send_notice_email(
+        &state,
+        recipient,
+        "A change of your email address was requested",
+        format!(
+            "A request was made to change the email address of username: {} to {}. \
+            The change only happens once the new address is confirmed. If you didn't request \
+            it, please change your password.",
+            user.username, email
+        ),
+        format!(
+            "A request was made to change the email address of username: <b>{}</b> to <b>{}</b>. \
+            The change only happens once the new address is confirmed. If you didn't request \
+            it, please change your password.",
+            user.username, email
+        ),
+    );
+
+    Ok(Json(json!({})))
+}
@@ -0,0 +1,115 @@
+use crate::errors::auth::UserRegistrationError;
+use crate::errors::invitation::InvitationError;
+use crate::extractors::authentication::AdminUser;
+use crate::extractors::invitation::Invitation;
+use crate::helpers::invitation::{
+    check_invitation, delete_invitation, fetch_invitation, insert_invitation,
+    list_pending_invitations, send_invitation, update_invitation_verifier,
+};
+use crate::helpers::user::fetch_by_email;
+use crate::payload::InvitePayload;
+use axum::extract::{Path, State};
+use axum::response::IntoResponse;
+use axum::Json;
+use serde_json::json;
+use utils::errors::ErrorPayload;
+use utils::state::AppState;
+use utils::validation::ValidatedForm;
+use uuid::Uuid;
+
+// Lets the sign-up page know whether to show the form at all.
+pub async fn registration_mode(State(state): State<AppState>) -> impl IntoResponse {
+    Json(json!({"mode": state.settings.registration.mode}))
+}
+
+#[tracing::instrument(name = "Checking invitation", skip(state, token))]
+pub async fn invitation_detail(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(InvitationError::Pool)?;
+    let invitation = check_invitation(&mut connection, &token).await?;
+    Ok(Json(json!({
+        "email": invitation.email,
+        "role": invitation.role,
+        "expires_at": invitation.expires_at,
+    })))
+}
+
+#[tracing::instrument(name = "Listing invitations", skip(admin, state), fields(admin = % admin.user.username))]
+pub async fn list_invitations(
+    admin: AdminUser,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(InvitationError::Pool)?;
+    let invitations = list_pending_invitations(&mut connection).await?;
+    Ok(Json(invitations))
+}
+
+#[tracing::instrument(name = "Creating invitation", skip(admin, state, payload), fields(admin = % admin.user.username, email = % payload.email))]
+pub async fn create_invitation(
+    admin: AdminUser,
+    State(state): State<AppState>,
+    ValidatedForm(payload): ValidatedForm<InvitePayload>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(InvitationError::Pool)?;
+    let existing = fetch_by_email(&mut transaction, &payload.email)
+        .await
+        .map_err(UserRegistrationError::EmailCheckError)?;
+    if existing.is_some() {
+        Err(UserRegistrationError::EmailNotAvailable)?;
+    }
+
+    let (invitation, token) = Invitation::new(
+        &payload.email,
+        payload.role,
+        admin.user.id,
+        state.settings.registration.invite_expiry(),
+    );
+    insert_invitation(&mut transaction, &invitation).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(InvitationError::DatabaseError)?;
+
+    send_invitation(&state, &invitation, token);
+    Ok(Json(invitation))
+}
+
+#[tracing::instrument(name = "Resending invitation", skip(admin, state), fields(admin = % admin.user.username))]
+pub async fn resend_invitation(
+    admin: AdminUser,
+    State(state): State<AppState>,
+    Path(identifier): Path<Uuid>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(InvitationError::Pool)?;
+    let mut invitation = fetch_invitation(&mut transaction, identifier).await?;
+    if invitation.accepted_at.is_some() {
+        Err(InvitationError::NotFound)?;
+    }
+    let token = invitation.renew(state.settings.registration.invite_expiry());
+    update_invitation_verifier(&mut transaction, &invitation).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(InvitationError::DatabaseError)?;
+
+    send_invitation(&state, &invitation, token);
+    Ok(Json(invitation))
+}
+
+#[tracing::instrument(name = "Revoking invitation", skip(admin, state), fields(admin = % admin.user.username))]
+pub async fn revoke_invitation(
+    admin: AdminUser,
+    State(state): State<AppState>,
+    Path(identifier): Path<Uuid>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(InvitationError::Pool)?;
+    delete_invitation(&mut connection, identifier).await?;
+    Ok(Json(json!({"ok": true})))
+}
@@ -1,21 +1,28 @@
+use crate::errors::audit::AuditError;
 use crate::errors::auth::{UserLoginError, UserRegistrationError};
-use crate::extractors::session::SESSION_TOKEN_COOKIE;
+use crate::extractors::audit::{AuditEventType, NewAuditEvent, RequestContext};
+use crate::extractors::session::session_cookie;
 
+use crate::extractors::user::User;
+use crate::helpers::audit::{record_audit_event, record_audit_event_now};
 use crate::helpers::sessions::create_new_session;
-use crate::helpers::user::fetch_by_username;
+use crate::helpers::throttle::{login_ip_key, login_user_key, record_login_failure};
+use crate::helpers::user::{fetch_by_login, update_password};
 use axum::extract::State;
 use axum::http::header::AUTHORIZATION;
 use axum::http::HeaderValue;
 use axum::response::IntoResponse;
 use axum::Json;
-use axum_extra::extract::cookie::Cookie;
 use axum_extra::extract::SignedCookieJar;
+use secrecy::Secret;
 use serde::Deserialize;
 use serde_json::json;
+use sqlx::{Postgres, Transaction};
 use validator::Validate;
 
 use crate::extractors::confirmation::ConfirmationActionType;
 use crate::helpers::confirmation::clear_confirmation_action_type;
+use utils::client_ip::ClientIp;
 use utils::errors::ErrorPayload;
 use utils::state::AppState;
 use utils::validation::ValidatedForm;
@@ -23,11 +30,12 @@ use utils::validation::ValidatedForm;
 #[derive(Debug, Deserialize, Validate)]
 pub struct LoginForm {
     #[validate(
-        length(min = 3, message = "Username cannot be empty"),
+        length(min = 3, message = "Username or email cannot be empty"),
         non_control_character
     )]
+    // Either the username or the email address of the account.
     pub username: String,
-    #[validate(length(min = 8, max = 72, message = "Password must contains 8-72 characters"))]
+    #[validate(length(min = 1, max = 1024, message = "Password cannot be empty"))]
     pub password: String,
 }
 
@@ -38,44 +46,145 @@ username = % payload.username,
 )]
 pub async fn login(
     jar: SignedCookieJar,
+    ClientIp(ip): ClientIp,
+    context: RequestContext,
     State(state): State<AppState>,
     ValidatedForm(payload): ValidatedForm<LoginForm>,
 ) -> Result<impl IntoResponse, ErrorPayload> {
+    let (user, mut transaction) = authenticate(&state, ip.as_deref(), &context, &payload).await?;
+    let session_token = create_new_session(&mut transaction, user.id, json!({}))
+        .await
+        .map_err(UserLoginError::UnexpectedUserError)?;
+
+    transaction
+        .commit()
+        .await
+        .map_err(UserLoginError::DatabaseError)?;
+
+    let session_header =
+        HeaderValue::from_str(&session_token).map_err(UserRegistrationError::HeaderError)?;
+
+    let jar = jar.add(session_cookie(session_token, &state.settings.cookie));
+
+    let mut response = (jar, Json(user)).into_response();
+    response.headers_mut().insert(AUTHORIZATION, session_header);
+    Ok(response)
+}
+
+// Checks the credentials with throttling and auditing, leaving the open transaction to the caller
+// to attach whatever credential it hands out.
+pub(crate) async fn authenticate(
+    state: &AppState,
+    ip: Option<&str>,
+    context: &RequestContext,
+    payload: &LoginForm,
+) -> Result<(User, Transaction<'static, Postgres>), ErrorPayload> {
     let pool = &state.connection;
+    let limits = &state.settings.rate_limit;
+    let hardened = state.settings.security.hardened;
+    let ip_key = ip.map(login_ip_key);
+
+    if let Some(ip_key) = &ip_key {
+        state
+            .rate_limiter
+            .check(ip_key, &limits.ip_policy())
+            .await?;
+    }
 
     let mut transaction = pool.begin().await.map_err(UserLoginError::Pool)?;
 
-    let user = fetch_by_username(&mut transaction, &payload.username)
+    let user = fetch_by_login(&mut transaction, &payload.username)
         .await
         .map_err(UserLoginError::UnexpectedError)?;
-    let user = user.ok_or(UserLoginError::LoginFailed("username not found".into()))?;
-
-    if user.check_password(&payload.password) {
-        let session_token = create_new_session(&mut transaction, user.id, json!({}))
-            .await
-            .map_err(UserLoginError::UnexpectedUserError)?;
+    // Keyed on the account, so its username and email share one counter.
+    let user_key = match &user {
+        Some(user) => login_user_key(&user.normalized_username),
+        None => login_user_key(&payload.username),
+    };
+    state
+        .rate_limiter
+        .check(&user_key, &limits.login_policy())
+        .await?;
+    let user = match user {
+        Some(user) => user,
+        None => {
+            // Always hashed, outside of the hardened mode as well, so the response time doesn't
+            // tell unknown logins apart from wrong passwords.
+            User::dummy_password_check(&payload.password, &state.settings.password_hash);
+            record_login_failure(state, &user_key, ip_key.as_deref(), None).await?;
+            let reason = if hardened {
+                "username or password is incorrect"
+            } else {
+                "username not found"
+            };
+            audit_login_failure(state, context, None, &payload.username, reason).await?;
+            return Err(UserLoginError::LoginFailed(reason.into()).into());
+        }
+    };
 
-        clear_confirmation_action_type(
+    if !user.check_password(&payload.password, &state.settings.password_hash) {
+        record_login_failure(state, &user_key, ip_key.as_deref(), Some(&user)).await?;
+        let reason = "username or password is incorrect";
+        audit_login_failure(state, context, Some(&user), &payload.username, reason).await?;
+        return Err(UserLoginError::LoginFailed(reason.into()).into());
+    }
+    if !user.is_active {
+        let reason = match user.deleted_at {
+            Some(_) => "account is scheduled for deletion",
+            None => "account is deactivated",
+        };
+        audit_login_failure(state, context, Some(&user), &payload.username, reason).await?;
+        return Err(UserLoginError::LoginFailed(reason.into()).into());
+    }
+    if let Some(err) = user.suspension_error() {
+        let reason = "account is suspended";
+        audit_login_failure(state, context, Some(&user), &payload.username, reason).await?;
+        return Err(err.into());
+    }
+    if user.needs_rehash(&state.settings.password_hash) {
+        update_password(
             &mut transaction,
             user.id,
-            ConfirmationActionType::PasswordReset,
+            Secret::from(payload.password.clone()),
+            &state.settings.password_hash,
         )
-        .await?;
-
-        transaction
-            .commit()
-            .await
-            .map_err(UserLoginError::DatabaseError)?;
-
-        let session_header =
-            HeaderValue::from_str(&session_token).map_err(UserRegistrationError::HeaderError)?;
+        .await
+        .map_err(UserLoginError::UnexpectedUserError)?;
+    }
 
-        let jar = jar.add(Cookie::new(SESSION_TOKEN_COOKIE, session_token));
+    clear_confirmation_action_type(
+        &mut transaction,
+        user.id,
+        ConfirmationActionType::PasswordReset,
+    )
+    .await?;
+    clear_confirmation_action_type(
+        &mut transaction,
+        user.id,
+        ConfirmationActionType::AccountUnlock,
+    )
+    .await?;
+    state.rate_limiter.clear(&user_key).await?;
+    record_audit_event(
+        &mut transaction,
+        NewAuditEvent::for_user(AuditEventType::LoginSucceeded, context, user.id),
+    )
+    .await?;
+    Ok((user, transaction))
+}
 
-        let mut response = (jar, Json(user)).into_response();
-        response.headers_mut().insert(AUTHORIZATION, session_header);
-        Ok(response)
-    } else {
-        Err(UserLoginError::LoginFailed("username or password is incorrect".into()).into())
+// Written outside the login transaction, which is rolled back on failure.
+async fn audit_login_failure(
+    state: &AppState,
+    context: &RequestContext,
+    user: Option<&User>,
+    username: &str,
+    reason: &str,
+) -> Result<(), AuditError> {
+    let mut event = NewAuditEvent::new(AuditEventType::LoginFailed, context)
+        .details(json!({"username": username, "reason": reason}));
+    if let Some(user) = user {
+        event = event.target(user.id);
     }
+    record_audit_event_now(&state.connection, event).await
 }
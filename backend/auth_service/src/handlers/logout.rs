@@ -1,6 +1,10 @@
 use crate::errors::auth::UserLoginError;
 use crate::errors::user::UserError;
-use crate::extractors::authentication::LoggedInUser;
+use crate::extractors::audit::{AuditEventType, NewAuditEvent, RequestContext};
+use crate::extractors::authentication::{Credential, LoggedInUser};
+use crate::helpers::api_tokens::revoke_api_token;
+use crate::helpers::audit::record_audit_event;
+use crate::helpers::client_tokens::revoke_token_family;
 use crate::helpers::sessions::delete_session;
 use axum::extract::State;
 use axum::response::IntoResponse;
@@ -12,11 +16,24 @@ use utils::state::AppState;
 #[tracing::instrument(name = "getting current login", skip(user, state), fields(username = % user.user.username))]
 pub async fn logout(
     user: LoggedInUser,
+    context: RequestContext,
     State(state): State<AppState>,
 ) -> Result<impl IntoResponse, ErrorPayload> {
     let pool = &state.connection;
     let mut transaction = pool.begin().await.map_err(UserError::SessionError)?;
-    delete_session(&mut transaction, user.session).await?;
+    match user.credential {
+        Credential::AccessToken => revoke_token_family(&mut transaction, user.session).await?,
+        // Logging out with an api token revokes the token itself.
+        Credential::ApiToken(_) => {
+            revoke_api_token(&mut transaction, user.user.id, user.session).await?;
+        }
+        Credential::Session => delete_session(&mut transaction, user.session).await?,
+    }
+    record_audit_event(
+        &mut transaction,
+        NewAuditEvent::for_user(AuditEventType::Logout, &context, user.user.id),
+    )
+    .await?;
 
     transaction
         .commit()
@@ -1,8 +1,47 @@
+use crate::errors::auth::UserRegistrationError;
 use crate::extractors::authentication::LoggedInUser;
+use crate::extractors::user::User;
+use crate::helpers::user::update_profile;
+use crate::payload::UpdateProfilePayload;
+use axum::extract::State;
 use axum::response::IntoResponse;
 use axum::Json;
+use utils::errors::ErrorPayload;
+use utils::state::AppState;
+use utils::validation::ValidatedForm;
 
 #[tracing::instrument(name = "getting current login", skip(user), fields(username = %user.user.username))]
 pub async fn me(user: LoggedInUser) -> impl IntoResponse {
     Json(user.user)
 }
+
+#[tracing::instrument(name = "updating current login", skip(user, state, payload), fields(username = %user.user.username))]
+pub async fn update_me(
+    State(state): State<AppState>,
+    user: LoggedInUser,
+    ValidatedForm(payload): ValidatedForm<UpdateProfilePayload>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    user.credential.require_session()?;
+    let LoggedInUser { user, .. } = user;
+    let pool = &state.connection;
+
+    let name = payload.name.unwrap_or(user.name);
+    let username = payload.username.unwrap_or(user.username);
+    let normalized_username = User::normalize_username(&username)?;
+
+    let mut transaction = pool.begin().await.map_err(UserRegistrationError::Pool)?;
+    let user = update_profile(
+        &mut transaction,
+        user.id,
+        &name,
+        &username,
+        &normalized_username,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(UserRegistrationError::TransactionCommitError)?;
+
+    Ok(Json(user))
+}
@@ -1,6 +1,15 @@
+pub mod account;
+pub mod admin;
+pub mod api_tokens;
+pub mod audit;
+pub mod client_tokens;
 pub mod confirmation;
+pub mod email;
+pub mod invitation;
 pub mod login;
 pub mod logout;
 pub mod me;
+pub mod password;
 pub mod registration;
 pub mod reset;
+pub mod unlock;
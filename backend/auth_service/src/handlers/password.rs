@@ -0,0 +1,95 @@
+use crate::errors::auth::UserRegistrationError;
+use crate::errors::user::UserError;
+use crate::extractors::audit::{AuditEventType, NewAuditEvent, RequestContext};
+use crate::extractors::authentication::LoggedInUser;
+use crate::extractors::confirmation::ConfirmationActionType;
+use crate::helpers::audit::record_audit_event;
+use crate::helpers::confirmation::{clear_confirmation_action_type, send_notice_email};
+use crate::helpers::password::check_new_password;
+use crate::helpers::sessions::clear_other_sessions;
+use crate::helpers::user::update_password;
+use crate::payload::ChangePasswordPayload;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use email_clients::email::EmailAddress;
+use secrecy::Secret;
+use serde_json::json;
+use utils::errors::ErrorPayload;
+use utils::state::AppState;
+use utils::validation::ValidatedForm;
+
+#[tracing::instrument(name = "Changing password", skip(state, user, payload), fields(username = % user.user.username))]
+pub async fn change_password(
+    State(state): State<AppState>,
+    context: RequestContext,
+    user: LoggedInUser,
+    ValidatedForm(payload): ValidatedForm<ChangePasswordPayload>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    user.credential.require_session()?;
+    let LoggedInUser { user, session, .. } = user;
+    let pool = &state.connection;
+
+    if !user.check_password(&payload.current_password, &state.settings.password_hash) {
+        Err(UserError::IncorrectPassword)?;
+    }
+    check_new_password(
+        &state.settings.password_policy,
+        &payload.password,
+        &[&user.name, &user.username],
+    )
+    .await?;
+
+    let mut transaction = pool.begin().await.map_err(UserRegistrationError::Pool)?;
+    let count = update_password(
+        &mut transaction,
+        user.id,
+        Secret::from(payload.password),
+        &state.settings.password_hash,
+    )
+    .await?;
+    if count < 1 {
+        Err(UserError::UnexpectedError)?;
+    }
+    if payload.revoke_other_sessions {
+        clear_other_sessions(&mut transaction, user.id, session).await?;
+    }
+    clear_confirmation_action_type(
+        &mut transaction,
+        user.id,
+        ConfirmationActionType::PasswordReset,
+    )
+    .await?;
+    record_audit_event(
+        &mut transaction,
+        NewAuditEvent::for_user(AuditEventType::PasswordChanged, &context, user.id)
+            .details(json!({"revoked_other_sessions": payload.revoke_other_sessions})),
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(UserRegistrationError::TransactionCommitError)?;
+
+    let recipient = EmailAddress {
+        name: user.name.clone(),
+        email: user.email.clone(),
+    };
+    send_notice_email(
+        &state,
+        recipient,
+        "Your password was changed recently",
+        format!(
+            "The password for username: {} was changed from the account settings. \
+            If you didn't change it, please reset your password immediately.",
+            user.username
+        ),
+        format!(
+            "The password for username: <b>{}</b> was changed from the account settings. \
+            If you didn't change it, please reset your password immediately.",
+            user.username
+        ),
+    );
+
+    Ok(Json(json!({"ok": true})))
+}
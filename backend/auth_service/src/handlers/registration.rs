@@ -1,19 +1,26 @@
 use crate::errors::auth::UserRegistrationError;
+use crate::errors::invitation::InvitationError;
+use crate::extractors::audit::{AuditEventType, NewAuditEvent, RequestContext};
 use crate::extractors::confirmation::{Confirmation, ConfirmationActionType};
-use crate::extractors::session::SESSION_TOKEN_COOKIE;
+use crate::extractors::invitation::InviteRole;
+use crate::extractors::session::session_cookie;
 use crate::extractors::user::User;
-use crate::helpers::confirmation::{add_confirmation, send_verification_link};
+use crate::helpers::audit::record_audit_event;
+use crate::helpers::confirmation::{add_confirmation, send_notice_email, send_verification_link};
+use crate::helpers::invitation::{check_invitation, mark_invitation_accepted};
+use crate::helpers::password::check_new_password;
 use crate::helpers::sessions::create_new_session;
-use crate::helpers::user::insert_user;
+use crate::helpers::user::{fetch_by_email, insert_user};
 use crate::payload::RegisterPayload;
 use axum::extract::State;
 use axum::http::header::AUTHORIZATION;
-use axum::http::HeaderValue;
-use axum::response::IntoResponse;
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
 use axum::Json;
-use axum_extra::extract::cookie::Cookie;
 use axum_extra::extract::SignedCookieJar;
+use email_clients::email::EmailAddress;
 use serde_json::json;
+use utils::configuration::RegistrationMode;
 use utils::errors::ErrorPayload;
 use utils::state::AppState;
 use utils::validation::ValidatedForm;
@@ -28,22 +35,82 @@ email = % payload.email
 pub async fn register(
     jar: SignedCookieJar,
     State(state): State<AppState>,
+    context: RequestContext,
     ValidatedForm(payload): ValidatedForm<RegisterPayload>,
 ) -> Result<impl IntoResponse, ErrorPayload> {
     let pool = &state.connection;
 
     let mut transaction = pool.begin().await.map_err(UserRegistrationError::Pool)?;
 
-    User::check_acceptable_password(&payload.password, &[&payload.name, &payload.username])?;
-    let mut user = User::try_from(payload)?;
+    let invitation = match (&state.settings.registration.mode, &payload.invitation) {
+        (RegistrationMode::Closed, _) => Err(InvitationError::RegistrationClosed)?,
+        (RegistrationMode::InviteOnly, None) => Err(InvitationError::InvitationRequired)?,
+        (_, Some(token)) => Some(check_invitation(&mut transaction, token).await?),
+        (RegistrationMode::Open, None) => None,
+    };
+
+    check_new_password(
+        &state.settings.password_policy,
+        &payload.password,
+        &[&payload.name, &payload.username],
+    )
+    .await?;
+    let mut user = User::from_registration(payload, &state.settings.password_hash)?;
+    let hardened = state.settings.security.hardened && invitation.is_none();
+    if hardened {
+        let existing = fetch_by_email(&mut transaction, &user.email)
+            .await
+            .map_err(UserRegistrationError::EmailCheckError)?;
+        if let Some(existing) = existing {
+            notify_existing_owner(&state, &existing);
+            return Ok(pending_response());
+        }
+    }
+    // The invitation link already proves ownership of the address.
+    if let Some(invitation) = &invitation {
+        if invitation.email != user.email {
+            Err(InvitationError::EmailMismatch)?;
+        }
+        user.is_confirmed = true;
+        user.is_admin = invitation.role == InviteRole::Admin;
+    }
     let id = insert_user(&mut transaction, &user).await?;
     user.id = id;
-    let (confirmation, confirmation_token) = Confirmation::new(
-        user.id,
-        ConfirmationActionType::UserVerification,
-        json!({"email": user.email}),
-    );
-    add_confirmation(&mut transaction, &confirmation).await?;
+    let verification = match &invitation {
+        Some(invitation) => {
+            mark_invitation_accepted(&mut transaction, invitation.identifier, user.id).await?;
+            if user.is_admin {
+                let event = NewAuditEvent::new(AuditEventType::RoleChanged, &context)
+                    .target(user.id)
+                    .details(json!({"is_admin": true, "invitation": invitation.identifier}));
+                let event = match invitation.inviter_id {
+                    Some(inviter_id) => event.actor(inviter_id),
+                    None => event,
+                };
+                record_audit_event(&mut transaction, event).await?;
+            }
+            None
+        }
+        None => {
+            let (confirmation, confirmation_token) = Confirmation::new(
+                user.id,
+                ConfirmationActionType::UserVerification,
+                json!({"email": user.email}),
+            );
+            add_confirmation(&mut transaction, &confirmation).await?;
+            Some((confirmation, confirmation_token))
+        }
+    };
+    if hardened {
+        transaction
+            .commit()
+            .await
+            .map_err(UserRegistrationError::TransactionCommitError)?;
+        if let Some((confirmation, confirmation_token)) = verification {
+            send_verification_link(&state, &user, &confirmation, confirmation_token).await?;
+        }
+        return Ok(pending_response());
+    }
     let session_token = create_new_session(&mut transaction, user.id, json!({})).await?;
 
     transaction
@@ -51,12 +118,43 @@ pub async fn register(
         .await
         .map_err(UserRegistrationError::TransactionCommitError)?;
 
-    send_verification_link(&state, &user, &confirmation, confirmation_token).await?;
+    if let Some((confirmation, confirmation_token)) = verification {
+        send_verification_link(&state, &user, &confirmation, confirmation_token).await?;
+    }
     let session_header =
         HeaderValue::from_str(&session_token).map_err(UserRegistrationError::HeaderError)?;
 
-    let jar = jar.add(Cookie::new(SESSION_TOKEN_COOKIE, session_token));
+    let jar = jar.add(session_cookie(session_token, &state.settings.cookie));
     let mut response = (jar, Json(user)).into_response();
     response.headers_mut().insert(AUTHORIZATION, session_header);
     Ok(response)
 }
+
+// Same body whether the email was free or already taken.
+fn pending_response() -> Response {
+    (StatusCode::ACCEPTED, Json(json!({"pending": true}))).into_response()
+}
+
+fn notify_existing_owner(state: &AppState, user: &User) {
+    let recipient = EmailAddress {
+        name: user.name.clone(),
+        email: user.email.clone(),
+    };
+    send_notice_email(
+        state,
+        recipient,
+        "Someone tried to sign up with your email",
+        format!(
+            "Someone tried to create a new account with this email address, which already \
+            belongs to username: {}. If it was you, sign in or reset your password instead. \
+            Otherwise you can ignore this email.",
+            user.username
+        ),
+        format!(
+            "Someone tried to create a new account with this email address, which already \
+            belongs to username: <b>{}</b>. If it was you, sign in or reset your password instead. \
+            Otherwise you can ignore this email.",
+            user.username
+        ),
+    );
+}
@@ -1,25 +1,30 @@
 use crate::errors::auth::UserRegistrationError;
 use crate::errors::confirm::ConfirmUserError;
 use crate::errors::user::UserError;
+use crate::extractors::audit::{AuditEventType, NewAuditEvent, RequestContext};
 use crate::extractors::confirmation::{Confirmation, ConfirmationActionType};
-use crate::extractors::session::SESSION_TOKEN_COOKIE;
+use crate::extractors::session::session_cookie;
 use crate::extractors::user::User;
+use crate::helpers::audit::record_audit_event;
 use crate::helpers::confirmation::{
     add_confirmation, clear_confirmation_action_type, send_verification_link,
 };
-use crate::helpers::user::{fetch_by_email, fetch_by_username, fetch_user, update_password};
+use crate::helpers::password::check_new_password;
+use crate::helpers::throttle::{login_user_key, reset_ip_key, reset_key};
+use crate::helpers::user::{fetch_by_login, fetch_user, update_password};
 use axum::extract::{Path, State};
 use axum::http::header::AUTHORIZATION;
 use axum::http::HeaderValue;
 use axum::response::IntoResponse;
 use axum::Json;
-use axum_extra::extract::cookie::Cookie;
 use axum_extra::extract::SignedCookieJar;
 use email_clients::email::{EmailAddress, EmailObject};
 use secrecy::Secret;
 use serde::Deserialize;
 use serde_json::json;
+use sqlx::{Postgres, Transaction};
 use tokio::task;
+use utils::client_ip::ClientIp;
 use utils::errors::ErrorPayload;
 use utils::state::{AppState, BackgroundTask};
 use utils::validation::ValidatedForm;
@@ -36,7 +41,7 @@ pub struct InitiateResetPasswordPayload {
 
 #[derive(Debug, Deserialize, Validate)]
 pub struct ResetPasswordPayload {
-    #[validate(length(min = 8, max = 72, message = "Password must contains 8-72 characters"))]
+    #[validate(length(min = 1, max = 1024, message = "Password cannot be empty"))]
     pub password: String,
     #[validate(must_match(
         other = "password",
@@ -46,40 +51,47 @@ pub struct ResetPasswordPayload {
 }
 
 pub async fn initiate_reset_password(
+    ClientIp(ip): ClientIp,
+    context: RequestContext,
     State(state): State<AppState>,
     ValidatedForm(payload): ValidatedForm<InitiateResetPasswordPayload>,
 ) -> Result<impl IntoResponse, ErrorPayload> {
     let pool = &state.connection;
     let tx = state.tasks.clone();
+    let limits = &state.settings.rate_limit;
+
+    // Every request counts, so a victim's inbox cannot be flooded with reset links.
+    let target_key = reset_key(&payload.username_or_email);
+    let ip_key = ip.as_deref().map(reset_ip_key);
+    if let Some(ip_key) = &ip_key {
+        state
+            .rate_limiter
+            .check(ip_key, &limits.ip_policy())
+            .await?;
+    }
+    state
+        .rate_limiter
+        .check(&target_key, &limits.reset_policy())
+        .await?;
+    if let Some(ip_key) = &ip_key {
+        state.rate_limiter.hit(ip_key, &limits.ip_policy()).await?;
+    }
+    state
+        .rate_limiter
+        .hit(&target_key, &limits.reset_policy())
+        .await?;
 
     let mut transaction = pool.begin().await.map_err(UserRegistrationError::Pool)?;
-    let user = if payload.username_or_email.contains('@') {
-        fetch_by_email(&mut transaction, &payload.username_or_email)
-            .await
-            .map_err(UserRegistrationError::EmailCheckError)?
-    } else {
-        fetch_by_username(&mut transaction, &payload.username_or_email)
-            .await
-            .map_err(UserRegistrationError::UsernameCheck)?
-    };
+    let user = fetch_by_login(&mut transaction, &payload.username_or_email)
+        .await
+        .map_err(UserRegistrationError::UsernameCheck)?;
 
     tracing::info!("Starting password reset");
     let handle = task::spawn(async move {
         if let Some(user) = user {
-            let (confirmation, confirmation_token) =
-                Confirmation::new(user.id, ConfirmationActionType::PasswordReset, json!({}));
-
-            add_confirmation(&mut transaction, &confirmation)
-                .await
-                .expect("Unable to add confirmation");
-            send_verification_link(&state, &user, &confirmation, confirmation_token)
-                .await
-                .expect("Failed to send verification email");
-            transaction
-                .commit()
-                .await
-                .map_err(UserRegistrationError::TransactionCommitError)
-                .expect("Failed to commit the transaction");
+            if let Err(err) = start_password_reset(&state, transaction, &context, &user).await {
+                tracing::error!("Unable to start password reset: {:?}", err);
+            }
         }
     });
     if let Some(tx) = tx {
@@ -89,6 +101,30 @@ pub async fn initiate_reset_password(
     Ok(Json(json!({})))
 }
 
+// Runs in the background, so the response doesn't reveal whether the account exists.
+async fn start_password_reset(
+    state: &AppState,
+    mut transaction: Transaction<'static, Postgres>,
+    context: &RequestContext,
+    user: &User,
+) -> Result<(), ErrorPayload> {
+    let (confirmation, confirmation_token) =
+        Confirmation::new(user.id, ConfirmationActionType::PasswordReset, json!({}));
+
+    add_confirmation(&mut transaction, &confirmation).await?;
+    record_audit_event(
+        &mut transaction,
+        NewAuditEvent::new(AuditEventType::PasswordResetRequested, context).target(user.id),
+    )
+    .await?;
+    send_verification_link(state, user, &confirmation, confirmation_token).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(UserRegistrationError::TransactionCommitError)?;
+    Ok(())
+}
+
 pub async fn check_reset_token(
     State(state): State<AppState>,
     Path(token): Path<String>,
@@ -113,6 +149,7 @@ pub async fn check_reset_token(
 
 pub async fn reset_password(
     jar: SignedCookieJar,
+    context: RequestContext,
     State(state): State<AppState>,
     Path(token): Path<String>,
     ValidatedForm(payload): ValidatedForm<ResetPasswordPayload>,
@@ -127,9 +164,20 @@ pub async fn reset_password(
     if let ConfirmationActionType::PasswordReset = confirmation.action_type {
         let user_id = confirmation.user_id;
         let user = fetch_user(&mut transaction, user_id).await?;
-        User::check_acceptable_password(&payload.password, &[&user.name, &user.username])?;
+        check_new_password(
+            &state.settings.password_policy,
+            &payload.password,
+            &[&user.name, &user.username],
+        )
+        .await?;
         let password = Secret::from(payload.password);
-        let count = update_password(&mut transaction, user_id, password).await?;
+        let count = update_password(
+            &mut transaction,
+            user_id,
+            password,
+            &state.settings.password_hash,
+        )
+        .await?;
         if count < 1 {
             Err(UserError::UnexpectedError)?;
         }
@@ -140,7 +188,22 @@ pub async fn reset_password(
             ConfirmationActionType::PasswordReset,
         )
         .await?;
+        clear_confirmation_action_type(
+            &mut transaction,
+            user.id,
+            ConfirmationActionType::AccountUnlock,
+        )
+        .await?;
+        state
+            .rate_limiter
+            .clear(&login_user_key(&user.normalized_username))
+            .await?;
         let session_token = create_new_session(&mut transaction, user.id, json!({})).await?;
+        record_audit_event(
+            &mut transaction,
+            NewAuditEvent::for_user(AuditEventType::PasswordResetCompleted, &context, user.id),
+        )
+        .await?;
         transaction
             .commit()
             .await
@@ -181,7 +244,7 @@ pub async fn reset_password(
         let session_header =
             HeaderValue::from_str(&session_token).map_err(UserRegistrationError::HeaderError)?;
 
-        let jar = jar.add(Cookie::new(SESSION_TOKEN_COOKIE, session_token));
+        let jar = jar.add(session_cookie(session_token, &state.settings.cookie));
         let mut response = (jar, Json(user)).into_response();
         response.headers_mut().insert(AUTHORIZATION, session_header);
 
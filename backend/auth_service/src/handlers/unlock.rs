@@ -0,0 +1,47 @@
+use crate::errors::auth::UserRegistrationError;
+use crate::errors::confirm::ConfirmUserError;
+use crate::extractors::confirmation::ConfirmationActionType;
+use crate::helpers::confirmation;
+use crate::helpers::confirmation::clear_confirmation_action_type;
+use crate::helpers::throttle::login_user_key;
+use crate::helpers::user::fetch_user;
+use axum::extract::{Path, State};
+use axum::response::IntoResponse;
+use axum::Json;
+use serde_json::json;
+use utils::errors::ErrorPayload;
+use utils::state::AppState;
+
+pub async fn unlock_account(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+
+    tracing::info!("starting account unlock");
+    let mut transaction = pool.begin().await.map_err(UserRegistrationError::Pool)?;
+
+    let confirmation = confirmation::check_confirmation(token, &mut transaction).await?;
+    if let ConfirmationActionType::AccountUnlock = confirmation.action_type {
+        let user = fetch_user(&mut transaction, confirmation.user_id).await?;
+        state
+            .rate_limiter
+            .clear(&login_user_key(&user.normalized_username))
+            .await?;
+        clear_confirmation_action_type(
+            &mut transaction,
+            user.id,
+            ConfirmationActionType::AccountUnlock,
+        )
+        .await?;
+        transaction
+            .commit()
+            .await
+            .map_err(UserRegistrationError::TransactionCommitError)?;
+        Ok(Json(json!({
+            "username": user.username
+        })))
+    } else {
+        Err(ConfirmUserError::InvalidActionType)?
+    }
+}
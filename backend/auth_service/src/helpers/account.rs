@@ -0,0 +1,297 @@
+use crate::errors::account::AccountError;
+use crate::extractors::data_export::DataExport;
+use crate::extractors::user::User;
+use crate::helpers::confirmation::send_notice_email;
+use chrono::{DateTime, Utc};
+use email_clients::email::EmailAddress;
+use serde_json::{json, Map, Value};
+use sqlx::PgConnection;
+use tokio::task;
+use utils::state::{AppState, BackgroundTask};
+use utils::user_data::UserDataOwner;
+use uuid::Uuid;
+
+#[tracing::instrument(name = "Marking account as deleted", skip(transaction))]
+pub async fn mark_account_deleted(
+    transaction: &mut PgConnection,
+    user_id: i32,
+) -> Result<DateTime<Utc>, AccountError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE users SET is_active = false, deleted_at = now(), updated_at = now()
+        WHERE id = $1 RETURNING deleted_at as "deleted_at!"
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(AccountError::DatabaseError)?;
+    Ok(result.deleted_at)
+}
+
+#[tracing::instrument(name = "Restoring deleted account", skip(transaction))]
+pub async fn restore_deleted_account(
+    transaction: &mut PgConnection,
+    user_id: i32,
+) -> Result<(), AccountError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE users SET is_active = true, deleted_at = null, updated_at = now()
+        WHERE id = $1 AND deleted_at IS NOT NULL
+        "#,
+        user_id
+    )
+    .execute(&mut *transaction)
+    .await
+    .map_err(AccountError::DatabaseError)?;
+    if result.rows_affected() < 1 {
+        Err(AccountError::NotDeleted)?;
+    }
+    Ok(())
+}
+
+// Removes accounts whose grace period ran out along with the data other apps hold for them.
+#[tracing::instrument(name = "Purging deleted accounts", skip(state))]
+pub async fn purge_deleted_accounts(state: &AppState) -> Result<u64, AccountError> {
+    let pool = &state.connection;
+    let cutoff = Utc::now() - state.settings.account.deletion_grace();
+    let mut transaction = pool.begin().await.map_err(AccountError::Pool)?;
+
+    let owners = sqlx::query_as!(
+        UserDataOwner,
+        r#"
+        SELECT id, email FROM users
+        WHERE is_active = false AND deleted_at < $1
+        FOR UPDATE SKIP LOCKED
+        "#,
+        cutoff
+    )
+    .fetch_all(&mut *transaction)
+    .await
+    .map_err(AccountError::DatabaseError)?;
+
+    for owner in &owners {
+        for source in &state.user_data {
+            source
+                .purge(&mut transaction, owner)
+                .await
+                .map_err(|e| AccountError::PurgeFailed(source.name().to_string(), e))?;
+        }
+        sqlx::query!("DELETE FROM users WHERE id = $1", owner.id)
+            .execute(&mut *transaction)
+            .await
+            .map_err(AccountError::DatabaseError)?;
+    }
+
+    sqlx::query!("DELETE FROM data_exports WHERE expires_at < now()")
+        .execute(&mut *transaction)
+        .await
+        .map_err(AccountError::DatabaseError)?;
+    transaction
+        .commit()
+        .await
+        .map_err(AccountError::DatabaseError)?;
+
+    Ok(owners.len() as u64)
+}
+
+#[tracing::instrument(name = "Inserting data export", skip(transaction, export))]
+pub async fn insert_data_export(
+    transaction: &mut PgConnection,
+    export: &DataExport,
+) -> Result<(), AccountError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO data_exports (identifier, user_id, created_at, expires_at)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        export.identifier,
+        export.user_id,
+        export.created_at,
+        export.expires_at
+    )
+    .execute(transaction)
+    .await
+    .map_err(AccountError::DatabaseError)?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "Fetching data export", skip(transaction))]
+pub async fn fetch_data_export(
+    transaction: &mut PgConnection,
+    user_id: i32,
+    identifier: Uuid,
+) -> Result<Option<DataExport>, AccountError> {
+    let export = sqlx::query_as!(
+        DataExport,
+        r#"
+        SELECT identifier, user_id, content, created_at, ready_at, expires_at
+        FROM data_exports WHERE identifier = $1 AND user_id = $2
+        "#,
+        identifier,
+        user_id
+    )
+    .fetch_optional(transaction)
+    .await
+    .map_err(AccountError::DatabaseError)?;
+    Ok(export)
+}
+
+#[tracing::instrument(name = "Collecting user data", skip(connection, state, user), fields(username = % user.username))]
+pub async fn collect_user_data(
+    connection: &mut PgConnection,
+    state: &AppState,
+    user: &User,
+) -> Result<Value, AccountError> {
+    let sessions = sqlx::query!(
+        r#"SELECT identifier, expiration_date, extra_info FROM sessions WHERE user_id = $1"#,
+        user.id
+    )
+    .fetch_all(&mut *connection)
+    .await
+    .map_err(|e| AccountError::ExportFailed("sessions".to_string(), e))?;
+    let confirmations = sqlx::query!(
+        r#"
+        SELECT confirmation_id, action_type, details, created_at, expires_at
+        FROM confirmations WHERE user_id = $1
+        "#,
+        user.id
+    )
+    .fetch_all(&mut *connection)
+    .await
+    .map_err(|e| AccountError::ExportFailed("confirmations".to_string(), e))?;
+    let api_tokens = sqlx::query!(
+        r#"
+        SELECT identifier, name, scopes, created_at, expires_at, last_used_at
+        FROM api_tokens WHERE user_id = $1
+        "#,
+        user.id
+    )
+    .fetch_all(&mut *connection)
+    .await
+    .map_err(|e| AccountError::ExportFailed("api_tokens".to_string(), e))?;
+
+    // Verifier hashes are left out, they are of no use outside this service.
+    let mut data = Map::new();
+    data.insert("profile".to_string(), json!(user));
+    data.insert(
+        "sessions".to_string(),
+        sessions
+            .into_iter()
+            .map(|s| {
+                json!({
+                    "identifier": s.identifier,
+                    "expiration_date": s.expiration_date,
+                    "extra_info": s.extra_info,
+                })
+            })
+            .collect(),
+    );
+    data.insert(
+        "confirmations".to_string(),
+        confirmations
+            .into_iter()
+            .map(|c| {
+                json!({
+                    "confirmation_id": c.confirmation_id,
+                    "action_type": c.action_type,
+                    "details": c.details,
+                    "created_at": c.created_at,
+                    "expires_at": c.expires_at,
+                })
+            })
+            .collect(),
+    );
+    data.insert(
+        "api_tokens".to_string(),
+        api_tokens
+            .into_iter()
+            .map(|t| {
+                json!({
+                    "identifier": t.identifier,
+                    "name": t.name,
+                    "scopes": t.scopes,
+                    "created_at": t.created_at,
+                    "expires_at": t.expires_at,
+                    "last_used_at": t.last_used_at,
+                })
+            })
+            .collect(),
+    );
+
+    let owner = UserDataOwner {
+        id: user.id,
+        email: user.email.clone(),
+    };
+    for source in &state.user_data {
+        let records = source
+            .export(&mut *connection, &owner)
+            .await
+            .map_err(|e| AccountError::ExportFailed(source.name().to_string(), e))?;
+        data.insert(source.name().to_string(), records);
+    }
+
+    Ok(Value::Object(data))
+}
+
+#[tracing::instrument(name = "Completing data export", skip(state, user, export), fields(username = % user.username))]
+async fn complete_data_export(
+    state: &AppState,
+    user: &User,
+    export: &DataExport,
+) -> Result<(), AccountError> {
+    let mut transaction = state.connection.begin().await.map_err(AccountError::Pool)?;
+    let content = collect_user_data(&mut transaction, state, user).await?;
+    sqlx::query!(
+        r#"UPDATE data_exports SET content = $1, ready_at = now() WHERE identifier = $2"#,
+        content,
+        export.identifier
+    )
+    .execute(&mut *transaction)
+    .await
+    .map_err(AccountError::DatabaseError)?;
+    transaction
+        .commit()
+        .await
+        .map_err(AccountError::DatabaseError)?;
+    Ok(())
+}
+
+// Collecting the data may take a while, so the user is emailed a download link once it is stored.
+pub fn prepare_data_export(state: &AppState, user: User, export: DataExport) {
+    let task_state = state.clone();
+    let handle = task::spawn(async move {
+        let state = task_state;
+        if let Err(e) = complete_data_export(&state, &user, &export).await {
+            tracing::error!("Failed to prepare data export: {:?}", e);
+            return;
+        }
+
+        let download_link = export.download_url(&state.settings.application.full_url());
+        let recipient = EmailAddress {
+            name: user.name.clone(),
+            email: user.email.clone(),
+        };
+        send_notice_email(
+            &state,
+            recipient,
+            "Your data export is ready",
+            format!(
+                "The export of your account data is ready. Please visit {} to download it. \
+                The link expires on {}.",
+                download_link,
+                export.expires_at.format("%Y-%m-%d")
+            ),
+            format!(
+                "<b>The export of your account data is ready.</b>\
+                 Please click <a href='{}' target='_blank'>here </a> to download it.<br>\
+                 The link expires on {}.",
+                download_link,
+                export.expires_at.format("%Y-%m-%d")
+            ),
+        );
+    });
+    if let Some(tx) = &state.tasks {
+        let _ = tx.send(BackgroundTask::new("prepare_data_export", handle));
+    }
+}
@@ -0,0 +1,200 @@
+use crate::errors::admin::AdminError;
+use crate::extractors::admin::{AdminAction, AdminAuditEntry};
+use crate::extractors::user::User;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::PgConnection;
+
+#[tracing::instrument(name = "Searching users", skip(transaction))]
+pub async fn search_users(
+    transaction: &mut PgConnection,
+    pattern: Option<String>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<User>, AdminError> {
+    let users = sqlx::query_as!(
+        User,
+        r#"
+        SELECT * FROM users
+        WHERE $1::text IS NULL OR username ILIKE $1 OR email ILIKE $1 OR name ILIKE $1
+        ORDER BY id LIMIT $2 OFFSET $3
+        "#,
+        pattern,
+        limit,
+        offset
+    )
+    .fetch_all(transaction)
+    .await
+    .map_err(AdminError::DatabaseError)?;
+    Ok(users)
+}
+
+#[tracing::instrument(name = "Counting users", skip(transaction))]
+pub async fn count_users(
+    transaction: &mut PgConnection,
+    pattern: Option<String>,
+) -> Result<i64, AdminError> {
+    let result = sqlx::query!(
+        r#"
+        SELECT COUNT(*) as "count!" FROM users
+        WHERE $1::text IS NULL OR username ILIKE $1 OR email ILIKE $1 OR name ILIKE $1
+        "#,
+        pattern
+    )
+    .fetch_one(transaction)
+    .await
+    .map_err(AdminError::DatabaseError)?;
+    Ok(result.count)
+}
+
+#[tracing::instrument(name = "Fetching user for admin", skip(transaction))]
+pub async fn fetch_target_user(
+    transaction: &mut PgConnection,
+    user_id: i32,
+) -> Result<User, AdminError> {
+    let user = sqlx::query_as!(User, r#"SELECT * FROM users WHERE id = $1"#, user_id)
+        .fetch_optional(transaction)
+        .await
+        .map_err(AdminError::DatabaseError)?;
+    user.ok_or(AdminError::UserNotFound)
+}
+
+#[tracing::instrument(name = "Counting sessions", skip(transaction))]
+pub async fn count_sessions(
+    transaction: &mut PgConnection,
+    user_id: i32,
+) -> Result<i64, AdminError> {
+    let result = sqlx::query!(
+        r#"SELECT COUNT(*) as "count!" FROM sessions WHERE user_id = $1"#,
+        user_id
+    )
+    .fetch_one(transaction)
+    .await
+    .map_err(AdminError::DatabaseError)?;
+    Ok(result.count)
+}
+
+// Reactivating also cancels a pending self-service deletion.
+#[tracing::instrument(name = "Setting user active state", skip(transaction))]
+pub async fn set_user_active(
+    transaction: &mut PgConnection,
+    user_id: i32,
+    active: bool,
+) -> Result<(), AdminError> {
+    sqlx::query!(
+        r#"
+        UPDATE users SET is_active = $1, updated_at = now(),
+            deleted_at = CASE WHEN $1 THEN null ELSE deleted_at END
+        WHERE id = $2
+        "#,
+        active,
+        user_id
+    )
+    .execute(transaction)
+    .await
+    .map_err(AdminError::DatabaseError)?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "Suspending user", skip(transaction))]
+pub async fn set_user_suspension(
+    transaction: &mut PgConnection,
+    user_id: i32,
+    reason: &str,
+    until: Option<DateTime<Utc>>,
+) -> Result<(), AdminError> {
+    sqlx::query!(
+        r#"
+        UPDATE users SET suspended_at = now(), suspension_reason = $1, suspended_until = $2,
+            updated_at = now()
+        WHERE id = $3
+        "#,
+        reason,
+        until,
+        user_id
+    )
+    .execute(transaction)
+    .await
+    .map_err(AdminError::DatabaseError)?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "Lifting user suspension", skip(transaction))]
+pub async fn clear_user_suspension(
+    transaction: &mut PgConnection,
+    user_id: i32,
+) -> Result<(), AdminError> {
+    sqlx::query!(
+        r#"
+        UPDATE users SET suspended_at = null, suspension_reason = null, suspended_until = null,
+            updated_at = now()
+        WHERE id = $1
+        "#,
+        user_id
+    )
+    .execute(transaction)
+    .await
+    .map_err(AdminError::DatabaseError)?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "Setting admin flag", skip(transaction))]
+pub async fn set_user_admin(
+    transaction: &mut PgConnection,
+    username: &str,
+    is_admin: bool,
+) -> Result<Option<i32>, AdminError> {
+    let row = sqlx::query!(
+        r#"UPDATE users SET is_admin = $1, updated_at = now() WHERE normalized_username = $2 RETURNING id"#,
+        is_admin,
+        User::normalize_username(username).map_err(|_| AdminError::UserNotFound)?
+    )
+    .fetch_optional(transaction)
+    .await
+    .map_err(AdminError::DatabaseError)?;
+    Ok(row.map(|row| row.id))
+}
+
+// Staff actions are only logged here, audit_events covers what happens through the account itself.
+#[tracing::instrument(name = "Recording admin action", skip(transaction, details))]
+pub async fn record_admin_action(
+    transaction: &mut PgConnection,
+    actor_id: i32,
+    target_id: i32,
+    action: AdminAction,
+    details: Value,
+) -> Result<(), AdminError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO admin_audit_log (actor_id, target_id, action, details)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        actor_id,
+        target_id,
+        String::from(action),
+        details
+    )
+    .execute(transaction)
+    .await
+    .map_err(AdminError::DatabaseError)?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "Listing admin actions", skip(transaction))]
+pub async fn list_admin_actions(
+    transaction: &mut PgConnection,
+    target_id: i32,
+) -> Result<Vec<AdminAuditEntry>, AdminError> {
+    let entries = sqlx::query_as!(
+        AdminAuditEntry,
+        r#"
+        SELECT id, actor_id, target_id, action, details, created_at
+        FROM admin_audit_log WHERE target_id = $1 ORDER BY created_at DESC, id DESC
+        "#,
+        target_id
+    )
+    .fetch_all(transaction)
+    .await
+    .map_err(AdminError::DatabaseError)?;
+    Ok(entries)
+}
@@ -0,0 +1,156 @@
+use crate::errors::token::ApiTokenError;
+use crate::errors::user::UserError;
+use crate::extractors::api_token::{ApiToken, API_TOKEN_PREFIX};
+use crate::extractors::user::User;
+use crate::helpers::user::fetch_user;
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use sqlx::PgConnection;
+use subtle::ConstantTimeEq;
+use uuid::Uuid;
+
+#[tracing::instrument(name = "Inserting api token", skip(transaction, api_token))]
+pub async fn insert_api_token(
+    transaction: &mut PgConnection,
+    api_token: &ApiToken,
+) -> Result<(), ApiTokenError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO api_tokens (identifier, name, verifier_hash, user_id, scopes, created_at, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+        api_token.identifier,
+        api_token.name,
+        api_token.verifier_hash,
+        api_token.user_id,
+        &api_token.scopes.to_vec(),
+        api_token.created_at,
+        api_token.expires_at
+    )
+    .execute(transaction)
+    .await
+    .map_err(ApiTokenError::DatabaseError)?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "Listing api tokens", skip(transaction))]
+pub async fn list_api_tokens(
+    transaction: &mut PgConnection,
+    user_id: i32,
+) -> Result<Vec<ApiToken>, ApiTokenError> {
+    let tokens = sqlx::query_as!(
+        ApiToken,
+        r#"
+        SELECT identifier, name, verifier_hash, user_id, scopes, created_at, expires_at, last_used_at
+        FROM api_tokens WHERE user_id = $1 ORDER BY created_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(transaction)
+    .await
+    .map_err(ApiTokenError::DatabaseError)?;
+    Ok(tokens)
+}
+
+#[tracing::instrument(name = "Revoking api token", skip(transaction))]
+pub async fn revoke_api_token(
+    transaction: &mut PgConnection,
+    user_id: i32,
+    identifier: Uuid,
+) -> Result<u64, ApiTokenError> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM api_tokens WHERE identifier = $1 AND user_id = $2
+        "#,
+        identifier,
+        user_id
+    )
+    .execute(transaction)
+    .await
+    .map_err(ApiTokenError::DatabaseError)?;
+    Ok(result.rows_affected())
+}
+
+#[tracing::instrument(name = "Revoking all api tokens", skip(transaction))]
+pub async fn revoke_all_api_tokens(
+    transaction: &mut PgConnection,
+    user_id: i32,
+) -> Result<(), ApiTokenError> {
+    sqlx::query!(
+        r#"
+        DELETE FROM api_tokens WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .execute(transaction)
+    .await
+    .map_err(ApiTokenError::DatabaseError)?;
+    Ok(())
+}
+
+pub async fn user_from_api_token(
+    transaction: &mut PgConnection,
+    token: &str,
+) -> Result<(User, ApiToken), UserError> {
+    let (identifier, verifier) = token
+        .strip_prefix(API_TOKEN_PREFIX)
+        .and_then(|token| token.split_once('.'))
+        .ok_or(UserError::AuthorizationTokenInvalid(
+            "incomplete token".into(),
+        ))?;
+
+    if identifier.is_empty() || verifier.is_empty() {
+        Err(UserError::AuthorizationTokenInvalid(
+            "empty token part".into(),
+        ))?;
+    }
+    let identifier = Uuid::parse_str(identifier)
+        .map_err(|_| UserError::AuthorizationTokenInvalid("invalid token".into()))?;
+
+    let api_token = sqlx::query_as!(
+        ApiToken,
+        r#"
+        SELECT identifier, name, verifier_hash, user_id, scopes, created_at, expires_at, last_used_at
+        FROM api_tokens WHERE identifier = $1
+        "#,
+        identifier
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(UserError::SessionError)?
+    .ok_or(UserError::AuthorizationTokenInvalid("token not found".into()))?;
+
+    if api_token.is_expired() {
+        Err(UserError::AuthorizationTokenInvalid("token expired".into()))?;
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(verifier.as_bytes());
+    let verifier_hash = format!("{:x}", hasher.finalize());
+    let matches: bool = verifier_hash
+        .as_bytes()
+        .ct_eq(api_token.verifier_hash.as_bytes())
+        .into();
+    if !matches {
+        Err(UserError::AuthorizationTokenInvalid(
+            "invalid token hash".into(),
+        ))?;
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE api_tokens SET last_used_at = $1 WHERE identifier = $2
+        "#,
+        Utc::now(),
+        identifier
+    )
+    .execute(&mut *transaction)
+    .await
+    .map_err(UserError::SessionError)?;
+
+    let user = fetch_user(&mut *transaction, api_token.user_id)
+        .await
+        .map_err(UserError::UserFetchError)?;
+
+    Ok((user, api_token))
+}
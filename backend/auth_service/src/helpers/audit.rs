@@ -0,0 +1,143 @@
+use crate::errors::audit::AuditError;
+use crate::extractors::audit::{AuditEvent, NewAuditEvent};
+use crate::payload::AuditQuery;
+use serde::Serialize;
+use sqlx::{PgConnection, PgPool};
+
+// Serializes writers so that every event links to the one before it.
+const AUDIT_CHAIN_LOCK: i64 = 0x6175_6469_7400;
+
+#[tracing::instrument(name = "Recording audit event", skip(transaction, event), fields(event_type = ? event.event_type))]
+pub async fn record_audit_event(
+    transaction: &mut PgConnection,
+    event: NewAuditEvent,
+) -> Result<(), AuditError> {
+    sqlx::query!("SELECT pg_advisory_xact_lock($1)", AUDIT_CHAIN_LOCK)
+        .execute(&mut *transaction)
+        .await
+        .map_err(AuditError::DatabaseError)?;
+    let previous = sqlx::query!(r#"SELECT hash FROM audit_events ORDER BY id DESC LIMIT 1"#)
+        .fetch_optional(&mut *transaction)
+        .await
+        .map_err(AuditError::DatabaseError)?
+        .map(|row| row.hash);
+
+    let created_at = AuditEvent::timestamp();
+    let hash = AuditEvent::chain_hash(previous.as_deref(), &event, &created_at);
+    sqlx::query!(
+        r#"
+        INSERT INTO audit_events (event_type, actor_id, target_id, ip, user_agent, details,
+                                  created_at, prev_hash, hash)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        "#,
+        String::from(event.event_type),
+        event.actor_id,
+        event.target_id,
+        event.ip,
+        event.user_agent,
+        event.details,
+        created_at,
+        previous,
+        hash
+    )
+    .execute(&mut *transaction)
+    .await
+    .map_err(AuditError::DatabaseError)?;
+    Ok(())
+}
+
+// For events that must survive the request failing, such as a rejected login.
+pub async fn record_audit_event_now(pool: &PgPool, event: NewAuditEvent) -> Result<(), AuditError> {
+    let mut transaction = pool.begin().await.map_err(AuditError::Pool)?;
+    record_audit_event(&mut transaction, event).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(AuditError::DatabaseError)?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "Searching audit events", skip(transaction))]
+pub async fn search_audit_events(
+    transaction: &mut PgConnection,
+    query: &AuditQuery,
+) -> Result<Vec<AuditEvent>, AuditError> {
+    let events = sqlx::query_as!(
+        AuditEvent,
+        r#"
+        SELECT * FROM audit_events
+        WHERE ($1::text IS NULL OR event_type = $1)
+          AND ($2::integer IS NULL OR actor_id = $2)
+          AND ($3::integer IS NULL OR target_id = $3)
+          AND ($4::timestamptz IS NULL OR created_at >= $4)
+          AND ($5::timestamptz IS NULL OR created_at < $5)
+        ORDER BY id DESC LIMIT $6 OFFSET $7
+        "#,
+        query.event_type,
+        query.actor_id,
+        query.target_id,
+        query.since,
+        query.until,
+        query.per_page(),
+        query.offset()
+    )
+    .fetch_all(transaction)
+    .await
+    .map_err(AuditError::DatabaseError)?;
+    Ok(events)
+}
+
+#[tracing::instrument(name = "Fetching recent security activity", skip(transaction))]
+pub async fn recent_security_activity(
+    transaction: &mut PgConnection,
+    user_id: i32,
+    limit: i64,
+) -> Result<Vec<AuditEvent>, AuditError> {
+    let events = sqlx::query_as!(
+        AuditEvent,
+        r#"SELECT * FROM audit_events WHERE target_id = $1 ORDER BY id DESC LIMIT $2"#,
+        user_id,
+        limit
+    )
+    .fetch_all(transaction)
+    .await
+    .map_err(AuditError::DatabaseError)?;
+    Ok(events)
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuditChainReport {
+    pub checked: u64,
+    pub valid: bool,
+    // First event whose hash or link does not match.
+    pub broken_at: Option<i64>,
+}
+
+#[tracing::instrument(name = "Verifying audit chain", skip(transaction))]
+pub async fn verify_audit_chain(
+    transaction: &mut PgConnection,
+) -> Result<AuditChainReport, AuditError> {
+    let events = sqlx::query_as!(AuditEvent, r#"SELECT * FROM audit_events ORDER BY id"#)
+        .fetch_all(transaction)
+        .await
+        .map_err(AuditError::DatabaseError)?;
+
+    let mut previous: Option<String> = None;
+    let mut checked = 0;
+    for event in events {
+        checked += 1;
+        if event.prev_hash != previous || event.expected_hash() != event.hash {
+            return Ok(AuditChainReport {
+                checked,
+                valid: false,
+                broken_at: Some(event.id),
+            });
+        }
+        previous = Some(event.hash);
+    }
+    Ok(AuditChainReport {
+        checked,
+        valid: true,
+        broken_at: None,
+    })
+}
@@ -0,0 +1,148 @@
+use crate::errors::client_token::ClientTokenError;
+use crate::extractors::client_token::{
+    AccessTokenClaims, ClientTokens, RefreshToken, REFRESH_TOKEN_PREFIX,
+};
+use axum_extra::extract::cookie::Key;
+use chrono::Utc;
+use sqlx::PgConnection;
+use subtle::ConstantTimeEq;
+use utils::configuration::Settings;
+use uuid::Uuid;
+
+// Stores a new refresh token in the family and signs a matching access token.
+#[tracing::instrument(name = "Issuing client tokens", skip(transaction, settings))]
+pub async fn issue_client_tokens(
+    transaction: &mut PgConnection,
+    settings: &Settings,
+    user_id: i32,
+    family_id: Uuid,
+) -> Result<ClientTokens, ClientTokenError> {
+    let (refresh_token, token) =
+        RefreshToken::new(user_id, family_id, settings.tokens.refresh_token_lifetime());
+    insert_refresh_token(transaction, &refresh_token).await?;
+
+    let lifetime = settings.tokens.access_token_lifetime();
+    let key = Key::from(settings.application.key.as_ref());
+    let access_token = AccessTokenClaims::new(user_id, family_id, lifetime).sign(&key);
+    Ok(ClientTokens {
+        access_token,
+        refresh_token: token,
+        token_type: "Bearer".to_string(),
+        expires_in: lifetime.num_seconds(),
+    })
+}
+
+pub async fn insert_refresh_token(
+    transaction: &mut PgConnection,
+    refresh_token: &RefreshToken,
+) -> Result<(), ClientTokenError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO refresh_tokens (identifier, family_id, verifier_hash, user_id, created_at, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+        refresh_token.identifier,
+        refresh_token.family_id,
+        refresh_token.verifier_hash,
+        refresh_token.user_id,
+        refresh_token.created_at,
+        refresh_token.expires_at
+    )
+    .execute(transaction)
+    .await
+    .map_err(ClientTokenError::DatabaseError)?;
+    Ok(())
+}
+
+// Used and revoked tokens are still returned so the caller can detect reuse.
+pub async fn fetch_refresh_token(
+    transaction: &mut PgConnection,
+    token: &str,
+) -> Result<RefreshToken, ClientTokenError> {
+    let (identifier, verifier) = token
+        .strip_prefix(REFRESH_TOKEN_PREFIX)
+        .and_then(|token| token.split_once('.'))
+        .ok_or(ClientTokenError::InvalidRefreshToken)?;
+    let identifier =
+        Uuid::parse_str(identifier).map_err(|_| ClientTokenError::InvalidRefreshToken)?;
+
+    let refresh_token = sqlx::query_as!(
+        RefreshToken,
+        r#"
+        SELECT identifier, family_id, verifier_hash, user_id, created_at, expires_at, used_at, revoked_at
+        FROM refresh_tokens WHERE identifier = $1
+        "#,
+        identifier
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(ClientTokenError::DatabaseError)?
+    .ok_or(ClientTokenError::InvalidRefreshToken)?;
+
+    let matches: bool = RefreshToken::hash_verifier(verifier)
+        .as_bytes()
+        .ct_eq(refresh_token.verifier_hash.as_bytes())
+        .into();
+    if !matches {
+        return Err(ClientTokenError::InvalidRefreshToken);
+    }
+    Ok(refresh_token)
+}
+
+// False when another request used the token first.
+pub async fn mark_refresh_token_used(
+    transaction: &mut PgConnection,
+    identifier: Uuid,
+) -> Result<bool, ClientTokenError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE refresh_tokens SET used_at = $1
+        WHERE identifier = $2 AND used_at IS NULL AND revoked_at IS NULL
+        "#,
+        Utc::now(),
+        identifier
+    )
+    .execute(transaction)
+    .await
+    .map_err(ClientTokenError::DatabaseError)?;
+    Ok(result.rows_affected() == 1)
+}
+
+#[tracing::instrument(name = "Revoking refresh token family", skip(transaction))]
+pub async fn revoke_token_family(
+    transaction: &mut PgConnection,
+    family_id: Uuid,
+) -> Result<(), ClientTokenError> {
+    sqlx::query!(
+        r#"
+        UPDATE refresh_tokens SET revoked_at = $1 WHERE family_id = $2 AND revoked_at IS NULL
+        "#,
+        Utc::now(),
+        family_id
+    )
+    .execute(transaction)
+    .await
+    .map_err(ClientTokenError::DatabaseError)?;
+    Ok(())
+}
+
+// Access tokens of a family stop working as soon as it is revoked or its tokens are cleared.
+pub async fn is_family_active(
+    transaction: &mut PgConnection,
+    family_id: Uuid,
+) -> Result<bool, ClientTokenError> {
+    let active = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM refresh_tokens
+            WHERE family_id = $1 AND revoked_at IS NULL AND expires_at > $2
+        ) AS "active!"
+        "#,
+        family_id,
+        Utc::now()
+    )
+    .fetch_one(transaction)
+    .await
+    .map_err(ClientTokenError::DatabaseError)?;
+    Ok(active)
+}
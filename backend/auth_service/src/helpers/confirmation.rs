@@ -18,6 +18,20 @@ pub async fn send_verification_link(
     user: &User,
     confirmation: &Confirmation,
     token: String,
+) -> Result<(), UserError> {
+    let recipient = EmailAddress {
+        name: user.name.clone(),
+        email: user.email.clone(),
+    };
+    send_confirmation_link_to(state, recipient, confirmation, token).await
+}
+
+#[tracing::instrument(name = "Send confirmation link", skip(state))]
+pub async fn send_confirmation_link_to(
+    state: &AppState,
+    recipient: EmailAddress,
+    confirmation: &Confirmation,
+    token: String,
 ) -> Result<(), UserError> {
     let confirmation_link =
         confirmation.confirmation_url(&state.settings.application.full_url(), Secret::from(token));
@@ -26,10 +40,7 @@ pub async fn send_verification_link(
     let client = state.email_client.to_owned().unwrap();
     let email_object = EmailObject {
         sender: client.get_sender(),
-        to: vec![EmailAddress {
-            name: user.name.clone(),
-            email: user.email.clone(),
-        }],
+        to: vec![recipient],
         subject: confirmation.subject(),
         plain: email_content.0,
         html: email_content.1,
@@ -48,6 +59,34 @@ pub async fn send_verification_link(
     Ok(())
 }
 
+#[tracing::instrument(name = "Send notice email", skip(state, plain, html))]
+pub fn send_notice_email(
+    state: &AppState,
+    recipient: EmailAddress,
+    subject: &str,
+    plain: String,
+    html: String,
+) {
+    let client = state.email_client.to_owned().unwrap();
+    let email_object = EmailObject {
+        sender: client.get_sender(),
+        to: vec![recipient],
+        subject: subject.to_string(),
+        plain,
+        html,
+    };
+    let handle = task::spawn(async move {
+        client
+            .send_emails(email_object)
+            .await
+            .map_err(UserError::ConfirmationEmailError)
+            .expect("Unable to send email");
+    });
+    if let Some(tx) = &state.tasks {
+        let _ = tx.send(BackgroundTask::new("send_notice_email", handle));
+    }
+}
+
 pub async fn add_confirmation(
     transaction: &mut PgConnection,
     confirmation: &Confirmation,
@@ -150,6 +189,38 @@ pub async fn mark_user_as_confirmed(
     Ok(())
 }
 
+#[tracing::instrument(name = "Changing user email.", skip(transaction))]
+pub async fn change_user_email(
+    transaction: &mut PgConnection,
+    user_id: i32,
+    email: &str,
+) -> Result<(), ConfirmUserError> {
+    // Another account may have claimed the address since the link was sent.
+    sqlx::query!(
+        "update users set email = $1, is_confirmed = true, updated_at = now() where id = $2;",
+        User::normalize_email(email),
+        user_id
+    )
+    .execute(&mut *transaction)
+    .await
+    .map_err(|e| match &e {
+        sqlx::Error::Database(error) if error.constraint() == Some("users_email_key") => {
+            ConfirmUserError::EmailNotAvailable
+        }
+        _ => ConfirmUserError::ConfirmationDatabaseError(e),
+    })?;
+    sqlx::query!(
+        "delete from confirmations where user_id = $1 and action_type in ($2, $3);",
+        user_id,
+        String::from(ConfirmationActionType::EmailChange),
+        String::from(ConfirmationActionType::UserVerification)
+    )
+    .execute(&mut *transaction)
+    .await
+    .map_err(ConfirmUserError::ConfirmationDatabaseError)?;
+    Ok(())
+}
+
 pub async fn check_confirmation(
     token: String,
     transaction: &mut PgConnection,
@@ -0,0 +1,158 @@
+use crate::errors::invitation::InvitationError;
+use crate::extractors::invitation::Invitation;
+use crate::helpers::confirmation::send_notice_email;
+use email_clients::email::EmailAddress;
+use secrecy::Secret;
+use sqlx::PgConnection;
+use utils::state::AppState;
+use uuid::Uuid;
+
+#[tracing::instrument(name = "Inserting invitation", skip(transaction, invitation))]
+pub async fn insert_invitation(
+    transaction: &mut PgConnection,
+    invitation: &Invitation,
+) -> Result<(), InvitationError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO invitations (identifier, verifier_hash, email, role, inviter_id, created_at, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+        invitation.identifier,
+        invitation.verifier_hash,
+        invitation.email,
+        String::from(invitation.role),
+        invitation.inviter_id,
+        invitation.created_at,
+        invitation.expires_at
+    )
+    .execute(transaction)
+    .await
+    .map_err(InvitationError::DatabaseError)?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "Fetching invitation", skip(transaction))]
+pub async fn fetch_invitation(
+    transaction: &mut PgConnection,
+    identifier: Uuid,
+) -> Result<Invitation, InvitationError> {
+    let invitation = sqlx::query_as!(
+        Invitation,
+        r#"SELECT * FROM invitations WHERE identifier = $1"#,
+        identifier
+    )
+    .fetch_optional(transaction)
+    .await
+    .map_err(InvitationError::DatabaseError)?;
+    invitation.ok_or(InvitationError::NotFound)
+}
+
+#[tracing::instrument(name = "Listing pending invitations", skip(transaction))]
+pub async fn list_pending_invitations(
+    transaction: &mut PgConnection,
+) -> Result<Vec<Invitation>, InvitationError> {
+    let invitations = sqlx::query_as!(
+        Invitation,
+        r#"SELECT * FROM invitations WHERE accepted_at IS NULL ORDER BY created_at DESC"#
+    )
+    .fetch_all(transaction)
+    .await
+    .map_err(InvitationError::DatabaseError)?;
+    Ok(invitations)
+}
+
+#[tracing::instrument(name = "Updating invitation verifier", skip(transaction, invitation))]
+pub async fn update_invitation_verifier(
+    transaction: &mut PgConnection,
+    invitation: &Invitation,
+) -> Result<(), InvitationError> {
+    sqlx::query!(
+        r#"UPDATE invitations SET verifier_hash = $1, expires_at = $2 WHERE identifier = $3"#,
+        invitation.verifier_hash,
+        invitation.expires_at,
+        invitation.identifier
+    )
+    .execute(transaction)
+    .await
+    .map_err(InvitationError::DatabaseError)?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "Revoking invitation", skip(transaction))]
+pub async fn delete_invitation(
+    transaction: &mut PgConnection,
+    identifier: Uuid,
+) -> Result<(), InvitationError> {
+    let result = sqlx::query!(
+        r#"DELETE FROM invitations WHERE identifier = $1 AND accepted_at IS NULL"#,
+        identifier
+    )
+    .execute(transaction)
+    .await
+    .map_err(InvitationError::DatabaseError)?;
+    if result.rows_affected() == 0 {
+        Err(InvitationError::NotFound)?;
+    }
+    Ok(())
+}
+
+#[tracing::instrument(name = "Accepting invitation", skip(transaction))]
+pub async fn mark_invitation_accepted(
+    transaction: &mut PgConnection,
+    identifier: Uuid,
+    user_id: i32,
+) -> Result<(), InvitationError> {
+    // Two sign-ups racing on the same token both pass the check, only one gets to accept it.
+    let result = sqlx::query!(
+        r#"
+        UPDATE invitations SET accepted_at = now(), accepted_by = $1
+        WHERE identifier = $2 AND accepted_at IS NULL
+        "#,
+        user_id,
+        identifier
+    )
+    .execute(transaction)
+    .await
+    .map_err(InvitationError::DatabaseError)?;
+    if result.rows_affected() == 0 {
+        Err(InvitationError::InvalidInvitation)?;
+    }
+    Ok(())
+}
+
+// Accepted, expired and tampered tokens all look the same to the caller.
+#[tracing::instrument(name = "Checking invitation token", skip(transaction, token))]
+pub async fn check_invitation(
+    transaction: &mut PgConnection,
+    token: &str,
+) -> Result<Invitation, InvitationError> {
+    let (identifier, verifier) = token
+        .split_once('.')
+        .ok_or(InvitationError::InvalidInvitation)?;
+    let identifier = Uuid::parse_str(identifier).map_err(|_| InvitationError::InvalidInvitation)?;
+    let invitation = match fetch_invitation(transaction, identifier).await {
+        Err(InvitationError::NotFound) => Err(InvitationError::InvalidInvitation),
+        result => result,
+    }?;
+    if !invitation.is_pending() || !invitation.matches_verifier(verifier) {
+        Err(InvitationError::InvalidInvitation)?;
+    }
+    Ok(invitation)
+}
+
+pub fn send_invitation(state: &AppState, invitation: &Invitation, token: String) {
+    let invite_link =
+        invitation.accept_url(&state.settings.application.full_url(), Secret::from(token));
+    let (plain, html) = invitation.email_contents(&invite_link);
+    let recipient = EmailAddress {
+        name: invitation.email.clone(),
+        email: invitation.email.clone(),
+    };
+    send_notice_email(
+        state,
+        recipient,
+        &invitation.subject(&state.settings.application.name),
+        plain,
+        html,
+    );
+}
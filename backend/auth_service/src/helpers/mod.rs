@@ -1,3 +1,11 @@
+pub mod account;
+pub mod admin;
+pub mod api_tokens;
+pub mod audit;
+pub mod client_tokens;
 pub mod confirmation;
+pub mod invitation;
+pub mod password;
 pub mod sessions;
+pub mod throttle;
 pub mod user;
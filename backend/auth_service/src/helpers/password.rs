@@ -0,0 +1,44 @@
+use crate::errors::user::UserError;
+use crate::extractors::user::User;
+use sha1::{Digest, Sha1};
+use std::io::ErrorKind;
+use std::path::Path;
+use utils::configuration::PasswordPolicy;
+
+// Policy checks for a password the user is about to start using.
+pub async fn check_new_password(
+    policy: &PasswordPolicy,
+    password: &str,
+    inputs: &[&str],
+) -> Result<(), UserError> {
+    User::check_acceptable_password(password, inputs, policy)?;
+    if let Some(dir) = &policy.breached_hashes_dir {
+        if is_breached_password(Path::new(dir), password).await {
+            return Err(UserError::BreachedPassword);
+        }
+    }
+    Ok(())
+}
+
+// Only the five character prefix picks a file, so the full hash never has to be listed in one place.
+pub async fn is_breached_password(dir: &Path, password: &str) -> bool {
+    let hash = format!("{:X}", Sha1::digest(password.as_bytes()));
+    let (prefix, suffix) = hash.split_at(5);
+    let path = dir.join(format!("{}.txt", prefix));
+    match tokio::fs::read_to_string(&path).await {
+        Ok(contents) => contents.lines().any(|line| {
+            line.split(':')
+                .next()
+                .is_some_and(|entry| entry.trim().eq_ignore_ascii_case(suffix))
+        }),
+        Err(e) if e.kind() == ErrorKind::NotFound => false,
+        Err(e) => {
+            tracing::warn!(
+                "Unable to read breached hashes from {}: {}",
+                path.display(),
+                e
+            );
+            false
+        }
+    }
+}
@@ -129,5 +129,44 @@ pub async fn clear_sessions(
     .execute(&mut *transaction)
     .await
     .map_err(UserError::SessionError)?;
+    sqlx::query!(
+        r#"
+        DELETE FROM refresh_tokens where user_id = $1
+        "#,
+        identifier
+    )
+    .execute(&mut *transaction)
+    .await
+    .map_err(UserError::SessionError)?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "Clearing other sessions for id", skip(transaction))]
+pub async fn clear_other_sessions(
+    transaction: &mut PgConnection,
+    user_id: i32,
+    current_session: Uuid,
+) -> Result<(), UserError> {
+    sqlx::query!(
+        r#"
+        DELETE FROM sessions where user_id = $1 and identifier != $2
+        "#,
+        user_id,
+        current_session
+    )
+    .execute(&mut *transaction)
+    .await
+    .map_err(UserError::SessionError)?;
+    // Api clients identify their session by the refresh token family.
+    sqlx::query!(
+        r#"
+        DELETE FROM refresh_tokens where user_id = $1 and family_id != $2
+        "#,
+        user_id,
+        current_session
+    )
+    .execute(&mut *transaction)
+    .await
+    .map_err(UserError::SessionError)?;
     Ok(())
 }
@@ -0,0 +1,69 @@
+use crate::errors::auth::UserRegistrationError;
+use crate::extractors::confirmation::{Confirmation, ConfirmationActionType};
+use crate::extractors::user::User;
+use crate::helpers::confirmation::{add_confirmation, send_verification_link};
+use serde_json::json;
+use utils::errors::ErrorPayload;
+use utils::state::AppState;
+
+// Uses the same normalization as the stored usernames, so lookalike spellings share a bucket
+// and reset or unlock clear the one login counted against.
+pub fn login_user_key(username: &str) -> String {
+    format!("login:user:{}", normalize_identifier(username))
+}
+
+pub fn login_ip_key(ip: &str) -> String {
+    format!("login:ip:{}", ip)
+}
+
+pub fn reset_key(username_or_email: &str) -> String {
+    format!("reset:{}", normalize_identifier(username_or_email))
+}
+
+fn normalize_identifier(identifier: &str) -> String {
+    let identifier = identifier.trim();
+    if identifier.contains('@') {
+        return User::normalize_email(identifier);
+    }
+    User::normalize_username(identifier).unwrap_or_else(|_| identifier.to_lowercase())
+}
+
+pub fn reset_ip_key(ip: &str) -> String {
+    format!("reset:ip:{}", ip)
+}
+
+// Failures are counted even for unknown usernames so that lockouts don't reveal which accounts exist.
+#[tracing::instrument(name = "Recording failed login", skip(state, user))]
+pub async fn record_login_failure(
+    state: &AppState,
+    user_key: &str,
+    ip_key: Option<&str>,
+    user: Option<&User>,
+) -> Result<(), ErrorPayload> {
+    let limits = &state.settings.rate_limit;
+    if let Some(ip_key) = ip_key {
+        state.rate_limiter.hit(ip_key, &limits.ip_policy()).await?;
+    }
+    let entry = state
+        .rate_limiter
+        .hit(user_key, &limits.login_policy())
+        .await?;
+
+    // Only the attempt that triggers the lockout sends the unlock link.
+    if entry.attempts != limits.lockout_after as i32 {
+        return Ok(());
+    }
+    if let Some(user) = user {
+        tracing::info!("Locking account after failed attempts");
+        let mut connection = state
+            .connection
+            .acquire()
+            .await
+            .map_err(UserRegistrationError::Pool)?;
+        let (confirmation, confirmation_token) =
+            Confirmation::new(user.id, ConfirmationActionType::AccountUnlock, json!({}));
+        add_confirmation(&mut connection, &confirmation).await?;
+        send_verification_link(state, user, &confirmation, confirmation_token).await?;
+    }
+    Ok(())
+}
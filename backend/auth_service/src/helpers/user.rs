@@ -5,6 +5,7 @@ use crate::errors::user::UserError;
 use crate::extractors::user::User;
 use secrecy::{ExposeSecret, Secret};
 use sqlx::PgConnection;
+use utils::configuration::PasswordHashSettings;
 
 #[tracing::instrument(name = "Checking for existing username")]
 pub async fn fetch_by_username(
@@ -24,6 +25,34 @@ pub async fn fetch_by_username(
     Ok(user)
 }
 
+// Emails always contain '@', which usernames never do, so a single field can take either.
+#[tracing::instrument(name = "Looking up login identifier")]
+pub async fn fetch_by_login(
+    transaction: &mut PgConnection,
+    identifier: &str,
+) -> Result<Option<User>, UsernameCheckError> {
+    let (email, username) = if identifier.contains('@') {
+        (Some(User::normalize_email(identifier)), None)
+    } else {
+        (None, User::normalize_username(identifier.trim()).ok())
+    };
+    if email.is_none() && username.is_none() {
+        return Ok(None);
+    }
+    let user = sqlx::query_as!(
+        User,
+        r#"
+        select * from users where email = $1 or normalized_username = $2
+        "#,
+        email,
+        username
+    )
+    .fetch_optional(transaction)
+    .await
+    .map_err(UsernameCheckError::UsernameCheck)?;
+    Ok(user)
+}
+
 #[tracing::instrument(name = "Checking for existing email")]
 pub async fn fetch_by_email(
     transaction: &mut PgConnection,
@@ -34,7 +63,7 @@ pub async fn fetch_by_email(
         r#"
         select * from users where email = $1
         "#,
-        email.to_lowercase()
+        User::normalize_email(email)
     )
     .fetch_optional(transaction)
     .await
@@ -51,18 +80,19 @@ pub async fn insert_user(
     let output = sqlx::query!(
         r#"
         INSERT INTO users (name, email, username, normalized_username, password_hash, created_at, updated_at,
-                                  is_active, is_confirmed)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) returning id;
+                                  is_active, is_confirmed, is_admin)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) returning id;
         "#,
         user.name,
-        user.email.to_lowercase(),
+        User::normalize_email(&user.email),
         user.username,
         user.normalized_username,
         password_hash.expose_secret(),
         user.created_at,
         user.updated_at,
         user.is_active,
-        user.is_confirmed
+        user.is_confirmed,
+        user.is_admin
     ).fetch_one(transaction).await.map_err(UserRegistrationError::InsertUserFailed)?;
     Ok(output.id)
 }
@@ -90,8 +120,9 @@ pub async fn update_password(
     transaction: &mut PgConnection,
     user_id: i32,
     password: Secret<String>,
+    settings: &PasswordHashSettings,
 ) -> Result<u64, UserError> {
-    let password_hash = User::hash_password(password.expose_secret())?;
+    let password_hash = User::hash_password(password.expose_secret(), settings)?;
     let result = sqlx::query!(
         "UPDATE users SET password_hash = $1 WHERE id = $2",
         password_hash.to_string(),
@@ -103,3 +134,29 @@ pub async fn update_password(
 
     Ok(result.rows_affected())
 }
+
+#[tracing::instrument(name = "Updating user profile", skip(transaction))]
+pub async fn update_profile(
+    transaction: &mut PgConnection,
+    user_id: i32,
+    name: &str,
+    username: &str,
+    normalized_username: &str,
+) -> Result<User, UserError> {
+    let user = sqlx::query_as!(
+        User,
+        r#"
+        UPDATE users SET name = $1, username = $2, normalized_username = $3, updated_at = now()
+        WHERE id = $4 RETURNING *
+        "#,
+        name,
+        username,
+        normalized_username,
+        user_id
+    )
+    .fetch_one(transaction)
+    .await
+    .map_err(UserError::UpdateUserFailed)?;
+
+    Ok(user)
+}
@@ -4,5 +4,6 @@ pub mod errors;
 pub mod extractors;
 mod handlers;
 pub mod helpers;
+pub mod middleware;
 pub mod payload;
 pub mod router;
@@ -0,0 +1,66 @@
+use crate::errors::csrf::CsrfError;
+use crate::extractors::session::SESSION_TOKEN_COOKIE;
+use axum::extract::{Request, State};
+use axum::http::header::{AUTHORIZATION, HOST, ORIGIN, REFERER};
+use axum::http::{HeaderMap, Method};
+use axum::middleware::Next;
+use axum::response::Response;
+use axum_extra::extract::CookieJar;
+use url::Url;
+use utils::errors::ErrorPayload;
+use utils::state::AppState;
+
+/// Rejects state changing requests that carry the session cookie but come from another site.
+/// Clients sending an `Authorization` header aren't affected, browsers never attach it on
+/// their own.
+pub async fn csrf_protection(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ErrorPayload> {
+    if state.settings.csrf.enabled && needs_origin_check(request.method(), request.headers()) {
+        check_origin(&state, request.headers())?;
+    }
+    Ok(next.run(request).await)
+}
+
+fn needs_origin_check(method: &Method, headers: &HeaderMap) -> bool {
+    let safe = matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE
+    );
+    !safe
+        && !headers.contains_key(AUTHORIZATION)
+        && CookieJar::from_headers(headers)
+            .get(SESSION_TOKEN_COOKIE)
+            .is_some()
+}
+
+// Browsers send Origin on every cross-origin write, Referer is the fallback for older ones.
+fn check_origin(state: &AppState, headers: &HeaderMap) -> Result<(), CsrfError> {
+    let origin = headers
+        .get(ORIGIN)
+        .or_else(|| headers.get(REFERER))
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| Url::parse(value).ok())
+        .filter(|url| url.has_host())
+        .ok_or(CsrfError::MissingOrigin)?;
+    let serialized = origin.origin().ascii_serialization();
+
+    let authority = match origin.port() {
+        Some(port) => format!("{}:{}", origin.host_str().unwrap_or_default(), port),
+        None => origin.host_str().unwrap_or_default().to_string(),
+    };
+    let same_host = headers
+        .get(HOST)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|host| host.eq_ignore_ascii_case(&authority));
+    let trusted = same_host
+        || std::iter::once(state.settings.application.full_url())
+            .chain(state.settings.csrf.trusted_origins.iter().cloned())
+            .any(|trusted| trusted.trim_end_matches('/') == serialized);
+    if !trusted {
+        return Err(CsrfError::UntrustedOrigin(serialized));
+    }
+    Ok(())
+}
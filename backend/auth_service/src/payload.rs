@@ -1,11 +1,13 @@
 use crate::errors::user::UserError;
+use crate::extractors::invitation::InviteRole;
 use crate::extractors::user::User;
+use chrono::{DateTime, Utc};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use rustrict::CensorStr;
 use secrecy::Secret;
 use serde::Deserialize;
-use std::convert::TryFrom;
+use utils::configuration::PasswordHashSettings;
 use validator::{Validate, ValidationError};
 
 static USERNAME_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[0-9A-Za-z_.]+$").unwrap());
@@ -22,7 +24,7 @@ pub struct RegisterPayload {
         )
     )]
     pub username: String,
-    #[validate(length(min = 8, max = 72, message = "Password must contains 8-72 characters"))]
+    #[validate(length(min = 1, max = 1024, message = "Password cannot be empty"))]
     pub password: String,
     #[validate(length(min = 1, max = 255, message = "Email cannot be empty"), email)]
     pub email: String,
@@ -33,17 +35,126 @@ pub struct RegisterPayload {
     pub confirm_password: String,
     #[validate(length(min = 1, message = "Name cannot be empty"))]
     pub name: String,
+    pub invitation: Option<String>,
 }
 
-impl TryFrom<RegisterPayload> for User {
-    type Error = UserError;
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateProfilePayload {
+    #[validate(
+        length(min = 3, message = "Username cannot be empty", max = 255),
+        non_control_character,
+        regex = "USERNAME_REGEX",
+        custom(
+            function = "validate_profanity",
+            message = "Invalid words in username."
+        )
+    )]
+    pub username: Option<String>,
+    #[validate(length(min = 1, max = 255, message = "Name cannot be empty"))]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ChangePasswordPayload {
+    #[validate(length(min = 1, max = 1024, message = "Password cannot be empty"))]
+    pub current_password: String,
+    #[validate(length(min = 1, max = 1024, message = "Password cannot be empty"))]
+    pub password: String,
+    #[validate(must_match(
+        other = "password",
+        message = "password and confirm password must match"
+    ))]
+    pub confirm_password: String,
+    #[serde(default)]
+    pub revoke_other_sessions: bool,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct DeleteAccountPayload {
+    #[validate(length(min = 1, max = 1024, message = "Password cannot be empty"))]
+    pub current_password: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct UserListQuery {
+    pub q: Option<String>,
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+impl UserListQuery {
+    pub fn per_page(&self) -> i64 {
+        self.per_page.unwrap_or(25).clamp(1, 100)
+    }
+
+    pub fn page(&self) -> i64 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn offset(&self) -> i64 {
+        (self.page() - 1) * self.per_page()
+    }
+
+    // Matches a substring of the username, email or name.
+    pub fn pattern(&self) -> Option<String> {
+        self.q
+            .as_ref()
+            .map(|q| q.trim())
+            .filter(|q| !q.is_empty())
+            .map(|q| format!("%{}%", q.replace('%', "\\%").replace('_', "\\_")))
+    }
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct AuditQuery {
+    pub event_type: Option<String>,
+    pub actor_id: Option<i32>,
+    pub target_id: Option<i32>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+impl AuditQuery {
+    pub fn per_page(&self) -> i64 {
+        self.per_page.unwrap_or(50).clamp(1, 200)
+    }
+
+    pub fn page(&self) -> i64 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn offset(&self) -> i64 {
+        (self.page() - 1) * self.per_page()
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct InvitePayload {
+    #[validate(length(min = 1, max = 255, message = "Email cannot be empty"), email)]
+    pub email: String,
+    #[serde(default)]
+    pub role: InviteRole,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SuspendPayload {
+    #[validate(length(min = 1, max = 500, message = "Reason must contains 1-500 characters"))]
+    pub reason: String,
+    pub until: Option<DateTime<Utc>>,
+}
 
-    fn try_from(value: RegisterPayload) -> Result<Self, Self::Error> {
+impl User {
+    pub fn from_registration(
+        value: RegisterPayload,
+        settings: &PasswordHashSettings,
+    ) -> Result<Self, UserError> {
         let normalized_username = User::normalize_username(&value.username)?;
-        let password_hash = Secret::from(User::hash_password(&value.password)?);
+        let password_hash = Secret::from(User::hash_password(&value.password, settings)?);
         Ok(User {
             name: value.name,
-            email: value.email,
+            email: User::normalize_email(&value.email),
             normalized_username,
             username: value.username,
             password_hash,
@@ -1,21 +1,75 @@
+use crate::handlers::account::{delete_account, download_export, request_export, restore_account};
+use crate::handlers::admin::{
+    activate_user, deactivate_user, force_password_reset, impersonate_user, list_users,
+    revoke_user_sessions, suspend_user, unsuspend_user, user_detail, verify_user,
+};
+use crate::handlers::api_tokens::{create_token, list_tokens, revoke_token};
+use crate::handlers::audit::{list_audit_events, security_activity, verify_audit_log};
+use crate::handlers::client_tokens::{issue_token, refresh_token, revoke_refresh_token};
 use crate::handlers::confirmation::{confirm, resend_verification};
+use crate::handlers::email::change_email;
+use crate::handlers::invitation::{
+    create_invitation, invitation_detail, list_invitations, registration_mode, resend_invitation,
+    revoke_invitation,
+};
 use crate::handlers::login::login;
 use crate::handlers::logout::logout;
-use crate::handlers::me::me;
+use crate::handlers::me::{me, update_me};
+use crate::handlers::password::change_password;
 use crate::handlers::registration::register;
 use crate::handlers::reset::{check_reset_token, initiate_reset_password, reset_password};
-use axum::routing::{get, post, Router};
+use crate::handlers::unlock::unlock_account;
+use axum::routing::{delete, get, post, Router};
 use utils::state::AppState;
 
 pub fn create_router() -> Router<AppState> {
     Router::new()
         .route("/register", post(register))
+        .route("/registration", get(registration_mode))
+        .route("/invitations/:token", get(invitation_detail))
         .route("/resend-verification", post(resend_verification))
         .route("/login", post(login))
         .route("/logout", post(logout))
-        .route("/me", get(me))
+        .route("/token", post(issue_token))
+        .route("/token/refresh", post(refresh_token))
+        .route("/token/revoke", post(revoke_refresh_token))
+        .route("/me", get(me).patch(update_me).delete(delete_account))
+        .route("/change-password", post(change_password))
         .route("/confirm/:token", post(confirm))
+        .route("/change-email", post(change_email))
         .route("/initiate-reset", post(initiate_reset_password))
         .route("/check-reset/:token", post(check_reset_token))
         .route("/reset-password/:token", post(reset_password))
+        .route("/unlock/:token", post(unlock_account))
+        .route("/restore/:token", post(restore_account))
+        .route("/export", post(request_export))
+        .route("/export/:identifier", get(download_export))
+        .route("/tokens", get(list_tokens).post(create_token))
+        .route("/tokens/:identifier", delete(revoke_token))
+        .route("/security-activity", get(security_activity))
+}
+
+pub fn create_admin_router() -> Router<AppState> {
+    Router::new()
+        .route("/users", get(list_users))
+        .route("/users/:user_id", get(user_detail))
+        .route("/users/:user_id/activate", post(activate_user))
+        .route("/users/:user_id/deactivate", post(deactivate_user))
+        .route("/users/:user_id/verify", post(verify_user))
+        .route("/users/:user_id/reset-password", post(force_password_reset))
+        .route(
+            "/users/:user_id/revoke-sessions",
+            post(revoke_user_sessions),
+        )
+        .route("/users/:user_id/suspend", post(suspend_user))
+        .route("/users/:user_id/unsuspend", post(unsuspend_user))
+        .route("/users/:user_id/impersonate", post(impersonate_user))
+        .route(
+            "/invitations",
+            get(list_invitations).post(create_invitation),
+        )
+        .route("/invitations/:identifier", delete(revoke_invitation))
+        .route("/invitations/:identifier/resend", post(resend_invitation))
+        .route("/audit", get(list_audit_events))
+        .route("/audit/verify", get(verify_audit_log))
 }
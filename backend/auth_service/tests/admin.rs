@@ -0,0 +1,318 @@
+use auth_service::helpers::admin::set_user_suspension;
+use auth_service::helpers::user::fetch_user;
+use auth_service::router::{create_admin_router, create_router};
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::Response;
+use axum::{http, Router};
+use chrono::{Duration, Utc};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::time;
+use tower::ServiceExt;
+use utils::state::AppState;
+use utils::test;
+
+mod common;
+
+#[sqlx::test]
+async fn admin_routes_require_admin(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_admin_router().with_state(state);
+
+    let user = common::verified_user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+
+    let response = send_request(&app, "/users", http::Method::GET, &session_token).await;
+    test::assert_response(
+        response,
+        StatusCode::FORBIDDEN,
+        "insufficient permission: admin access required",
+    )
+    .await;
+}
+
+#[sqlx::test]
+async fn list_users_with_search_and_pagination(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_admin_router().with_state(state);
+
+    let admin = common::admin_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, admin.id).await;
+    let target = common::user_fixture(&mut conn).await;
+    common::user_fixture(&mut conn).await;
+
+    let response = send_request(
+        &app,
+        "/users?per_page=2&page=2",
+        http::Method::GET,
+        &session_token,
+    )
+    .await;
+    let body = json_body(response).await;
+    assert_eq!(body["total"], json!(3));
+    assert_eq!(body["page"], json!(2));
+    assert_eq!(body["users"].as_array().unwrap().len(), 1);
+
+    let url = format!("/users?q={}", target.email.to_uppercase());
+    let response = send_request(&app, &url, http::Method::GET, &session_token).await;
+    let body = json_body(response).await;
+    assert_eq!(body["total"], json!(1));
+    assert_eq!(body["users"][0]["id"], json!(target.id));
+}
+
+#[sqlx::test]
+async fn deactivate_blocks_login_until_activated(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let admin_app = create_admin_router().with_state(state.clone());
+    let app = create_router().with_state(state);
+
+    let admin = common::admin_fixture(&mut conn).await;
+    let admin_session = common::session_fixture(&mut conn, admin.id).await;
+    let target = common::user_fixture(&mut conn).await;
+    let target_session = common::session_fixture(&mut conn, target.id).await;
+
+    let url = format!("/users/{}/deactivate", target.id);
+    let response = send_request(&admin_app, &url, http::Method::POST, &admin_session).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = send_request(&app, "/me", http::Method::GET, &target_session).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = login(&app, &target.normalized_username).await;
+    test::assert_response(
+        response,
+        StatusCode::BAD_REQUEST,
+        "login failed: account is deactivated",
+    )
+    .await;
+
+    let url = format!("/users/{}/activate", target.id);
+    let response = send_request(&admin_app, &url, http::Method::POST, &admin_session).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = login(&app, &target.normalized_username).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let url = format!("/users/{}", target.id);
+    let response = send_request(&admin_app, &url, http::Method::GET, &admin_session).await;
+    let body = json_body(response).await;
+    assert_eq!(body["audit"][0]["action"], json!("activate"));
+    assert_eq!(body["audit"][1]["action"], json!("deactivate"));
+    assert_eq!(body["audit"][1]["actor_id"], json!(admin.id));
+
+    // Admins cannot lock themselves out.
+    let url = format!("/users/{}/deactivate", admin.id);
+    let response = send_request(&admin_app, &url, http::Method::POST, &admin_session).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn force_verify_and_revoke_sessions(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_admin_router().with_state(state);
+
+    let admin = common::admin_fixture(&mut conn).await;
+    let admin_session = common::session_fixture(&mut conn, admin.id).await;
+    let target = common::user_fixture(&mut conn).await;
+    common::session_fixture(&mut conn, target.id).await;
+
+    let url = format!("/users/{}/verify", target.id);
+    let response = send_request(&app, &url, http::Method::POST, &admin_session).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(fetch_user(&mut conn, target.id).await.unwrap().is_confirmed);
+
+    let url = format!("/users/{}/revoke-sessions", target.id);
+    let response = send_request(&app, &url, http::Method::POST, &admin_session).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let url = format!("/users/{}", target.id);
+    let response = send_request(&app, &url, http::Method::GET, &admin_session).await;
+    let body = json_body(response).await;
+    assert_eq!(body["sessions"], json!(0));
+
+    // Kept in the audit chain as well as the admin log.
+    let revoked = sqlx::query!(
+        r#"SELECT actor_id FROM audit_events WHERE target_id = $1 AND event_type = 'sessions_revoked'"#,
+        target.id
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .expect("Unable to fetch audit events");
+    assert_eq!(revoked.len(), 1);
+    assert_eq!(revoked[0].actor_id, Some(admin.id));
+    let logged = sqlx::query!(
+        r#"SELECT COUNT(*) as "count!" FROM admin_audit_log WHERE target_id = $1 AND action = 'revoke_sessions'"#,
+        target.id
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .expect("Unable to fetch admin actions");
+    assert_eq!(logged.count, 1);
+}
+
+#[sqlx::test]
+async fn force_password_reset_sends_link(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let (email_tx, email_rx) = std::sync::mpsc::sync_channel(5);
+    let (task_tx, task_rx) = std::sync::mpsc::sync_channel(5);
+    let mut state = test::test_state_for_email(pool, email_tx);
+    state.tasks = Some(task_tx);
+    let app = create_admin_router().with_state(state);
+
+    let admin = common::admin_fixture(&mut conn).await;
+    let admin_session = common::session_fixture(&mut conn, admin.id).await;
+    let target = common::user_fixture(&mut conn).await;
+
+    let url = format!("/users/{}/reset-password", target.id);
+    let response = send_request(&app, &url, http::Method::POST, &admin_session).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let task = task_rx.try_recv().expect("Task not thrown out.");
+    task.handle.await.expect("Join error, task panicked");
+    let email = email_rx.recv_timeout(time::Duration::from_secs(5)).unwrap();
+    assert_eq!(email.to[0].email, target.email);
+    assert!(email.plain.contains("/auth/reset-password/"));
+
+    let saved = fetch_user(&mut conn, target.id).await.unwrap();
+    assert!(!saved.check_password(common::STRONG_PASSWORD, &Default::default()));
+}
+
+#[sqlx::test]
+async fn impersonate_creates_tagged_session(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let admin_app = create_admin_router().with_state(state.clone());
+    let app = create_router().with_state(state);
+
+    let admin = common::admin_fixture(&mut conn).await;
+    let admin_session = common::session_fixture(&mut conn, admin.id).await;
+    let target = common::user_fixture(&mut conn).await;
+
+    let url = format!("/users/{}/impersonate", target.id);
+    let response = send_request(&admin_app, &url, http::Method::POST, &admin_session).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let session_token = response
+        .headers()
+        .get(AUTHORIZATION)
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string();
+
+    let response = send_request(&app, "/me", http::Method::GET, &session_token).await;
+    let body = json_body(response).await;
+    assert_eq!(body["id"], json!(target.id));
+
+    let session = sqlx::query!(
+        r#"SELECT extra_info FROM sessions WHERE user_id = $1"#,
+        target.id
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .unwrap();
+    assert_eq!(session.extra_info["impersonated_by"], json!(admin.id));
+
+    let url = format!("/users/{}/impersonate", admin.id);
+    let response = send_request(&admin_app, &url, http::Method::POST, &admin_session).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn suspension_rejects_sessions_and_login(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let admin_app = create_admin_router().with_state(state.clone());
+    let app = create_router().with_state(state);
+
+    let admin = common::admin_fixture(&mut conn).await;
+    let admin_session = common::session_fixture(&mut conn, admin.id).await;
+    let target = common::verified_user_fixture(&mut conn).await;
+    let target_session = common::session_fixture(&mut conn, target.id).await;
+
+    let url = format!("/users/{}/suspend", target.id);
+    let data = json!({"reason": "spam", "until": Utc::now() - Duration::try_hours(1).unwrap()});
+    let response = send_json(&admin_app, &url, &data, &admin_session).await;
+    test::assert_response(
+        response,
+        StatusCode::BAD_REQUEST,
+        "Suspension end must be in the future",
+    )
+    .await;
+
+    let data = json!({"reason": "spam", "until": Utc::now() + Duration::try_days(1).unwrap()});
+    let response = send_json(&admin_app, &url, &data, &admin_session).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = send_request(&app, "/me", http::Method::GET, &target_session).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        body["details"]["auth"][0]["code"],
+        json!("account_suspended")
+    );
+    assert_eq!(body["details"]["auth"][0]["message"], json!("spam"));
+
+    let response = login(&app, &target.normalized_username).await;
+    test::assert_response(response, StatusCode::FORBIDDEN, "Account suspended: spam").await;
+
+    let url = format!("/users/{}/unsuspend", target.id);
+    let response = send_request(&admin_app, &url, http::Method::POST, &admin_session).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = send_request(&app, "/me", http::Method::GET, &target_session).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn expired_suspension_is_ignored(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let target = common::verified_user_fixture(&mut conn).await;
+    set_user_suspension(
+        &mut conn,
+        target.id,
+        "cool down",
+        Some(Utc::now() - Duration::try_minutes(1).unwrap()),
+    )
+    .await
+    .unwrap();
+
+    let response = login(&app, &target.normalized_username).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+async fn login(app: &Router, username: &str) -> Response {
+    let data = json!({"username": username, "password": common::STRONG_PASSWORD});
+    let request = test::build_request("/login", http::Method::POST, &data);
+    app.clone().oneshot(request).await.unwrap()
+}
+
+async fn json_body(response: Response) -> Value {
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&body).unwrap()
+}
+
+async fn send_json(app: &Router, url: &str, data: &Value, session_token: &str) -> Response {
+    let mut request = test::build_request(url, http::Method::POST, data);
+    let session_header = HeaderValue::from_str(session_token).unwrap();
+    request.headers_mut().insert(AUTHORIZATION, session_header);
+    app.clone().oneshot(request).await.unwrap()
+}
+
+async fn send_request(
+    app: &Router,
+    url: &str,
+    method: http::Method,
+    session_token: &str,
+) -> Response {
+    let mut request = test::build_request(url, method, &json!({}));
+    let session_header = HeaderValue::from_str(session_token).unwrap();
+    request.headers_mut().insert(AUTHORIZATION, session_header);
+    app.clone().oneshot(request).await.unwrap()
+}
@@ -0,0 +1,274 @@
+use auth_service::extractors::api_token::{ApiToken, TokenScope, TokenScopes};
+use auth_service::helpers::api_tokens::insert_api_token;
+use auth_service::router::create_router;
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::Response;
+use axum::{http, Router};
+use chrono::{Duration, Utc};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::{PgConnection, PgPool};
+use tower::ServiceExt;
+use utils::state::AppState;
+use utils::test;
+
+mod common;
+
+#[sqlx::test]
+async fn create_token_returns_token_once(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::verified_user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+
+    let data = json!({"name": "ci", "scopes": ["read"], "expires_in_days": 30});
+    let response = send_request(&app, "/tokens", http::Method::POST, &data, &session_token).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response_json(response).await;
+    let token = body["token"].as_str().unwrap();
+    assert!(token.starts_with("pat_"));
+    assert_eq!(body["api_token"]["name"], json!("ci"));
+    assert_eq!(body["api_token"]["scopes"], json!(["read"]));
+    assert!(body["api_token"].get("verifier_hash").is_none());
+
+    let response = send_request(
+        &app,
+        "/tokens",
+        http::Method::GET,
+        &json!({}),
+        &session_token,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response_json(response).await;
+    let tokens = body.as_array().unwrap();
+    assert_eq!(tokens.len(), 1);
+    assert!(tokens[0].get("token").is_none());
+}
+
+#[sqlx::test]
+async fn bearer_token_authenticates_and_tracks_usage(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::verified_user_fixture(&mut conn).await;
+    let (api_token, token) = token_fixture(&mut conn, user.id, vec![TokenScope::Read], None).await;
+
+    let response = send_request(
+        &app,
+        "/me",
+        http::Method::GET,
+        &json!({}),
+        &format!("Bearer {}", token),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response_json(response).await;
+    assert_eq!(body["username"], json!(user.username));
+
+    let saved = sqlx::query!(
+        "SELECT last_used_at FROM api_tokens WHERE identifier = $1",
+        api_token.identifier
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .expect("Unable to fetch token");
+    assert!(saved.last_used_at.is_some());
+}
+
+#[sqlx::test]
+async fn read_scope_cannot_make_changes(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::verified_user_fixture(&mut conn).await;
+    let (_, token) = token_fixture(&mut conn, user.id, vec![TokenScope::Read], None).await;
+
+    let response = send_request(
+        &app,
+        "/logout",
+        http::Method::POST,
+        &json!({}),
+        &format!("Bearer {}", token),
+    )
+    .await;
+    test::assert_response(
+        response,
+        StatusCode::FORBIDDEN,
+        "Api token does not have the required scope: POST",
+    )
+    .await;
+}
+
+#[sqlx::test]
+async fn expired_token_is_rejected(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::verified_user_fixture(&mut conn).await;
+    let expired = Some(Utc::now() - Duration::try_days(1).unwrap());
+    let (_, token) = token_fixture(&mut conn, user.id, vec![TokenScope::Read], expired).await;
+
+    let response = send_request(
+        &app,
+        "/me",
+        http::Method::GET,
+        &json!({}),
+        &format!("Bearer {}", token),
+    )
+    .await;
+    test::assert_response(
+        response,
+        StatusCode::UNAUTHORIZED,
+        "Authorization token invalid: token expired",
+    )
+    .await;
+}
+
+#[sqlx::test]
+async fn token_cannot_manage_tokens(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::verified_user_fixture(&mut conn).await;
+    let (_, token) = token_fixture(&mut conn, user.id, vec![TokenScope::Write], None).await;
+
+    let data = json!({"name": "escalate", "scopes": ["write"]});
+    let response = send_request(
+        &app,
+        "/tokens",
+        http::Method::POST,
+        &data,
+        &format!("Bearer {}", token),
+    )
+    .await;
+    test::assert_response(
+        response,
+        StatusCode::FORBIDDEN,
+        "Api tokens can only be managed from a login session",
+    )
+    .await;
+}
+
+#[sqlx::test]
+async fn revoked_token_no_longer_works(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::verified_user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+    let (api_token, token) = token_fixture(&mut conn, user.id, vec![TokenScope::Read], None).await;
+
+    let url = format!("/tokens/{}", api_token.identifier);
+    let response = send_request(&app, &url, http::Method::DELETE, &json!({}), &session_token).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = send_request(&app, &url, http::Method::DELETE, &json!({}), &session_token).await;
+    test::assert_response(response, StatusCode::NOT_FOUND, "Api token not found").await;
+
+    let response = send_request(
+        &app,
+        "/me",
+        http::Method::GET,
+        &json!({}),
+        &format!("Bearer {}", token),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[sqlx::test]
+async fn create_token_requires_verified_user(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+
+    let data = json!({"name": "ci", "scopes": ["read"]});
+    let response = send_request(&app, "/tokens", http::Method::POST, &data, &session_token).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test]
+async fn create_token_returns_400_for_invalid_data(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::verified_user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+
+    let test_cases = vec![
+        (json!({"scopes": ["read"]}), "missing the name"),
+        (json!({"name": "", "scopes": ["read"]}), "empty name"),
+        (json!({"name": "ci", "scopes": []}), "empty scopes"),
+        (json!({"name": "ci", "scopes": ["admin"]}), "unknown scope"),
+        (
+            json!({"name": "ci", "scopes": ["read"], "expires_in_days": 0}),
+            "expiry in the past",
+        ),
+    ];
+
+    for (payload, error_message) in test_cases {
+        let response = send_request(
+            &app,
+            "/tokens",
+            http::Method::POST,
+            &payload,
+            &session_token,
+        )
+        .await;
+        assert_eq!(
+            response.status(),
+            StatusCode::BAD_REQUEST,
+            "The request didn't throw 400 request for the case when {}",
+            error_message
+        );
+    }
+}
+
+async fn token_fixture(
+    conn: &mut PgConnection,
+    user_id: i32,
+    scopes: Vec<TokenScope>,
+    expires_at: Option<chrono::DateTime<Utc>>,
+) -> (ApiToken, String) {
+    let (api_token, token) = ApiToken::new(
+        user_id,
+        "fixture".to_string(),
+        TokenScopes(scopes),
+        expires_at,
+    );
+    insert_api_token(conn, &api_token)
+        .await
+        .expect("Cannot insert api token");
+    (api_token, token)
+}
+
+async fn send_request(
+    app: &Router,
+    url: &str,
+    method: http::Method,
+    data: &Value,
+    authorization: &str,
+) -> Response {
+    let mut request = test::build_request(url, method, data);
+    let header = HeaderValue::from_str(authorization).unwrap();
+    request.headers_mut().insert(AUTHORIZATION, header);
+    app.clone().oneshot(request).await.unwrap()
+}
+
+async fn response_json(response: Response) -> Value {
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&body).unwrap()
+}
@@ -0,0 +1,137 @@
+use auth_service::helpers::audit::verify_audit_chain;
+use auth_service::router::{create_admin_router, create_router};
+use axum::http::header::{AUTHORIZATION, USER_AGENT};
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::Response;
+use axum::{http, Router};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tower::ServiceExt;
+use utils::state::AppState;
+use utils::test;
+
+mod common;
+
+#[sqlx::test]
+async fn login_attempts_are_recorded(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let mut state = AppState::test_state(pool, None);
+    state.settings.rate_limit.trust_forwarded_for = true;
+    let app = create_router().with_state(state.clone());
+    let admin_app = create_admin_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let admin = common::admin_fixture(&mut conn).await;
+    let admin_session = common::session_fixture(&mut conn, admin.id).await;
+
+    let response = send_login(&app, &user.username, "wrong password").await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = send_login(&app, &user.username, common::STRONG_PASSWORD).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let url = format!("/audit?target_id={}", user.id);
+    let response = send_request(&admin_app, &url, &admin_session).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = json_body(response).await;
+    let events = body["events"].as_array().unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0]["event_type"], json!("login_succeeded"));
+    assert_eq!(events[0]["actor_id"], json!(user.id));
+    assert_eq!(events[0]["ip"], json!("203.0.113.7"));
+    assert_eq!(events[0]["user_agent"], json!("audit-test"));
+    assert_eq!(events[1]["event_type"], json!("login_failed"));
+    assert_eq!(events[1]["actor_id"], Value::Null);
+    assert_eq!(
+        events[1]["details"]["reason"],
+        json!("username or password is incorrect")
+    );
+
+    let response = send_request(&admin_app, "/audit?event_type=login_failed", &admin_session).await;
+    let body = json_body(response).await;
+    assert_eq!(body["events"].as_array().unwrap().len(), 1);
+}
+
+#[sqlx::test]
+async fn tampering_breaks_the_chain(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state.clone());
+    let admin_app = create_admin_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let admin = common::admin_fixture(&mut conn).await;
+    let admin_session = common::session_fixture(&mut conn, admin.id).await;
+    for _ in 0..3 {
+        send_login(&app, &user.username, common::STRONG_PASSWORD).await;
+    }
+
+    let response = send_request(&admin_app, "/audit/verify", &admin_session).await;
+    let body = json_body(response).await;
+    assert_eq!(body["valid"], json!(true));
+    assert_eq!(body["checked"], json!(3));
+
+    sqlx::query("UPDATE audit_events SET ip = '198.51.100.1' WHERE id = (SELECT min(id) + 1 FROM audit_events)")
+        .execute(&mut *conn)
+        .await
+        .unwrap();
+    let report = verify_audit_chain(&mut conn).await.unwrap();
+    assert!(!report.valid);
+    assert!(report.broken_at.is_some());
+
+    let member = common::verified_user_fixture(&mut conn).await;
+    let member_session = common::session_fixture(&mut conn, member.id).await;
+    let response = send_request(&admin_app, "/audit", &member_session).await;
+    test::assert_response(
+        response,
+        StatusCode::FORBIDDEN,
+        "insufficient permission: admin access required",
+    )
+    .await;
+}
+
+#[sqlx::test]
+async fn security_activity_lists_own_events(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let other = common::user_fixture(&mut conn).await;
+    send_login(&app, &user.username, common::STRONG_PASSWORD).await;
+    send_login(&app, &other.username, common::STRONG_PASSWORD).await;
+    send_login(&app, &other.username, "wrong password").await;
+
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+    let response = send_request(&app, "/security-activity", &session_token).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = json_body(response).await;
+    let events = body.as_array().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["target_id"], json!(user.id));
+    assert!(events[0].get("hash").is_none());
+}
+
+async fn send_login(app: &Router, username: &str, password: &str) -> Response {
+    let data = json!({
+        "username": username,
+        "password": password
+    });
+    let mut request = test::build_request("/login", http::Method::POST, &data);
+    let headers = request.headers_mut();
+    headers.insert("x-forwarded-for", HeaderValue::from_static("203.0.113.7"));
+    headers.insert(USER_AGENT, HeaderValue::from_static("audit-test"));
+    app.clone().oneshot(request).await.unwrap()
+}
+
+async fn send_request(app: &Router, url: &str, session_token: &str) -> Response {
+    let mut request = test::build_request(url, http::Method::GET, &json!({}));
+    let session_header = HeaderValue::from_str(session_token).unwrap();
+    request.headers_mut().insert(AUTHORIZATION, session_header);
+    app.clone().oneshot(request).await.unwrap()
+}
+
+async fn json_body(response: Response) -> Value {
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&body).unwrap()
+}
@@ -0,0 +1,146 @@
+use auth_service::extractors::confirmation::{Confirmation, ConfirmationActionType};
+use auth_service::helpers::confirmation::add_confirmation;
+use auth_service::helpers::user::fetch_user;
+use auth_service::router::create_router;
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::Response;
+use axum::{http, Router};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::time::Duration;
+use tower::ServiceExt;
+use utils::state::AppState;
+use utils::test;
+
+mod common;
+
+#[sqlx::test]
+async fn change_email_sends_link_to_new_address_and_notice_to_old(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+
+    let (email_rx, task_rx, _, app) = common::setup_app_with_task_thread(pool);
+    let user = common::verified_user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+
+    let data = json!({"email": "New.Address@example.com", "password": common::STRONG_PASSWORD});
+    let response = send_request(&app, "/change-email", &data, &session_token).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    for _ in 0..2 {
+        let task = task_rx.try_recv().expect("Task not thrown out.");
+        task.handle.await.expect("Join error, task panicked");
+    }
+    let mut emails = [
+        email_rx.recv_timeout(Duration::from_secs(5)).unwrap(),
+        email_rx.recv_timeout(Duration::from_secs(5)).unwrap(),
+    ];
+    emails.sort_by(|a, b| a.subject.cmp(&b.subject));
+
+    assert_eq!(emails[0].to[0].email, user.email);
+    assert_eq!(
+        emails[0].subject,
+        "A change of your email address was requested"
+    );
+    assert_eq!(emails[1].to[0].email, "new.address@example.com");
+    assert_eq!(emails[1].subject, "Please confirm your new email address");
+
+    let saved = fetch_user(&mut conn, user.id).await.unwrap();
+    assert_eq!(saved.email, user.email);
+}
+
+#[sqlx::test]
+async fn change_email_requires_current_password(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::verified_user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+
+    let data = json!({"email": "new@example.com", "password": "incorrect password"});
+    let response = send_request(&app, "/change-email", &data, &session_token).await;
+    test::assert_response(
+        response,
+        StatusCode::BAD_REQUEST,
+        "Current password is incorrect",
+    )
+    .await;
+}
+
+#[sqlx::test]
+async fn change_email_rejects_registered_address(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::verified_user_fixture(&mut conn).await;
+    let other = common::user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+
+    let data = json!({"email": other.email, "password": common::STRONG_PASSWORD});
+    let response = send_request(&app, "/change-email", &data, &session_token).await;
+    test::assert_response(response, StatusCode::BAD_REQUEST, "Email already used").await;
+}
+
+#[sqlx::test]
+async fn confirming_email_change_swaps_address(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+    let token = email_change_fixture(&mut conn, user.id, "swapped@example.com").await;
+
+    let url = format!("/confirm/{}", token);
+    let response = send_request(&app, &url, &json!({}), &session_token).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let saved = fetch_user(&mut conn, user.id).await.unwrap();
+    assert_eq!(saved.email, "swapped@example.com");
+    assert!(saved.is_confirmed);
+}
+
+#[sqlx::test]
+async fn confirming_email_change_handles_address_taken_meanwhile(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let other = common::user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+    let token = email_change_fixture(&mut conn, user.id, &other.email).await;
+
+    let url = format!("/confirm/{}", token);
+    let response = send_request(&app, &url, &json!({}), &session_token).await;
+    test::assert_response(
+        response,
+        StatusCode::BAD_REQUEST,
+        "Email already registered",
+    )
+    .await;
+
+    let saved = fetch_user(&mut conn, user.id).await.unwrap();
+    assert_eq!(saved.email, user.email);
+}
+
+async fn email_change_fixture(conn: &mut sqlx::PgConnection, user_id: i32, email: &str) -> String {
+    let (confirmation, token) = Confirmation::new(
+        user_id,
+        ConfirmationActionType::EmailChange,
+        json!({"email": email}),
+    );
+    add_confirmation(conn, &confirmation)
+        .await
+        .expect("Cannot add confirmation");
+    token
+}
+
+async fn send_request(app: &Router, url: &str, data: &Value, session_token: &str) -> Response {
+    let mut request = test::build_request(url, http::Method::POST, data);
+    let session_header = HeaderValue::from_str(session_token).unwrap();
+    request.headers_mut().insert(AUTHORIZATION, session_header);
+    app.clone().oneshot(request).await.unwrap()
+}
@@ -0,0 +1,196 @@
+use auth_service::helpers::user::fetch_user;
+use auth_service::router::create_router;
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::Response;
+use axum::{http, Router};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tower::ServiceExt;
+use utils::state::AppState;
+use utils::test;
+use uuid::Uuid;
+
+mod common;
+
+static NEW_PASSWORD: &str = "c0rrect-h0rse-battery-staple!";
+
+#[sqlx::test]
+async fn change_password_successfully(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+    let other_session = common::session_fixture(&mut conn, user.id).await;
+
+    let data = json!({
+        "current_password": common::STRONG_PASSWORD,
+        "password": NEW_PASSWORD,
+        "confirm_password": NEW_PASSWORD
+    });
+    let response = send_request(&app, &data, &session_token).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let saved = fetch_user(&mut conn, user.id).await.unwrap();
+    assert!(saved.check_password(NEW_PASSWORD, &Default::default()));
+    assert!(!saved.check_password(common::STRONG_PASSWORD, &Default::default()));
+
+    // Other sessions are kept unless asked otherwise.
+    let response = send_me_request(&app, &other_session).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn change_password_can_revoke_other_sessions(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+    let other_session = common::session_fixture(&mut conn, user.id).await;
+
+    let data = json!({
+        "current_password": common::STRONG_PASSWORD,
+        "password": NEW_PASSWORD,
+        "confirm_password": NEW_PASSWORD,
+        "revoke_other_sessions": true
+    });
+    let response = send_request(&app, &data, &session_token).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = send_me_request(&app, &other_session).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = send_me_request(&app, &session_token).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn change_password_rejects_incorrect_current_password(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+
+    let data = json!({
+        "current_password": "not the password",
+        "password": NEW_PASSWORD,
+        "confirm_password": NEW_PASSWORD
+    });
+    let response = send_request(&app, &data, &session_token).await;
+    test::assert_response(
+        response,
+        StatusCode::BAD_REQUEST,
+        "Current password is incorrect",
+    )
+    .await;
+}
+
+#[sqlx::test]
+async fn change_password_rejects_weak_password(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+
+    let data = json!({
+        "current_password": common::STRONG_PASSWORD,
+        "password": "password",
+        "confirm_password": "password"
+    });
+    let response = send_request(&app, &data, &session_token).await;
+    test::assert_response(response, StatusCode::BAD_REQUEST, "Weak password").await;
+}
+
+#[sqlx::test]
+async fn change_password_follows_configured_policy(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let breached_dir = std::env::temp_dir().join(format!("breached-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&breached_dir).unwrap();
+    // SHA-1 of NEW_PASSWORD split after the first five characters.
+    std::fs::write(
+        breached_dir.join("C3D1A.txt"),
+        "0000000000000000000000000000000000A:1\r\n62786E4111B8F4CA5469349C006EF7204A5:42\r\n",
+    )
+    .unwrap();
+
+    let mut state = AppState::test_state(pool, None);
+    state.settings.password_policy.min_length = 40;
+    let app = create_router().with_state(state.clone());
+    let user = common::user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+    let data = json!({
+        "current_password": common::STRONG_PASSWORD,
+        "password": NEW_PASSWORD,
+        "confirm_password": NEW_PASSWORD
+    });
+
+    let response = send_request(&app, &data, &session_token).await;
+    let body = error_body(response).await;
+    assert_eq!(
+        body["message"],
+        json!("Password must contain 40-72 characters")
+    );
+    assert_eq!(
+        body["details"]["password"][0]["code"],
+        json!("password_length")
+    );
+
+    state.settings.password_policy.min_length = 8;
+    state.settings.password_policy.forbidden_inputs = vec!["horse".to_string()];
+    state.settings.password_policy.min_score = 4;
+    let app = create_router().with_state(state.clone());
+    let weak = json!({
+        "current_password": common::STRONG_PASSWORD,
+        "password": "horse-horse",
+        "confirm_password": "horse-horse"
+    });
+    let response = send_request(&app, &weak, &session_token).await;
+    let body = error_body(response).await;
+    assert_eq!(
+        body["details"]["password"][0]["code"],
+        json!("weak_password")
+    );
+    assert!(body["details"]["password"][0]["params"]["suggestions"].is_array());
+
+    state.settings.password_policy.min_score = 3;
+    state.settings.password_policy.forbidden_inputs = vec![];
+    state.settings.password_policy.breached_hashes_dir =
+        Some(breached_dir.to_string_lossy().to_string());
+    let app = create_router().with_state(state);
+    let response = send_request(&app, &data, &session_token).await;
+    let body = error_body(response).await;
+    assert_eq!(
+        body["details"]["password"][0]["code"],
+        json!("breached_password")
+    );
+
+    std::fs::remove_dir_all(breached_dir).unwrap();
+}
+
+async fn send_request(app: &Router, data: &Value, session_token: &str) -> Response {
+    let mut request = test::build_request("/change-password", http::Method::POST, data);
+    let session_header = HeaderValue::from_str(session_token).unwrap();
+    request.headers_mut().insert(AUTHORIZATION, session_header);
+    app.clone().oneshot(request).await.unwrap()
+}
+
+async fn send_me_request(app: &Router, session_token: &str) -> Response {
+    let mut request = test::build_request("/me", http::Method::GET, &json!({}));
+    let session_header = HeaderValue::from_str(session_token).unwrap();
+    request.headers_mut().insert(AUTHORIZATION, session_header);
+    app.clone().oneshot(request).await.unwrap()
+}
+
+async fn error_body(response: Response) -> Value {
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&body).unwrap()
+}
@@ -0,0 +1,177 @@
+use auth_service::extractors::client_token::AccessTokenClaims;
+use auth_service::router::create_router;
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::Response;
+use axum::{http, Router};
+use axum_extra::extract::cookie::Key;
+use chrono::Duration;
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tower::ServiceExt;
+use utils::state::AppState;
+use utils::test;
+use uuid::Uuid;
+
+mod common;
+
+#[sqlx::test]
+async fn password_grant_issues_tokens_that_authenticate(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let body = issue_tokens(&app, &user.username).await;
+    assert_eq!(body["token_type"], json!("Bearer"));
+    assert_eq!(body["expires_in"], json!(15 * 60));
+    assert!(body["refresh_token"].as_str().unwrap().starts_with("rt_"));
+
+    let access_token = body["access_token"].as_str().unwrap();
+    assert!(access_token.starts_with("at_"));
+    let response = send_request(&app, "/me", &json!({}), Some(access_token)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response_json(response).await;
+    assert_eq!(body["username"], json!(user.username));
+
+    let response = send_request(
+        &app,
+        "/token",
+        &json!({"username": user.username, "password": "invalid!"}),
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn refresh_rotates_and_reuse_revokes_the_family(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let first = issue_tokens(&app, &user.username).await;
+    let first_refresh = first["refresh_token"].as_str().unwrap();
+
+    let response = refresh(&app, first_refresh).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let second = response_json(response).await;
+    let second_refresh = second["refresh_token"].as_str().unwrap();
+    let second_access = second["access_token"].as_str().unwrap();
+    assert_ne!(first_refresh, second_refresh);
+
+    let response = refresh(&app, first_refresh).await;
+    test::assert_response(
+        response,
+        StatusCode::UNAUTHORIZED,
+        "Refresh token was already used, please sign in again",
+    )
+    .await;
+
+    let response = refresh(&app, second_refresh).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = send_request(&app, "/me", &json!({}), Some(second_access)).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let revoked = sqlx::query!(
+        r#"SELECT COUNT(*) as count FROM audit_events WHERE target_id = $1 AND event_type = 'sessions_revoked'"#,
+        user.id
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .expect("Unable to fetch audit events");
+    assert_eq!(revoked.count, Some(1));
+}
+
+#[sqlx::test]
+async fn revoke_and_logout_end_the_family(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let body = issue_tokens(&app, &user.username).await;
+    let refresh_token = body["refresh_token"].as_str().unwrap();
+    let response = send_request(
+        &app,
+        "/token/revoke",
+        &json!({"refresh_token": refresh_token}),
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = refresh(&app, refresh_token).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let body = issue_tokens(&app, &user.username).await;
+    let access_token = body["access_token"].as_str().unwrap();
+    let response = send_request(&app, "/logout", &json!({}), Some(access_token)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = send_request(&app, "/me", &json!({}), Some(access_token)).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = refresh(&app, body["refresh_token"].as_str().unwrap()).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[sqlx::test]
+async fn expired_or_tampered_access_token_is_rejected(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let key = Key::from(state.settings.application.key.as_ref());
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let body = issue_tokens(&app, &user.username).await;
+    let access_token = body["access_token"].as_str().unwrap();
+    let claims = AccessTokenClaims::verify(access_token, &key).unwrap();
+
+    let expired =
+        AccessTokenClaims::new(user.id, claims.fam, Duration::try_minutes(-1).unwrap()).sign(&key);
+    let forged = AccessTokenClaims::new(user.id, claims.fam, Duration::try_minutes(5).unwrap())
+        .sign(&Key::generate());
+    let unknown_family =
+        AccessTokenClaims::new(user.id, Uuid::new_v4(), Duration::try_minutes(5).unwrap())
+            .sign(&key);
+
+    for token in [expired, forged, unknown_family] {
+        let response = send_request(&app, "/me", &json!({}), Some(&token)).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}
+
+async fn issue_tokens(app: &Router, username: &str) -> Value {
+    let data = json!({"username": username, "password": common::STRONG_PASSWORD});
+    let response = send_request(app, "/token", &data, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    response_json(response).await
+}
+
+async fn refresh(app: &Router, refresh_token: &str) -> Response {
+    let data = json!({"refresh_token": refresh_token});
+    send_request(app, "/token/refresh", &data, None).await
+}
+
+async fn send_request(
+    app: &Router,
+    url: &str,
+    data: &Value,
+    access_token: Option<&str>,
+) -> Response {
+    let method = match url {
+        "/me" => http::Method::GET,
+        _ => http::Method::POST,
+    };
+    let mut request = test::build_request(url, method, data);
+    if let Some(access_token) = access_token {
+        let header = HeaderValue::from_str(&format!("Bearer {}", access_token)).unwrap();
+        request.headers_mut().insert(AUTHORIZATION, header);
+    }
+    app.clone().oneshot(request).await.unwrap()
+}
+
+async fn response_json(response: Response) -> Value {
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&body).unwrap()
+}
@@ -1,6 +1,6 @@
 use auth_service::extractors::confirmation::{Confirmation, ConfirmationActionType};
 use auth_service::extractors::user::User;
-use auth_service::helpers::confirmation::add_confirmation;
+use auth_service::helpers::confirmation::{add_confirmation, mark_user_as_confirmed};
 use auth_service::helpers::sessions::create_new_session;
 use auth_service::helpers::user::insert_user;
 use auth_service::payload::RegisterPayload;
@@ -14,7 +14,7 @@ use serde_json::json;
 use sqlx::{PgConnection, PgPool};
 use std::sync::mpsc;
 use std::sync::mpsc::Receiver;
-use utils::configuration::{RunMode, Settings};
+use utils::configuration::{PasswordHashSettings, RunMode, Settings};
 use utils::state::BackgroundTask;
 use utils::test;
 
@@ -69,9 +69,11 @@ pub async fn user_fixture(transaction: &mut PgConnection) -> User {
         email: SafeEmail().fake(),
         confirm_password: STRONG_PASSWORD.to_string(),
         name: Name().fake(),
+        invitation: None,
     };
 
-    let mut user = User::try_from(user_payload).expect("Cannot form new user");
+    let mut user = User::from_registration(user_payload, &PasswordHashSettings::default())
+        .expect("Cannot form new user");
     let id = insert_user(transaction, &user)
         .await
         .expect("Cannot insert user");
@@ -79,9 +81,30 @@ pub async fn user_fixture(transaction: &mut PgConnection) -> User {
     user
 }
 
+#[allow(dead_code)]
+pub async fn verified_user_fixture(transaction: &mut PgConnection) -> User {
+    let mut user = user_fixture(transaction).await;
+    mark_user_as_confirmed(transaction, user.id)
+        .await
+        .expect("Cannot verify user");
+    user.is_confirmed = true;
+    user
+}
+
 #[allow(dead_code)]
 pub async fn session_fixture(transaction: &mut PgConnection, user_id: i32) -> String {
     create_new_session(transaction, user_id, json!({}))
         .await
         .unwrap()
 }
+
+#[allow(dead_code)]
+pub async fn admin_fixture(transaction: &mut PgConnection) -> User {
+    let mut user = verified_user_fixture(transaction).await;
+    sqlx::query!("update users set is_admin = true where id = $1", user.id)
+        .execute(&mut *transaction)
+        .await
+        .expect("Cannot promote user");
+    user.is_admin = true;
+    user
+}
@@ -0,0 +1,127 @@
+use auth_service::middleware::csrf_protection;
+use auth_service::router::create_router;
+use axum::http::header::{AUTHORIZATION, COOKIE, HOST, ORIGIN, SET_COOKIE};
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::Response;
+use axum::{http, middleware, Router};
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+use utils::state::AppState;
+use utils::test;
+
+mod common;
+
+#[sqlx::test]
+async fn session_cookie_has_configured_attributes(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let mut state = AppState::test_state(pool, None);
+    state.settings.cookie.domain = Some("example.com".to_string());
+    let app = csrf_app(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let response = login(&app, &user.username).await;
+    let cookie = response
+        .headers()
+        .get(SET_COOKIE)
+        .unwrap()
+        .to_str()
+        .unwrap();
+    assert!(cookie.contains("HttpOnly"));
+    assert!(cookie.contains("Secure"));
+    assert!(cookie.contains("SameSite=Lax"));
+    assert!(cookie.contains("Path=/"));
+    assert!(cookie.contains("Domain=example.com"));
+}
+
+#[sqlx::test]
+async fn cookie_requests_from_other_origins_are_rejected(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let mut state = AppState::test_state(pool, None);
+    state.settings.csrf.trusted_origins = vec!["https://admin.example.com/".to_string()];
+    let app = csrf_app(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let cookie = session_cookie(&login(&app, &user.username).await);
+
+    let response = send_request(&app, &cookie, None).await;
+    test::assert_response(response, StatusCode::FORBIDDEN, "Request origin is missing").await;
+
+    let response = send_request(&app, &cookie, Some("https://evil.example.com")).await;
+    test::assert_response(
+        response,
+        StatusCode::FORBIDDEN,
+        "Request origin is not allowed: https://evil.example.com",
+    )
+    .await;
+
+    let response = send_request(&app, &cookie, Some("https://admin.example.com")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = send_request(&app, &cookie, Some("http://cms.example.com")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn header_and_anonymous_requests_skip_origin_check(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = csrf_app(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let response = login(&app, &user.username).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let cookie = session_cookie(&response);
+    let authorization = response.headers().get(AUTHORIZATION).unwrap().clone();
+
+    let mut request = test::build_request("/logout", http::Method::POST, &json!({}));
+    request
+        .headers_mut()
+        .insert(COOKIE, HeaderValue::from_str(&cookie).unwrap());
+    request.headers_mut().insert(AUTHORIZATION, authorization);
+    request
+        .headers_mut()
+        .insert(ORIGIN, HeaderValue::from_static("https://evil.example.com"));
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+fn csrf_app(state: AppState) -> Router {
+    create_router()
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            csrf_protection,
+        ))
+        .with_state(state)
+}
+
+async fn login(app: &Router, username: &str) -> Response {
+    let data = json!({"username": username, "password": common::STRONG_PASSWORD});
+    let mut request = test::build_request("/login", http::Method::POST, &data);
+    request
+        .headers_mut()
+        .insert(ORIGIN, HeaderValue::from_static("https://evil.example.com"));
+    app.clone().oneshot(request).await.unwrap()
+}
+
+fn session_cookie(response: &Response) -> String {
+    let header = response
+        .headers()
+        .get(SET_COOKIE)
+        .unwrap()
+        .to_str()
+        .unwrap();
+    header.split(';').next().unwrap().to_string()
+}
+
+async fn send_request(app: &Router, cookie: &str, origin: Option<&'static str>) -> Response {
+    let data = json!({"name": "Csrf Check"});
+    let mut request = test::build_request("/me", http::Method::PATCH, &data);
+    let headers = request.headers_mut();
+    headers.insert(COOKIE, HeaderValue::from_str(cookie).unwrap());
+    headers.insert(HOST, HeaderValue::from_static("cms.example.com"));
+    if let Some(origin) = origin {
+        headers.insert(ORIGIN, HeaderValue::from_static(origin));
+    }
+    app.clone().oneshot(request).await.unwrap()
+}
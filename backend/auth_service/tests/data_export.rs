@@ -0,0 +1,107 @@
+use auth_service::extractors::data_export::DataExport;
+use auth_service::helpers::account::insert_data_export;
+use auth_service::router::create_router;
+use axum::http::header::{AUTHORIZATION, CONTENT_DISPOSITION};
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::Response;
+use axum::{http, Router};
+use chrono::{Duration, Utc};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::time;
+use tower::ServiceExt;
+use utils::state::AppState;
+use utils::test;
+
+mod common;
+
+#[sqlx::test]
+async fn export_is_prepared_and_emailed(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let (email_rx, task_rx, _, app) = common::setup_app_with_task_thread(pool);
+
+    let user = common::user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+
+    let response = send_request(&app, "/export", http::Method::POST, &session_token).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    let identifier = body["identifier"].as_str().unwrap().to_string();
+    assert_eq!(body["ready_at"], Value::Null);
+
+    // Preparing the export queues the email once it is stored.
+    for _ in 0..2 {
+        let task = task_rx
+            .recv_timeout(time::Duration::from_secs(5))
+            .expect("Task not thrown out.");
+        task.handle.await.expect("Join error, task panicked");
+    }
+    let email = email_rx.recv_timeout(time::Duration::from_secs(5)).unwrap();
+    assert_eq!(email.to[0].email, user.email);
+    assert_eq!(email.subject, "Your data export is ready");
+    assert!(email.plain.contains(&identifier));
+
+    let url = format!("/export/{}", identifier);
+    let response = send_request(&app, &url, http::Method::GET, &session_token).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let disposition = response.headers().get(CONTENT_DISPOSITION).unwrap();
+    assert!(disposition.to_str().unwrap().starts_with("attachment"));
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["profile"]["username"], json!(user.username));
+    assert_eq!(body["sessions"].as_array().unwrap().len(), 1);
+    assert!(body["sessions"][0].get("verifier_hash").is_none());
+    assert!(body["profile"].get("password_hash").is_none());
+}
+
+#[sqlx::test]
+async fn export_not_ready(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+    let export = DataExport::new(user.id, Utc::now() + Duration::try_days(1).unwrap());
+    insert_data_export(&mut conn, &export).await.unwrap();
+
+    let url = format!("/export/{}", export.identifier);
+    let response = send_request(&app, &url, http::Method::GET, &session_token).await;
+    test::assert_response(
+        response,
+        StatusCode::CONFLICT,
+        "Data export is still being prepared",
+    )
+    .await;
+}
+
+#[sqlx::test]
+async fn export_of_other_user_not_found(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let owner = common::user_fixture(&mut conn).await;
+    let other = common::user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, other.id).await;
+    let export = DataExport::new(owner.id, Utc::now() + Duration::try_days(1).unwrap());
+    insert_data_export(&mut conn, &export).await.unwrap();
+
+    let url = format!("/export/{}", export.identifier);
+    let response = send_request(&app, &url, http::Method::GET, &session_token).await;
+    test::assert_response(response, StatusCode::NOT_FOUND, "Data export not found").await;
+}
+
+async fn send_request(
+    app: &Router,
+    url: &str,
+    method: http::Method,
+    session_token: &str,
+) -> Response {
+    let mut request = test::build_request(url, method, &json!({}));
+    let session_header = HeaderValue::from_str(session_token).unwrap();
+    request.headers_mut().insert(AUTHORIZATION, session_header);
+    app.clone().oneshot(request).await.unwrap()
+}
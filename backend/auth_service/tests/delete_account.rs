@@ -0,0 +1,174 @@
+use auth_service::extractors::confirmation::{Confirmation, ConfirmationActionType};
+use auth_service::helpers::account::{mark_account_deleted, purge_deleted_accounts};
+use auth_service::helpers::audit::verify_audit_chain;
+use auth_service::helpers::confirmation::add_confirmation;
+use auth_service::helpers::user::fetch_user;
+use auth_service::router::create_router;
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::Response;
+use axum::{http, Router};
+use chrono::{Duration, Utc};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::time;
+use tower::ServiceExt;
+use utils::state::AppState;
+use utils::test;
+
+mod common;
+
+#[sqlx::test]
+async fn delete_account_requires_current_password(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+
+    let data = json!({"current_password": "incorrect password"});
+    let response = send_request(&app, "/me", http::Method::DELETE, &data, &session_token).await;
+    test::assert_response(
+        response,
+        StatusCode::BAD_REQUEST,
+        "Current password is incorrect",
+    )
+    .await;
+
+    let saved = fetch_user(&mut conn, user.id).await.unwrap();
+    assert!(saved.is_active);
+}
+
+#[sqlx::test]
+async fn delete_account_deactivates_and_sends_restore_link(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let (email_rx, task_rx, _, app) = common::setup_app_with_task_thread(pool);
+
+    let user = common::user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+
+    let data = json!({"current_password": common::STRONG_PASSWORD});
+    let response = send_request(&app, "/me", http::Method::DELETE, &data, &session_token).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let task = task_rx.try_recv().expect("Task not thrown out.");
+    task.handle.await.expect("Join error, task panicked");
+    let email = email_rx.recv_timeout(time::Duration::from_secs(5)).unwrap();
+    assert_eq!(email.to[0].email, user.email);
+    assert_eq!(email.subject, "Your account is scheduled for deletion");
+    assert!(email.plain.contains("/auth/restore/"));
+
+    let saved = fetch_user(&mut conn, user.id).await.unwrap();
+    assert!(!saved.is_active);
+    assert!(saved.deleted_at.is_some());
+
+    // The session used for the deletion is gone as well.
+    let response = send_request(&app, "/me", http::Method::GET, &json!({}), &session_token).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let data = json!({"username": user.normalized_username, "password": common::STRONG_PASSWORD});
+    let request = test::build_request("/login", http::Method::POST, &data);
+    let response = app.clone().oneshot(request).await.unwrap();
+    test::assert_response(
+        response,
+        StatusCode::BAD_REQUEST,
+        "login failed: account is scheduled for deletion",
+    )
+    .await;
+}
+
+#[sqlx::test]
+async fn restore_account_reactivates_user(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    mark_account_deleted(&mut conn, user.id).await.unwrap();
+    let (confirmation, token) =
+        Confirmation::new(user.id, ConfirmationActionType::AccountRestore, json!({}));
+    add_confirmation(&mut conn, &confirmation).await.unwrap();
+
+    let url = format!("/restore/{}", token);
+    let request = test::build_request(&url, http::Method::POST, &json!({}));
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let saved = fetch_user(&mut conn, user.id).await.unwrap();
+    assert!(saved.is_active);
+    assert!(saved.deleted_at.is_none());
+
+    // The link can only be used once.
+    let request = test::build_request(&url, http::Method::POST, &json!({}));
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn purge_removes_accounts_after_grace_period(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+
+    let expired = common::user_fixture(&mut conn).await;
+    let recent = common::user_fixture(&mut conn).await;
+    let active = common::user_fixture(&mut conn).await;
+    mark_account_deleted(&mut conn, expired.id).await.unwrap();
+    mark_account_deleted(&mut conn, recent.id).await.unwrap();
+    sqlx::query!(
+        "update users set deleted_at = $1 where id = $2",
+        Utc::now() - Duration::try_days(31).unwrap(),
+        expired.id
+    )
+    .execute(&mut *conn)
+    .await
+    .expect("Cannot backdate deletion");
+
+    let count = purge_deleted_accounts(&state).await.unwrap();
+    assert_eq!(count, 1);
+
+    assert!(fetch_user(&mut conn, expired.id).await.is_err());
+    assert!(fetch_user(&mut conn, recent.id).await.is_ok());
+    assert!(fetch_user(&mut conn, active.id).await.is_ok());
+}
+
+#[sqlx::test]
+async fn purge_keeps_the_audit_chain_valid(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state.clone());
+
+    let user = common::user_fixture(&mut conn).await;
+    let data = json!({"username": user.username, "password": common::STRONG_PASSWORD});
+    let request = test::build_request("/login", http::Method::POST, &data);
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    mark_account_deleted(&mut conn, user.id).await.unwrap();
+    sqlx::query!(
+        "update users set deleted_at = $1 where id = $2",
+        Utc::now() - Duration::try_days(31).unwrap(),
+        user.id
+    )
+    .execute(&mut *conn)
+    .await
+    .expect("Cannot backdate deletion");
+    assert_eq!(purge_deleted_accounts(&state).await.unwrap(), 1);
+
+    let report = verify_audit_chain(&mut conn).await.unwrap();
+    assert!(report.valid);
+    assert_eq!(report.checked, 1);
+}
+
+async fn send_request(
+    app: &Router,
+    url: &str,
+    method: http::Method,
+    data: &Value,
+    session_token: &str,
+) -> Response {
+    let mut request = test::build_request(url, method, data);
+    let session_header = HeaderValue::from_str(session_token).unwrap();
+    request.headers_mut().insert(AUTHORIZATION, session_header);
+    app.clone().oneshot(request).await.unwrap()
+}
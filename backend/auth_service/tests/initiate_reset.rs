@@ -136,6 +136,39 @@ async fn initiate_reset_successfully_sends_email(pool: PgPool) {
     )
 }
 
+#[sqlx::test]
+async fn initiate_reset_is_rate_limited_per_account(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+
+    let (email_rx, task_rx, settings, app) = common::setup_app_with_task_thread(pool);
+    let user = user_fixture(&mut conn).await;
+
+    let request_data = json!({
+        "username_or_email": user.email,
+    });
+    for _ in 0..settings.rate_limit.reset_requests_per_hour {
+        let response = send_request(&app, "/initiate-reset", &request_data).await;
+        assert_eq!(response.status(), http::StatusCode::OK);
+        // The reset task queues the email task in turn.
+        for _ in 0..2 {
+            let task = task_rx.try_recv().expect("Task not thrown out.");
+            task.handle.await.expect("Join error, task panicked");
+        }
+        email_rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("Email not sent during the reset");
+    }
+
+    let response = send_request(&app, "/initiate-reset", &request_data).await;
+    test::assert_response(
+        response,
+        http::StatusCode::TOO_MANY_REQUESTS,
+        "Too many attempts, please try again later",
+    )
+    .await;
+    assert!(task_rx.try_recv().is_err());
+}
+
 async fn send_request(app: &Router, path: &str, data: &Value) -> Response {
     let request = test::build_request(path, http::Method::POST, data);
     app.clone().oneshot(request).await.unwrap()
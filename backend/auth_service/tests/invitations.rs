@@ -0,0 +1,235 @@
+use auth_service::extractors::invitation::{Invitation, InviteRole};
+use auth_service::helpers::invitation::{insert_invitation, mark_invitation_accepted};
+use auth_service::helpers::user::fetch_user;
+use auth_service::router::{create_admin_router, create_router};
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::Response;
+use axum::{http, Router};
+use chrono::Duration;
+use email_clients::email::EmailObject;
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+use std::time;
+use tower::ServiceExt;
+use utils::configuration::RegistrationMode;
+use utils::email::get_link;
+use utils::state::{AppState, BackgroundTask};
+use utils::test;
+
+mod common;
+
+#[sqlx::test]
+async fn registration_modes_are_enforced(pool: PgPool) {
+    let (_email_rx, _task_rx, mut state) = invite_state(pool);
+
+    state.settings.registration.mode = RegistrationMode::Closed;
+    let app = create_router().with_state(state.clone());
+    let response = register(&app, "closed@example.com", None).await;
+    test::assert_response(response, StatusCode::BAD_REQUEST, "Registration is closed").await;
+
+    state.settings.registration.mode = RegistrationMode::InviteOnly;
+    let app = create_router().with_state(state);
+    let response = register(&app, "invite@example.com", None).await;
+    test::assert_response(
+        response,
+        StatusCode::BAD_REQUEST,
+        "Registration requires an invitation",
+    )
+    .await;
+
+    let request = test::build_request("/registration", http::Method::GET, &json!({}));
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(json_body(response).await["mode"], json!("invite-only"));
+}
+
+#[sqlx::test]
+async fn invited_user_registers_verified(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let (email_rx, task_rx, mut state) = invite_state(pool);
+    state.settings.registration.mode = RegistrationMode::InviteOnly;
+    let admin_app = create_admin_router().with_state(state.clone());
+    let app = create_router().with_state(state);
+
+    let admin = common::admin_fixture(&mut conn).await;
+    let admin_session = common::session_fixture(&mut conn, admin.id).await;
+
+    let data = json!({"email": "New.Editor@example.com", "role": "admin"});
+    let response = send_request(
+        &admin_app,
+        "/invitations",
+        http::Method::POST,
+        &data,
+        &admin_session,
+    )
+    .await;
+    let invitation = json_body(response).await;
+    assert_eq!(invitation["email"], json!("new.editor@example.com"));
+    assert_eq!(invitation["inviter_id"], json!(admin.id));
+
+    let task = task_rx.try_recv().expect("Task not thrown out.");
+    task.handle.await.expect("Join error, task panicked");
+    let email = email_rx.recv_timeout(time::Duration::from_secs(5)).unwrap();
+    assert_eq!(email.to[0].email, "new.editor@example.com");
+    let link = get_link(&email.plain);
+    let token = link.split("/auth/invite/").nth(1).unwrap().to_string();
+
+    let url = format!("/invitations/{}", token);
+    let request = test::build_request(&url, http::Method::GET, &json!({}));
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(
+        json_body(response).await["email"],
+        json!("new.editor@example.com")
+    );
+
+    let response = register(&app, "someone.else@example.com", Some(&token)).await;
+    test::assert_response(
+        response,
+        StatusCode::BAD_REQUEST,
+        "Email does not match the invitation",
+    )
+    .await;
+
+    let response = register(&app, "new.editor@example.com", Some(&token)).await;
+    let body = json_body(response).await;
+    let user = fetch_user(&mut conn, body["id"].as_i64().unwrap() as i32)
+        .await
+        .unwrap();
+    assert!(user.is_confirmed);
+    assert!(user.is_admin);
+    // No verification email is needed for an invited address.
+    assert!(task_rx.try_recv().is_err());
+
+    let response = register(&app, "new.editor@example.com", Some(&token)).await;
+    test::assert_response(
+        response,
+        StatusCode::BAD_REQUEST,
+        "Invitation is invalid or expired",
+    )
+    .await;
+}
+
+#[sqlx::test]
+async fn invitation_can_only_be_accepted_once(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let admin = common::admin_fixture(&mut conn).await;
+    let first = common::user_fixture(&mut conn).await;
+    let second = common::user_fixture(&mut conn).await;
+    let (invitation, _) = Invitation::new(
+        "guest@example.com",
+        InviteRole::User,
+        admin.id,
+        Duration::try_days(1).unwrap(),
+    );
+    insert_invitation(&mut conn, &invitation).await.unwrap();
+
+    mark_invitation_accepted(&mut conn, invitation.identifier, first.id)
+        .await
+        .expect("First acceptance failed");
+    let result = mark_invitation_accepted(&mut conn, invitation.identifier, second.id).await;
+    assert!(result.is_err(), "Invitation was accepted twice");
+}
+
+#[sqlx::test]
+async fn resend_rotates_and_revoke_removes_invitation(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let (email_rx, task_rx, state) = invite_state(pool);
+    let admin_app = create_admin_router().with_state(state.clone());
+    let app = create_router().with_state(state);
+
+    let admin = common::admin_fixture(&mut conn).await;
+    let admin_session = common::session_fixture(&mut conn, admin.id).await;
+    let (invitation, old_token) = Invitation::new(
+        "guest@example.com",
+        InviteRole::User,
+        admin.id,
+        Duration::try_days(1).unwrap(),
+    );
+    insert_invitation(&mut conn, &invitation).await.unwrap();
+
+    let url = format!("/invitations/{}/resend", invitation.identifier);
+    let response = send_request(
+        &admin_app,
+        &url,
+        http::Method::POST,
+        &json!({}),
+        &admin_session,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let task = task_rx.try_recv().expect("Task not thrown out.");
+    task.handle.await.expect("Join error, task panicked");
+    let email = email_rx.recv_timeout(time::Duration::from_secs(5)).unwrap();
+    let new_token = get_link(&email.plain)
+        .split("/auth/invite/")
+        .nth(1)
+        .unwrap()
+        .to_string();
+
+    let response = invitation_detail(&app, &old_token).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = invitation_detail(&app, &new_token).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let url = format!("/invitations/{}", invitation.identifier);
+    let response = send_request(
+        &admin_app,
+        &url,
+        http::Method::DELETE,
+        &json!({}),
+        &admin_session,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = invitation_detail(&app, &new_token).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+fn invite_state(pool: PgPool) -> (Receiver<EmailObject>, Receiver<BackgroundTask>, AppState) {
+    let (email_tx, email_rx) = mpsc::sync_channel(5);
+    let (task_tx, task_rx) = mpsc::sync_channel(5);
+    let mut state = test::test_state_for_email(pool, email_tx);
+    state.tasks = Some(task_tx);
+    (email_rx, task_rx, state)
+}
+
+async fn register(app: &Router, email: &str, invitation: Option<&str>) -> Response {
+    let data = json!({
+        "username": "invited_user",
+        "name": "Invited User",
+        "email": email,
+        "password": common::STRONG_PASSWORD,
+        "confirm_password": common::STRONG_PASSWORD,
+        "invitation": invitation,
+    });
+    let request = test::build_request("/register", http::Method::POST, &data);
+    app.clone().oneshot(request).await.unwrap()
+}
+
+async fn invitation_detail(app: &Router, token: &str) -> Response {
+    let url = format!("/invitations/{}", token);
+    let request = test::build_request(&url, http::Method::GET, &json!({}));
+    app.clone().oneshot(request).await.unwrap()
+}
+
+async fn json_body(response: Response) -> Value {
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&body).unwrap()
+}
+
+async fn send_request(
+    app: &Router,
+    url: &str,
+    method: http::Method,
+    data: &Value,
+    session_token: &str,
+) -> Response {
+    let mut request = test::build_request(url, method, data);
+    let session_header = HeaderValue::from_str(session_token).unwrap();
+    request.headers_mut().insert(AUTHORIZATION, session_header);
+    app.clone().oneshot(request).await.unwrap()
+}
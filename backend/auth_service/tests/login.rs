@@ -1,14 +1,17 @@
 use auth_service::extractors::confirmation::ConfirmationActionType;
 use auth_service::extractors::session::SESSION_TOKEN_COOKIE;
-use auth_service::helpers::user::fetch_user;
+use auth_service::helpers::user::{fetch_user, update_password};
 use auth_service::router::create_router;
-use axum::http::header::{AUTHORIZATION, SET_COOKIE};
+use axum::http::header::{AUTHORIZATION, RETRY_AFTER, SET_COOKIE};
 use axum::http::StatusCode;
 use axum::response::Response;
 use axum::{http, Router};
+use secrecy::{ExposeSecret, Secret};
 use serde_json::json;
 use sqlx::PgPool;
+use std::time::Duration;
 use tower::ServiceExt;
+use utils::email::get_link;
 use utils::state::AppState;
 use utils::test;
 
@@ -68,6 +71,57 @@ async fn login_clears_resets_token(pool: PgPool) {
     assert_eq!(resets.count, Some(0));
 }
 
+#[sqlx::test]
+async fn login_upgrades_outdated_password_hash(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+
+    let mut state = AppState::test_state(pool, None);
+    state.settings.password_hash.memory_kib = 8 * 1024;
+    state.settings.password_hash.iterations = 1;
+    let app = create_router().with_state(state.clone());
+
+    let user = common::user_fixture(&mut conn).await;
+    let response = send_request(&app, &user.username, common::STRONG_PASSWORD).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let saved = fetch_user(&mut conn, user.id).await.unwrap();
+    assert!(saved
+        .password_hash
+        .expose_secret()
+        .contains("m=8192,t=1,p=1"));
+    assert!(!saved.needs_rehash(&state.settings.password_hash));
+
+    state.settings.password_hash.pepper = Some(Secret::from("pepper".to_string()));
+    let app = create_router().with_state(state.clone());
+    let response = send_request(&app, &user.username, common::STRONG_PASSWORD).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let saved = fetch_user(&mut conn, user.id).await.unwrap();
+    assert!(saved.password_hash.expose_secret().contains("keyid="));
+    assert!(saved.check_password(common::STRONG_PASSWORD, &state.settings.password_hash));
+    assert!(!saved.check_password(common::STRONG_PASSWORD, &Default::default()));
+}
+
+#[sqlx::test]
+async fn login_accepts_long_passwords(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let password = "correct horse battery staple ".repeat(4)[..100].to_string();
+    update_password(
+        &mut conn,
+        user.id,
+        Secret::from(password.clone()),
+        &Default::default(),
+    )
+    .await
+    .expect("Cannot update password");
+
+    let response = send_request(&app, &user.username, &password).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
 #[sqlx::test]
 async fn login_username_not_found(pool: PgPool) {
     let mut conn = pool.acquire().await.expect("Unable to acquire connection");
@@ -85,6 +139,67 @@ async fn login_username_not_found(pool: PgPool) {
     .await;
 }
 
+#[sqlx::test]
+async fn hardened_login_hides_unknown_accounts(pool: PgPool) {
+    let mut state = AppState::test_state(pool, None);
+    state.settings.security.hardened = true;
+    let app = create_router().with_state(state);
+
+    let response = send_request(&app, "nobody@example.com", common::STRONG_PASSWORD).await;
+    test::assert_response(
+        response,
+        StatusCode::BAD_REQUEST,
+        "login failed: username or password is incorrect",
+    )
+    .await;
+}
+
+#[sqlx::test]
+async fn login_with_email_or_username_variants(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let email = format!("  {}", user.email.to_uppercase());
+    let response = send_request(&app, &email, common::STRONG_PASSWORD).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = send_request(&app, &user.username.to_uppercase(), common::STRONG_PASSWORD).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = send_request(&app, "nobody@example.com", common::STRONG_PASSWORD).await;
+    test::assert_response(
+        response,
+        StatusCode::BAD_REQUEST,
+        "login failed: username not found",
+    )
+    .await;
+}
+
+#[sqlx::test]
+async fn login_throttle_is_shared_by_username_and_email(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let free_attempts = state.settings.rate_limit.free_attempts;
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    for attempt in 0..free_attempts {
+        let identifier = if attempt % 2 == 0 {
+            &user.username
+        } else {
+            &user.email
+        };
+        let response = send_request(&app, identifier, "invalid!").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    let response = send_request(&app, &user.email, common::STRONG_PASSWORD).await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+}
+
 #[sqlx::test]
 async fn login_username_invalid_password(pool: PgPool) {
     let mut conn = pool.acquire().await.expect("Unable to acquire connection");
@@ -136,6 +251,105 @@ async fn login_returns_a_400_for_invalid_form_data(pool: PgPool) {
     }
 }
 
+#[sqlx::test]
+async fn login_is_throttled_after_repeated_failures(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+
+    let state = AppState::test_state(pool, None);
+    let free_attempts = state.settings.rate_limit.free_attempts;
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    for _ in 0..free_attempts {
+        let response = send_request(&app, &user.username, "invalid!").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    let response = send_request(&app, &user.username, common::STRONG_PASSWORD).await;
+    let retry_after: u64 = response
+        .headers()
+        .get(RETRY_AFTER)
+        .expect("Retry-After header missing")
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(retry_after > 0);
+    test::assert_response(
+        response,
+        StatusCode::TOO_MANY_REQUESTS,
+        "Too many attempts, please try again later",
+    )
+    .await;
+}
+
+#[sqlx::test]
+async fn login_throttle_counts_username_lookalikes_together(pool: PgPool) {
+    let state = AppState::test_state(pool, None);
+    let free_attempts = state.settings.rate_limit.free_attempts;
+    let app = create_router().with_state(state);
+
+    for attempt in 0..free_attempts {
+        let username = if attempt % 2 == 0 { "Apple1" } else { "applel" };
+        let response = send_request(&app, username, "invalid!").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    let response = send_request(&app, "APPLE1", "invalid!").await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[sqlx::test]
+async fn login_locks_account_and_unlock_link_restores_it(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+
+    let (email_tx, email_rx) = std::sync::mpsc::sync_channel(5);
+    let (task_tx, task_rx) = std::sync::mpsc::sync_channel(5);
+    let mut state = test::test_state_for_email(pool, email_tx);
+    state.tasks = Some(task_tx);
+    state.settings.rate_limit.base_delay_seconds = 0;
+    state.settings.rate_limit.lockout_after = 3;
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    for _ in 0..3 {
+        let response = send_request(&app, &user.username, "invalid!").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    let response = send_request(&app, &user.username, common::STRONG_PASSWORD).await;
+    test::assert_response(
+        response,
+        StatusCode::TOO_MANY_REQUESTS,
+        "Too many failed attempts, please try again later",
+    )
+    .await;
+
+    let task = task_rx.try_recv().expect("Unlock email not queued.");
+    task.handle.await.expect("Join error, task panicked");
+    let email_object = email_rx
+        .recv_timeout(Duration::from_secs(5))
+        .expect("Unlock email not sent");
+    assert_eq!(email_object.to[0].email, user.email);
+    assert_eq!(
+        email_object.subject,
+        "Your account was locked after failed login attempts"
+    );
+
+    let link = get_link(&email_object.plain);
+    let token = link.rsplit('/').next().unwrap();
+    let request = test::build_request(
+        &format!("/unlock/{}", token),
+        http::Method::POST,
+        &json!({}),
+    );
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = send_request(&app, &user.username, common::STRONG_PASSWORD).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
 async fn send_request(app: &Router, username: &str, password: &str) -> Response {
     let data = json!({
         "username": username,
@@ -1,14 +1,15 @@
+use auth_service::extractors::api_token::{ApiToken, TokenScope, TokenScopes};
+use auth_service::helpers::api_tokens::insert_api_token;
 use auth_service::router::create_router;
 use axum::http::header::AUTHORIZATION;
 use axum::http::{HeaderValue, StatusCode};
 use axum::response::Response;
-use axum::{http, response, Router};
+use axum::{http, Router};
 use serde_json::json;
 use sqlx::PgPool;
 use tower::ServiceExt;
 use utils::state::AppState;
 use utils::test;
-use uuid::Uuid;
 
 mod common;
 
@@ -35,6 +36,36 @@ async fn logout(pool: PgPool) {
     assert_eq!(sessions.count, Some(0));
 }
 
+#[sqlx::test]
+async fn logout_with_api_token_revokes_it(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::verified_user_fixture(&mut conn).await;
+    let (api_token, token) = ApiToken::new(
+        user.id,
+        "fixture".to_string(),
+        TokenScopes(vec![TokenScope::Read, TokenScope::Write]),
+        None,
+    );
+    insert_api_token(&mut conn, &api_token)
+        .await
+        .expect("Cannot insert api token");
+
+    let response = send_request(&app, &format!("Bearer {}", token)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let tokens = sqlx::query!(
+        r#"SELECT COUNT(*) as count FROM api_tokens WHERE user_id = $1"#,
+        user.id,
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .expect("Unable to fetch api tokens");
+    assert_eq!(tokens.count, Some(0));
+}
+
 async fn send_request(app: &Router, session_token: &str) -> Response {
     let data = json!({});
     let mut request = test::build_request("/logout", http::Method::POST, &data);
@@ -9,14 +9,17 @@ use fake::faker::internet::en::SafeEmail;
 use fake::faker::internet::en::Username;
 use fake::faker::name::en::Name;
 use fake::{Dummy, Fake, Faker};
+use http_body_util::BodyExt;
 use serde::Serialize;
 use serde_json::{json, Value};
+use std::sync::mpsc;
 use std::time::Duration;
 use tower::util::ServiceExt;
 
 use auth_service::extractors::confirmation::ConfirmationActionType;
 use auth_service::extractors::session::SESSION_TOKEN_COOKIE;
 use auth_service::extractors::user::User;
+use auth_service::router::create_router;
 use sqlx::PgPool;
 use url::Url;
 use utils::email::get_link;
@@ -77,7 +80,7 @@ async fn registration_valid_form_data_is_inserted(pool: PgPool) {
 
     let saved = sqlx::query_as!(
         User,
-        "SELECT id, name, email, username, normalized_username, is_active, is_confirmed, created_at, updated_at, password_hash from users"
+        "SELECT id, name, email, username, normalized_username, is_active, is_confirmed, created_at, updated_at, password_hash, deleted_at, is_admin, suspended_at, suspension_reason, suspended_until from users"
     )
         .fetch_one(&mut *conn)
         .await
@@ -92,8 +95,8 @@ async fn registration_valid_form_data_is_inserted(pool: PgPool) {
     );
     assert!(saved.is_active);
     assert!(!saved.is_confirmed);
-    assert!(saved.check_password(common::STRONG_PASSWORD));
-    assert!(!saved.check_password("wrong"));
+    assert!(saved.check_password(common::STRONG_PASSWORD, &Default::default()));
+    assert!(!saved.check_password("wrong", &Default::default()));
 
     let confirmation = sqlx::query!("SELECT * from confirmations")
         .fetch_one(&mut *conn)
@@ -161,6 +164,55 @@ async fn registration_already_exists(pool: PgPool) {
         "Email already registered",
     )
     .await;
+
+    // Same address with different case
+    let data = replace_key(&data, "username", "case_check");
+    let data = replace_key(&data, "email", &payload.email.to_uppercase());
+    let response = send_request(&app, &data).await;
+    test::assert_response(
+        response,
+        StatusCode::BAD_REQUEST,
+        "Email already registered",
+    )
+    .await;
+}
+
+#[sqlx::test]
+async fn hardened_registration_hides_taken_email(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let (email_tx, email_rx) = mpsc::sync_channel(5);
+    let (task_tx, task_rx) = mpsc::sync_channel(5);
+    let mut state = test::test_state_for_email(pool, email_tx);
+    state.tasks = Some(task_tx);
+    state.settings.security.hardened = true;
+    let app = create_router().with_state(state);
+
+    let existing = common::user_fixture(&mut conn).await;
+    let (payload, data) = registration_payload();
+
+    let response = send_request(&app, &data).await;
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    assert!(response.headers().get(SET_COOKIE).is_none());
+    let fresh = response.into_body().collect().await.unwrap().to_bytes();
+    let task = task_rx.try_recv().expect("Task not thrown out.");
+    task.handle.await.expect("Join error, task panicked");
+    let email_object = email_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(email_object.to[0].email, payload.email);
+
+    let data = replace_key(&data, "username", "another_name");
+    let data = replace_key(&data, "email", &existing.email.to_uppercase());
+    let response = send_request(&app, &data).await;
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let taken = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(fresh, taken);
+    let task = task_rx.try_recv().expect("Task not thrown out.");
+    task.handle.await.expect("Join error, task panicked");
+    let email_object = email_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(email_object.to[0].email, existing.email);
+    assert_eq!(
+        email_object.subject,
+        "Someone tried to sign up with your email"
+    );
 }
 
 #[sqlx::test]
@@ -220,6 +272,21 @@ async fn register_returns_a_400_for_invalid_form_data(pool: PgPool) {
     }
 }
 
+#[sqlx::test]
+async fn register_rejects_profane_username(pool: PgPool) {
+    let (_, _, app) = common::setup_app(pool);
+    let (_, data) = registration_payload();
+
+    let response = send_request(&app, &replace_key(&data, "username", "crap")).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        body["details"]["username"][0]["code"],
+        json!("username_not_valid")
+    );
+}
+
 fn registration_payload() -> (RegistrationPayload, Value) {
     let payload: RegistrationPayload = Faker.fake();
     let data = serde_json::to_value(payload.clone()).unwrap();
@@ -177,7 +177,7 @@ async fn reset_password_valid_token_password_hash(pool: PgPool) {
         previous_password_hash.expose_secret(),
         new_password_hash.expose_secret()
     );
-    assert!(user.check_password(NEW_PASSWORD));
+    assert!(user.check_password(NEW_PASSWORD, &Default::default()));
 }
 
 #[sqlx::test]
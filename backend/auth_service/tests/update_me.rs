@@ -0,0 +1,113 @@
+use auth_service::router::create_router;
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::Response;
+use axum::{http, Router};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tower::ServiceExt;
+use utils::state::AppState;
+use utils::test;
+
+mod common;
+
+#[sqlx::test]
+async fn update_me_changes_name_and_username(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+
+    let data = json!({"name": "New Name", "username": "Renamed_2"});
+    let response = send_request(&app, &data, &session_token).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["name"], json!("New Name"));
+    assert_eq!(body["username"], json!("Renamed_2"));
+
+    let saved = sqlx::query!(
+        "SELECT normalized_username, email FROM users WHERE id = $1",
+        user.id
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .expect("Unable to fetch user");
+    assert_eq!(saved.normalized_username, "renamed_z");
+    assert_eq!(saved.email, user.email);
+}
+
+#[sqlx::test]
+async fn update_me_keeps_missing_fields(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+
+    let data = json!({"name": "Only Name"});
+    let response = send_request(&app, &data, &session_token).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["username"], json!(user.username));
+}
+
+#[sqlx::test]
+async fn update_me_rejects_lookalike_username(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let other = common::user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+    // Fake usernames don't always pass validation, so the taken one is fixed.
+    sqlx::query!(
+        "update users set username = 'lookalike', normalized_username = 'lookalike' where id = $1",
+        other.id
+    )
+    .execute(&mut *conn)
+    .await
+    .expect("Unable to rename user");
+
+    let data = json!({"username": "LOOKALIKE"});
+    let response = send_request(&app, &data, &session_token).await;
+    test::assert_response(response, StatusCode::BAD_REQUEST, "Username not available").await;
+}
+
+#[sqlx::test]
+async fn update_me_returns_400_for_invalid_data(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let user = common::user_fixture(&mut conn).await;
+    let session_token = common::session_fixture(&mut conn, user.id).await;
+
+    let test_cases = vec![
+        (json!({"name": ""}), "empty name"),
+        (json!({"username": "ab"}), "short username"),
+        (json!({"username": "with space"}), "invalid characters"),
+    ];
+    for (payload, error_message) in test_cases {
+        let response = send_request(&app, &payload, &session_token).await;
+        assert_eq!(
+            response.status(),
+            StatusCode::BAD_REQUEST,
+            "The request didn't throw 400 request for the case when {}",
+            error_message
+        );
+    }
+}
+
+async fn send_request(app: &Router, data: &Value, session_token: &str) -> Response {
+    let mut request = test::build_request("/me", http::Method::PATCH, data);
+    let session_header = HeaderValue::from_str(session_token).unwrap();
+    request.headers_mut().insert(AUTHORIZATION, session_header);
+    app.clone().oneshot(request).await.unwrap()
+}
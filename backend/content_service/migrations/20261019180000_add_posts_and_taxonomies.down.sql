@@ -0,0 +1,4 @@
+-- Add down migration script here
+drop table if exists post_terms;
+drop table if exists terms;
+drop table if exists posts;
@@ -0,0 +1,51 @@
+create table posts
+(
+    id           serial primary key,
+    -- Posts are dated entries, pages stand on their own.
+    kind         varchar(20)  not null default 'post',
+    title        varchar(255) not null,
+    slug         varchar(255) not null,
+    excerpt      text         not null default '',
+    body         text         not null default '',
+    status       varchar(20)  not null default 'draft',
+    author_id    integer
+        constraint posts_author_fk
+            references users on delete set null,
+    published_at timestamptz,
+    created_at   timestamptz  not null,
+    updated_at   timestamptz  not null,
+    constraint posts_kind_slug_key unique (kind, slug)
+);
+
+create index posts_status_published_at_index on posts (status, published_at);
+
+create table terms
+(
+    id          serial primary key,
+    taxonomy    varchar(20)  not null,
+    name        varchar(100) not null,
+    slug        varchar(100) not null,
+    description text         not null default '',
+    -- Only categories nest.
+    parent_id   integer
+        constraint terms_parent_fk
+            references terms on delete set null,
+    created_at  timestamptz  not null,
+    updated_at  timestamptz  not null,
+    constraint terms_taxonomy_slug_key unique (taxonomy, slug)
+);
+
+create table post_terms
+(
+    post_id integer not null
+        constraint post_terms_post_fk
+            references posts on delete cascade,
+    term_id integer not null
+        constraint post_terms_term_fk
+            references terms on delete cascade,
+    primary key (post_id, term_id)
+);
+
+create index post_terms_term_id_index on post_terms (term_id);
+
+comment on table terms is 'Categories and tags used to classify posts';
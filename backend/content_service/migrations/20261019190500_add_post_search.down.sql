@@ -0,0 +1,4 @@
+-- Add down migration script here
+drop index if exists posts_search_vector_index;
+alter table posts drop column if exists search_vector;
+alter table posts drop column if exists search_language;
@@ -0,0 +1,11 @@
+-- Titles rank above excerpts and excerpts above the body.
+alter table posts
+    add column search_language regconfig not null default 'english';
+alter table posts
+    add column search_vector tsvector generated always as (
+        setweight(to_tsvector(search_language, title), 'A') ||
+        setweight(to_tsvector(search_language, excerpt), 'B') ||
+        setweight(to_tsvector(search_language, body), 'C')
+    ) stored;
+
+create index posts_search_vector_index on posts using gin (search_vector);
@@ -0,0 +1,25 @@
+-- Every public url of the site in a stable order, so sitemap files can be paged.
+-- Drafts, private and scheduled posts are left out, taxonomy archives only once they list a post.
+create view sitemap_entries as
+select 0 as section, 0 as position, 'home'::varchar as kind, ''::varchar as slug,
+       (select max(greatest(p.updated_at, p.published_at))
+        from posts p
+        where p.status = 'published' and p.published_at <= now()) as lastmod
+union all
+select 1, p.id, p.kind, p.slug, greatest(p.updated_at, p.published_at)
+from posts p
+where p.status = 'published' and p.published_at <= now()
+union all
+select 2, t.id, t.taxonomy, t.slug, max(greatest(p.updated_at, p.published_at))
+from terms t
+         join post_terms pt on pt.term_id = t.id
+         join posts p on p.id = pt.post_id
+where p.kind = 'post' and p.status = 'published' and p.published_at <= now()
+group by t.id
+union all
+select 3, 0, 'newsletters', '', max(n.sent_at)
+from newsletters n
+having count(*) > 0
+union all
+select 4, n.id, 'newsletter', n.id::varchar, n.sent_at
+from newsletters n;
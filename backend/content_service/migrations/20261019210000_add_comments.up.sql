@@ -0,0 +1,31 @@
+create table comments
+(
+    id                serial primary key,
+    post_id           integer      not null
+        constraint comments_post_fk
+            references posts on delete cascade,
+    parent_id         integer
+        constraint comments_parent_fk
+            references comments on delete cascade,
+    -- Top level comments have depth zero.
+    depth             integer      not null default 0,
+    -- Either a verified user or a guest with an email address.
+    user_id           integer
+        constraint comments_user_fk
+            references users on delete cascade,
+    guest_name        varchar(255),
+    guest_email       varchar(255),
+    body              text         not null,
+    status            varchar(20)  not null default 'pending',
+    spam_score        integer      not null default 0,
+    ip_address        varchar(64)  not null default '',
+    -- Sha256 of the token mailed to a guest, cleared once confirmed.
+    confirmation_hash varchar(64)
+        constraint comments_confirmation_hash_key unique,
+    created_at        timestamptz  not null,
+    updated_at        timestamptz  not null,
+    constraint comments_author_check check (user_id is not null or guest_email is not null)
+);
+
+create index comments_post_id_status_index on comments (post_id, status);
+create index comments_status_created_at_index on comments (status, created_at);
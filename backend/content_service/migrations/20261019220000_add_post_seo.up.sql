@@ -0,0 +1,32 @@
+-- Empty values fall back to defaults derived from the post itself.
+alter table posts
+    add column meta_title       varchar(255),
+    add column meta_description text,
+    add column canonical_url    text,
+    add column og_image         text,
+    -- Keeps the entry out of search engines and the sitemap.
+    add column noindex          boolean not null default false;
+
+create or replace view sitemap_entries as
+select 0 as section, 0 as position, 'home'::varchar as kind, ''::varchar as slug,
+       (select max(greatest(p.updated_at, p.published_at))
+        from posts p
+        where p.status = 'published' and p.published_at <= now()) as lastmod
+union all
+select 1, p.id, p.kind, p.slug, greatest(p.updated_at, p.published_at)
+from posts p
+where p.status = 'published' and p.published_at <= now() and not p.noindex
+union all
+select 2, t.id, t.taxonomy, t.slug, max(greatest(p.updated_at, p.published_at))
+from terms t
+         join post_terms pt on pt.term_id = t.id
+         join posts p on p.id = pt.post_id
+where p.kind = 'post' and p.status = 'published' and p.published_at <= now()
+group by t.id
+union all
+select 3, 0, 'newsletters', '', max(n.sent_at)
+from newsletters n
+having count(*) > 0
+union all
+select 4, n.id, 'newsletter', n.id::varchar, n.sent_at
+from newsletters n;
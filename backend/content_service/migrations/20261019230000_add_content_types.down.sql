@@ -0,0 +1,3 @@
+-- Add down migration script here
+drop table if exists content_entries;
+drop table if exists content_types;
@@ -0,0 +1,34 @@
+create table content_types
+(
+    id          serial primary key,
+    name        varchar(100) not null,
+    slug        varchar(100) not null
+        constraint content_types_slug_key unique,
+    description text         not null default '',
+    -- Ordered field definitions, validated by the content service before they are stored.
+    fields      jsonb        not null default '[]'::jsonb,
+    created_at  timestamptz  not null,
+    updated_at  timestamptz  not null
+);
+
+create table content_entries
+(
+    id              serial primary key,
+    content_type_id integer      not null
+        constraint content_entries_type_fk
+            references content_types on delete cascade,
+    title           varchar(255) not null,
+    slug            varchar(255) not null,
+    status          varchar(20)  not null default 'draft',
+    -- Field values keyed by field name.
+    data            jsonb        not null default '{}'::jsonb,
+    author_id       integer
+        constraint content_entries_author_fk
+            references users on delete set null,
+    published_at    timestamptz,
+    created_at      timestamptz  not null,
+    updated_at      timestamptz  not null,
+    constraint content_entries_type_slug_key unique (content_type_id, slug)
+);
+
+create index content_entries_type_status_index on content_entries (content_type_id, status, published_at);
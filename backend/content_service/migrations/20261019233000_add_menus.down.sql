@@ -0,0 +1,3 @@
+-- Add down migration script here
+drop table if exists menu_items;
+drop table if exists menus;
@@ -0,0 +1,35 @@
+create table menus
+(
+    id         serial primary key,
+    name       varchar(100) not null,
+    -- Referenced by the frontend, the main navigation uses `primary`.
+    slug       varchar(100) not null
+        constraint menus_slug_key unique,
+    created_at timestamptz  not null,
+    updated_at timestamptz  not null
+);
+
+create table menu_items
+(
+    id              serial primary key,
+    menu_id         integer       not null
+        constraint menu_items_menu_fk
+            references menus on delete cascade,
+    parent_id       integer
+        constraint menu_items_parent_fk
+            references menu_items on delete cascade,
+    -- Order among the items sharing a parent.
+    position        integer       not null default 0,
+    -- Falls back to the title of the linked content when empty.
+    label           varchar(255)  not null default '',
+    -- post, page, category, tag or url.
+    link_type       varchar(20)   not null,
+    -- Post or term id, links to removed content are skipped when the menu is served.
+    target_id       integer,
+    url             varchar(2000),
+    open_in_new_tab bool          not null default false,
+    created_at      timestamptz   not null,
+    updated_at      timestamptz   not null
+);
+
+create index menu_items_menu_id_index on menu_items (menu_id, parent_id, position);
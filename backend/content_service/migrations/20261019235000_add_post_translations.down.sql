@@ -0,0 +1,39 @@
+-- Add down migration script here
+drop view if exists sitemap_entries;
+create view sitemap_entries as
+select 0 as section, 0 as position, 'home'::varchar as kind, ''::varchar as slug,
+       (select max(greatest(p.updated_at, p.published_at))
+        from posts p
+        where p.status = 'published' and p.published_at <= now()) as lastmod
+union all
+select 1, p.id, p.kind, p.slug, greatest(p.updated_at, p.published_at)
+from posts p
+where p.status = 'published' and p.published_at <= now() and not p.noindex
+union all
+select 2, t.id, t.taxonomy, t.slug, max(greatest(p.updated_at, p.published_at))
+from terms t
+         join post_terms pt on pt.term_id = t.id
+         join posts p on p.id = pt.post_id
+where p.kind = 'post' and p.status = 'published' and p.published_at <= now()
+group by t.id
+union all
+select 3, 0, 'newsletters', '', max(n.sent_at)
+from newsletters n
+having count(*) > 0
+union all
+select 4, n.id, 'newsletter', n.id::varchar, n.sent_at
+from newsletters n;
+
+-- Translations sharing a slug with an older post get their locale appended.
+update posts p
+set slug = left(p.slug, 244) || '-' || lower(p.locale)
+where exists (select 1
+              from posts o
+              where o.kind = p.kind and o.slug = p.slug and o.id < p.id);
+
+alter table posts
+    drop constraint if exists posts_translation_group_locale_key,
+    drop constraint if exists posts_kind_locale_slug_key,
+    add constraint posts_kind_slug_key unique (kind, slug),
+    drop column if exists translation_group,
+    drop column if exists locale;
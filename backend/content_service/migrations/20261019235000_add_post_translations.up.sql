@@ -0,0 +1,33 @@
+-- Every post is written in one locale, translations of it share its translation group.
+alter table posts
+    add column locale            varchar(10) not null default 'en',
+    add column translation_group uuid        not null default gen_random_uuid(),
+    drop constraint posts_kind_slug_key,
+    -- Translations may keep the slug of the original.
+    add constraint posts_kind_locale_slug_key unique (kind, locale, slug),
+    add constraint posts_translation_group_locale_key unique (translation_group, locale);
+
+create or replace view sitemap_entries as
+select 0 as section, 0 as position, 'home'::varchar as kind, ''::varchar as slug,
+       (select max(greatest(p.updated_at, p.published_at))
+        from posts p
+        where p.status = 'published' and p.published_at <= now()) as lastmod,
+       null::varchar as locale
+union all
+select 1, p.id, p.kind, p.slug, greatest(p.updated_at, p.published_at), p.locale
+from posts p
+where p.status = 'published' and p.published_at <= now() and not p.noindex
+union all
+select 2, t.id, t.taxonomy, t.slug, max(greatest(p.updated_at, p.published_at)), null
+from terms t
+         join post_terms pt on pt.term_id = t.id
+         join posts p on p.id = pt.post_id
+where p.kind = 'post' and p.status = 'published' and p.published_at <= now()
+group by t.id
+union all
+select 3, 0, 'newsletters', '', max(n.sent_at), null
+from newsletters n
+having count(*) > 0
+union all
+select 4, n.id, 'newsletter', n.id::varchar, n.sent_at, null
+from newsletters n;
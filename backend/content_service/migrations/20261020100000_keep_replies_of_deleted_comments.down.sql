@@ -0,0 +1,9 @@
+-- Add down migration script here
+update comments
+set guest_email = ''
+where user_id is null and guest_email is null;
+
+alter table comments
+    drop constraint if exists comments_author_check,
+    add constraint comments_author_check check (user_id is not null or guest_email is not null),
+    drop column if exists deleted_at;
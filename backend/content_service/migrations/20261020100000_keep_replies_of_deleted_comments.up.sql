@@ -0,0 +1,6 @@
+-- Comments of a deleted account that others replied to stay behind as a marker.
+alter table comments
+    add column deleted_at timestamptz,
+    drop constraint comments_author_check,
+    add constraint comments_author_check
+        check (user_id is not null or guest_email is not null or deleted_at is not null);
@@ -0,0 +1,114 @@
+use serde_json::Value;
+use util_macros::ErrorPayloadMacro;
+use utils::errors::{ErrorPayload, ErrorReport};
+
+#[derive(Debug, thiserror::Error, ErrorPayloadMacro)]
+pub enum ContentError {
+    #[error("Failed to acquire a Postgres connection from the pool")]
+    Pool(#[source] sqlx::Error),
+    // The source is part of the message so unique slug violations get a friendly message.
+    #[error("Content database failed: {0}")]
+    DatabaseError(#[source] sqlx::Error),
+    #[error("Post not found")]
+    PostNotFound,
+    #[error("Term not found")]
+    TermNotFound,
+    #[error("Author not found")]
+    AuthorNotFound,
+    #[error("Feed not found")]
+    FeedNotFound,
+    #[error("Sitemap not found")]
+    SitemapNotFound,
+    #[error("Comment not found")]
+    CommentNotFound,
+    #[error("Content type not found")]
+    ContentTypeNotFound,
+    #[error("Entry not found")]
+    EntryNotFound,
+    #[error("Menu not found")]
+    MenuNotFound,
+    // Field level errors of a content type, entry or menu, already in the details shape.
+    #[error("Failed to validate input")]
+    InvalidFields(Value),
+    #[error("Comments are disabled")]
+    CommentsDisabled,
+    #[error("Please sign in to comment")]
+    GuestCommentsDisabled,
+    #[error("Please provide your {0} to comment")]
+    MissingGuestDetail(&'static str),
+    #[error("Replies are only possible to approved comments on the same post")]
+    InvalidReply,
+    #[error("Replies cannot be nested any deeper")]
+    ReplyTooDeep,
+    #[error("Comments can only be approved, rejected, marked as spam or sent back to the queue")]
+    InvalidModeration,
+    #[error("A slug could not be derived, please provide one")]
+    MissingSlug,
+    #[error("This language is not supported")]
+    UnsupportedLocale(String),
+    #[error("Only a post of the same kind can be translated")]
+    InvalidTranslation,
+    #[error("Only categories can have a parent category")]
+    InvalidParent,
+    #[error("A category cannot be moved below itself")]
+    ParentCycle,
+}
+
+impl ErrorReport for ContentError {
+    fn message(&self) -> String {
+        self.to_string()
+    }
+
+    fn status(&self) -> u16 {
+        match self {
+            ContentError::Pool(_) => 500,
+            ContentError::DatabaseError(_) => 500,
+            ContentError::PostNotFound => 404,
+            ContentError::TermNotFound => 404,
+            ContentError::AuthorNotFound => 404,
+            ContentError::FeedNotFound => 404,
+            ContentError::SitemapNotFound => 404,
+            ContentError::CommentNotFound => 404,
+            ContentError::ContentTypeNotFound => 404,
+            ContentError::EntryNotFound => 404,
+            ContentError::MenuNotFound => 404,
+            ContentError::CommentsDisabled => 403,
+            ContentError::GuestCommentsDisabled => 401,
+            _ => 400,
+        }
+    }
+
+    fn details(&self) -> Value {
+        match self {
+            ContentError::MissingSlug => {
+                ErrorPayload::form_details("slug", "missing_slug", &self.to_string(), None)
+            }
+            ContentError::InvalidParent | ContentError::ParentCycle => {
+                ErrorPayload::form_details("parent_id", "invalid_parent", &self.to_string(), None)
+            }
+            ContentError::MissingGuestDetail(field) => {
+                ErrorPayload::form_details(field, "required", &self.to_string(), None)
+            }
+            ContentError::InvalidReply | ContentError::ReplyTooDeep => {
+                ErrorPayload::form_details("parent_id", "invalid_reply", &self.to_string(), None)
+            }
+            ContentError::InvalidModeration => {
+                ErrorPayload::form_details("status", "invalid_status", &self.to_string(), None)
+            }
+            ContentError::UnsupportedLocale(locale) => ErrorPayload::form_details(
+                "locale",
+                "unsupported_locale",
+                &self.to_string(),
+                Some(locale),
+            ),
+            ContentError::InvalidTranslation => ErrorPayload::form_details(
+                "translation_of",
+                "invalid_translation",
+                &self.to_string(),
+                None,
+            ),
+            ContentError::InvalidFields(details) => details.clone(),
+            _ => serde_json::json!({}),
+        }
+    }
+}
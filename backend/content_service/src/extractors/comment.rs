@@ -0,0 +1,131 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::FromRow;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentStatus {
+    // Guest comments wait for their email address to be confirmed.
+    Unconfirmed,
+    Pending,
+    Approved,
+    Rejected,
+    Spam,
+}
+
+impl From<String> for CommentStatus {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "unconfirmed" => CommentStatus::Unconfirmed,
+            "approved" => CommentStatus::Approved,
+            "rejected" => CommentStatus::Rejected,
+            "spam" => CommentStatus::Spam,
+            _ => CommentStatus::Pending,
+        }
+    }
+}
+
+impl From<CommentStatus> for String {
+    fn from(value: CommentStatus) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+impl CommentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommentStatus::Unconfirmed => "unconfirmed",
+            CommentStatus::Pending => "pending",
+            CommentStatus::Approved => "approved",
+            CommentStatus::Rejected => "rejected",
+            CommentStatus::Spam => "spam",
+        }
+    }
+}
+
+// Full record as seen by moderators.
+#[derive(Debug, FromRow, Serialize, Clone)]
+pub struct Comment {
+    pub id: i32,
+    pub post_id: i32,
+    pub post_title: String,
+    pub parent_id: Option<i32>,
+    pub depth: i32,
+    pub user_id: Option<i32>,
+    pub author_name: String,
+    pub guest_email: Option<String>,
+    pub body: String,
+    pub status: CommentStatus,
+    pub spam_score: i32,
+    pub ip_address: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct NewComment {
+    pub post_id: i32,
+    pub parent_id: Option<i32>,
+    pub depth: i32,
+    pub user_id: Option<i32>,
+    pub guest_name: Option<String>,
+    pub guest_email: Option<String>,
+    pub body: String,
+    pub status: CommentStatus,
+    pub spam_score: i32,
+    pub ip_address: String,
+    pub confirmation_hash: Option<String>,
+}
+
+// Public view of an approved comment with its approved replies.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommentNode {
+    pub id: i32,
+    pub author_name: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub replies: Vec<CommentNode>,
+}
+
+impl CommentNode {
+    // Replies whose parent is not among the comments, such as one later marked as spam, are left out.
+    pub fn thread(comments: Vec<Comment>) -> Vec<CommentNode> {
+        let mut children: HashMap<Option<i32>, Vec<Comment>> = HashMap::new();
+        for comment in comments {
+            children.entry(comment.parent_id).or_default().push(comment);
+        }
+        Self::replies_of(None, &mut children)
+    }
+
+    fn replies_of(
+        parent_id: Option<i32>,
+        children: &mut HashMap<Option<i32>, Vec<Comment>>,
+    ) -> Vec<CommentNode> {
+        children
+            .remove(&parent_id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|comment| CommentNode {
+                id: comment.id,
+                replies: Self::replies_of(Some(comment.id), children),
+                author_name: comment.author_name,
+                body: comment.body,
+                created_at: comment.created_at,
+            })
+            .collect()
+    }
+}
+
+// Token mailed to a guest and the hash stored with the comment.
+pub fn confirmation_token() -> (String, String) {
+    let token = Uuid::new_v4().simple().to_string();
+    let hash = hash_confirmation_token(&token);
+    (token, hash)
+}
+
+pub fn hash_confirmation_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
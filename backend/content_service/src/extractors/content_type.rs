@@ -0,0 +1,84 @@
+use crate::extractors::post::PostStatus;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::types::Json;
+use sqlx::FromRow;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FieldKind {
+    Text {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_length: Option<usize>,
+    },
+    // Stored as sent, rendering is up to the frontend like post bodies.
+    RichText,
+    Number {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min: Option<f64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max: Option<f64>,
+        #[serde(default)]
+        integer: bool,
+    },
+    // Calendar date in `YYYY-MM-DD` form.
+    Date,
+    Boolean,
+    // Identifier of an uploaded media file.
+    Media,
+    // Id of an entry of the content type with this slug.
+    Relation {
+        content_type: String,
+    },
+    Enum {
+        choices: Vec<String>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FieldDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub label: String,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(flatten)]
+    pub kind: FieldKind,
+}
+
+#[derive(Debug, FromRow, Serialize, Clone)]
+pub struct ContentType {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    pub description: String,
+    pub fields: Json<Vec<FieldDefinition>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow, Serialize, Clone)]
+pub struct ContentEntry {
+    pub id: i32,
+    pub content_type_id: i32,
+    pub title: String,
+    pub slug: String,
+    pub status: PostStatus,
+    // Field values keyed by field name, validated against the type on every write.
+    pub data: Value,
+    pub author_id: Option<i32>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ContentEntry {
+    // Same publishing rules as posts.
+    pub fn set_status(&mut self, status: PostStatus) {
+        self.status = status;
+        if status == PostStatus::Published && self.published_at.is_none() {
+            self.published_at = Some(Utc::now());
+        }
+    }
+}
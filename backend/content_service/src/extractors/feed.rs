@@ -0,0 +1,92 @@
+use crate::extractors::post::Post;
+use crate::extractors::term::Term;
+use chrono::{DateTime, Utc};
+use translations::LocaleConfig;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FeedFormat {
+    Rss,
+    Atom,
+    Json,
+}
+
+impl FeedFormat {
+    /// Format served for the last segment of a feed url.
+    ///
+    /// ```
+    /// use content_service::extractors::feed::FeedFormat;
+    ///
+    /// assert_eq!(FeedFormat::from_file_name("atom.xml"), Some(FeedFormat::Atom));
+    /// assert_eq!(FeedFormat::from_file_name("feed.xml"), None);
+    /// ```
+    pub fn from_file_name(name: &str) -> Option<Self> {
+        match name {
+            "rss.xml" => Some(FeedFormat::Rss),
+            "atom.xml" => Some(FeedFormat::Atom),
+            "feed.json" => Some(FeedFormat::Json),
+            _ => None,
+        }
+    }
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "rss.xml",
+            FeedFormat::Atom => "atom.xml",
+            FeedFormat::Json => "feed.json",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "application/rss+xml; charset=utf-8",
+            FeedFormat::Atom => "application/atom+xml; charset=utf-8",
+            FeedFormat::Json => "application/feed+json; charset=utf-8",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Feed {
+    pub title: String,
+    pub description: String,
+    // Absolute urls of the site and of this feed.
+    pub home_url: String,
+    pub feed_url: String,
+    pub items: Vec<FeedItem>,
+}
+
+impl Feed {
+    // Newest change of any entry, none for an empty feed.
+    pub fn last_modified(&self) -> Option<DateTime<Utc>> {
+        self.items.iter().map(|item| item.updated).max()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FeedItem {
+    pub url: String,
+    pub title: String,
+    pub summary: String,
+    pub content_html: String,
+    pub author: Option<String>,
+    pub tags: Vec<String>,
+    pub published: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl FeedItem {
+    pub fn new(post: Post, terms: Vec<Term>, base_url: &str, config: &LocaleConfig) -> Self {
+        let published = post.published_at.unwrap_or(post.created_at);
+        Self {
+            url: format!("{}{}", base_url, post.localized_path(config)),
+            title: post.title,
+            summary: post.excerpt,
+            content_html: post.body,
+            author: post.author_name,
+            tags: terms.into_iter().map(|term| term.name).collect(),
+            // A post published after its last edit was changed when it went out.
+            updated: post.updated_at.max(published),
+            published,
+        }
+    }
+}
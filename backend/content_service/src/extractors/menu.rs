@@ -0,0 +1,153 @@
+use crate::extractors::post::PostKind;
+use crate::extractors::term::Taxonomy;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::collections::HashMap;
+
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkType {
+    Post,
+    Page,
+    Category,
+    Tag,
+    Url,
+}
+
+impl From<String> for LinkType {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "post" => LinkType::Post,
+            "page" => LinkType::Page,
+            "category" => LinkType::Category,
+            "tag" => LinkType::Tag,
+            _ => LinkType::Url,
+        }
+    }
+}
+
+impl From<LinkType> for String {
+    fn from(value: LinkType) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+impl LinkType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LinkType::Post => "post",
+            LinkType::Page => "page",
+            LinkType::Category => "category",
+            LinkType::Tag => "tag",
+            LinkType::Url => "url",
+        }
+    }
+
+    /// Address of the linked content, relative to the site root.
+    ///
+    /// ```
+    /// use content_service::extractors::menu::LinkType;
+    ///
+    /// assert_eq!(LinkType::Page.path("about"), Some("/pages/about".to_string()));
+    /// assert_eq!(LinkType::Tag.path("rust"), Some("/tags/rust".to_string()));
+    /// assert_eq!(LinkType::Url.path("rust"), None);
+    /// ```
+    pub fn path(&self, slug: &str) -> Option<String> {
+        match self {
+            LinkType::Post => Some(PostKind::Post.path(slug)),
+            LinkType::Page => Some(PostKind::Page.path(slug)),
+            LinkType::Category => Some(Taxonomy::Category.archive_path(slug)),
+            LinkType::Tag => Some(Taxonomy::Tag.archive_path(slug)),
+            LinkType::Url => None,
+        }
+    }
+}
+
+#[derive(Debug, FromRow, Serialize, Clone)]
+pub struct Menu {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Stored item along with the content it links to, when that still exists.
+#[derive(Debug, FromRow, Clone)]
+pub struct MenuItem {
+    pub id: i32,
+    pub parent_id: Option<i32>,
+    pub label: String,
+    pub link_type: LinkType,
+    pub target_id: Option<i32>,
+    pub url: Option<String>,
+    pub open_in_new_tab: bool,
+    pub target_title: Option<String>,
+    pub target_slug: Option<String>,
+    // Drafts, private and scheduled posts are hidden from visitors.
+    pub target_public: bool,
+}
+
+impl MenuItem {
+    pub fn href(&self) -> Option<String> {
+        match self.link_type {
+            LinkType::Url => self.url.clone(),
+            link_type => link_type.path(self.target_slug.as_deref()?),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MenuNode {
+    pub id: i32,
+    pub label: String,
+    pub link_type: LinkType,
+    pub target_id: Option<i32>,
+    pub url: Option<String>,
+    // Missing when the linked content was removed.
+    pub href: Option<String>,
+    pub open_in_new_tab: bool,
+    pub children: Vec<MenuNode>,
+}
+
+impl MenuNode {
+    // Visitors only get working links, an item that is left out takes its children with it.
+    pub fn tree(items: Vec<MenuItem>, public: bool) -> Vec<MenuNode> {
+        let mut children: HashMap<Option<i32>, Vec<MenuItem>> = HashMap::new();
+        for item in items {
+            if public && (item.href().is_none() || !item.target_public) {
+                continue;
+            }
+            children.entry(item.parent_id).or_default().push(item);
+        }
+        Self::children_of(None, &mut children)
+    }
+
+    fn children_of(
+        parent_id: Option<i32>,
+        children: &mut HashMap<Option<i32>, Vec<MenuItem>>,
+    ) -> Vec<MenuNode> {
+        children
+            .remove(&parent_id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|item| {
+                let label = match item.label.is_empty() {
+                    true => item.target_title.clone().unwrap_or_default(),
+                    false => item.label.clone(),
+                };
+                MenuNode {
+                    id: item.id,
+                    href: item.href(),
+                    children: Self::children_of(Some(item.id), children),
+                    label,
+                    link_type: item.link_type,
+                    target_id: item.target_id,
+                    url: item.url,
+                    open_in_new_tab: item.open_in_new_tab,
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,9 @@
+pub mod comment;
+pub mod content_type;
+pub mod feed;
+pub mod menu;
+pub mod post;
+pub mod search;
+pub mod seo;
+pub mod sitemap;
+pub mod term;
@@ -0,0 +1,217 @@
+use crate::extractors::term::{Taxonomy, Term};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use translations::LocaleConfig;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PostKind {
+    #[default]
+    Post,
+    Page,
+}
+
+impl From<String> for PostKind {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "page" => PostKind::Page,
+            _ => PostKind::Post,
+        }
+    }
+}
+
+impl From<PostKind> for String {
+    fn from(value: PostKind) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+impl PostKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostKind::Post => "post",
+            PostKind::Page => "page",
+        }
+    }
+
+    /// Public address of a post or page, relative to the site root.
+    ///
+    /// ```
+    /// use content_service::extractors::post::PostKind;
+    ///
+    /// assert_eq!(PostKind::Post.path("hello-world"), "/posts/hello-world");
+    /// assert_eq!(PostKind::Page.path("about"), "/pages/about");
+    /// ```
+    pub fn path(&self, slug: &str) -> String {
+        format!("/{}s/{}", self.as_str(), slug)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PostStatus {
+    #[default]
+    Draft,
+    Published,
+    // Published but only visible to admins, never listed publicly.
+    Private,
+}
+
+impl From<String> for PostStatus {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "published" => PostStatus::Published,
+            "private" => PostStatus::Private,
+            _ => PostStatus::Draft,
+        }
+    }
+}
+
+impl From<PostStatus> for String {
+    fn from(value: PostStatus) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+impl PostStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostStatus::Draft => "draft",
+            PostStatus::Published => "published",
+            PostStatus::Private => "private",
+        }
+    }
+}
+
+#[derive(Debug, FromRow, Clone)]
+pub struct Post {
+    pub id: i32,
+    pub kind: PostKind,
+    pub title: String,
+    pub slug: String,
+    pub locale: String,
+    // Shared by every translation of the post, one post per locale.
+    pub translation_group: Uuid,
+    pub excerpt: String,
+    pub body: String,
+    pub status: PostStatus,
+    pub author_id: Option<i32>,
+    pub author_username: Option<String>,
+    pub author_name: Option<String>,
+    pub meta_title: Option<String>,
+    pub meta_description: Option<String>,
+    pub canonical_url: Option<String>,
+    pub og_image: Option<String>,
+    pub noindex: bool,
+    pub published_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Post {
+    // The first publish stamps the date, later edits keep it so archives stay stable.
+    pub fn set_status(&mut self, status: PostStatus) {
+        self.status = status;
+        if status == PostStatus::Published && self.published_at.is_none() {
+            self.published_at = Some(Utc::now());
+        }
+    }
+
+    // Public address of the post on the site, relative to the site root.
+    pub fn path(&self) -> String {
+        self.kind.path(&self.slug)
+    }
+
+    // Address including the locale prefix, the default locale is served without one.
+    pub fn localized_path(&self, config: &LocaleConfig) -> String {
+        config.localized_path(&self.locale, &self.path())
+    }
+
+    pub fn is_public(&self) -> bool {
+        self.status == PostStatus::Published
+            && self
+                .published_at
+                .map(|published_at| published_at <= Utc::now())
+                .unwrap_or(false)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PostAuthor {
+    pub username: String,
+    pub name: String,
+}
+
+// Version of a post in another locale.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PostTranslation {
+    pub id: i32,
+    pub locale: String,
+    pub title: String,
+    pub slug: String,
+    pub path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PostDetail {
+    pub id: i32,
+    pub kind: PostKind,
+    pub title: String,
+    pub slug: String,
+    pub locale: String,
+    pub translation_group: Uuid,
+    // Only filled for a single post, listings leave it empty.
+    pub translations: Vec<PostTranslation>,
+    pub excerpt: String,
+    pub body: String,
+    pub status: PostStatus,
+    pub author: Option<PostAuthor>,
+    pub categories: Vec<Term>,
+    pub tags: Vec<Term>,
+    // As entered, the head tags fall back to defaults derived from the content.
+    pub meta_title: Option<String>,
+    pub meta_description: Option<String>,
+    pub canonical_url: Option<String>,
+    pub og_image: Option<String>,
+    pub noindex: bool,
+    pub published_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PostDetail {
+    pub fn new(post: Post, terms: Vec<Term>) -> Self {
+        let (categories, tags) = terms
+            .into_iter()
+            .partition(|term| term.taxonomy == Taxonomy::Category);
+        let author = match (post.author_username, post.author_name) {
+            (Some(username), Some(name)) => Some(PostAuthor { username, name }),
+            _ => None,
+        };
+        Self {
+            id: post.id,
+            kind: post.kind,
+            title: post.title,
+            slug: post.slug,
+            locale: post.locale,
+            translation_group: post.translation_group,
+            translations: vec![],
+            excerpt: post.excerpt,
+            body: post.body,
+            status: post.status,
+            author,
+            categories,
+            tags,
+            meta_title: post.meta_title,
+            meta_description: post.meta_description,
+            canonical_url: post.canonical_url,
+            og_image: post.og_image,
+            noindex: post.noindex,
+            published_at: post.published_at,
+            created_at: post.created_at,
+            updated_at: post.updated_at,
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchKind {
+    Post,
+    Page,
+    Newsletter,
+}
+
+impl From<String> for SearchKind {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "page" => SearchKind::Page,
+            "newsletter" => SearchKind::Newsletter,
+            _ => SearchKind::Post,
+        }
+    }
+}
+
+impl SearchKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SearchKind::Post => "post",
+            SearchKind::Page => "page",
+            SearchKind::Newsletter => "newsletter",
+        }
+    }
+}
+
+#[derive(Debug, FromRow, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub kind: SearchKind,
+    pub id: i32,
+    // Newsletters are addressed by id.
+    pub slug: Option<String>,
+    pub title: String,
+    // Escaped html with the matched words wrapped in `<mark>`.
+    pub snippet: String,
+    pub rank: f32,
+    pub published_at: DateTime<Utc>,
+}
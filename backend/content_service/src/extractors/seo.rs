@@ -0,0 +1,108 @@
+use crate::extractors::post::{Post, PostKind, PostTranslation};
+use crate::helpers::seo::{absolute_url, first_image, summarize};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utils::configuration::Settings;
+
+// Search engines cut descriptions at about this length.
+const DESCRIPTION_LENGTH: usize = 160;
+
+// Head tags of a server rendered page.
+#[derive(Debug, Clone, Serialize)]
+pub struct SeoMeta {
+    pub title: String,
+    pub site_name: String,
+    pub description: String,
+    pub canonical_url: String,
+    pub image: Option<String>,
+    pub noindex: bool,
+    // `article` for posts, `website` for everything else.
+    pub og_type: &'static str,
+    pub published_at: Option<DateTime<Utc>>,
+    pub modified_at: Option<DateTime<Utc>>,
+    pub twitter_site: Option<String>,
+    pub locale: String,
+    // Locale and absolute url of every translation including this page, `x-default` last.
+    pub alternates: Vec<(String, String)>,
+}
+
+impl SeoMeta {
+    pub fn for_site(settings: &Settings, path: &str) -> Self {
+        Self::for_locale(settings, path, &settings.i18n.default_locale)
+    }
+
+    pub fn for_locale(settings: &Settings, path: &str, locale: &str) -> Self {
+        let base_url = settings.application.full_url();
+        Self {
+            title: settings.application.name.clone(),
+            site_name: settings.application.name.clone(),
+            description: settings.seo.description.clone(),
+            canonical_url: format!("{}{}", base_url, path),
+            image: settings
+                .seo
+                .default_image
+                .as_deref()
+                .map(|image| absolute_url(&base_url, image)),
+            noindex: !settings.seo.robots.allow_indexing,
+            og_type: "website",
+            published_at: None,
+            modified_at: None,
+            twitter_site: settings.seo.twitter_site.clone(),
+            locale: locale.to_string(),
+            alternates: vec![],
+        }
+    }
+
+    // Overrides set on the post win, otherwise everything is derived from its content.
+    pub fn for_post(settings: &Settings, post: &Post, translations: &[PostTranslation]) -> Self {
+        let site = Self::for_locale(settings, &post.localized_path(&settings.i18n), &post.locale);
+        let base_url = settings.application.full_url();
+        let mut alternates = vec![];
+        if !translations.is_empty() {
+            alternates.push((post.locale.clone(), site.canonical_url.clone()));
+            for translation in translations {
+                let url = format!("{}{}", base_url, translation.path);
+                alternates.push((translation.locale.clone(), url));
+            }
+            // Visitors matching none of the locales get the default locale, or this page.
+            let default = alternates
+                .iter()
+                .find(|(locale, _)| *locale == settings.i18n.default_locale)
+                .map(|(_, url)| url.clone())
+                .unwrap_or_else(|| site.canonical_url.clone());
+            alternates.push(("x-default".to_string(), default));
+        }
+        let description = post
+            .meta_description
+            .clone()
+            .or_else(|| Some(summarize(&post.excerpt, DESCRIPTION_LENGTH)))
+            .filter(|description| !description.is_empty())
+            .unwrap_or_else(|| summarize(&post.body, DESCRIPTION_LENGTH));
+        let image = post
+            .og_image
+            .clone()
+            .or_else(|| first_image(&post.body))
+            .map(|image| absolute_url(&base_url, &image));
+        Self {
+            title: post
+                .meta_title
+                .clone()
+                .unwrap_or_else(|| post.title.clone()),
+            description: match description.is_empty() {
+                true => site.description,
+                false => description,
+            },
+            canonical_url: post.canonical_url.clone().unwrap_or(site.canonical_url),
+            image: image.or(site.image),
+            noindex: site.noindex || post.noindex || !post.is_public(),
+            og_type: match post.kind {
+                PostKind::Post => "article",
+                PostKind::Page => "website",
+            },
+            published_at: post.published_at,
+            modified_at: Some(post.updated_at),
+            alternates,
+            ..site
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::extractors::post::PostKind;
+use crate::extractors::term::Taxonomy;
+use chrono::{DateTime, Utc};
+use translations::LocaleConfig;
+
+// A row of the `sitemap_entries` view.
+#[derive(Debug, Clone)]
+pub struct SitemapEntry {
+    pub kind: String,
+    pub slug: String,
+    pub lastmod: Option<DateTime<Utc>>,
+    // Set for posts and pages, everything else is listed once.
+    pub locale: Option<String>,
+}
+
+impl SitemapEntry {
+    /// Public address of the entry, relative to the site root.
+    ///
+    /// ```
+    /// use content_service::extractors::sitemap::SitemapEntry;
+    ///
+    /// let entry = |kind: &str, slug: &str| SitemapEntry {
+    ///     kind: kind.to_string(),
+    ///     slug: slug.to_string(),
+    ///     lastmod: None,
+    ///     locale: None,
+    /// };
+    /// assert_eq!(entry("home", "").path(), "/");
+    /// assert_eq!(entry("page", "about").path(), "/pages/about");
+    /// assert_eq!(entry("tag", "rust").path(), "/tags/rust");
+    /// assert_eq!(entry("newsletter", "4").path(), "/newsletters/4");
+    /// ```
+    pub fn path(&self) -> String {
+        match self.kind.as_str() {
+            "home" => "/".to_string(),
+            "post" | "page" => PostKind::from(self.kind.clone()).path(&self.slug),
+            "category" | "tag" => Taxonomy::from(self.kind.clone()).archive_path(&self.slug),
+            "newsletters" => "/newsletters".to_string(),
+            _ => format!("/newsletters/{}", self.slug),
+        }
+    }
+
+    pub fn localized_path(&self, config: &LocaleConfig) -> String {
+        match &self.locale {
+            Some(locale) => config.localized_path(locale, &self.path()),
+            None => self.path(),
+        }
+    }
+}
+
+// One file listed in the sitemap index, numbered from one.
+#[derive(Debug, Clone)]
+pub struct SitemapFile {
+    pub number: i64,
+    pub lastmod: Option<DateTime<Utc>>,
+}
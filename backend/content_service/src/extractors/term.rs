@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Taxonomy {
+    // Hierarchical, a post usually sits in one or two.
+    Category,
+    // Flat keywords, created freely while writing.
+    Tag,
+}
+
+impl From<String> for Taxonomy {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "category" => Taxonomy::Category,
+            _ => Taxonomy::Tag,
+        }
+    }
+}
+
+impl From<Taxonomy> for String {
+    fn from(value: Taxonomy) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+impl Taxonomy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Taxonomy::Category => "category",
+            Taxonomy::Tag => "tag",
+        }
+    }
+
+    /// Public archive of a term, relative to the site root.
+    ///
+    /// ```
+    /// use content_service::extractors::term::Taxonomy;
+    ///
+    /// assert_eq!(Taxonomy::Category.archive_path("rust"), "/categories/rust");
+    /// assert_eq!(Taxonomy::Tag.archive_path("axum"), "/tags/axum");
+    /// ```
+    pub fn archive_path(&self, slug: &str) -> String {
+        match self {
+            Taxonomy::Category => format!("/categories/{}", slug),
+            Taxonomy::Tag => format!("/tags/{}", slug),
+        }
+    }
+}
+
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct Term {
+    pub id: i32,
+    pub taxonomy: Taxonomy,
+    pub name: String,
+    pub slug: String,
+    pub description: String,
+    pub parent_id: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
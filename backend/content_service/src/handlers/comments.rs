@@ -0,0 +1,282 @@
+use crate::errors::content::ContentError;
+use crate::extractors::comment::{
+    confirmation_token, hash_confirmation_token, Comment, CommentNode, CommentStatus, NewComment,
+};
+use crate::extractors::post::{Post, PostKind};
+use crate::helpers::comments::{
+    confirm_guest_comment, count_recent_duplicates, delete_comment, fetch_author_contact,
+    fetch_comment, insert_comment, list_comments, list_post_comments, notify_post_author,
+    send_comment_confirmation, set_comment_status,
+};
+use crate::helpers::posts::{fetch_post, fetch_public_post};
+use crate::helpers::spam::{spam_score, SpamSignals, SPAM_THRESHOLD};
+use crate::payload::{CommentListQuery, CommentPayload, CommentTokenQuery, ModerateCommentPayload};
+use auth_service::extractors::authentication::{AdminUser, OptionalAuthenticatedUser};
+use auth_service::extractors::user::User;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use email_clients::email::EmailAddress;
+use serde_json::json;
+use sqlx::PgConnection;
+use utils::client_ip::ClientIp;
+use utils::errors::ErrorPayload;
+use utils::i18n::RequestLocale;
+use utils::state::AppState;
+use utils::validation::ValidatedForm;
+
+#[tracing::instrument(name = "Listing comments of post", skip(state))]
+pub async fn post_comments(
+    State(state): State<AppState>,
+    RequestLocale(locale): RequestLocale,
+    Path(slug): Path<String>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    let locales = state.settings.i18n.fallback_chain(&locale);
+    let post = fetch_public_post(&mut connection, PostKind::Post, &slug, &locales).await?;
+    let comments = list_post_comments(&mut connection, post.id).await?;
+    let total = comments.len();
+    Ok(Json(json!({
+        "comments": CommentNode::thread(comments),
+        "total": total,
+    })))
+}
+
+// Verified users and, when enabled, guests who confirm their email address afterwards.
+#[tracing::instrument(name = "Adding comment", skip(user, state, payload))]
+pub async fn add_comment(
+    OptionalAuthenticatedUser(user): OptionalAuthenticatedUser,
+    ClientIp(ip): ClientIp,
+    RequestLocale(locale): RequestLocale,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    ValidatedForm(payload): ValidatedForm<CommentPayload>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let settings = &state.settings.comments;
+    if !settings.enabled {
+        return Err(ContentError::CommentsDisabled.into());
+    }
+    let guest = match &user {
+        Some(_) => None,
+        None if settings.allow_guests => Some(guest_contact(&payload)?),
+        None => return Err(ContentError::GuestCommentsDisabled.into()),
+    };
+
+    // Guests from an unknown address are limited per email, each comment mails a confirmation.
+    let rate_limit_key = match (&user, &ip, &guest) {
+        (Some(user), _, _) => format!("comment:user:{}", user.user.id),
+        (None, Some(ip), _) => format!("comment:ip:{}", ip),
+        (None, None, Some(guest)) => {
+            format!("comment:email:{}", User::normalize_email(&guest.email))
+        }
+        (None, None, None) => "comment:guest".to_string(),
+    };
+    let policy = settings.rate_limit_policy();
+    state.rate_limiter.check(&rate_limit_key, &policy).await?;
+    state.rate_limiter.hit(&rate_limit_key, &policy).await?;
+
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(ContentError::Pool)?;
+    let locales = state.settings.i18n.fallback_chain(&locale);
+    let post = fetch_public_post(&mut transaction, PostKind::Post, &slug, &locales).await?;
+    let depth = match payload.parent_id {
+        Some(parent_id) => {
+            reply_depth(&mut transaction, &post, parent_id, settings.max_depth).await?
+        }
+        None => 0,
+    };
+
+    let user_id = user.as_ref().map(|user| user.user.id);
+    let duplicates = count_recent_duplicates(
+        &mut transaction,
+        &payload.body,
+        user_id,
+        guest.as_ref().map(|guest| guest.email.as_str()),
+        ip.as_deref(),
+    )
+    .await?;
+    let spam_score = spam_score(&SpamSignals {
+        body: &payload.body,
+        honeypot: payload.website.as_deref(),
+        duplicates,
+        max_links: settings.max_links,
+    });
+    let status = match &user {
+        _ if spam_score >= SPAM_THRESHOLD => CommentStatus::Spam,
+        None => CommentStatus::Unconfirmed,
+        Some(user) if user.user.is_admin || !settings.moderate_users => CommentStatus::Approved,
+        Some(_) => CommentStatus::Pending,
+    };
+    let confirmation = (status == CommentStatus::Unconfirmed).then(confirmation_token);
+
+    let new_comment = NewComment {
+        post_id: post.id,
+        parent_id: payload.parent_id,
+        depth,
+        user_id,
+        guest_name: guest.as_ref().map(|guest| guest.name.clone()),
+        guest_email: guest.as_ref().map(|guest| guest.email.clone()),
+        body: payload.body,
+        status,
+        spam_score,
+        ip_address: ip.unwrap_or_default(),
+        confirmation_hash: confirmation.as_ref().map(|(_, hash)| hash.clone()),
+    };
+    let id = insert_comment(&mut transaction, &new_comment).await?;
+    let comment = fetch_comment(&mut transaction, id).await?;
+    if status == CommentStatus::Approved {
+        notify_author(&state, &mut transaction, &post, &comment).await?;
+    }
+    transaction
+        .commit()
+        .await
+        .map_err(ContentError::DatabaseError)?;
+
+    if let (Some(guest), Some((token, _))) = (guest, confirmation) {
+        send_comment_confirmation(&state, guest, &token);
+    }
+    // Spam is reported as pending so spammers learn nothing about the filter.
+    let reported = match status {
+        CommentStatus::Spam => CommentStatus::Pending,
+        status => status,
+    };
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({"id": comment.id, "status": reported})),
+    ))
+}
+
+#[tracing::instrument(name = "Confirming comment", skip(state, query))]
+pub async fn confirm_comment(
+    State(state): State<AppState>,
+    Query(query): Query<CommentTokenQuery>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    confirm_guest_comment(&mut connection, &hash_confirmation_token(&query.token)).await?;
+    Ok("Comment confirmed, it will appear once a moderator approves it")
+}
+
+// Pending comments unless another status is asked for.
+#[tracing::instrument(name = "Moderation queue", skip(user, state), fields(username = % user.user.username))]
+pub async fn moderation_queue(
+    user: AdminUser,
+    State(state): State<AppState>,
+    Query(query): Query<CommentListQuery>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    let status = query.status.unwrap_or(CommentStatus::Pending);
+    let (comments, total) =
+        list_comments(&mut connection, status, query.per_page(), query.offset()).await?;
+    Ok(Json(json!({
+        "comments": comments,
+        "total": total,
+        "page": query.page(),
+        "per_page": query.per_page(),
+    })))
+}
+
+#[tracing::instrument(name = "Moderating comment", skip(user, state), fields(username = % user.user.username))]
+pub async fn moderate_comment(
+    user: AdminUser,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    ValidatedForm(payload): ValidatedForm<ModerateCommentPayload>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    if payload.status == CommentStatus::Unconfirmed {
+        return Err(ContentError::InvalidModeration.into());
+    }
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(ContentError::Pool)?;
+    let previous = fetch_comment(&mut transaction, id).await?;
+    set_comment_status(&mut transaction, id, payload.status).await?;
+    let comment = fetch_comment(&mut transaction, id).await?;
+    if comment.status == CommentStatus::Approved && previous.status != CommentStatus::Approved {
+        let post = fetch_post(&mut transaction, comment.post_id).await?;
+        notify_author(&state, &mut transaction, &post, &comment).await?;
+    }
+    transaction
+        .commit()
+        .await
+        .map_err(ContentError::DatabaseError)?;
+    Ok(Json(comment))
+}
+
+#[tracing::instrument(name = "Removing comment", skip(user, state), fields(username = % user.user.username))]
+pub async fn remove_comment(
+    user: AdminUser,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    delete_comment(&mut connection, id).await?;
+    Ok(Json(json!({"ok": true})))
+}
+
+fn guest_contact(payload: &CommentPayload) -> Result<EmailAddress, ContentError> {
+    let name = payload
+        .name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .ok_or(ContentError::MissingGuestDetail("name"))?;
+    let email = payload
+        .email
+        .as_deref()
+        .map(str::trim)
+        .filter(|email| !email.is_empty())
+        .ok_or(ContentError::MissingGuestDetail("email"))?;
+    Ok(EmailAddress {
+        name: name.to_string(),
+        email: email.to_string(),
+    })
+}
+
+// Replies go to approved comments of the same post and stop at the configured depth.
+async fn reply_depth(
+    connection: &mut PgConnection,
+    post: &Post,
+    parent_id: i32,
+    max_depth: i32,
+) -> Result<i32, ContentError> {
+    let parent = match fetch_comment(connection, parent_id).await {
+        Err(ContentError::CommentNotFound) => return Err(ContentError::InvalidReply),
+        parent => parent?,
+    };
+    if parent.post_id != post.id || parent.status != CommentStatus::Approved {
+        return Err(ContentError::InvalidReply);
+    }
+    if parent.depth >= max_depth {
+        return Err(ContentError::ReplyTooDeep);
+    }
+    Ok(parent.depth + 1)
+}
+
+// Authors are not told about their own comments.
+async fn notify_author(
+    state: &AppState,
+    connection: &mut PgConnection,
+    post: &Post,
+    comment: &Comment,
+) -> Result<(), ContentError> {
+    let author_id = match post.author_id {
+        Some(author_id) if comment.user_id != Some(author_id) => author_id,
+        _ => return Ok(()),
+    };
+    if !state.settings.comments.notify_authors {
+        return Ok(());
+    }
+    if let Some(recipient) = fetch_author_contact(connection, author_id).await? {
+        let post_url = format!(
+            "{}{}",
+            state.settings.application.full_url(),
+            post.localized_path(&state.settings.i18n)
+        );
+        notify_post_author(state, recipient, comment, &post_url);
+    }
+    Ok(())
+}
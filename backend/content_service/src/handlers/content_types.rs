@@ -0,0 +1,293 @@
+use crate::errors::content::ContentError;
+use crate::extractors::content_type::{ContentEntry, ContentType, FieldDefinition};
+use crate::helpers::content_types::{
+    check_references, check_related_types, delete_content_type, delete_entry, fetch_content_type,
+    fetch_entry, fetch_public_entry, insert_content_type, insert_entry, list_content_types,
+    list_entries, update_content_type, update_entry,
+};
+use crate::helpers::fields::{merge_entry_data, validate_definitions, validate_entry_data};
+use crate::helpers::slug::resolve_slug;
+use crate::payload::{
+    ContentTypePayload, EntryListQuery, EntryPayload, UpdateContentTypePayload, UpdateEntryPayload,
+};
+use auth_service::extractors::authentication::AdminUser;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::Utc;
+use serde_json::{json, Map, Value};
+use sqlx::types::Json as JsonColumn;
+use sqlx::PgConnection;
+use utils::errors::ErrorPayload;
+use utils::state::AppState;
+use utils::validation::ValidatedForm;
+
+#[tracing::instrument(name = "Listing content types", skip(user, state), fields(username = % user.user.username))]
+pub async fn manage_content_types(
+    user: AdminUser,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    let content_types = list_content_types(&mut connection).await?;
+    Ok(Json(json!({ "content_types": content_types })))
+}
+
+#[tracing::instrument(name = "Adding content type", skip(user, state, payload), fields(username = % user.user.username))]
+pub async fn add_content_type(
+    user: AdminUser,
+    State(state): State<AppState>,
+    ValidatedForm(payload): ValidatedForm<ContentTypePayload>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let mut content_type = ContentType {
+        id: 0,
+        slug: resolve_slug(payload.slug.as_deref(), &payload.name, 100)?,
+        name: payload.name,
+        description: payload.description.unwrap_or_default(),
+        fields: JsonColumn(payload.fields),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    };
+
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(ContentError::Pool)?;
+    check_definitions(&mut transaction, &content_type.slug, &content_type.fields).await?;
+    content_type.id = insert_content_type(&mut transaction, &content_type).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(ContentError::DatabaseError)?;
+    Ok((StatusCode::CREATED, Json(content_type)))
+}
+
+#[tracing::instrument(name = "Viewing content type", skip(user, state), fields(username = % user.user.username))]
+pub async fn manage_content_type(
+    user: AdminUser,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    Ok(Json(fetch_content_type(&mut connection, &slug).await?))
+}
+
+// Existing entries keep their data, values of removed fields are dropped on their next save.
+#[tracing::instrument(name = "Editing content type", skip(user, state, payload), fields(username = % user.user.username))]
+pub async fn edit_content_type(
+    user: AdminUser,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    ValidatedForm(payload): ValidatedForm<UpdateContentTypePayload>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(ContentError::Pool)?;
+    let mut content_type = fetch_content_type(&mut transaction, &slug).await?;
+    if let Some(name) = payload.name {
+        content_type.name = name;
+    }
+    if let Some(description) = payload.description {
+        content_type.description = description;
+    }
+    if let Some(fields) = payload.fields {
+        check_definitions(&mut transaction, &content_type.slug, &fields).await?;
+        content_type.fields = JsonColumn(fields);
+    }
+    update_content_type(&mut transaction, &content_type).await?;
+    let content_type = fetch_content_type(&mut transaction, &slug).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(ContentError::DatabaseError)?;
+    Ok(Json(content_type))
+}
+
+#[tracing::instrument(name = "Removing content type", skip(user, state), fields(username = % user.user.username))]
+pub async fn remove_content_type(
+    user: AdminUser,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    delete_content_type(&mut connection, &slug).await?;
+    Ok(Json(json!({"ok": true})))
+}
+
+// Every entry of the type regardless of status, for the admin dashboard.
+#[tracing::instrument(name = "Managing entries", skip(user, state), fields(username = % user.user.username))]
+pub async fn manage_entries(
+    user: AdminUser,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    Query(query): Query<EntryListQuery>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    Ok(Json(
+        entry_page(&mut connection, &slug, false, &query).await?,
+    ))
+}
+
+#[tracing::instrument(name = "Adding entry", skip(user, state, payload), fields(username = % user.user.username))]
+pub async fn add_entry(
+    user: AdminUser,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    ValidatedForm(payload): ValidatedForm<EntryPayload>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(ContentError::Pool)?;
+    let content_type = fetch_content_type(&mut transaction, &slug).await?;
+    let mut entry = ContentEntry {
+        id: 0,
+        content_type_id: content_type.id,
+        slug: resolve_slug(payload.slug.as_deref(), &payload.title, 255)?,
+        title: payload.title,
+        status: payload.status,
+        data: check_data(&mut transaction, &content_type, &payload.data).await?,
+        author_id: Some(user.user.id),
+        published_at: payload.published_at,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    };
+    entry.set_status(payload.status);
+    entry.id = insert_entry(&mut transaction, &entry).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(ContentError::DatabaseError)?;
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+#[tracing::instrument(name = "Viewing entry", skip(user, state), fields(username = % user.user.username))]
+pub async fn manage_entry(
+    user: AdminUser,
+    State(state): State<AppState>,
+    Path((slug, id)): Path<(String, i32)>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    let content_type = fetch_content_type(&mut connection, &slug).await?;
+    Ok(Json(
+        fetch_entry(&mut connection, content_type.id, id).await?,
+    ))
+}
+
+#[tracing::instrument(name = "Editing entry", skip(user, state, payload), fields(username = % user.user.username))]
+pub async fn edit_entry(
+    user: AdminUser,
+    State(state): State<AppState>,
+    Path((slug, id)): Path<(String, i32)>,
+    ValidatedForm(payload): ValidatedForm<UpdateEntryPayload>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(ContentError::Pool)?;
+    let content_type = fetch_content_type(&mut transaction, &slug).await?;
+    let mut entry = fetch_entry(&mut transaction, content_type.id, id).await?;
+    if let Some(slug) = payload.slug.as_deref() {
+        entry.slug = resolve_slug(Some(slug), "", 255)?;
+    }
+    if let Some(title) = payload.title {
+        entry.title = title;
+    }
+    if payload.published_at.is_some() {
+        entry.published_at = payload.published_at;
+    }
+    if let Some(status) = payload.status {
+        entry.set_status(status);
+    }
+    let changes = payload.data.unwrap_or_default();
+    let data = merge_entry_data(&content_type.fields, &entry.data, changes);
+    entry.data = check_data(&mut transaction, &content_type, &data).await?;
+    update_entry(&mut transaction, &entry).await?;
+    let entry = fetch_entry(&mut transaction, content_type.id, id).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(ContentError::DatabaseError)?;
+    Ok(Json(entry))
+}
+
+#[tracing::instrument(name = "Removing entry", skip(user, state), fields(username = % user.user.username))]
+pub async fn remove_entry(
+    user: AdminUser,
+    State(state): State<AppState>,
+    Path((slug, id)): Path<(String, i32)>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    let content_type = fetch_content_type(&mut connection, &slug).await?;
+    delete_entry(&mut connection, content_type.id, id).await?;
+    Ok(Json(json!({"ok": true})))
+}
+
+#[tracing::instrument(name = "Listing published entries", skip(state))]
+pub async fn list_published_entries(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    Query(query): Query<EntryListQuery>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    Ok(Json(
+        entry_page(&mut connection, &slug, true, &query).await?,
+    ))
+}
+
+#[tracing::instrument(name = "Viewing published entry", skip(state))]
+pub async fn published_entry(
+    State(state): State<AppState>,
+    Path((slug, entry_slug)): Path<(String, String)>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    let content_type = fetch_content_type(&mut connection, &slug).await?;
+    let entry = fetch_public_entry(&mut connection, content_type.id, &entry_slug).await?;
+    Ok(Json(entry))
+}
+
+async fn check_definitions(
+    connection: &mut PgConnection,
+    slug: &str,
+    fields: &[FieldDefinition],
+) -> Result<(), ContentError> {
+    let mut errors = validate_definitions(fields);
+    check_related_types(connection, slug, fields, &mut errors).await?;
+    errors.check()
+}
+
+// Validated data of an entry, ready to be stored.
+async fn check_data(
+    connection: &mut PgConnection,
+    content_type: &ContentType,
+    data: &Map<String, Value>,
+) -> Result<Value, ContentError> {
+    let (data, references) = validate_entry_data(&content_type.fields, data)?;
+    check_references(connection, &references).await?;
+    Ok(data)
+}
+
+async fn entry_page(
+    connection: &mut PgConnection,
+    slug: &str,
+    public_only: bool,
+    query: &EntryListQuery,
+) -> Result<Value, ContentError> {
+    let content_type = fetch_content_type(connection, slug).await?;
+    let (entries, total) = list_entries(
+        connection,
+        content_type.id,
+        public_only,
+        query.per_page(),
+        query.offset(),
+    )
+    .await?;
+    Ok(json!({
+        "content_type": content_type,
+        "entries": entries,
+        "total": total,
+        "page": query.page(),
+        "per_page": query.per_page(),
+    }))
+}
@@ -0,0 +1,196 @@
+use crate::errors::content::ContentError;
+use crate::extractors::feed::{Feed, FeedFormat, FeedItem};
+use crate::extractors::post::PostKind;
+use crate::extractors::term::Taxonomy;
+use crate::helpers::feeds::{http_date, render_feed};
+use crate::helpers::posts::{fetch_author, list_posts, terms_for_posts, PostFilter};
+use crate::helpers::terms::{fetch_term_by_slug, term_descendants};
+use axum::extract::{Path, State};
+use axum::http::header::{
+    CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+};
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::PgConnection;
+use utils::errors::ErrorPayload;
+use utils::state::AppState;
+
+const FEED_ITEMS: i64 = 20;
+
+#[tracing::instrument(name = "Serving site feed", skip(state, headers))]
+pub async fn site_feed(
+    State(state): State<AppState>,
+    Path(file_name): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, ErrorPayload> {
+    let format = FeedFormat::from_file_name(&file_name).ok_or(ContentError::FeedNotFound)?;
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    let scope = FeedScope {
+        title: state.settings.application.name.clone(),
+        description: format!("Latest posts from {}", state.settings.application.name),
+        path: String::new(),
+        filter: PostFilter::default(),
+    };
+    let feed = build_feed(&mut connection, &state, scope, format).await?;
+    Ok(feed_response(&feed, format, &headers))
+}
+
+#[tracing::instrument(name = "Serving category feed", skip(state, headers))]
+pub async fn category_feed(
+    State(state): State<AppState>,
+    Path((slug, file_name)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<Response, ErrorPayload> {
+    let format = FeedFormat::from_file_name(&file_name).ok_or(ContentError::FeedNotFound)?;
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    let category = fetch_term_by_slug(&mut connection, Taxonomy::Category, &slug).await?;
+    let scope = FeedScope {
+        title: format!("{}: {}", state.settings.application.name, category.name),
+        description: category.description,
+        path: format!("/category/{}", category.slug),
+        filter: PostFilter {
+            category_ids: Some(term_descendants(&mut connection, category.id).await?),
+            ..Default::default()
+        },
+    };
+    let feed = build_feed(&mut connection, &state, scope, format).await?;
+    Ok(feed_response(&feed, format, &headers))
+}
+
+#[tracing::instrument(name = "Serving tag feed", skip(state, headers))]
+pub async fn tag_feed(
+    State(state): State<AppState>,
+    Path((slug, file_name)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<Response, ErrorPayload> {
+    let format = FeedFormat::from_file_name(&file_name).ok_or(ContentError::FeedNotFound)?;
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    let tag = fetch_term_by_slug(&mut connection, Taxonomy::Tag, &slug).await?;
+    let scope = FeedScope {
+        title: format!("{}: {}", state.settings.application.name, tag.name),
+        description: tag.description,
+        path: format!("/tag/{}", tag.slug),
+        filter: PostFilter {
+            tag_ids: Some(vec![tag.id]),
+            ..Default::default()
+        },
+    };
+    let feed = build_feed(&mut connection, &state, scope, format).await?;
+    Ok(feed_response(&feed, format, &headers))
+}
+
+#[tracing::instrument(name = "Serving author feed", skip(state, headers))]
+pub async fn author_feed(
+    State(state): State<AppState>,
+    Path((username, file_name)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<Response, ErrorPayload> {
+    let format = FeedFormat::from_file_name(&file_name).ok_or(ContentError::FeedNotFound)?;
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    let (author_id, name) = fetch_author(&mut connection, &username).await?;
+    let scope = FeedScope {
+        title: format!("{}: {}", state.settings.application.name, name),
+        description: format!("Latest posts by {}", name),
+        path: format!("/author/{}", username),
+        filter: PostFilter {
+            author_id: Some(author_id),
+            ..Default::default()
+        },
+    };
+    let feed = build_feed(&mut connection, &state, scope, format).await?;
+    Ok(feed_response(&feed, format, &headers))
+}
+
+struct FeedScope {
+    title: String,
+    description: String,
+    // Prefix of the feed url below `/feeds`.
+    path: String,
+    filter: PostFilter,
+}
+
+async fn build_feed(
+    connection: &mut PgConnection,
+    state: &AppState,
+    scope: FeedScope,
+    format: FeedFormat,
+) -> Result<Feed, ContentError> {
+    let filter = PostFilter {
+        kind: Some(PostKind::Post),
+        public_only: true,
+        ..scope.filter
+    };
+    let posts = list_posts(connection, &filter, FEED_ITEMS, 0).await?;
+    let ids: Vec<i32> = posts.iter().map(|post| post.id).collect();
+    let mut terms = terms_for_posts(connection, &ids).await?;
+
+    let base_url = state.settings.application.full_url();
+    let items = posts
+        .into_iter()
+        .map(|post| {
+            let post_terms = terms.remove(&post.id).unwrap_or_default();
+            FeedItem::new(post, post_terms, &base_url, &state.settings.i18n)
+        })
+        .collect();
+    Ok(Feed {
+        title: scope.title,
+        description: scope.description,
+        feed_url: format!("{}/feeds{}/{}", base_url, scope.path, format.file_name()),
+        home_url: base_url,
+        items,
+    })
+}
+
+// Feed readers poll often, unchanged feeds are answered with an empty 304.
+fn feed_response(feed: &Feed, format: FeedFormat, headers: &HeaderMap) -> Response {
+    let body = render_feed(feed, format);
+    let etag = format!("\"{:x}\"", Sha256::digest(body.as_bytes()));
+    let last_modified = feed.last_modified();
+
+    let mut response = match is_fresh(headers, &etag, last_modified) {
+        true => StatusCode::NOT_MODIFIED.into_response(),
+        false => body.into_response(),
+    };
+    let response_headers = response.headers_mut();
+    response_headers.insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static(format.content_type()),
+    );
+    response_headers.insert(
+        CACHE_CONTROL,
+        HeaderValue::from_static("public, max-age=300"),
+    );
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        response_headers.insert(ETAG, value);
+    }
+    if let Some(value) = last_modified.and_then(|date| HeaderValue::from_str(&http_date(date)).ok())
+    {
+        response_headers.insert(LAST_MODIFIED, value);
+    }
+    response
+}
+
+// `If-None-Match` wins over `If-Modified-Since` when a client sends both.
+fn is_fresh(headers: &HeaderMap, etag: &str, last_modified: Option<DateTime<Utc>>) -> bool {
+    if let Some(value) = headers.get(IF_NONE_MATCH) {
+        let value = value.to_str().unwrap_or_default();
+        return value
+            .split(',')
+            .map(str::trim)
+            .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag);
+    }
+    let since = headers
+        .get(IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| DateTime::parse_from_rfc2822(value).ok());
+    match (since, last_modified) {
+        (Some(since), Some(last_modified)) => last_modified.timestamp() <= since.timestamp(),
+        _ => false,
+    }
+}
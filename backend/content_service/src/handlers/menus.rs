@@ -0,0 +1,145 @@
+use crate::errors::content::ContentError;
+use crate::extractors::menu::{Menu, MenuNode};
+use crate::helpers::menus::{
+    check_menu_targets, delete_menu, fetch_menu, insert_menu, list_menus, menu_items,
+    replace_menu_items, update_menu, validate_menu_items,
+};
+use crate::helpers::slug::resolve_slug;
+use crate::payload::{MenuItemPayload, MenuPayload, UpdateMenuPayload};
+use auth_service::extractors::authentication::AdminUser;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::Utc;
+use serde_json::{json, Value};
+use sqlx::PgConnection;
+use utils::errors::ErrorPayload;
+use utils::state::AppState;
+use utils::validation::ValidatedForm;
+
+// Only the items that currently lead somewhere, for the site navigation.
+#[tracing::instrument(name = "Viewing menu", skip(state))]
+pub async fn published_menu(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    let menu = fetch_menu(&mut connection, &slug).await?;
+    Ok(Json(menu_detail(&mut connection, menu, true).await?))
+}
+
+#[tracing::instrument(name = "Managing menus", skip(user, state), fields(username = % user.user.username))]
+pub async fn manage_menus(
+    user: AdminUser,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    let menus = list_menus(&mut connection).await?;
+    Ok(Json(json!({ "menus": menus })))
+}
+
+#[tracing::instrument(name = "Adding menu", skip(user, state, payload), fields(username = % user.user.username))]
+pub async fn add_menu(
+    user: AdminUser,
+    State(state): State<AppState>,
+    ValidatedForm(payload): ValidatedForm<MenuPayload>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let mut menu = Menu {
+        id: 0,
+        slug: resolve_slug(payload.slug.as_deref(), &payload.name, 100)?,
+        name: payload.name,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    };
+
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(ContentError::Pool)?;
+    check_items(&mut transaction, &payload.items).await?;
+    menu.id = insert_menu(&mut transaction, &menu).await?;
+    replace_menu_items(&mut transaction, menu.id, &payload.items).await?;
+    let detail = menu_detail(&mut transaction, menu, false).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(ContentError::DatabaseError)?;
+    Ok((StatusCode::CREATED, Json(detail)))
+}
+
+// Every item including links to drafts and removed content, for the menu builder.
+#[tracing::instrument(name = "Viewing menu items", skip(user, state), fields(username = % user.user.username))]
+pub async fn manage_menu(
+    user: AdminUser,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    let menu = fetch_menu(&mut connection, &slug).await?;
+    Ok(Json(menu_detail(&mut connection, menu, false).await?))
+}
+
+#[tracing::instrument(name = "Editing menu", skip(user, state, payload), fields(username = % user.user.username))]
+pub async fn edit_menu(
+    user: AdminUser,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    ValidatedForm(payload): ValidatedForm<UpdateMenuPayload>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(ContentError::Pool)?;
+    let mut menu = fetch_menu(&mut transaction, &slug).await?;
+    if let Some(name) = payload.name {
+        menu.name = name;
+        update_menu(&mut transaction, &menu).await?;
+    }
+    if let Some(items) = payload.items {
+        check_items(&mut transaction, &items).await?;
+        replace_menu_items(&mut transaction, menu.id, &items).await?;
+    }
+    let menu = fetch_menu(&mut transaction, &slug).await?;
+    let detail = menu_detail(&mut transaction, menu, false).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(ContentError::DatabaseError)?;
+    Ok(Json(detail))
+}
+
+#[tracing::instrument(name = "Removing menu", skip(user, state), fields(username = % user.user.username))]
+pub async fn remove_menu(
+    user: AdminUser,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    delete_menu(&mut connection, &slug).await?;
+    Ok(Json(json!({"ok": true})))
+}
+
+async fn check_items(
+    connection: &mut PgConnection,
+    items: &[MenuItemPayload],
+) -> Result<(), ContentError> {
+    let (mut errors, targets) = validate_menu_items(items);
+    check_menu_targets(connection, &targets, &mut errors).await?;
+    errors.check()
+}
+
+async fn menu_detail(
+    connection: &mut PgConnection,
+    menu: Menu,
+    public: bool,
+) -> Result<Value, ContentError> {
+    let items = menu_items(connection, menu.id).await?;
+    Ok(json!({
+        "id": menu.id,
+        "name": menu.name,
+        "slug": menu.slug,
+        "updated_at": menu.updated_at,
+        "items": MenuNode::tree(items, public),
+    }))
+}
@@ -0,0 +1,8 @@
+pub mod comments;
+pub mod content_types;
+pub mod feeds;
+pub mod menus;
+pub mod posts;
+pub mod search;
+pub mod sitemap;
+pub mod terms;
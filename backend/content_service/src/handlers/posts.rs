@@ -0,0 +1,349 @@
+use crate::errors::content::ContentError;
+use crate::extractors::post::{Post, PostDetail, PostKind};
+use crate::extractors::term::Taxonomy;
+use crate::helpers::posts::{
+    count_posts, delete_post, fetch_post, fetch_public_post, insert_post, list_posts,
+    list_translations, set_post_terms, terms_for_posts, update_post, PostFilter,
+};
+use crate::helpers::slug::resolve_slug;
+use crate::helpers::terms::{fetch_term_by_slug, term_descendants};
+use crate::payload::{LocaleQuery, PostListQuery, PostPayload, UpdatePostPayload};
+use auth_service::extractors::authentication::AdminUser;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::Utc;
+use serde_json::{json, Value};
+use sqlx::PgConnection;
+use translations::LocaleConfig;
+use utils::errors::ErrorPayload;
+use utils::i18n::RequestLocale;
+use utils::state::AppState;
+use utils::validation::ValidatedForm;
+use uuid::Uuid;
+
+#[tracing::instrument(name = "Listing published posts", skip(state))]
+pub async fn list_published_posts(
+    State(state): State<AppState>,
+    preferred: RequestLocale,
+    Query(query): Query<PostListQuery>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    let mut filter = PostFilter {
+        kind: Some(PostKind::Post),
+        public_only: true,
+        locale: Some(requested_locale(
+            &state,
+            query.locale.as_deref(),
+            preferred,
+        )?),
+        ..Default::default()
+    };
+    if let Some(slug) = &query.category {
+        let category = fetch_term_by_slug(&mut connection, Taxonomy::Category, slug).await?;
+        filter.category_ids = Some(term_descendants(&mut connection, category.id).await?);
+    }
+    if let Some(slug) = &query.tag {
+        let tag = fetch_term_by_slug(&mut connection, Taxonomy::Tag, slug).await?;
+        filter.tag_ids = Some(vec![tag.id]);
+    }
+    Ok(Json(post_page(&mut connection, &filter, &query).await?))
+}
+
+#[tracing::instrument(name = "Viewing published post", skip(state))]
+pub async fn published_post(
+    State(state): State<AppState>,
+    preferred: RequestLocale,
+    Path(slug): Path<String>,
+    Query(query): Query<LocaleQuery>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let locale = requested_locale(&state, query.locale.as_deref(), preferred)?;
+    published(state, PostKind::Post, &slug, &locale).await
+}
+
+#[tracing::instrument(name = "Viewing published page", skip(state))]
+pub async fn published_page(
+    State(state): State<AppState>,
+    preferred: RequestLocale,
+    Path(slug): Path<String>,
+    Query(query): Query<LocaleQuery>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let locale = requested_locale(&state, query.locale.as_deref(), preferred)?;
+    published(state, PostKind::Page, &slug, &locale).await
+}
+
+// Every post and page regardless of status, for the admin dashboard.
+#[tracing::instrument(name = "Managing posts", skip(user, state), fields(username = % user.user.username))]
+pub async fn manage_posts(
+    user: AdminUser,
+    State(state): State<AppState>,
+    Query(query): Query<PostListQuery>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    let filter = PostFilter {
+        kind: query.kind,
+        locale: query
+            .locale
+            .as_deref()
+            .map(|tag| supported_locale(&state.settings.i18n, tag))
+            .transpose()?,
+        ..Default::default()
+    };
+    Ok(Json(post_page(&mut connection, &filter, &query).await?))
+}
+
+#[tracing::instrument(name = "Adding post", skip(user, state, payload), fields(username = % user.user.username))]
+pub async fn add_post(
+    user: AdminUser,
+    State(state): State<AppState>,
+    ValidatedForm(payload): ValidatedForm<PostPayload>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let mut post = Post {
+        id: 0,
+        kind: payload.kind,
+        slug: resolve_slug(payload.slug.as_deref(), &payload.title, 255)?,
+        title: payload.title,
+        locale: match payload.locale.as_deref() {
+            Some(tag) => supported_locale(&state.settings.i18n, tag)?,
+            None => state.settings.i18n.default_locale.clone(),
+        },
+        translation_group: Uuid::new_v4(),
+        excerpt: payload.excerpt.unwrap_or_default(),
+        body: payload.body.unwrap_or_default(),
+        status: payload.status,
+        author_id: Some(user.user.id),
+        author_username: Some(user.user.username.clone()),
+        author_name: Some(user.user.name.clone()),
+        meta_title: non_empty(payload.meta_title),
+        meta_description: non_empty(payload.meta_description),
+        canonical_url: non_empty(payload.canonical_url),
+        og_image: non_empty(payload.og_image),
+        noindex: payload.noindex,
+        published_at: payload.published_at,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    };
+    post.set_status(payload.status);
+
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(ContentError::Pool)?;
+    if let Some(original) = payload.translation_of {
+        post.translation_group = translation_group(&mut transaction, original, post.kind).await?;
+    }
+    let search_language = search_language(&state, &post.locale);
+    post.id = insert_post(&mut transaction, &post, search_language).await?;
+    set_post_terms(&mut transaction, post.id, &payload.term_ids).await?;
+    let detail = post_detail(&mut transaction, post, &state.settings.i18n, false).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(ContentError::DatabaseError)?;
+    Ok((StatusCode::CREATED, Json(detail)))
+}
+
+#[tracing::instrument(name = "Viewing post", skip(user, state), fields(username = % user.user.username))]
+pub async fn manage_post(
+    user: AdminUser,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    let post = fetch_post(&mut connection, id).await?;
+    Ok(Json(
+        post_detail(&mut connection, post, &state.settings.i18n, false).await?,
+    ))
+}
+
+#[tracing::instrument(name = "Editing post", skip(user, state, payload), fields(username = % user.user.username))]
+pub async fn edit_post(
+    user: AdminUser,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    ValidatedForm(payload): ValidatedForm<UpdatePostPayload>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(ContentError::Pool)?;
+    let mut post = fetch_post(&mut transaction, id).await?;
+    if let Some(kind) = payload.kind {
+        post.kind = kind;
+    }
+    if let Some(slug) = payload.slug.as_deref() {
+        post.slug = resolve_slug(Some(slug), "", 255)?;
+    }
+    if let Some(title) = payload.title {
+        post.title = title;
+    }
+    if let Some(excerpt) = payload.excerpt {
+        post.excerpt = excerpt;
+    }
+    if let Some(body) = payload.body {
+        post.body = body;
+    }
+    if payload.published_at.is_some() {
+        post.published_at = payload.published_at;
+    }
+    if let Some(status) = payload.status {
+        post.set_status(status);
+    }
+    if let Some(meta_title) = payload.meta_title {
+        post.meta_title = non_empty(meta_title);
+    }
+    if let Some(meta_description) = payload.meta_description {
+        post.meta_description = non_empty(meta_description);
+    }
+    if let Some(canonical_url) = payload.canonical_url {
+        post.canonical_url = non_empty(canonical_url);
+    }
+    if let Some(og_image) = payload.og_image {
+        post.og_image = non_empty(og_image);
+    }
+    if let Some(noindex) = payload.noindex {
+        post.noindex = noindex;
+    }
+    if let Some(tag) = payload.locale.as_deref() {
+        post.locale = supported_locale(&state.settings.i18n, tag)?;
+    }
+    match payload.translation_of {
+        Some(original) if original != post.id => {
+            post.translation_group =
+                translation_group(&mut transaction, original, post.kind).await?;
+        }
+        _ => {}
+    }
+    update_post(
+        &mut transaction,
+        &post,
+        search_language(&state, &post.locale),
+    )
+    .await?;
+    if let Some(term_ids) = payload.term_ids {
+        set_post_terms(&mut transaction, post.id, &term_ids).await?;
+    }
+    let post = fetch_post(&mut transaction, id).await?;
+    let detail = post_detail(&mut transaction, post, &state.settings.i18n, false).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(ContentError::DatabaseError)?;
+    Ok(Json(detail))
+}
+
+#[tracing::instrument(name = "Removing post", skip(user, state), fields(username = % user.user.username))]
+pub async fn remove_post(
+    user: AdminUser,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    delete_post(&mut connection, id).await?;
+    Ok(Json(json!({"ok": true})))
+}
+
+async fn published(
+    state: AppState,
+    kind: PostKind,
+    slug: &str,
+    locale: &str,
+) -> Result<Json<PostDetail>, ErrorPayload> {
+    let config = &state.settings.i18n;
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    let post =
+        fetch_public_post(&mut connection, kind, slug, &config.fallback_chain(locale)).await?;
+    Ok(Json(
+        post_detail(&mut connection, post, config, true).await?,
+    ))
+}
+
+pub(crate) fn supported_locale(config: &LocaleConfig, tag: &str) -> Result<String, ContentError> {
+    config
+        .resolve(tag)
+        .ok_or_else(|| ContentError::UnsupportedLocale(tag.to_string()))
+}
+
+fn search_language<'a>(state: &'a AppState, locale: &str) -> &'a str {
+    let settings = &state.settings;
+    settings
+        .i18n
+        .search_language(locale, &settings.search.language)
+}
+
+// Locale asked for with `?locale=`, otherwise the one the client prefers.
+pub(crate) fn requested_locale(
+    state: &AppState,
+    requested: Option<&str>,
+    RequestLocale(preferred): RequestLocale,
+) -> Result<String, ContentError> {
+    match requested {
+        Some(tag) => supported_locale(&state.settings.i18n, tag),
+        None => Ok(preferred),
+    }
+}
+
+// Translations are grouped with the post they translate, which must be of the same kind.
+async fn translation_group(
+    transaction: &mut PgConnection,
+    original: i32,
+    kind: PostKind,
+) -> Result<Uuid, ContentError> {
+    match fetch_post(transaction, original).await {
+        Ok(post) if post.kind == kind => Ok(post.translation_group),
+        Ok(_) | Err(ContentError::PostNotFound) => Err(ContentError::InvalidTranslation),
+        Err(err) => Err(err),
+    }
+}
+
+// Blank overrides are stored as missing so the defaults apply.
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|value| !value.trim().is_empty())
+}
+
+pub(crate) async fn post_detail(
+    connection: &mut PgConnection,
+    post: Post,
+    config: &LocaleConfig,
+    public_only: bool,
+) -> Result<PostDetail, ContentError> {
+    let mut terms = terms_for_posts(connection, &[post.id]).await?;
+    let terms = terms.remove(&post.id).unwrap_or_default();
+    let translations = list_translations(connection, &post, public_only, config).await?;
+    let mut detail = PostDetail::new(post, terms);
+    detail.translations = translations;
+    Ok(detail)
+}
+
+// One page of posts in the shape every listing shares.
+pub(crate) async fn post_page(
+    connection: &mut PgConnection,
+    filter: &PostFilter,
+    query: &PostListQuery,
+) -> Result<Value, ContentError> {
+    let posts = list_posts(connection, filter, query.per_page(), query.offset()).await?;
+    let total = count_posts(connection, filter).await?;
+    Ok(json!({
+        "posts": details(connection, posts).await?,
+        "total": total,
+        "page": query.page(),
+        "per_page": query.per_page(),
+    }))
+}
+
+async fn details(
+    connection: &mut PgConnection,
+    posts: Vec<Post>,
+) -> Result<Vec<PostDetail>, ContentError> {
+    let ids: Vec<i32> = posts.iter().map(|post| post.id).collect();
+    let mut terms = terms_for_posts(connection, &ids).await?;
+    Ok(posts
+        .into_iter()
+        .map(|post| {
+            let post_terms = terms.remove(&post.id).unwrap_or_default();
+            PostDetail::new(post, post_terms)
+        })
+        .collect())
+}
@@ -0,0 +1,58 @@
+use crate::errors::content::ContentError;
+use crate::extractors::term::Taxonomy;
+use crate::helpers::search::{search_documents, SearchFilter};
+use crate::helpers::terms::{fetch_term_by_slug, term_descendants};
+use crate::payload::SearchQuery;
+use axum::extract::{Query, State};
+use axum::response::IntoResponse;
+use axum::Json;
+use serde_json::json;
+use utils::errors::ErrorPayload;
+use utils::state::AppState;
+
+#[tracing::instrument(name = "Searching content", skip(state))]
+pub async fn search(
+    State(state): State<AppState>,
+    Query(query): Query<SearchQuery>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let text = query.q.trim();
+    if text.is_empty() {
+        return Ok(Json(json!({
+            "results": [],
+            "total": 0,
+            "page": query.page(),
+            "per_page": query.per_page(),
+        })));
+    }
+
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    let mut filter = SearchFilter {
+        kind: query.kind,
+        from: query.from,
+        to: query.to,
+        ..Default::default()
+    };
+    if let Some(slug) = &query.category {
+        let category = fetch_term_by_slug(&mut connection, Taxonomy::Category, slug).await?;
+        filter.category_ids = Some(term_descendants(&mut connection, category.id).await?);
+    }
+    if let Some(slug) = &query.tag {
+        let tag = fetch_term_by_slug(&mut connection, Taxonomy::Tag, slug).await?;
+        filter.tag_ids = Some(vec![tag.id]);
+    }
+    let (results, total) = search_documents(
+        &mut connection,
+        text,
+        &filter,
+        query.per_page(),
+        query.offset(),
+    )
+    .await?;
+    Ok(Json(json!({
+        "results": results,
+        "total": total,
+        "page": query.page(),
+        "per_page": query.per_page(),
+    })))
+}
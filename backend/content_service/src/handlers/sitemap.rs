@@ -0,0 +1,83 @@
+use crate::errors::content::ContentError;
+use crate::helpers::sitemap::{
+    count_sitemap_entries, list_sitemap_entries, list_sitemap_files, render_index, render_robots,
+    render_urlset,
+};
+use axum::extract::{Path, State};
+use axum::http::header::{CACHE_CONTROL, CONTENT_TYPE};
+use axum::response::{IntoResponse, Response};
+use utils::errors::ErrorPayload;
+use utils::state::AppState;
+
+const XML: &str = "application/xml; charset=utf-8";
+
+// A single urlset while everything fits in one file, a sitemap index otherwise.
+#[tracing::instrument(name = "Serving sitemap", skip(state))]
+pub async fn sitemap(State(state): State<AppState>) -> Result<Response, ErrorPayload> {
+    let per_file = state.settings.seo.sitemap_urls_per_file.max(1);
+    let base_url = state.settings.application.full_url();
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+
+    let body = match count_sitemap_entries(&mut connection).await? > per_file {
+        true => render_index(
+            &base_url,
+            &list_sitemap_files(&mut connection, per_file).await?,
+        ),
+        false => render_urlset(
+            &base_url,
+            &state.settings.i18n,
+            &list_sitemap_entries(&mut connection, per_file, 0).await?,
+        ),
+    };
+    Ok(text_response(body, XML))
+}
+
+#[tracing::instrument(name = "Serving sitemap file", skip(state))]
+pub async fn sitemap_file(
+    State(state): State<AppState>,
+    Path(file_name): Path<String>,
+) -> Result<Response, ErrorPayload> {
+    let per_file = state.settings.seo.sitemap_urls_per_file.max(1);
+    // Numbers past the last representable offset cannot hold any entries.
+    let offset = file_name
+        .strip_suffix(".xml")
+        .and_then(|number| number.parse::<i64>().ok())
+        .filter(|number| *number > 0)
+        .and_then(|number| number.checked_sub(1))
+        .and_then(|number| number.checked_mul(per_file))
+        .ok_or(ContentError::SitemapNotFound)?;
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+
+    let entries = list_sitemap_entries(&mut connection, per_file, offset).await?;
+    if entries.is_empty() {
+        return Err(ContentError::SitemapNotFound.into());
+    }
+    let body = render_urlset(
+        &state.settings.application.full_url(),
+        &state.settings.i18n,
+        &entries,
+    );
+    Ok(text_response(body, XML))
+}
+
+#[tracing::instrument(name = "Serving robots.txt", skip(state))]
+pub async fn robots(State(state): State<AppState>) -> Response {
+    let body = render_robots(
+        &state.settings.application.full_url(),
+        &state.settings.seo.robots,
+    );
+    text_response(body, "text/plain; charset=utf-8")
+}
+
+fn text_response(body: String, content_type: &'static str) -> Response {
+    (
+        [
+            (CONTENT_TYPE, content_type),
+            (CACHE_CONTROL, "public, max-age=3600"),
+        ],
+        body,
+    )
+        .into_response()
+}
@@ -0,0 +1,205 @@
+use crate::errors::content::ContentError;
+use crate::extractors::post::PostKind;
+use crate::extractors::term::{Taxonomy, Term};
+use crate::handlers::posts::{post_page, requested_locale};
+use crate::helpers::posts::PostFilter;
+use crate::helpers::slug::resolve_slug;
+use crate::helpers::terms::{
+    delete_term, fetch_term, fetch_term_by_slug, insert_term, list_terms, suggest_tags,
+    term_descendants, update_term,
+};
+use crate::payload::{PostListQuery, SuggestQuery, TermPayload, UpdateTermPayload};
+use auth_service::extractors::authentication::AdminUser;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::Utc;
+use serde_json::json;
+use sqlx::PgConnection;
+use utils::errors::ErrorPayload;
+use utils::i18n::RequestLocale;
+use utils::state::AppState;
+use utils::validation::ValidatedForm;
+
+const SUGGESTION_LIMIT: i64 = 10;
+
+#[tracing::instrument(name = "Listing categories", skip(state))]
+pub async fn list_categories(
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    list_taxonomy(state, Taxonomy::Category).await
+}
+
+#[tracing::instrument(name = "Listing tags", skip(state))]
+pub async fn list_tags(State(state): State<AppState>) -> Result<impl IntoResponse, ErrorPayload> {
+    list_taxonomy(state, Taxonomy::Tag).await
+}
+
+// Posts of a category and every category nested below it.
+#[tracing::instrument(name = "Viewing category archive", skip(state))]
+pub async fn category_posts(
+    State(state): State<AppState>,
+    preferred: RequestLocale,
+    Path(slug): Path<String>,
+    Query(query): Query<PostListQuery>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let locale = requested_locale(&state, query.locale.as_deref(), preferred)?;
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    let category = fetch_term_by_slug(&mut connection, Taxonomy::Category, &slug).await?;
+    let filter = PostFilter {
+        kind: Some(PostKind::Post),
+        public_only: true,
+        locale: Some(locale),
+        category_ids: Some(term_descendants(&mut connection, category.id).await?),
+        ..Default::default()
+    };
+    let mut page = post_page(&mut connection, &filter, &query).await?;
+    page["category"] = json!(category);
+    Ok(Json(page))
+}
+
+#[tracing::instrument(name = "Viewing tag archive", skip(state))]
+pub async fn tag_posts(
+    State(state): State<AppState>,
+    preferred: RequestLocale,
+    Path(slug): Path<String>,
+    Query(query): Query<PostListQuery>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let locale = requested_locale(&state, query.locale.as_deref(), preferred)?;
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    let tag = fetch_term_by_slug(&mut connection, Taxonomy::Tag, &slug).await?;
+    let filter = PostFilter {
+        kind: Some(PostKind::Post),
+        public_only: true,
+        locale: Some(locale),
+        tag_ids: Some(vec![tag.id]),
+        ..Default::default()
+    };
+    let mut page = post_page(&mut connection, &filter, &query).await?;
+    page["tag"] = json!(tag);
+    Ok(Json(page))
+}
+
+// Auto-complete for the editor while typing tags.
+#[tracing::instrument(name = "Suggesting tags", skip(user, state), fields(username = % user.user.username))]
+pub async fn tag_suggestions(
+    user: AdminUser,
+    State(state): State<AppState>,
+    Query(query): Query<SuggestQuery>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let prefix = query.q.trim();
+    if prefix.is_empty() {
+        return Ok(Json(json!({"tags": []})));
+    }
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    let tags = suggest_tags(&mut connection, prefix, SUGGESTION_LIMIT).await?;
+    Ok(Json(json!({"tags": tags})))
+}
+
+#[tracing::instrument(name = "Adding term", skip(user, state, payload), fields(username = % user.user.username))]
+pub async fn add_term(
+    user: AdminUser,
+    State(state): State<AppState>,
+    ValidatedForm(payload): ValidatedForm<TermPayload>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let mut term = Term {
+        id: 0,
+        taxonomy: payload.taxonomy,
+        slug: resolve_slug(payload.slug.as_deref(), &payload.name, 100)?,
+        name: payload.name,
+        description: payload.description.unwrap_or_default(),
+        parent_id: payload.parent_id,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    };
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(ContentError::Pool)?;
+    check_parent(&mut transaction, &term).await?;
+    term.id = insert_term(&mut transaction, &term).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(ContentError::DatabaseError)?;
+    Ok((StatusCode::CREATED, Json(term)))
+}
+
+#[tracing::instrument(name = "Editing term", skip(user, state, payload), fields(username = % user.user.username))]
+pub async fn edit_term(
+    user: AdminUser,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    ValidatedForm(payload): ValidatedForm<UpdateTermPayload>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(ContentError::Pool)?;
+    let mut term = fetch_term(&mut transaction, id).await?;
+    if let Some(slug) = payload.slug.as_deref() {
+        term.slug = resolve_slug(Some(slug), "", 100)?;
+    }
+    if let Some(name) = payload.name {
+        term.name = name;
+    }
+    if let Some(description) = payload.description {
+        term.description = description;
+    }
+    if let Some(parent_id) = payload.parent_id {
+        term.parent_id = parent_id;
+        check_parent(&mut transaction, &term).await?;
+    }
+    update_term(&mut transaction, &term).await?;
+    let term = fetch_term(&mut transaction, id).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(ContentError::DatabaseError)?;
+    Ok(Json(term))
+}
+
+#[tracing::instrument(name = "Removing term", skip(user, state), fields(username = % user.user.username))]
+pub async fn remove_term(
+    user: AdminUser,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    delete_term(&mut connection, id).await?;
+    Ok(Json(json!({"ok": true})))
+}
+
+async fn list_taxonomy(
+    state: AppState,
+    taxonomy: Taxonomy,
+) -> Result<Json<Vec<Term>>, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    Ok(Json(list_terms(&mut connection, taxonomy).await?))
+}
+
+// Tags stay flat, categories nest below another category but never below themselves.
+async fn check_parent(connection: &mut PgConnection, term: &Term) -> Result<(), ContentError> {
+    let Some(parent_id) = term.parent_id else {
+        return Ok(());
+    };
+    if term.taxonomy != Taxonomy::Category {
+        return Err(ContentError::InvalidParent);
+    }
+    let parent = fetch_term(connection, parent_id)
+        .await
+        .map_err(|_| ContentError::InvalidParent)?;
+    if parent.taxonomy != Taxonomy::Category {
+        return Err(ContentError::InvalidParent);
+    }
+    if term.id != 0
+        && term_descendants(connection, term.id)
+            .await?
+            .contains(&parent_id)
+    {
+        return Err(ContentError::ParentCycle);
+    }
+    Ok(())
+}
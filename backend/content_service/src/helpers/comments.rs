@@ -0,0 +1,301 @@
+use crate::errors::content::ContentError;
+use crate::extractors::comment::{Comment, CommentStatus, NewComment};
+use email_clients::email::{EmailAddress, EmailObject};
+use email_clients::traits::EmailTrait;
+use sqlx::PgConnection;
+use tokio::task;
+use utils::state::{AppState, BackgroundTask};
+
+#[tracing::instrument(name = "Inserting comment", skip(transaction, comment), fields(post_id = comment.post_id))]
+pub async fn insert_comment(
+    transaction: &mut PgConnection,
+    comment: &NewComment,
+) -> Result<i32, ContentError> {
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO comments (post_id, parent_id, depth, user_id, guest_name, guest_email, body,
+            status, spam_score, ip_address, confirmation_hash, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, now(), now())
+        RETURNING id
+        "#,
+        comment.post_id,
+        comment.parent_id,
+        comment.depth,
+        comment.user_id,
+        comment.guest_name,
+        comment.guest_email,
+        comment.body,
+        comment.status.as_str(),
+        comment.spam_score,
+        comment.ip_address,
+        comment.confirmation_hash
+    )
+    .fetch_one(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    Ok(row.id)
+}
+
+#[tracing::instrument(name = "Fetching comment", skip(transaction))]
+pub async fn fetch_comment(
+    transaction: &mut PgConnection,
+    id: i32,
+) -> Result<Comment, ContentError> {
+    let comment = sqlx::query_as!(
+        Comment,
+        r#"
+        SELECT c.id, c.post_id, p.title as post_title, c.parent_id, c.depth, c.user_id,
+            coalesce(u.name, c.guest_name) as "author_name!", c.guest_email, c.body, c.status,
+            c.spam_score, c.ip_address, c.created_at, c.updated_at
+        FROM comments c
+        JOIN posts p ON p.id = c.post_id
+        LEFT JOIN users u ON u.id = c.user_id
+        WHERE c.id = $1
+        "#,
+        id
+    )
+    .fetch_optional(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    comment.ok_or(ContentError::CommentNotFound)
+}
+
+// Approved comments of a post, oldest first so threads read top to bottom.
+#[tracing::instrument(name = "Listing post comments", skip(transaction))]
+pub async fn list_post_comments(
+    transaction: &mut PgConnection,
+    post_id: i32,
+) -> Result<Vec<Comment>, ContentError> {
+    sqlx::query_as!(
+        Comment,
+        r#"
+        SELECT c.id, c.post_id, p.title as post_title, c.parent_id, c.depth, c.user_id,
+            coalesce(u.name, c.guest_name) as "author_name!", c.guest_email, c.body, c.status,
+            c.spam_score, c.ip_address, c.created_at, c.updated_at
+        FROM comments c
+        JOIN posts p ON p.id = c.post_id
+        LEFT JOIN users u ON u.id = c.user_id
+        WHERE c.post_id = $1 AND c.status = 'approved'
+        ORDER BY c.created_at, c.id
+        "#,
+        post_id
+    )
+    .fetch_all(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)
+}
+
+// Newest first, the moderation queue shows what arrived last on top.
+#[tracing::instrument(name = "Listing comments", skip(transaction))]
+pub async fn list_comments(
+    transaction: &mut PgConnection,
+    status: CommentStatus,
+    limit: i64,
+    offset: i64,
+) -> Result<(Vec<Comment>, i64), ContentError> {
+    let comments = sqlx::query_as!(
+        Comment,
+        r#"
+        SELECT c.id, c.post_id, p.title as post_title, c.parent_id, c.depth, c.user_id,
+            coalesce(u.name, c.guest_name) as "author_name!", c.guest_email, c.body, c.status,
+            c.spam_score, c.ip_address, c.created_at, c.updated_at
+        FROM comments c
+        JOIN posts p ON p.id = c.post_id
+        LEFT JOIN users u ON u.id = c.user_id
+        WHERE c.status = $1
+        ORDER BY c.created_at DESC, c.id DESC
+        LIMIT $2 OFFSET $3
+        "#,
+        status.as_str(),
+        limit,
+        offset
+    )
+    .fetch_all(&mut *transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    let total = sqlx::query!(
+        r#"SELECT count(*) as "count!" FROM comments WHERE status = $1"#,
+        status.as_str()
+    )
+    .fetch_one(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?
+    .count;
+    Ok((comments, total))
+}
+
+#[tracing::instrument(name = "Setting comment status", skip(transaction))]
+pub async fn set_comment_status(
+    transaction: &mut PgConnection,
+    id: i32,
+    status: CommentStatus,
+) -> Result<(), ContentError> {
+    sqlx::query!(
+        r#"UPDATE comments SET status = $2, updated_at = now() WHERE id = $1"#,
+        id,
+        status.as_str()
+    )
+    .execute(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    Ok(())
+}
+
+// Moves a guest comment into the moderation queue, the token only works once.
+#[tracing::instrument(
+    name = "Confirming guest comment",
+    skip(transaction, confirmation_hash)
+)]
+pub async fn confirm_guest_comment(
+    transaction: &mut PgConnection,
+    confirmation_hash: &str,
+) -> Result<i32, ContentError> {
+    let row = sqlx::query!(
+        r#"
+        UPDATE comments SET status = 'pending', confirmation_hash = NULL, updated_at = now()
+        WHERE confirmation_hash = $1 AND status = 'unconfirmed'
+        RETURNING id
+        "#,
+        confirmation_hash
+    )
+    .fetch_optional(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    row.map(|row| row.id).ok_or(ContentError::CommentNotFound)
+}
+
+#[tracing::instrument(name = "Deleting comment", skip(transaction))]
+pub async fn delete_comment(transaction: &mut PgConnection, id: i32) -> Result<(), ContentError> {
+    let result = sqlx::query!("DELETE FROM comments WHERE id = $1", id)
+        .execute(transaction)
+        .await
+        .map_err(ContentError::DatabaseError)?;
+    if result.rows_affected() == 0 {
+        return Err(ContentError::CommentNotFound);
+    }
+    Ok(())
+}
+
+// Comments with the same text from the same author or address in the last day.
+#[tracing::instrument(name = "Counting duplicate comments", skip(transaction, body))]
+pub async fn count_recent_duplicates(
+    transaction: &mut PgConnection,
+    body: &str,
+    user_id: Option<i32>,
+    email: Option<&str>,
+    ip_address: Option<&str>,
+) -> Result<i64, ContentError> {
+    let row = sqlx::query!(
+        r#"
+        SELECT count(*) as "count!" FROM comments
+        WHERE body = $1 AND created_at > now() - interval '1 day'
+            AND (user_id = $2 OR lower(guest_email) = lower($3) OR ip_address = $4)
+        "#,
+        body,
+        user_id,
+        email,
+        ip_address
+    )
+    .fetch_one(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    Ok(row.count)
+}
+
+#[tracing::instrument(name = "Fetching author contact", skip(transaction))]
+pub async fn fetch_author_contact(
+    transaction: &mut PgConnection,
+    author_id: i32,
+) -> Result<Option<EmailAddress>, ContentError> {
+    let row = sqlx::query!(r#"SELECT name, email FROM users WHERE id = $1"#, author_id)
+        .fetch_optional(transaction)
+        .await
+        .map_err(ContentError::DatabaseError)?;
+    Ok(row.map(|row| EmailAddress {
+        name: row.name,
+        email: row.email,
+    }))
+}
+
+#[tracing::instrument(name = "Sending comment confirmation", skip(state, token))]
+pub fn send_comment_confirmation(state: &AppState, recipient: EmailAddress, token: &str) {
+    let confirmation_link = format!(
+        "{}/api/content/comments/confirm?token={}",
+        state.settings.application.full_url(),
+        token
+    );
+    let client = state.email_client.to_owned().unwrap();
+    let email_object = EmailObject {
+        sender: client.get_sender(),
+        to: vec![recipient],
+        subject: "Please confirm your comment".to_string(),
+        plain: format!(
+            "Thanks for your comment. Please visit {} to confirm your email address, \
+            the comment is published once a moderator approves it.",
+            confirmation_link
+        ),
+        html: format!(
+            "<b>Thanks for your comment.</b>\
+                 Please click <a href='{}' target='_blank'>here </a>\
+                  or copy the link below to confirm your email address.<br>\
+                 \
+                 {}
+                 ",
+            confirmation_link, confirmation_link
+        ),
+    };
+    send_in_background(state, "send_comment_confirmation", client, email_object);
+}
+
+#[tracing::instrument(name = "Notifying post author", skip(state, comment))]
+pub fn notify_post_author(
+    state: &AppState,
+    recipient: EmailAddress,
+    comment: &Comment,
+    post_url: &str,
+) {
+    let client = state.email_client.to_owned().unwrap();
+    let email_object = EmailObject {
+        sender: client.get_sender(),
+        to: vec![recipient],
+        subject: format!("New comment on {}", comment.post_title),
+        plain: format!(
+            "{} commented on {}:\n\n{}\n\nRead the discussion at {}",
+            comment.author_name, comment.post_title, comment.body, post_url
+        ),
+        html: format!(
+            "<b>{} commented on {}</b>\
+                 <blockquote>{}</blockquote>\
+                 Read the discussion <a href='{}' target='_blank'>here</a>.",
+            escape_html(&comment.author_name),
+            escape_html(&comment.post_title),
+            escape_html(&comment.body),
+            post_url
+        ),
+    };
+    send_in_background(state, "notify_post_author", client, email_object);
+}
+
+fn send_in_background(
+    state: &AppState,
+    name: &str,
+    client: Box<dyn EmailTrait + Send>,
+    email_object: EmailObject,
+) {
+    let handle = task::spawn(async move {
+        if let Err(err) = client.send_emails(email_object).await {
+            tracing::error!("Unable to send comment email: {:?}", err);
+        }
+    });
+    if let Some(tx) = &state.tasks {
+        let _ = tx.send(BackgroundTask::new(name, handle));
+    }
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,338 @@
+use crate::errors::content::ContentError;
+use crate::extractors::content_type::{ContentEntry, ContentType, FieldDefinition, FieldKind};
+use crate::helpers::fields::{EntryReferences, FieldErrors};
+use serde_json::json;
+use sqlx::types::Json;
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+#[tracing::instrument(name = "Inserting content type", skip(transaction, content_type))]
+pub async fn insert_content_type(
+    transaction: &mut PgConnection,
+    content_type: &ContentType,
+) -> Result<i32, ContentError> {
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO content_types (name, slug, description, fields, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id
+        "#,
+        content_type.name,
+        content_type.slug,
+        content_type.description,
+        json!(content_type.fields.0),
+        content_type.created_at,
+        content_type.updated_at
+    )
+    .fetch_one(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    Ok(row.id)
+}
+
+#[tracing::instrument(name = "Updating content type", skip(transaction, content_type), fields(content_type_id = content_type.id))]
+pub async fn update_content_type(
+    transaction: &mut PgConnection,
+    content_type: &ContentType,
+) -> Result<(), ContentError> {
+    sqlx::query!(
+        r#"
+        UPDATE content_types SET name = $2, description = $3, fields = $4, updated_at = now()
+        WHERE id = $1
+        "#,
+        content_type.id,
+        content_type.name,
+        content_type.description,
+        json!(content_type.fields.0)
+    )
+    .execute(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "Fetching content type", skip(transaction))]
+pub async fn fetch_content_type(
+    transaction: &mut PgConnection,
+    slug: &str,
+) -> Result<ContentType, ContentError> {
+    let content_type = sqlx::query_as!(
+        ContentType,
+        r#"
+        SELECT id, name, slug, description, fields as "fields: Json<Vec<FieldDefinition>>",
+            created_at, updated_at
+        FROM content_types WHERE slug = $1
+        "#,
+        slug
+    )
+    .fetch_optional(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    content_type.ok_or(ContentError::ContentTypeNotFound)
+}
+
+#[tracing::instrument(name = "Listing content types", skip(transaction))]
+pub async fn list_content_types(
+    transaction: &mut PgConnection,
+) -> Result<Vec<ContentType>, ContentError> {
+    sqlx::query_as!(
+        ContentType,
+        r#"
+        SELECT id, name, slug, description, fields as "fields: Json<Vec<FieldDefinition>>",
+            created_at, updated_at
+        FROM content_types ORDER BY name, id
+        "#
+    )
+    .fetch_all(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)
+}
+
+// Entries of the type are removed with it.
+#[tracing::instrument(name = "Deleting content type", skip(transaction))]
+pub async fn delete_content_type(
+    transaction: &mut PgConnection,
+    slug: &str,
+) -> Result<(), ContentError> {
+    let result = sqlx::query!("DELETE FROM content_types WHERE slug = $1", slug)
+        .execute(transaction)
+        .await
+        .map_err(ContentError::DatabaseError)?;
+    match result.rows_affected() {
+        0 => Err(ContentError::ContentTypeNotFound),
+        _ => Ok(()),
+    }
+}
+
+// Relation fields must point at a type that exists, or at the type being defined.
+#[tracing::instrument(name = "Checking related content types", skip(transaction, fields))]
+pub async fn check_related_types(
+    transaction: &mut PgConnection,
+    own_slug: &str,
+    fields: &[FieldDefinition],
+    errors: &mut FieldErrors,
+) -> Result<(), ContentError> {
+    let slugs: Vec<String> = sqlx::query!("SELECT slug FROM content_types")
+        .fetch_all(transaction)
+        .await
+        .map_err(ContentError::DatabaseError)?
+        .into_iter()
+        .map(|row| row.slug)
+        .collect();
+    for field in fields {
+        if let FieldKind::Relation { content_type } = &field.kind {
+            if content_type != own_slug && !slugs.contains(content_type) {
+                let message = format!("{} relates to an unknown content type", field.name);
+                errors.add("fields", "unknown_type", &message, Some(&field.name));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[tracing::instrument(name = "Inserting content entry", skip(transaction, entry))]
+pub async fn insert_entry(
+    transaction: &mut PgConnection,
+    entry: &ContentEntry,
+) -> Result<i32, ContentError> {
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO content_entries (content_type_id, title, slug, status, data, author_id,
+            published_at, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING id
+        "#,
+        entry.content_type_id,
+        entry.title,
+        entry.slug,
+        entry.status.as_str(),
+        entry.data,
+        entry.author_id,
+        entry.published_at,
+        entry.created_at,
+        entry.updated_at
+    )
+    .fetch_one(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    Ok(row.id)
+}
+
+#[tracing::instrument(name = "Updating content entry", skip(transaction, entry), fields(entry_id = entry.id))]
+pub async fn update_entry(
+    transaction: &mut PgConnection,
+    entry: &ContentEntry,
+) -> Result<(), ContentError> {
+    sqlx::query!(
+        r#"
+        UPDATE content_entries SET title = $2, slug = $3, status = $4, data = $5,
+            published_at = $6, updated_at = now()
+        WHERE id = $1
+        "#,
+        entry.id,
+        entry.title,
+        entry.slug,
+        entry.status.as_str(),
+        entry.data,
+        entry.published_at
+    )
+    .execute(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "Fetching content entry", skip(transaction))]
+pub async fn fetch_entry(
+    transaction: &mut PgConnection,
+    content_type_id: i32,
+    id: i32,
+) -> Result<ContentEntry, ContentError> {
+    let entry = sqlx::query_as!(
+        ContentEntry,
+        r#"
+        SELECT id, content_type_id, title, slug, status, data, author_id, published_at,
+            created_at, updated_at
+        FROM content_entries WHERE content_type_id = $1 AND id = $2
+        "#,
+        content_type_id,
+        id
+    )
+    .fetch_optional(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    entry.ok_or(ContentError::EntryNotFound)
+}
+
+#[tracing::instrument(name = "Fetching public content entry", skip(transaction))]
+pub async fn fetch_public_entry(
+    transaction: &mut PgConnection,
+    content_type_id: i32,
+    slug: &str,
+) -> Result<ContentEntry, ContentError> {
+    let entry = sqlx::query_as!(
+        ContentEntry,
+        r#"
+        SELECT id, content_type_id, title, slug, status, data, author_id, published_at,
+            created_at, updated_at
+        FROM content_entries
+        WHERE content_type_id = $1 AND slug = $2 AND status = 'published'
+            AND published_at <= now()
+        "#,
+        content_type_id,
+        slug
+    )
+    .fetch_optional(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    entry.ok_or(ContentError::EntryNotFound)
+}
+
+// Newest first, drafts are ordered by when they were created.
+#[tracing::instrument(name = "Listing content entries", skip(transaction))]
+pub async fn list_entries(
+    transaction: &mut PgConnection,
+    content_type_id: i32,
+    public_only: bool,
+    limit: i64,
+    offset: i64,
+) -> Result<(Vec<ContentEntry>, i64), ContentError> {
+    let entries = sqlx::query_as!(
+        ContentEntry,
+        r#"
+        SELECT id, content_type_id, title, slug, status, data, author_id, published_at,
+            created_at, updated_at
+        FROM content_entries
+        WHERE content_type_id = $1
+            AND (NOT $2 OR (status = 'published' AND published_at <= now()))
+        ORDER BY coalesce(published_at, created_at) DESC, id DESC
+        LIMIT $3 OFFSET $4
+        "#,
+        content_type_id,
+        public_only,
+        limit,
+        offset
+    )
+    .fetch_all(&mut *transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    let row = sqlx::query!(
+        r#"
+        SELECT count(*) as "count!" FROM content_entries
+        WHERE content_type_id = $1
+            AND (NOT $2 OR (status = 'published' AND published_at <= now()))
+        "#,
+        content_type_id,
+        public_only
+    )
+    .fetch_one(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    Ok((entries, row.count))
+}
+
+#[tracing::instrument(name = "Deleting content entry", skip(transaction))]
+pub async fn delete_entry(
+    transaction: &mut PgConnection,
+    content_type_id: i32,
+    id: i32,
+) -> Result<(), ContentError> {
+    let result = sqlx::query!(
+        "DELETE FROM content_entries WHERE content_type_id = $1 AND id = $2",
+        content_type_id,
+        id
+    )
+    .execute(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    match result.rows_affected() {
+        0 => Err(ContentError::EntryNotFound),
+        _ => Ok(()),
+    }
+}
+
+// Media and related entries must exist, missing ones are reported on their field.
+#[tracing::instrument(name = "Checking entry references", skip(transaction))]
+pub async fn check_references(
+    transaction: &mut PgConnection,
+    references: &EntryReferences,
+) -> Result<(), ContentError> {
+    let mut errors = FieldErrors::default();
+    if !references.media.is_empty() {
+        let identifiers: Vec<Uuid> = references.media.iter().map(|(_, id)| *id).collect();
+        let found: Vec<Uuid> = sqlx::query!(
+            "SELECT identifier FROM media WHERE identifier = ANY($1)",
+            &identifiers
+        )
+        .fetch_all(&mut *transaction)
+        .await
+        .map_err(ContentError::DatabaseError)?
+        .into_iter()
+        .map(|row| row.identifier)
+        .collect();
+        for (field, identifier) in &references.media {
+            if !found.contains(identifier) {
+                errors.add(field, "media_not_found", "Media file not found", None);
+            }
+        }
+    }
+    for (field, content_type, id) in &references.relations {
+        let row = sqlx::query!(
+            r#"
+            SELECT count(*) as "count!" FROM content_entries e
+            JOIN content_types t ON t.id = e.content_type_id
+            WHERE t.slug = $1 AND e.id = $2
+            "#,
+            content_type,
+            id
+        )
+        .fetch_one(&mut *transaction)
+        .await
+        .map_err(ContentError::DatabaseError)?;
+        if row.count == 0 {
+            let message = format!("No {} entry with this id", content_type);
+            errors.add(field, "relation_not_found", &message, None);
+        }
+    }
+    errors.check()
+}
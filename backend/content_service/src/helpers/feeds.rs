@@ -0,0 +1,169 @@
+use crate::extractors::feed::{Feed, FeedFormat, FeedItem};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::json;
+use std::fmt::Write;
+
+pub fn render_feed(feed: &Feed, format: FeedFormat) -> String {
+    match format {
+        FeedFormat::Rss => render_rss(feed),
+        FeedFormat::Atom => render_atom(feed),
+        FeedFormat::Json => render_json(feed),
+    }
+}
+
+/// Escapes text for xml element content and attribute values.
+///
+/// Control characters are not allowed in xml 1.0 and are dropped.
+///
+/// ```
+/// use content_service::helpers::feeds::escape_xml;
+///
+/// assert_eq!(escape_xml("<b>\"Tom\" & 'Jerry'</b>\u{0}"), "&lt;b&gt;&quot;Tom&quot; &amp; &apos;Jerry&apos;&lt;/b&gt;");
+/// ```
+pub fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Date format of `Last-Modified` and `If-Modified-Since`.
+pub fn http_date(date: DateTime<Utc>) -> String {
+    date.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+fn render_rss(feed: &Feed) -> String {
+    let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push_str(concat!(
+        r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom""#,
+        r#" xmlns:content="http://purl.org/rss/1.0/modules/content/""#,
+        r#" xmlns:dc="http://purl.org/dc/elements/1.1/"><channel>"#
+    ));
+    let _ = write!(
+        xml,
+        r#"<title>{}</title><link>{}</link><description>{}</description><atom:link href="{}" rel="self" type="application/rss+xml"/>"#,
+        escape_xml(&feed.title),
+        escape_xml(&feed.home_url),
+        escape_xml(&feed.description),
+        escape_xml(&feed.feed_url)
+    );
+    if let Some(last_modified) = feed.last_modified() {
+        let _ = write!(
+            xml,
+            "<lastBuildDate>{}</lastBuildDate>",
+            last_modified.to_rfc2822()
+        );
+    }
+    for item in &feed.items {
+        let _ = write!(
+            xml,
+            r#"<item><title>{}</title><link>{}</link><guid isPermaLink="true">{}</guid><pubDate>{}</pubDate><description>{}</description><content:encoded>{}</content:encoded>"#,
+            escape_xml(&item.title),
+            escape_xml(&item.url),
+            escape_xml(&item.url),
+            item.published.to_rfc2822(),
+            escape_xml(&item.summary),
+            escape_xml(&item.content_html)
+        );
+        if let Some(author) = &item.author {
+            let _ = write!(xml, "<dc:creator>{}</dc:creator>", escape_xml(author));
+        }
+        for tag in &item.tags {
+            let _ = write!(xml, "<category>{}</category>", escape_xml(tag));
+        }
+        xml.push_str("</item>");
+    }
+    xml.push_str("</channel></rss>");
+    xml
+}
+
+fn render_atom(feed: &Feed) -> String {
+    let updated = feed.last_modified().unwrap_or(DateTime::UNIX_EPOCH);
+    let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    // The feed level author covers entries whose author was deleted.
+    let _ = write!(
+        xml,
+        r#"<feed xmlns="http://www.w3.org/2005/Atom"><title>{}</title><subtitle>{}</subtitle><link href="{}"/><link rel="self" href="{}"/><id>{}</id><updated>{}</updated><author><name>{}</name></author>"#,
+        escape_xml(&feed.title),
+        escape_xml(&feed.description),
+        escape_xml(&feed.home_url),
+        escape_xml(&feed.feed_url),
+        escape_xml(&feed.feed_url),
+        atom_date(updated),
+        escape_xml(&feed.title)
+    );
+    for item in &feed.items {
+        let _ = write!(
+            xml,
+            r#"<entry><title>{}</title><link href="{}"/><id>{}</id><published>{}</published><updated>{}</updated>"#,
+            escape_xml(&item.title),
+            escape_xml(&item.url),
+            escape_xml(&item.url),
+            atom_date(item.published),
+            atom_date(item.updated)
+        );
+        if let Some(author) = &item.author {
+            let _ = write!(xml, "<author><name>{}</name></author>", escape_xml(author));
+        }
+        if !item.summary.is_empty() {
+            let _ = write!(xml, "<summary>{}</summary>", escape_xml(&item.summary));
+        }
+        let _ = write!(
+            xml,
+            r#"<content type="html">{}</content>"#,
+            escape_xml(&item.content_html)
+        );
+        for tag in &item.tags {
+            let _ = write!(xml, r#"<category term="{}"/>"#, escape_xml(tag));
+        }
+        xml.push_str("</entry>");
+    }
+    xml.push_str("</feed>");
+    xml
+}
+
+fn render_json(feed: &Feed) -> String {
+    let items: Vec<_> = feed.items.iter().map(json_item).collect();
+    json!({
+        "version": "https://jsonfeed.org/version/1.1",
+        "title": feed.title,
+        "description": feed.description,
+        "home_page_url": feed.home_url,
+        "feed_url": feed.feed_url,
+        "items": items,
+    })
+    .to_string()
+}
+
+fn json_item(item: &FeedItem) -> serde_json::Value {
+    let mut value = json!({
+        "id": item.url,
+        "url": item.url,
+        "title": item.title,
+        "content_html": item.content_html,
+        "date_published": atom_date(item.published),
+        "date_modified": atom_date(item.updated),
+        "tags": item.tags,
+    });
+    if !item.summary.is_empty() {
+        value["summary"] = json!(item.summary);
+    }
+    if let Some(author) = &item.author {
+        value["authors"] = json!([{ "name": author }]);
+    }
+    value
+}
+
+fn atom_date(date: DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
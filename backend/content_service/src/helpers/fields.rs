@@ -0,0 +1,337 @@
+use crate::errors::content::ContentError;
+use crate::extractors::content_type::{FieldDefinition, FieldKind};
+use chrono::NaiveDate;
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+// Entry columns share the error keys with fields, so fields cannot take their names.
+const RESERVED_NAMES: [&str; 4] = ["title", "slug", "status", "published_at"];
+
+/// Field level errors keyed by field name, in the shape `validator` reports them.
+///
+/// ```
+/// use content_service::helpers::fields::FieldErrors;
+/// use serde_json::json;
+///
+/// let mut errors = FieldErrors::default();
+/// assert!(errors.is_empty());
+/// errors.add("price", "range", "Price must be at least 0", None);
+/// assert_eq!(
+///     errors.into_value(),
+///     json!({"price": [{"code": "range", "message": "Price must be at least 0", "params": {}}]})
+/// );
+/// ```
+#[derive(Debug, Default)]
+pub struct FieldErrors(Map<String, Value>);
+
+impl FieldErrors {
+    pub fn add(&mut self, field: &str, code: &str, message: &str, value: Option<&str>) {
+        let params = match value {
+            Some(value) => json!({"value": value}),
+            None => json!({}),
+        };
+        let error = json!({"code": code, "message": message, "params": params});
+        match self.0.get_mut(field) {
+            Some(Value::Array(errors)) => errors.push(error),
+            _ => {
+                self.0.insert(field.to_string(), json!([error]));
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn into_value(self) -> Value {
+        Value::Object(self.0)
+    }
+
+    pub fn check(self) -> Result<(), ContentError> {
+        match self.is_empty() {
+            true => Ok(()),
+            false => Err(self.into()),
+        }
+    }
+}
+
+impl From<FieldErrors> for ContentError {
+    fn from(value: FieldErrors) -> Self {
+        ContentError::InvalidFields(value.into_value())
+    }
+}
+
+// Values pointing at other rows, checked against the database after the shape is valid.
+#[derive(Debug, Default, PartialEq)]
+pub struct EntryReferences {
+    // Field name and media identifier.
+    pub media: Vec<(String, Uuid)>,
+    // Field name, slug of the related content type and entry id.
+    pub relations: Vec<(String, String, i32)>,
+}
+
+/// Checks the field definitions of a content type, errors are reported under `fields`.
+///
+/// Whether related content types exist is left to the caller.
+///
+/// ```
+/// use content_service::extractors::content_type::{FieldDefinition, FieldKind};
+/// use content_service::helpers::fields::validate_definitions;
+///
+/// let field = |name: &str, kind| FieldDefinition {
+///     name: name.to_string(),
+///     label: String::new(),
+///     required: false,
+///     kind,
+/// };
+/// let fields = vec![
+///     field("venue", FieldKind::Text { max_length: None }),
+///     field("venue", FieldKind::Boolean),
+///     field("Starts At", FieldKind::Date),
+///     field("title", FieldKind::RichText),
+///     field("kind", FieldKind::Enum { choices: vec![] }),
+/// ];
+/// let errors = validate_definitions(&fields).into_value();
+/// let codes: Vec<_> = errors["fields"]
+///     .as_array()
+///     .unwrap()
+///     .iter()
+///     .map(|error| error["code"].as_str().unwrap())
+///     .collect();
+/// assert_eq!(codes, ["duplicate_name", "invalid_name", "reserved_name", "empty_choices"]);
+/// ```
+pub fn validate_definitions(fields: &[FieldDefinition]) -> FieldErrors {
+    let mut errors = FieldErrors::default();
+    let mut seen = HashSet::new();
+    for field in fields {
+        let name = field.name.as_str();
+        if !valid_name(name) {
+            errors.add(
+                "fields",
+                "invalid_name",
+                "Field names must start with a letter and contain only lowercase letters, digits and underscores",
+                Some(name),
+            );
+        } else if RESERVED_NAMES.contains(&name) {
+            let message = format!("{} is reserved for entries", name);
+            errors.add("fields", "reserved_name", &message, Some(name));
+        } else if !seen.insert(name) {
+            let message = format!("{} is defined more than once", name);
+            errors.add("fields", "duplicate_name", &message, Some(name));
+        }
+        match &field.kind {
+            FieldKind::Enum { choices } if choices.is_empty() => {
+                let message = format!("{} needs at least one choice", name);
+                errors.add("fields", "empty_choices", &message, Some(name));
+            }
+            FieldKind::Number {
+                min: Some(min),
+                max: Some(max),
+                ..
+            } if min > max => {
+                let message = format!("{} has a minimum above its maximum", name);
+                errors.add("fields", "invalid_range", &message, Some(name));
+            }
+            _ => {}
+        }
+    }
+    errors
+}
+
+fn valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some('a'..='z'))
+        && chars.all(|c| matches!(c, 'a'..='z' | '0'..='9' | '_'))
+        && name.len() <= 64
+}
+
+/// Validates entry data against the field definitions of its type.
+///
+/// Returns the data with empty optional values dropped, along with the media and related
+/// entries it points at.
+///
+/// ```
+/// use content_service::extractors::content_type::{FieldDefinition, FieldKind};
+/// use content_service::helpers::fields::validate_entry_data;
+/// use serde_json::json;
+///
+/// let fields = vec![
+///     FieldDefinition {
+///         name: "seats".to_string(),
+///         label: "Seats".to_string(),
+///         required: true,
+///         kind: FieldKind::Number { min: Some(1.0), max: None, integer: true },
+///     },
+///     FieldDefinition {
+///         name: "venue".to_string(),
+///         label: String::new(),
+///         required: false,
+///         kind: FieldKind::Text { max_length: Some(10) },
+///     },
+/// ];
+///
+/// let data = json!({"seats": 20, "venue": null});
+/// let (data, _) = validate_entry_data(&fields, data.as_object().unwrap()).unwrap();
+/// assert_eq!(data, json!({"seats": 20}));
+///
+/// let data = json!({"seats": 0.5, "speaker": "Amrit"});
+/// let errors = validate_entry_data(&fields, data.as_object().unwrap()).unwrap_err();
+/// let errors = errors.into_value();
+/// assert_eq!(errors["seats"][0]["code"], "integer");
+/// assert_eq!(errors["seats"][1]["message"], "Seats must be at least 1");
+/// assert_eq!(errors["speaker"][0]["code"], "unknown_field");
+/// ```
+pub fn validate_entry_data(
+    fields: &[FieldDefinition],
+    data: &Map<String, Value>,
+) -> Result<(Value, EntryReferences), FieldErrors> {
+    let mut errors = FieldErrors::default();
+    let mut references = EntryReferences::default();
+    let mut cleaned = Map::new();
+
+    for key in data.keys() {
+        if !fields.iter().any(|field| &field.name == key) {
+            errors.add(key, "unknown_field", "Unknown field", None);
+        }
+    }
+
+    for field in fields {
+        let label = match field.label.is_empty() {
+            true => field.name.as_str(),
+            false => field.label.as_str(),
+        };
+        let value = match data.get(&field.name) {
+            None | Some(Value::Null) => None,
+            Some(Value::String(text)) if text.trim().is_empty() => None,
+            Some(value) => Some(value),
+        };
+        let Some(value) = value else {
+            if field.required {
+                let message = format!("{} is required", label);
+                errors.add(&field.name, "required", &message, None);
+            }
+            continue;
+        };
+        let before = errors.0.len();
+        let value = check_value(field, label, value, &mut errors, &mut references);
+        if errors.0.len() == before {
+            cleaned.insert(field.name.clone(), value);
+        }
+    }
+
+    match errors.is_empty() {
+        true => Ok((Value::Object(cleaned), references)),
+        false => Err(errors),
+    }
+}
+
+fn check_value(
+    field: &FieldDefinition,
+    label: &str,
+    value: &Value,
+    errors: &mut FieldErrors,
+    references: &mut EntryReferences,
+) -> Value {
+    let name = field.name.as_str();
+    let mut invalid = |code: &str, message: String| errors.add(name, code, &message, None);
+    match &field.kind {
+        FieldKind::Text { max_length } => match value.as_str() {
+            Some(text) => {
+                if let Some(max_length) = max_length {
+                    if text.chars().count() > *max_length {
+                        let message =
+                            format!("{} must contain at most {} characters", label, max_length);
+                        invalid("length", message);
+                    }
+                }
+            }
+            None => invalid("invalid_type", format!("{} must be text", label)),
+        },
+        FieldKind::RichText => {
+            if !value.is_string() {
+                invalid("invalid_type", format!("{} must be text", label));
+            }
+        }
+        FieldKind::Number { min, max, integer } => match value.as_f64() {
+            Some(number) => {
+                if *integer && number.fract() != 0.0 {
+                    invalid("integer", format!("{} must be a whole number", label));
+                }
+                if let Some(min) = min.filter(|min| number < *min) {
+                    invalid("range", format!("{} must be at least {}", label, min));
+                }
+                if let Some(max) = max.filter(|max| number > *max) {
+                    invalid("range", format!("{} must be at most {}", label, max));
+                }
+            }
+            None => invalid("invalid_type", format!("{} must be a number", label)),
+        },
+        FieldKind::Date => {
+            match value
+                .as_str()
+                .and_then(|text| NaiveDate::parse_from_str(text, "%Y-%m-%d").ok())
+            {
+                Some(date) => return json!(date),
+                None => invalid(
+                    "invalid_date",
+                    format!("{} must be a date like 2024-01-31", label),
+                ),
+            }
+        }
+        FieldKind::Boolean => {
+            if !value.is_boolean() {
+                invalid("invalid_type", format!("{} must be true or false", label));
+            }
+        }
+        FieldKind::Media => match value.as_str().and_then(|text| Uuid::parse_str(text).ok()) {
+            Some(identifier) => {
+                references.media.push((name.to_string(), identifier));
+                return json!(identifier);
+            }
+            None => invalid(
+                "invalid_media",
+                format!("{} must be a media identifier", label),
+            ),
+        },
+        FieldKind::Relation { content_type } => {
+            match value.as_i64().and_then(|id| i32::try_from(id).ok()) {
+                Some(id) => references
+                    .relations
+                    .push((name.to_string(), content_type.clone(), id)),
+                None => invalid(
+                    "invalid_relation",
+                    format!("{} must be the id of an entry", label),
+                ),
+            }
+        }
+        FieldKind::Enum { choices } => {
+            if !value
+                .as_str()
+                .is_some_and(|text| choices.iter().any(|choice| choice == text))
+            {
+                let message = format!("{} must be one of {}", label, choices.join(", "));
+                invalid("invalid_choice", message);
+            }
+        }
+    }
+    value.clone()
+}
+
+// Partial updates only send the changed fields, values of fields removed from the type since
+// the entry was last saved are dropped.
+pub fn merge_entry_data(
+    fields: &[FieldDefinition],
+    existing: &Value,
+    changes: Map<String, Value>,
+) -> Map<String, Value> {
+    let mut data: Map<String, Value> = existing
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(key, _)| fields.iter().any(|field| &field.name == *key))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    data.extend(changes);
+    data
+}
@@ -0,0 +1,289 @@
+use crate::errors::content::ContentError;
+use crate::extractors::menu::{LinkType, Menu, MenuItem};
+use crate::helpers::fields::FieldErrors;
+use crate::payload::MenuItemPayload;
+use sqlx::PgConnection;
+
+// Deeper menus do not fit a navbar dropdown.
+pub const MAX_MENU_DEPTH: usize = 3;
+
+// Item linking to a post or term, keyed by its position in the payload.
+#[derive(Debug, PartialEq)]
+pub struct MenuTarget {
+    pub key: String,
+    pub link_type: LinkType,
+    pub id: i32,
+}
+
+/// Checks the shape of a menu, errors are keyed by the item position like `items[0].url`.
+///
+/// Returns the posts and terms linked, their existence is left to the caller.
+///
+/// ```
+/// use content_service::extractors::menu::LinkType;
+/// use content_service::helpers::menus::validate_menu_items;
+/// use content_service::payload::MenuItemPayload;
+/// use serde_json::json;
+///
+/// let items: Vec<MenuItemPayload> = serde_json::from_value(json!([
+///     {"link_type": "page", "target_id": 3, "children": [
+///         {"label": "Source", "link_type": "url", "url": "https://github.com"},
+///         {"label": "Broken", "link_type": "url", "url": "javascript:alert(1)"},
+///     ]},
+///     {"label": "Rust", "link_type": "tag"},
+/// ]))
+/// .unwrap();
+/// let (errors, targets) = validate_menu_items(&items);
+/// let errors = errors.into_value();
+/// assert_eq!(errors["items[0].children[1].url"][0]["code"], "invalid_url");
+/// assert_eq!(errors["items[1].target_id"][0]["code"], "required");
+/// assert_eq!(targets.len(), 1);
+/// assert_eq!(targets[0].link_type, LinkType::Page);
+/// ```
+pub fn validate_menu_items(items: &[MenuItemPayload]) -> (FieldErrors, Vec<MenuTarget>) {
+    let mut errors = FieldErrors::default();
+    let mut targets = vec![];
+    let mut pending: Vec<(String, usize, &[MenuItemPayload])> = vec![("items".into(), 1, items)];
+    while let Some((prefix, depth, items)) = pending.pop() {
+        for (index, item) in items.iter().enumerate() {
+            let key = format!("{}[{}]", prefix, index);
+            if item.label.chars().count() > 255 {
+                let message = "Label must contain at most 255 characters";
+                errors.add(&format!("{}.label", key), "length", message, None);
+            }
+            match item.link_type {
+                LinkType::Url => {
+                    if item.label.trim().is_empty() {
+                        let message = "Links to other sites need a label";
+                        errors.add(&format!("{}.label", key), "required", message, None);
+                    }
+                    match item.url.as_deref() {
+                        Some(url) if valid_url(url) => {}
+                        Some(_) => {
+                            let message = "Links must start with /, http://, https:// or mailto:";
+                            errors.add(&format!("{}.url", key), "invalid_url", message, None);
+                        }
+                        None => {
+                            let message = "Url is required";
+                            errors.add(&format!("{}.url", key), "required", message, None);
+                        }
+                    }
+                }
+                link_type => match item.target_id {
+                    Some(id) => targets.push(MenuTarget {
+                        key: format!("{}.target_id", key),
+                        link_type,
+                        id,
+                    }),
+                    None => {
+                        let message = format!("Pick the {} to link", link_type.as_str());
+                        errors.add(&format!("{}.target_id", key), "required", &message, None);
+                    }
+                },
+            }
+            if item.children.is_empty() {
+                continue;
+            }
+            if depth >= MAX_MENU_DEPTH {
+                let message = format!("Menus can be at most {} levels deep", MAX_MENU_DEPTH);
+                errors.add(&format!("{}.children", key), "too_deep", &message, None);
+            } else {
+                pending.push((format!("{}.children", key), depth + 1, &item.children));
+            }
+        }
+    }
+    (errors, targets)
+}
+
+fn valid_url(url: &str) -> bool {
+    let url = url.trim();
+    url.len() <= 2000
+        && (url.starts_with("https://")
+            || url.starts_with("http://")
+            || url.starts_with("mailto:")
+            || (url.starts_with('/') && !url.starts_with("//")))
+}
+
+// Posts and pages of any status can be linked, drafts only show up once published.
+#[tracing::instrument(name = "Checking menu targets", skip(transaction, targets))]
+pub async fn check_menu_targets(
+    transaction: &mut PgConnection,
+    targets: &[MenuTarget],
+    errors: &mut FieldErrors,
+) -> Result<(), ContentError> {
+    for target in targets {
+        let row = match target.link_type {
+            LinkType::Post | LinkType::Page => sqlx::query!(
+                r#"SELECT count(*) as "count!" FROM posts WHERE id = $1 AND kind = $2"#,
+                target.id,
+                target.link_type.as_str()
+            )
+            .fetch_one(&mut *transaction)
+            .await
+            .map(|row| row.count),
+            LinkType::Category | LinkType::Tag => sqlx::query!(
+                r#"SELECT count(*) as "count!" FROM terms WHERE id = $1 AND taxonomy = $2"#,
+                target.id,
+                target.link_type.as_str()
+            )
+            .fetch_one(&mut *transaction)
+            .await
+            .map(|row| row.count),
+            LinkType::Url => continue,
+        };
+        if row.map_err(ContentError::DatabaseError)? == 0 {
+            let message = format!("No {} with this id", target.link_type.as_str());
+            errors.add(&target.key, "target_not_found", &message, None);
+        }
+    }
+    Ok(())
+}
+
+#[tracing::instrument(name = "Inserting menu", skip(transaction, menu))]
+pub async fn insert_menu(transaction: &mut PgConnection, menu: &Menu) -> Result<i32, ContentError> {
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO menus (name, slug, created_at, updated_at) VALUES ($1, $2, $3, $4)
+        RETURNING id
+        "#,
+        menu.name,
+        menu.slug,
+        menu.created_at,
+        menu.updated_at
+    )
+    .fetch_one(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    Ok(row.id)
+}
+
+#[tracing::instrument(name = "Updating menu", skip(transaction, menu), fields(menu_id = menu.id))]
+pub async fn update_menu(transaction: &mut PgConnection, menu: &Menu) -> Result<(), ContentError> {
+    sqlx::query!(
+        "UPDATE menus SET name = $2, updated_at = now() WHERE id = $1",
+        menu.id,
+        menu.name
+    )
+    .execute(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "Fetching menu", skip(transaction))]
+pub async fn fetch_menu(transaction: &mut PgConnection, slug: &str) -> Result<Menu, ContentError> {
+    let menu = sqlx::query_as!(
+        Menu,
+        "SELECT id, name, slug, created_at, updated_at FROM menus WHERE slug = $1",
+        slug
+    )
+    .fetch_optional(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    menu.ok_or(ContentError::MenuNotFound)
+}
+
+#[tracing::instrument(name = "Listing menus", skip(transaction))]
+pub async fn list_menus(transaction: &mut PgConnection) -> Result<Vec<Menu>, ContentError> {
+    sqlx::query_as!(
+        Menu,
+        "SELECT id, name, slug, created_at, updated_at FROM menus ORDER BY name, id"
+    )
+    .fetch_all(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)
+}
+
+#[tracing::instrument(name = "Deleting menu", skip(transaction))]
+pub async fn delete_menu(transaction: &mut PgConnection, slug: &str) -> Result<(), ContentError> {
+    let result = sqlx::query!("DELETE FROM menus WHERE slug = $1", slug)
+        .execute(transaction)
+        .await
+        .map_err(ContentError::DatabaseError)?;
+    match result.rows_affected() {
+        0 => Err(ContentError::MenuNotFound),
+        _ => Ok(()),
+    }
+}
+
+// Items in menu order with the title and slug of what they link to.
+#[tracing::instrument(name = "Listing menu items", skip(transaction))]
+pub async fn menu_items(
+    transaction: &mut PgConnection,
+    menu_id: i32,
+) -> Result<Vec<MenuItem>, ContentError> {
+    sqlx::query_as!(
+        MenuItem,
+        r#"
+        SELECT i.id, i.parent_id, i.label, i.link_type, i.target_id, i.url, i.open_in_new_tab,
+            coalesce(p.title, t.name) as target_title, coalesce(p.slug, t.slug) as target_slug,
+            CASE
+                WHEN i.link_type = 'url' THEN true
+                WHEN p.id IS NOT NULL THEN p.status = 'published' AND p.published_at <= now()
+                ELSE t.id IS NOT NULL
+            END as "target_public!"
+        FROM menu_items i
+        LEFT JOIN posts p ON i.link_type IN ('post', 'page') AND p.id = i.target_id
+            AND p.kind = i.link_type
+        LEFT JOIN terms t ON i.link_type IN ('category', 'tag') AND t.id = i.target_id
+            AND t.taxonomy = i.link_type
+        WHERE i.menu_id = $1
+        ORDER BY i.position, i.id
+        "#,
+        menu_id
+    )
+    .fetch_all(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)
+}
+
+// The builder saves the whole menu at once, so ordering and nesting change together.
+#[tracing::instrument(name = "Replacing menu items", skip(transaction, items))]
+pub async fn replace_menu_items(
+    transaction: &mut PgConnection,
+    menu_id: i32,
+    items: &[MenuItemPayload],
+) -> Result<(), ContentError> {
+    sqlx::query!("DELETE FROM menu_items WHERE menu_id = $1", menu_id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(ContentError::DatabaseError)?;
+    let mut pending: Vec<(Option<i32>, &[MenuItemPayload])> = vec![(None, items)];
+    while let Some((parent_id, items)) = pending.pop() {
+        for (position, item) in items.iter().enumerate() {
+            let url = match item.link_type {
+                LinkType::Url => item.url.as_deref().map(str::trim),
+                _ => None,
+            };
+            let target_id = match item.link_type {
+                LinkType::Url => None,
+                _ => item.target_id,
+            };
+            let row = sqlx::query!(
+                r#"
+                INSERT INTO menu_items (menu_id, parent_id, position, label, link_type, target_id,
+                    url, open_in_new_tab, created_at, updated_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, now(), now())
+                RETURNING id
+                "#,
+                menu_id,
+                parent_id,
+                position as i32,
+                item.label.trim(),
+                item.link_type.as_str(),
+                target_id,
+                url,
+                item.open_in_new_tab
+            )
+            .fetch_one(&mut *transaction)
+            .await
+            .map_err(ContentError::DatabaseError)?;
+            pending.push((Some(row.id), &item.children));
+        }
+    }
+    sqlx::query!("UPDATE menus SET updated_at = now() WHERE id = $1", menu_id)
+        .execute(transaction)
+        .await
+        .map_err(ContentError::DatabaseError)?;
+    Ok(())
+}
@@ -0,0 +1,12 @@
+pub mod comments;
+pub mod content_types;
+pub mod feeds;
+pub mod fields;
+pub mod menus;
+pub mod posts;
+pub mod search;
+pub mod seo;
+pub mod sitemap;
+pub mod slug;
+pub mod spam;
+pub mod terms;
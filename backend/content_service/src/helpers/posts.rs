@@ -0,0 +1,361 @@
+use crate::errors::content::ContentError;
+use crate::extractors::post::{Post, PostKind, PostTranslation};
+use crate::extractors::term::Term;
+use sqlx::PgConnection;
+use std::collections::HashMap;
+use translations::LocaleConfig;
+
+#[derive(Debug, Default)]
+pub struct PostFilter {
+    pub kind: Option<PostKind>,
+    // Only published posts whose publish date has passed.
+    pub public_only: bool,
+    // Posts in any of these categories, usually one with its descendants.
+    pub category_ids: Option<Vec<i32>>,
+    // Posts with any of these tags.
+    pub tag_ids: Option<Vec<i32>>,
+    pub author_id: Option<i32>,
+    pub locale: Option<String>,
+}
+
+// The search language follows the locale of the post, the index is a generated column.
+#[tracing::instrument(name = "Inserting post", skip(transaction, post))]
+pub async fn insert_post(
+    transaction: &mut PgConnection,
+    post: &Post,
+    search_language: &str,
+) -> Result<i32, ContentError> {
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO posts (kind, title, slug, excerpt, body, status, author_id, published_at,
+            created_at, updated_at, search_language, meta_title, meta_description, canonical_url,
+            og_image, noindex, locale, translation_group)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11::text::regconfig, $12, $13, $14,
+            $15, $16, $17, $18)
+        RETURNING id
+        "#,
+        post.kind.as_str(),
+        post.title,
+        post.slug,
+        post.excerpt,
+        post.body,
+        post.status.as_str(),
+        post.author_id,
+        post.published_at,
+        post.created_at,
+        post.updated_at,
+        search_language,
+        post.meta_title,
+        post.meta_description,
+        post.canonical_url,
+        post.og_image,
+        post.noindex,
+        post.locale,
+        post.translation_group
+    )
+    .fetch_one(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    Ok(row.id)
+}
+
+#[tracing::instrument(name = "Updating post", skip(transaction, post), fields(post_id = post.id))]
+pub async fn update_post(
+    transaction: &mut PgConnection,
+    post: &Post,
+    search_language: &str,
+) -> Result<(), ContentError> {
+    sqlx::query!(
+        r#"
+        UPDATE posts SET kind = $2, title = $3, slug = $4, excerpt = $5, body = $6, status = $7,
+            published_at = $8, meta_title = $9, meta_description = $10, canonical_url = $11,
+            og_image = $12, noindex = $13, locale = $14, translation_group = $15,
+            search_language = $16::text::regconfig, updated_at = now()
+        WHERE id = $1
+        "#,
+        post.id,
+        post.kind.as_str(),
+        post.title,
+        post.slug,
+        post.excerpt,
+        post.body,
+        post.status.as_str(),
+        post.published_at,
+        post.meta_title,
+        post.meta_description,
+        post.canonical_url,
+        post.og_image,
+        post.noindex,
+        post.locale,
+        post.translation_group,
+        search_language
+    )
+    .execute(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "Fetching post", skip(transaction))]
+pub async fn fetch_post(transaction: &mut PgConnection, id: i32) -> Result<Post, ContentError> {
+    let post = sqlx::query_as!(
+        Post,
+        r#"
+        SELECT p.id, p.kind, p.title, p.slug, p.locale, p.translation_group, p.excerpt, p.body,
+            p.status, p.author_id,
+            u.username as "author_username?", u.name as "author_name?",
+            p.meta_title, p.meta_description, p.canonical_url, p.og_image, p.noindex,
+            p.published_at, p.created_at, p.updated_at
+        FROM posts p LEFT JOIN users u ON u.id = p.author_id
+        WHERE p.id = $1
+        "#,
+        id
+    )
+    .fetch_optional(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    post.ok_or(ContentError::PostNotFound)
+}
+
+// The slug may belong to any translation, the one in the most preferred of `locales` is
+// returned.
+#[tracing::instrument(name = "Fetching public post", skip(transaction))]
+pub async fn fetch_public_post(
+    transaction: &mut PgConnection,
+    kind: PostKind,
+    slug: &str,
+    locales: &[String],
+) -> Result<Post, ContentError> {
+    let post = sqlx::query_as!(
+        Post,
+        r#"
+        SELECT p.id, p.kind, p.title, p.slug, p.locale, p.translation_group, p.excerpt, p.body,
+            p.status, p.author_id,
+            u.username as "author_username?", u.name as "author_name?",
+            p.meta_title, p.meta_description, p.canonical_url, p.og_image, p.noindex,
+            p.published_at, p.created_at, p.updated_at
+        FROM posts p LEFT JOIN users u ON u.id = p.author_id
+        WHERE p.kind = $1 AND p.status = 'published' AND p.published_at <= now()
+            AND p.locale = ANY($3)
+            AND p.translation_group IN (SELECT translation_group FROM posts WHERE kind = $1 AND slug = $2)
+        ORDER BY array_position($3, p.locale), p.slug = $2 DESC
+        LIMIT 1
+        "#,
+        kind.as_str(),
+        slug,
+        locales
+    )
+    .fetch_optional(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    post.ok_or(ContentError::PostNotFound)
+}
+
+// Newest first, drafts are ordered by when they were created.
+#[tracing::instrument(name = "Listing posts", skip(transaction))]
+pub async fn list_posts(
+    transaction: &mut PgConnection,
+    filter: &PostFilter,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<Post>, ContentError> {
+    let posts = sqlx::query_as!(
+        Post,
+        r#"
+        SELECT p.id, p.kind, p.title, p.slug, p.locale, p.translation_group, p.excerpt, p.body,
+            p.status, p.author_id,
+            u.username as "author_username?", u.name as "author_name?",
+            p.meta_title, p.meta_description, p.canonical_url, p.og_image, p.noindex,
+            p.published_at, p.created_at, p.updated_at
+        FROM posts p LEFT JOIN users u ON u.id = p.author_id
+        WHERE ($1::varchar IS NULL OR p.kind = $1)
+            AND (NOT $2 OR (p.status = 'published' AND p.published_at <= now()))
+            AND ($3::int[] IS NULL OR EXISTS (
+                SELECT 1 FROM post_terms pt WHERE pt.post_id = p.id AND pt.term_id = ANY($3)
+            ))
+            AND ($4::int[] IS NULL OR EXISTS (
+                SELECT 1 FROM post_terms pt WHERE pt.post_id = p.id AND pt.term_id = ANY($4)
+            ))
+            AND ($5::int IS NULL OR p.author_id = $5)
+            AND ($6::varchar IS NULL OR p.locale = $6)
+        ORDER BY coalesce(p.published_at, p.created_at) DESC, p.id DESC
+        LIMIT $7 OFFSET $8
+        "#,
+        filter.kind.map(|kind| kind.as_str()),
+        filter.public_only,
+        filter.category_ids.as_deref(),
+        filter.tag_ids.as_deref(),
+        filter.author_id,
+        filter.locale,
+        limit,
+        offset
+    )
+    .fetch_all(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    Ok(posts)
+}
+
+#[tracing::instrument(name = "Counting posts", skip(transaction))]
+pub async fn count_posts(
+    transaction: &mut PgConnection,
+    filter: &PostFilter,
+) -> Result<i64, ContentError> {
+    let row = sqlx::query!(
+        r#"
+        SELECT count(*) as "count!" FROM posts p
+        WHERE ($1::varchar IS NULL OR p.kind = $1)
+            AND (NOT $2 OR (p.status = 'published' AND p.published_at <= now()))
+            AND ($3::int[] IS NULL OR EXISTS (
+                SELECT 1 FROM post_terms pt WHERE pt.post_id = p.id AND pt.term_id = ANY($3)
+            ))
+            AND ($4::int[] IS NULL OR EXISTS (
+                SELECT 1 FROM post_terms pt WHERE pt.post_id = p.id AND pt.term_id = ANY($4)
+            ))
+            AND ($5::int IS NULL OR p.author_id = $5)
+            AND ($6::varchar IS NULL OR p.locale = $6)
+        "#,
+        filter.kind.map(|kind| kind.as_str()),
+        filter.public_only,
+        filter.category_ids.as_deref(),
+        filter.tag_ids.as_deref(),
+        filter.author_id,
+        filter.locale
+    )
+    .fetch_one(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    Ok(row.count)
+}
+
+// The other locales of a post, public listings only include the published ones.
+#[tracing::instrument(name = "Listing post translations", skip(transaction, post, config), fields(post_id = post.id))]
+pub async fn list_translations(
+    transaction: &mut PgConnection,
+    post: &Post,
+    public_only: bool,
+    config: &LocaleConfig,
+) -> Result<Vec<PostTranslation>, ContentError> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, kind, locale, title, slug FROM posts
+        WHERE translation_group = $1 AND id <> $2
+            AND (NOT $3 OR (status = 'published' AND published_at <= now()))
+        ORDER BY locale
+        "#,
+        post.translation_group,
+        post.id,
+        public_only
+    )
+    .fetch_all(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    Ok(rows
+        .into_iter()
+        .map(|row| PostTranslation {
+            path: config.localized_path(&row.locale, &PostKind::from(row.kind).path(&row.slug)),
+            id: row.id,
+            locale: row.locale,
+            title: row.title,
+            slug: row.slug,
+        })
+        .collect())
+}
+
+// Id and display name of a post author, looked up by username.
+#[tracing::instrument(name = "Fetching author", skip(transaction))]
+pub async fn fetch_author(
+    transaction: &mut PgConnection,
+    username: &str,
+) -> Result<(i32, String), ContentError> {
+    let row = sqlx::query!("SELECT id, name FROM users WHERE username = $1", username)
+        .fetch_optional(transaction)
+        .await
+        .map_err(ContentError::DatabaseError)?;
+    row.map(|row| (row.id, row.name))
+        .ok_or(ContentError::AuthorNotFound)
+}
+
+#[tracing::instrument(name = "Deleting post", skip(transaction))]
+pub async fn delete_post(transaction: &mut PgConnection, id: i32) -> Result<(), ContentError> {
+    let result = sqlx::query!("DELETE FROM posts WHERE id = $1", id)
+        .execute(transaction)
+        .await
+        .map_err(ContentError::DatabaseError)?;
+    match result.rows_affected() {
+        0 => Err(ContentError::PostNotFound),
+        _ => Ok(()),
+    }
+}
+
+// Replaces every assignment of the post, unknown terms reject the whole set.
+#[tracing::instrument(name = "Assigning post terms", skip(transaction))]
+pub async fn set_post_terms(
+    transaction: &mut PgConnection,
+    post_id: i32,
+    term_ids: &[i32],
+) -> Result<(), ContentError> {
+    let mut term_ids = term_ids.to_vec();
+    term_ids.sort_unstable();
+    term_ids.dedup();
+    let found = sqlx::query!(
+        r#"SELECT count(*) as "count!" FROM terms WHERE id = ANY($1)"#,
+        &term_ids
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    if found.count != term_ids.len() as i64 {
+        return Err(ContentError::TermNotFound);
+    }
+
+    sqlx::query!("DELETE FROM post_terms WHERE post_id = $1", post_id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(ContentError::DatabaseError)?;
+    sqlx::query!(
+        "INSERT INTO post_terms (post_id, term_id) SELECT $1, unnest($2::int[])",
+        post_id,
+        &term_ids
+    )
+    .execute(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    Ok(())
+}
+
+// Terms of several posts in one query, keyed by post id.
+#[tracing::instrument(name = "Fetching post terms", skip(transaction))]
+pub async fn terms_for_posts(
+    transaction: &mut PgConnection,
+    post_ids: &[i32],
+) -> Result<HashMap<i32, Vec<Term>>, ContentError> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT pt.post_id, t.id, t.taxonomy, t.name, t.slug, t.description, t.parent_id,
+            t.created_at, t.updated_at
+        FROM post_terms pt JOIN terms t ON t.id = pt.term_id
+        WHERE pt.post_id = ANY($1)
+        ORDER BY t.name
+        "#,
+        post_ids
+    )
+    .fetch_all(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+
+    let mut terms: HashMap<i32, Vec<Term>> = HashMap::new();
+    for row in rows {
+        terms.entry(row.post_id).or_default().push(Term {
+            id: row.id,
+            taxonomy: row.taxonomy.into(),
+            name: row.name,
+            slug: row.slug,
+            description: row.description,
+            parent_id: row.parent_id,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        });
+    }
+    Ok(terms)
+}
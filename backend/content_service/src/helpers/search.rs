@@ -0,0 +1,97 @@
+use crate::errors::content::ContentError;
+use crate::extractors::search::{SearchKind, SearchResult};
+use chrono::NaiveDate;
+use sqlx::PgConnection;
+
+#[derive(Debug, Default)]
+pub struct SearchFilter {
+    pub kind: Option<SearchKind>,
+    // Taxonomy filters only match posts, newsletters are left out when set.
+    pub category_ids: Option<Vec<i32>>,
+    pub tag_ids: Option<Vec<i32>>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+// Published posts, pages and newsletters matching the query, best match first.
+// Each document is searched with the language it was indexed in, so stemming matches.
+#[tracing::instrument(name = "Searching content", skip(transaction))]
+pub async fn search_documents(
+    transaction: &mut PgConnection,
+    query: &str,
+    filter: &SearchFilter,
+    limit: i64,
+    offset: i64,
+) -> Result<(Vec<SearchResult>, i64), ContentError> {
+    let rows = sqlx::query!(
+        r#"
+        WITH documents AS (
+            SELECT p.kind, p.id, p.slug, p.title, p.body AS content, p.search_language,
+                p.search_vector, p.published_at
+            FROM posts p
+            WHERE p.status = 'published' AND p.published_at <= now()
+                AND ($3::int[] IS NULL OR EXISTS (
+                    SELECT 1 FROM post_terms pt WHERE pt.post_id = p.id AND pt.term_id = ANY($3)
+                ))
+                AND ($4::int[] IS NULL OR EXISTS (
+                    SELECT 1 FROM post_terms pt WHERE pt.post_id = p.id AND pt.term_id = ANY($4)
+                ))
+            UNION ALL
+            SELECT 'newsletter', n.id, NULL, n.title, n.plain, n.search_language,
+                n.search_vector, n.sent_at
+            FROM newsletters n
+            WHERE $3::int[] IS NULL AND $4::int[] IS NULL
+        ), matches AS (
+            SELECT d.*, websearch_to_tsquery(d.search_language, $1) AS query
+            FROM documents d
+            WHERE ($2::varchar IS NULL OR d.kind = $2)
+                AND ($5::date IS NULL OR d.published_at >= $5::date)
+                AND ($6::date IS NULL OR d.published_at < $6::date + 1)
+        ), ranked AS (
+            SELECT m.*, ts_rank(m.search_vector, m.query) AS rank
+            FROM matches m
+            WHERE m.search_vector @@ m.query
+        )
+        SELECT kind as "kind!", id as "id!", slug, title as "title!",
+            ts_headline(
+                search_language,
+                replace(replace(replace(content, '&', '&amp;'), '<', '&lt;'), '>', '&gt;'),
+                query,
+                'StartSel=<mark>, StopSel=</mark>, MaxWords=35, MinWords=15, MaxFragments=2'
+            ) as "snippet!",
+            rank as "rank!",
+            published_at as "published_at!",
+            count(*) OVER () as "total!"
+        FROM ranked
+        ORDER BY rank DESC, published_at DESC, id DESC
+        LIMIT $7 OFFSET $8
+        "#,
+        query,
+        filter.kind.map(|kind| kind.as_str()),
+        filter.category_ids.as_deref(),
+        filter.tag_ids.as_deref(),
+        filter.from,
+        filter.to,
+        limit,
+        offset
+    )
+    .fetch_all(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+
+    // The total comes with every row, a page past the end reports none.
+    let total = rows.first().map(|row| row.total).unwrap_or_default();
+    let results = rows
+        .into_iter()
+        .map(|row| SearchResult {
+            kind: row.kind.into(),
+            id: row.id,
+            slug: row.slug,
+            title: row.title,
+            snippet: row.snippet,
+            rank: row.rank,
+            published_at: row.published_at,
+        })
+        .collect();
+    Ok((results, total))
+}
@@ -0,0 +1,234 @@
+use crate::extractors::seo::SeoMeta;
+use crate::helpers::feeds::escape_xml;
+use chrono::SecondsFormat;
+use std::fmt::Write;
+
+/// Plain text of an html fragment, cut at a word boundary to at most `max_chars` characters.
+///
+/// ```
+/// use content_service::helpers::seo::summarize;
+///
+/// assert_eq!(summarize("<p>Hello <b>big</b>\n world</p>", 100), "Hello big world");
+/// assert_eq!(summarize("<p>Tom &amp; Jerry chase each other</p>", 16), "Tom & Jerry…");
+/// ```
+pub fn summarize(html: &str, max_chars: usize) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            // Tags separate words, `a<br>b` reads as two.
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    let words: Vec<&str> = text.split_whitespace().collect();
+
+    let mut summary = String::new();
+    for word in words {
+        let length = summary.chars().count() + word.chars().count() + 1;
+        if !summary.is_empty() && length > max_chars {
+            summary.push('…');
+            return summary;
+        }
+        if !summary.is_empty() {
+            summary.push(' ');
+        }
+        summary.push_str(word);
+    }
+    summary
+}
+
+/// Source of the first image in an html fragment.
+///
+/// ```
+/// use content_service::helpers::seo::first_image;
+///
+/// assert_eq!(first_image(r#"<p>Hi</p><img alt="x" src="/media/a.png">"#), Some("/media/a.png".to_string()));
+/// assert_eq!(first_image("<p>No images</p>"), None);
+/// assert_eq!(first_image(r#"İstanbul İzmir <IMG SRC="/media/a.png">"#), Some("/media/a.png".to_string()));
+/// ```
+pub fn first_image(html: &str) -> Option<String> {
+    // Ascii lowercasing keeps the byte offsets valid for the original text.
+    let lowercase = html.to_ascii_lowercase();
+    let tag_start = lowercase.find("<img")?;
+    let tag_end = lowercase[tag_start..].find('>')? + tag_start;
+    let tag = &html[tag_start..tag_end];
+    let src_start = lowercase[tag_start..tag_end].find("src=")? + 4;
+    let value = &tag[src_start..];
+    let quote = value.chars().next()?;
+    let source = match quote {
+        '"' | '\'' => value[1..].split(quote).next()?,
+        _ => value.split(char::is_whitespace).next()?,
+    };
+    Some(source.to_string()).filter(|source| !source.is_empty())
+}
+
+/// Resolves a site relative url, absolute urls are kept as they are.
+///
+/// ```
+/// use content_service::helpers::seo::absolute_url;
+///
+/// assert_eq!(absolute_url("https://example.com", "/media/a.png"), "https://example.com/media/a.png");
+/// assert_eq!(absolute_url("https://example.com", "https://cdn.example.com/a.png"), "https://cdn.example.com/a.png");
+/// ```
+pub fn absolute_url(base_url: &str, url: &str) -> String {
+    match url.starts_with('/') && !url.starts_with("//") {
+        true => format!("{}{}", base_url, url),
+        false => url.to_string(),
+    }
+}
+
+// Title, description, canonical link, Open Graph and Twitter card tags.
+pub fn render_head(meta: &SeoMeta) -> String {
+    let mut head = String::new();
+    let title = match meta.title == meta.site_name {
+        true => meta.title.clone(),
+        false => format!("{} | {}", meta.title, meta.site_name),
+    };
+    let _ = write!(head, "<title>{}</title>", escape_xml(&title));
+    if !meta.description.is_empty() {
+        push_meta(&mut head, "name", "description", &meta.description);
+    }
+    let _ = write!(
+        head,
+        r#"<link rel="canonical" href="{}">"#,
+        escape_xml(&meta.canonical_url)
+    );
+    for (locale, url) in &meta.alternates {
+        let _ = write!(
+            head,
+            r#"<link rel="alternate" hreflang="{}" href="{}">"#,
+            escape_xml(locale),
+            escape_xml(url)
+        );
+    }
+    if meta.noindex {
+        push_meta(&mut head, "name", "robots", "noindex, nofollow");
+    }
+
+    push_meta(&mut head, "property", "og:type", meta.og_type);
+    push_meta(&mut head, "property", "og:site_name", &meta.site_name);
+    push_meta(&mut head, "property", "og:title", &meta.title);
+    if !meta.description.is_empty() {
+        push_meta(&mut head, "property", "og:description", &meta.description);
+    }
+    push_meta(&mut head, "property", "og:url", &meta.canonical_url);
+    push_meta(
+        &mut head,
+        "property",
+        "og:locale",
+        &meta.locale.replace('-', "_"),
+    );
+    if let Some(image) = &meta.image {
+        push_meta(&mut head, "property", "og:image", image);
+    }
+    if let Some(published_at) = meta.published_at {
+        let date = published_at.to_rfc3339_opts(SecondsFormat::Secs, true);
+        push_meta(&mut head, "property", "article:published_time", &date);
+    }
+    if let Some(modified_at) = meta.modified_at {
+        let date = modified_at.to_rfc3339_opts(SecondsFormat::Secs, true);
+        push_meta(&mut head, "property", "article:modified_time", &date);
+    }
+
+    let card = match meta.image {
+        Some(_) => "summary_large_image",
+        None => "summary",
+    };
+    push_meta(&mut head, "name", "twitter:card", card);
+    push_meta(&mut head, "name", "twitter:title", &meta.title);
+    if !meta.description.is_empty() {
+        push_meta(&mut head, "name", "twitter:description", &meta.description);
+    }
+    if let Some(image) = &meta.image {
+        push_meta(&mut head, "name", "twitter:image", image);
+    }
+    if let Some(site) = &meta.twitter_site {
+        push_meta(&mut head, "name", "twitter:site", site);
+    }
+    head
+}
+
+/// Puts the tags at the end of the document head, replacing the title of the template.
+///
+/// ```
+/// use content_service::helpers::seo::inject_head;
+///
+/// let html = "<html><head><title>App</title></head><body></body></html>";
+/// assert_eq!(
+///     inject_head(html, "<title>Post</title>"),
+///     "<html><head><title>Post</title></head><body></body></html>"
+/// );
+/// ```
+pub fn inject_head(html: &str, tags: &str) -> String {
+    let head_end = match html.find("</head>") {
+        Some(head_end) => head_end,
+        None => return html.to_string(),
+    };
+    let (head, rest) = html.split_at(head_end);
+    let head = match (head.find("<title>"), head.find("</title>")) {
+        (Some(start), Some(end)) if start < end => {
+            format!("{}{}", &head[..start], &head[end + "</title>".len()..])
+        }
+        _ => head.to_string(),
+    };
+    format!("{}{}{}", head, tags, rest)
+}
+
+/// Sets the language of the document on its `html` tag.
+///
+/// ```
+/// use content_service::helpers::seo::set_lang;
+///
+/// assert_eq!(set_lang(r#"<html class="h-full"><head>"#, "ne"), r#"<html lang="ne" class="h-full"><head>"#);
+/// assert_eq!(set_lang(r#"<html lang="en"><head>"#, "ne"), r#"<html lang="ne"><head>"#);
+/// assert_eq!(set_lang("<div></div>", "ne"), "<div></div>");
+/// ```
+pub fn set_lang(html: &str, locale: &str) -> String {
+    let start = match html.find("<html") {
+        Some(start) => start + "<html".len(),
+        None => return html.to_string(),
+    };
+    let end = match html[start..].find('>') {
+        Some(end) => start + end,
+        None => return html.to_string(),
+    };
+    let mut attributes = html[start..end].to_string();
+    if let Some(lang_start) = attributes.find(" lang=\"") {
+        let value_start = lang_start + " lang=\"".len();
+        let value_end = attributes[value_start..]
+            .find('"')
+            .map(|value_end| value_start + value_end)
+            .unwrap_or(attributes.len());
+        attributes.replace_range(lang_start..(value_end + 1).min(attributes.len()), "");
+    }
+    format!(
+        r#"{} lang="{}"{}{}"#,
+        &html[..start],
+        escape_xml(locale),
+        attributes,
+        &html[end..]
+    )
+}
+
+fn push_meta(head: &mut String, attribute: &str, name: &str, content: &str) {
+    let _ = write!(
+        head,
+        r#"<meta {}="{}" content="{}">"#,
+        attribute,
+        name,
+        escape_xml(content)
+    );
+}
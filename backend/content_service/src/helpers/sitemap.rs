@@ -0,0 +1,135 @@
+use crate::errors::content::ContentError;
+use crate::extractors::sitemap::{SitemapEntry, SitemapFile};
+use crate::helpers::feeds::escape_xml;
+use chrono::{DateTime, SecondsFormat, Utc};
+use sqlx::PgConnection;
+use std::fmt::Write;
+use translations::LocaleConfig;
+use utils::configuration::RobotsSettings;
+
+#[tracing::instrument(name = "Counting sitemap entries", skip(transaction))]
+pub async fn count_sitemap_entries(transaction: &mut PgConnection) -> Result<i64, ContentError> {
+    let row = sqlx::query!(r#"SELECT count(*) as "count!" FROM sitemap_entries"#)
+        .fetch_one(transaction)
+        .await
+        .map_err(ContentError::DatabaseError)?;
+    Ok(row.count)
+}
+
+#[tracing::instrument(name = "Listing sitemap entries", skip(transaction))]
+pub async fn list_sitemap_entries(
+    transaction: &mut PgConnection,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<SitemapEntry>, ContentError> {
+    sqlx::query_as!(
+        SitemapEntry,
+        r#"
+        SELECT kind as "kind!", slug as "slug!", lastmod, locale
+        FROM sitemap_entries
+        ORDER BY section, position
+        LIMIT $1 OFFSET $2
+        "#,
+        limit,
+        offset
+    )
+    .fetch_all(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)
+}
+
+// Files of `per_file` entries each, with the newest change in every file.
+#[tracing::instrument(name = "Listing sitemap files", skip(transaction))]
+pub async fn list_sitemap_files(
+    transaction: &mut PgConnection,
+    per_file: i64,
+) -> Result<Vec<SitemapFile>, ContentError> {
+    sqlx::query_as!(
+        SitemapFile,
+        r#"
+        SELECT number as "number!", max(lastmod) as lastmod
+        FROM (
+            SELECT (row_number() OVER (ORDER BY section, position) - 1) / $1 + 1 AS number, lastmod
+            FROM sitemap_entries
+        ) files
+        GROUP BY number
+        ORDER BY number
+        "#,
+        per_file
+    )
+    .fetch_all(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)
+}
+
+pub fn render_urlset(base_url: &str, config: &LocaleConfig, entries: &[SitemapEntry]) -> String {
+    let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push_str(r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#);
+    for entry in entries {
+        let _ = write!(
+            xml,
+            "<url><loc>{}</loc>{}</url>",
+            escape_xml(&format!("{}{}", base_url, entry.localized_path(config))),
+            lastmod(entry.lastmod)
+        );
+    }
+    xml.push_str("</urlset>");
+    xml
+}
+
+pub fn render_index(base_url: &str, files: &[SitemapFile]) -> String {
+    let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push_str(r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#);
+    for file in files {
+        let _ = write!(
+            xml,
+            "<sitemap><loc>{}</loc>{}</sitemap>",
+            escape_xml(&format!("{}/sitemaps/{}.xml", base_url, file.number)),
+            lastmod(file.lastmod)
+        );
+    }
+    xml.push_str("</sitemapindex>");
+    xml
+}
+
+/// Renders `robots.txt` for every crawler, pointing them at the sitemap.
+///
+/// ```
+/// use content_service::helpers::sitemap::render_robots;
+/// use utils::configuration::RobotsSettings;
+///
+/// let robots = RobotsSettings {
+///     allow_indexing: true,
+///     disallow: vec!["/api/".to_string()],
+///     extra: String::new(),
+/// };
+/// assert_eq!(
+///     render_robots("https://example.com", &robots),
+///     "User-agent: *\nDisallow: /api/\n\nSitemap: https://example.com/sitemap.xml\n"
+/// );
+/// ```
+pub fn render_robots(base_url: &str, robots: &RobotsSettings) -> String {
+    let mut text = String::from("User-agent: *\n");
+    if robots.allow_indexing {
+        for path in &robots.disallow {
+            let _ = writeln!(text, "Disallow: {}", path);
+        }
+    } else {
+        text.push_str("Disallow: /\n");
+    }
+    let _ = write!(text, "\nSitemap: {}/sitemap.xml\n", base_url);
+    if !robots.extra.trim().is_empty() {
+        let _ = write!(text, "\n{}\n", robots.extra.trim());
+    }
+    text
+}
+
+fn lastmod(date: Option<DateTime<Utc>>) -> String {
+    date.map(|date| {
+        format!(
+            "<lastmod>{}</lastmod>",
+            date.to_rfc3339_opts(SecondsFormat::Secs, true)
+        )
+    })
+    .unwrap_or_default()
+}
@@ -0,0 +1,48 @@
+use crate::errors::content::ContentError;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Lowercase, dash separated form of a title used in urls.
+///
+/// Accents are dropped from latin letters, other scripts are kept as they are.
+///
+/// ```
+/// use content_service::helpers::slug::slugify;
+///
+/// assert_eq!(slugify("Hello, World!"), "hello-world");
+/// assert_eq!(slugify("  Café  au   lait "), "cafe-au-lait");
+/// assert_eq!(slugify("नमस्ते संसार"), "नमस्ते-संसार");
+/// assert_eq!(slugify("--"), "");
+/// ```
+pub fn slugify(value: &str) -> String {
+    let mut slug = String::with_capacity(value.len());
+    let mut after_ascii = false;
+    for c in value.nfkd() {
+        if is_combining_mark(c) {
+            if !after_ascii {
+                slug.push(c);
+            }
+        } else if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+            after_ascii = c.is_ascii();
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').nfc().collect()
+}
+
+// Uses the given slug, or the fallback title when none was sent.
+pub fn resolve_slug(
+    slug: Option<&str>,
+    fallback: &str,
+    max_length: usize,
+) -> Result<String, ContentError> {
+    let slug = slugify(slug.unwrap_or(fallback));
+    let slug: String = slug.chars().take(max_length).collect();
+    let slug = slug.trim_end_matches('-');
+    match slug.is_empty() {
+        true => Err(ContentError::MissingSlug),
+        false => Ok(slug.to_string()),
+    }
+}
@@ -0,0 +1,75 @@
+// Comments scoring at least this much skip the moderation queue and go straight to spam.
+pub const SPAM_THRESHOLD: i32 = 5;
+
+const SPAM_PHRASES: [&str; 8] = [
+    "buy now",
+    "casino",
+    "click here",
+    "crypto giveaway",
+    "free money",
+    "payday loan",
+    "viagra",
+    "work from home",
+];
+
+#[derive(Debug, Default)]
+pub struct SpamSignals<'a> {
+    pub body: &'a str,
+    // Hidden form field only bots fill in.
+    pub honeypot: Option<&'a str>,
+    // Same text posted recently by the same author or address.
+    pub duplicates: i64,
+    pub max_links: usize,
+}
+
+/// Scores a comment with a few cheap heuristics, higher is more likely spam.
+///
+/// ```
+/// use content_service::helpers::spam::{spam_score, SpamSignals, SPAM_THRESHOLD};
+///
+/// let fine = SpamSignals { body: "Great read, thanks!", max_links: 2, ..Default::default() };
+/// assert!(spam_score(&fine) < SPAM_THRESHOLD);
+///
+/// let links = "Visit http://a.example http://b.example http://c.example http://d.example";
+/// let spam = SpamSignals { body: links, max_links: 2, ..Default::default() };
+/// assert!(spam_score(&spam) >= SPAM_THRESHOLD);
+/// ```
+pub fn spam_score(signals: &SpamSignals) -> i32 {
+    let mut score = 0;
+    if signals
+        .honeypot
+        .map(|value| !value.trim().is_empty())
+        .unwrap_or(false)
+    {
+        score += SPAM_THRESHOLD;
+    }
+    if signals.duplicates > 0 {
+        score += 3;
+    }
+
+    let links = count_links(signals.body);
+    if links > signals.max_links {
+        score += 1 + 2 * (links - signals.max_links) as i32;
+    }
+
+    let lowercase = signals.body.to_lowercase();
+    score += 2 * SPAM_PHRASES
+        .iter()
+        .filter(|phrase| lowercase.contains(*phrase))
+        .count() as i32;
+
+    let letters: Vec<char> = signals.body.chars().filter(|c| c.is_alphabetic()).collect();
+    let uppercase = letters.iter().filter(|c| c.is_uppercase()).count();
+    if letters.len() >= 20 && uppercase * 10 >= letters.len() * 7 {
+        score += 2;
+    }
+    score
+}
+
+fn count_links(body: &str) -> usize {
+    let lowercase = body.to_lowercase();
+    lowercase.matches("http://").count()
+        + lowercase.matches("https://").count()
+        + lowercase.matches("www.").count()
+        - lowercase.matches("://www.").count()
+}
@@ -0,0 +1,170 @@
+use crate::errors::content::ContentError;
+use crate::extractors::term::{Taxonomy, Term};
+use sqlx::PgConnection;
+
+#[tracing::instrument(name = "Inserting term", skip(transaction, term))]
+pub async fn insert_term(transaction: &mut PgConnection, term: &Term) -> Result<i32, ContentError> {
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO terms (taxonomy, name, slug, description, parent_id, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id
+        "#,
+        term.taxonomy.as_str(),
+        term.name,
+        term.slug,
+        term.description,
+        term.parent_id,
+        term.created_at,
+        term.updated_at
+    )
+    .fetch_one(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    Ok(row.id)
+}
+
+#[tracing::instrument(name = "Updating term", skip(transaction, term), fields(term_id = term.id))]
+pub async fn update_term(transaction: &mut PgConnection, term: &Term) -> Result<(), ContentError> {
+    sqlx::query!(
+        r#"
+        UPDATE terms SET name = $2, slug = $3, description = $4, parent_id = $5, updated_at = now()
+        WHERE id = $1
+        "#,
+        term.id,
+        term.name,
+        term.slug,
+        term.description,
+        term.parent_id
+    )
+    .execute(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    Ok(())
+}
+
+// Children of a deleted category move to the top level.
+#[tracing::instrument(name = "Deleting term", skip(transaction))]
+pub async fn delete_term(transaction: &mut PgConnection, id: i32) -> Result<(), ContentError> {
+    let result = sqlx::query!("DELETE FROM terms WHERE id = $1", id)
+        .execute(transaction)
+        .await
+        .map_err(ContentError::DatabaseError)?;
+    match result.rows_affected() {
+        0 => Err(ContentError::TermNotFound),
+        _ => Ok(()),
+    }
+}
+
+#[tracing::instrument(name = "Fetching term", skip(transaction))]
+pub async fn fetch_term(transaction: &mut PgConnection, id: i32) -> Result<Term, ContentError> {
+    let term = sqlx::query_as!(
+        Term,
+        r#"
+        SELECT id, taxonomy, name, slug, description, parent_id, created_at, updated_at
+        FROM terms WHERE id = $1
+        "#,
+        id
+    )
+    .fetch_optional(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    term.ok_or(ContentError::TermNotFound)
+}
+
+#[tracing::instrument(name = "Fetching term by slug", skip(transaction))]
+pub async fn fetch_term_by_slug(
+    transaction: &mut PgConnection,
+    taxonomy: Taxonomy,
+    slug: &str,
+) -> Result<Term, ContentError> {
+    let term = sqlx::query_as!(
+        Term,
+        r#"
+        SELECT id, taxonomy, name, slug, description, parent_id, created_at, updated_at
+        FROM terms WHERE taxonomy = $1 AND slug = $2
+        "#,
+        taxonomy.as_str(),
+        slug
+    )
+    .fetch_optional(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    term.ok_or(ContentError::TermNotFound)
+}
+
+#[tracing::instrument(name = "Listing terms", skip(transaction))]
+pub async fn list_terms(
+    transaction: &mut PgConnection,
+    taxonomy: Taxonomy,
+) -> Result<Vec<Term>, ContentError> {
+    let terms = sqlx::query_as!(
+        Term,
+        r#"
+        SELECT id, taxonomy, name, slug, description, parent_id, created_at, updated_at
+        FROM terms WHERE taxonomy = $1 ORDER BY name
+        "#,
+        taxonomy.as_str()
+    )
+    .fetch_all(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    Ok(terms)
+}
+
+// Tags starting with the typed text, the most used ones first.
+#[tracing::instrument(name = "Suggesting tags", skip(transaction))]
+pub async fn suggest_tags(
+    transaction: &mut PgConnection,
+    prefix: &str,
+    limit: i64,
+) -> Result<Vec<Term>, ContentError> {
+    let pattern = format!(
+        "{}%",
+        prefix
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    );
+    let terms = sqlx::query_as!(
+        Term,
+        r#"
+        SELECT t.id, t.taxonomy, t.name, t.slug, t.description, t.parent_id, t.created_at,
+            t.updated_at
+        FROM terms t LEFT JOIN post_terms pt ON pt.term_id = t.id
+        WHERE t.taxonomy = 'tag' AND (t.name ILIKE $1 OR t.slug ILIKE $1)
+        GROUP BY t.id
+        ORDER BY count(pt.post_id) DESC, t.name
+        LIMIT $2
+        "#,
+        pattern,
+        limit
+    )
+    .fetch_all(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    Ok(terms)
+}
+
+// The term itself and every category nested below it.
+#[tracing::instrument(name = "Fetching term descendants", skip(transaction))]
+pub async fn term_descendants(
+    transaction: &mut PgConnection,
+    id: i32,
+) -> Result<Vec<i32>, ContentError> {
+    let rows = sqlx::query!(
+        r#"
+        WITH RECURSIVE tree AS (
+            SELECT id FROM terms WHERE id = $1
+            UNION
+            SELECT t.id FROM terms t JOIN tree ON t.parent_id = tree.id
+        )
+        SELECT id as "id!" FROM tree
+        "#,
+        id
+    )
+    .fetch_all(transaction)
+    .await
+    .map_err(ContentError::DatabaseError)?;
+    Ok(rows.into_iter().map(|row| row.id).collect())
+}
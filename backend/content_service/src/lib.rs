@@ -0,0 +1,8 @@
+pub mod errors;
+pub mod extractors;
+mod handlers;
+pub mod helpers;
+pub mod middleware;
+pub mod payload;
+pub mod router;
+pub mod user_data;
@@ -0,0 +1,89 @@
+use crate::errors::content::ContentError;
+use crate::extractors::post::PostKind;
+use crate::extractors::seo::SeoMeta;
+use crate::helpers::posts::{fetch_public_post, list_translations};
+use crate::helpers::seo::{inject_head, render_head, set_lang};
+use axum::body::{to_bytes, Body};
+use axum::extract::{Request, State};
+use axum::http::header::{CONTENT_LENGTH, CONTENT_TYPE};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use utils::state::AppState;
+
+// Server rendered pages are small, anything larger is passed through untouched.
+const MAX_PAGE_SIZE: usize = 10 * 1024 * 1024;
+
+// Crawlers and link unfurlers read the head without running the wasm app,
+// so the tags of the requested entry are written into the server rendered page.
+pub async fn seo_head(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let path = request.uri().path().to_string();
+    let response = next.run(request).await;
+    let is_html = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.starts_with("text/html"))
+        .unwrap_or(false);
+    if !is_html {
+        return response;
+    }
+
+    let meta = match page_meta(&state, &path).await {
+        Ok(meta) => meta,
+        Err(err) => {
+            tracing::error!("Unable to load page metadata: {:?}", err);
+            SeoMeta::for_site(&state.settings, &path)
+        }
+    };
+    let (mut parts, body) = response.into_parts();
+    let html = match to_bytes(body, MAX_PAGE_SIZE).await {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        Err(err) => {
+            tracing::error!("Unable to read rendered page: {:?}", err);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    // The template is written in the default locale.
+    let html = match meta.locale == state.settings.i18n.default_locale {
+        true => html,
+        false => set_lang(&html, &meta.locale),
+    };
+    parts.headers.remove(CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(inject_head(&html, &render_head(&meta))))
+}
+
+// Pages without a locale prefix are in the default locale.
+async fn page_meta(state: &AppState, path: &str) -> Result<SeoMeta, ContentError> {
+    let config = &state.settings.i18n;
+    let (locale, unprefixed) = config.strip_locale(path);
+    let locale = locale.unwrap_or_else(|| config.default_locale.clone());
+    let entry = match unprefixed
+        .trim_end_matches('/')
+        .split('/')
+        .collect::<Vec<_>>()[..]
+    {
+        ["", "posts", slug] => Some((PostKind::Post, slug)),
+        ["", "pages", slug] => Some((PostKind::Page, slug)),
+        _ => None,
+    };
+    let (kind, slug) = match entry {
+        Some(entry) => entry,
+        None => return Ok(SeoMeta::for_locale(&state.settings, path, &locale)),
+    };
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(ContentError::Pool)?;
+    let locales = config.fallback_chain(&locale);
+    match fetch_public_post(&mut connection, kind, slug, &locales).await {
+        Ok(post) => {
+            let translations = list_translations(&mut connection, &post, true, config).await?;
+            Ok(SeoMeta::for_post(&state.settings, &post, &translations))
+        }
+        Err(ContentError::PostNotFound) => {
+            let mut meta = SeoMeta::for_locale(&state.settings, path, &locale);
+            meta.noindex = true;
+            Ok(meta)
+        }
+        Err(err) => Err(err),
+    }
+}
@@ -0,0 +1,403 @@
+use crate::extractors::comment::CommentStatus;
+use crate::extractors::content_type::FieldDefinition;
+use crate::extractors::menu::LinkType;
+use crate::extractors::post::{PostKind, PostStatus};
+use crate::extractors::search::SearchKind;
+use crate::extractors::term::Taxonomy;
+use chrono::{DateTime, NaiveDate, Utc};
+use rustrict::CensorStr;
+use serde::{Deserialize, Deserializer};
+use serde_json::{Map, Value};
+use validator::{Validate, ValidationError};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct PostPayload {
+    #[serde(default)]
+    pub kind: PostKind,
+    #[validate(length(
+        min = 1,
+        max = 255,
+        message = "Title must contain between 1 and 255 characters"
+    ))]
+    pub title: String,
+    #[validate(length(max = 255, message = "Slug must contain at most 255 characters"))]
+    pub slug: Option<String>,
+    #[validate(length(max = 2000, message = "Excerpt must contain at most 2000 characters"))]
+    pub excerpt: Option<String>,
+    pub body: Option<String>,
+    #[serde(default)]
+    pub status: PostStatus,
+    // Set to schedule a post, otherwise the first publish sets it.
+    pub published_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub term_ids: Vec<i32>,
+    #[validate(length(max = 255, message = "Meta title must contain at most 255 characters"))]
+    pub meta_title: Option<String>,
+    #[validate(length(
+        max = 500,
+        message = "Meta description must contain at most 500 characters"
+    ))]
+    pub meta_description: Option<String>,
+    #[validate(url(message = "Canonical url must be an absolute url"))]
+    pub canonical_url: Option<String>,
+    #[validate(length(max = 2000, message = "Image url must contain at most 2000 characters"))]
+    pub og_image: Option<String>,
+    #[serde(default)]
+    pub noindex: bool,
+    // The default locale when missing.
+    pub locale: Option<String>,
+    // Id of the post this one translates.
+    pub translation_of: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdatePostPayload {
+    pub kind: Option<PostKind>,
+    #[validate(length(
+        min = 1,
+        max = 255,
+        message = "Title must contain between 1 and 255 characters"
+    ))]
+    pub title: Option<String>,
+    #[validate(length(max = 255, message = "Slug must contain at most 255 characters"))]
+    pub slug: Option<String>,
+    #[validate(length(max = 2000, message = "Excerpt must contain at most 2000 characters"))]
+    pub excerpt: Option<String>,
+    pub body: Option<String>,
+    pub status: Option<PostStatus>,
+    pub published_at: Option<DateTime<Utc>>,
+    // Replaces the assigned terms when present.
+    pub term_ids: Option<Vec<i32>>,
+    // Missing keeps the value, `null` falls back to the default again.
+    #[serde(default, deserialize_with = "present")]
+    #[validate(length(max = 255, message = "Meta title must contain at most 255 characters"))]
+    pub meta_title: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    #[validate(length(
+        max = 500,
+        message = "Meta description must contain at most 500 characters"
+    ))]
+    pub meta_description: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    #[validate(url(message = "Canonical url must be an absolute url"))]
+    pub canonical_url: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    #[validate(length(max = 2000, message = "Image url must contain at most 2000 characters"))]
+    pub og_image: Option<Option<String>>,
+    pub noindex: Option<bool>,
+    pub locale: Option<String>,
+    // Moves the post into the translations of another post.
+    pub translation_of: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct TermPayload {
+    pub taxonomy: Taxonomy,
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Name must contain between 1 and 100 characters"
+    ))]
+    pub name: String,
+    #[validate(length(max = 100, message = "Slug must contain at most 100 characters"))]
+    pub slug: Option<String>,
+    #[validate(length(
+        max = 2000,
+        message = "Description must contain at most 2000 characters"
+    ))]
+    pub description: Option<String>,
+    pub parent_id: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateTermPayload {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Name must contain between 1 and 100 characters"
+    ))]
+    pub name: Option<String>,
+    #[validate(length(max = 100, message = "Slug must contain at most 100 characters"))]
+    pub slug: Option<String>,
+    #[validate(length(
+        max = 2000,
+        message = "Description must contain at most 2000 characters"
+    ))]
+    pub description: Option<String>,
+    // Missing keeps the parent, `null` moves the category to the top level.
+    #[serde(default, deserialize_with = "present")]
+    pub parent_id: Option<Option<i32>>,
+}
+
+fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::deserialize(deserializer).map(Some)
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct PostListQuery {
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+    pub kind: Option<PostKind>,
+    pub category: Option<String>,
+    pub tag: Option<String>,
+    // Public listings default to the locale the client prefers.
+    pub locale: Option<String>,
+}
+
+impl PostListQuery {
+    pub fn per_page(&self) -> i64 {
+        self.per_page.unwrap_or(10).clamp(1, 100)
+    }
+
+    pub fn page(&self) -> i64 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn offset(&self) -> i64 {
+        (self.page() - 1) * self.per_page()
+    }
+}
+
+// Public posts and pages fall back to other locales when missing in this one.
+#[derive(Debug, Deserialize, Default)]
+pub struct LocaleQuery {
+    pub locale: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SuggestQuery {
+    #[serde(default)]
+    pub q: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct SearchQuery {
+    #[serde(default)]
+    pub q: String,
+    pub kind: Option<SearchKind>,
+    pub category: Option<String>,
+    pub tag: Option<String>,
+    // Inclusive range of publish dates.
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+impl SearchQuery {
+    pub fn per_page(&self) -> i64 {
+        self.per_page.unwrap_or(10).clamp(1, 50)
+    }
+
+    pub fn page(&self) -> i64 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn offset(&self) -> i64 {
+        (self.page() - 1) * self.per_page()
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CommentPayload {
+    #[validate(
+        length(
+            min = 1,
+            max = 5000,
+            message = "Comment must contain between 1 and 5000 characters"
+        ),
+        custom(
+            function = "validate_profanity",
+            message = "Comment contains inappropriate words."
+        )
+    )]
+    pub body: String,
+    pub parent_id: Option<i32>,
+    // Only read for guests, signed in users comment under their own name.
+    #[validate(
+        length(min = 1, max = 255, message = "Name cannot be empty"),
+        custom(
+            function = "validate_profanity",
+            message = "Name contains inappropriate words."
+        )
+    )]
+    pub name: Option<String>,
+    #[validate(length(min = 1, max = 255, message = "Email cannot be empty"), email)]
+    pub email: Option<String>,
+    // Left empty by people, the field is hidden from them.
+    pub website: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommentTokenQuery {
+    pub token: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct CommentListQuery {
+    pub status: Option<CommentStatus>,
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+impl CommentListQuery {
+    pub fn per_page(&self) -> i64 {
+        self.per_page.unwrap_or(20).clamp(1, 100)
+    }
+
+    pub fn page(&self) -> i64 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn offset(&self) -> i64 {
+        (self.page() - 1) * self.per_page()
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ModerateCommentPayload {
+    pub status: CommentStatus,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ContentTypePayload {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Name must contain between 1 and 100 characters"
+    ))]
+    pub name: String,
+    #[validate(length(max = 100, message = "Slug must contain at most 100 characters"))]
+    pub slug: Option<String>,
+    #[validate(length(
+        max = 2000,
+        message = "Description must contain at most 2000 characters"
+    ))]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub fields: Vec<FieldDefinition>,
+}
+
+// The slug is fixed once created since it is part of every entry url.
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateContentTypePayload {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Name must contain between 1 and 100 characters"
+    ))]
+    pub name: Option<String>,
+    #[validate(length(
+        max = 2000,
+        message = "Description must contain at most 2000 characters"
+    ))]
+    pub description: Option<String>,
+    // Replaces every field definition when present.
+    pub fields: Option<Vec<FieldDefinition>>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct EntryPayload {
+    #[validate(length(
+        min = 1,
+        max = 255,
+        message = "Title must contain between 1 and 255 characters"
+    ))]
+    pub title: String,
+    #[validate(length(max = 255, message = "Slug must contain at most 255 characters"))]
+    pub slug: Option<String>,
+    #[serde(default)]
+    pub status: PostStatus,
+    pub published_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub data: Map<String, Value>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateEntryPayload {
+    #[validate(length(
+        min = 1,
+        max = 255,
+        message = "Title must contain between 1 and 255 characters"
+    ))]
+    pub title: Option<String>,
+    #[validate(length(max = 255, message = "Slug must contain at most 255 characters"))]
+    pub slug: Option<String>,
+    pub status: Option<PostStatus>,
+    pub published_at: Option<DateTime<Utc>>,
+    // Only the sent fields change, `null` clears a field.
+    pub data: Option<Map<String, Value>>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct EntryListQuery {
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+impl EntryListQuery {
+    pub fn per_page(&self) -> i64 {
+        self.per_page.unwrap_or(10).clamp(1, 100)
+    }
+
+    pub fn page(&self) -> i64 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn offset(&self) -> i64 {
+        (self.page() - 1) * self.per_page()
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct MenuPayload {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Name must contain between 1 and 100 characters"
+    ))]
+    pub name: String,
+    #[validate(length(max = 100, message = "Slug must contain at most 100 characters"))]
+    pub slug: Option<String>,
+    // Items can be sent along, otherwise the menu starts empty.
+    #[serde(default)]
+    pub items: Vec<MenuItemPayload>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateMenuPayload {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Name must contain between 1 and 100 characters"
+    ))]
+    pub name: Option<String>,
+    // Replaces every item, in the order sent, when present.
+    pub items: Option<Vec<MenuItemPayload>>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct MenuItemPayload {
+    #[serde(default)]
+    pub label: String,
+    pub link_type: LinkType,
+    // Post or term id, ignored for urls.
+    pub target_id: Option<i32>,
+    pub url: Option<String>,
+    #[serde(default)]
+    pub open_in_new_tab: bool,
+    #[serde(default)]
+    pub children: Vec<MenuItemPayload>,
+}
+
+fn validate_profanity(value: &str) -> Result<(), ValidationError> {
+    if value.is_inappropriate() {
+        return Err(ValidationError::new("profanity"));
+    }
+
+    Ok(())
+}
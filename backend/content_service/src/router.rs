@@ -0,0 +1,97 @@
+use crate::handlers::comments::{
+    add_comment, confirm_comment, moderate_comment, moderation_queue, post_comments, remove_comment,
+};
+use crate::handlers::content_types::{
+    add_content_type, add_entry, edit_content_type, edit_entry, list_published_entries,
+    manage_content_type, manage_content_types, manage_entries, manage_entry, published_entry,
+    remove_content_type, remove_entry,
+};
+use crate::handlers::feeds::{author_feed, category_feed, site_feed, tag_feed};
+use crate::handlers::menus::{
+    add_menu, edit_menu, manage_menu, manage_menus, published_menu, remove_menu,
+};
+use crate::handlers::posts::{
+    add_post, edit_post, list_published_posts, manage_post, manage_posts, published_page,
+    published_post, remove_post,
+};
+use crate::handlers::search::search;
+use crate::handlers::sitemap::{robots, sitemap, sitemap_file};
+use crate::handlers::terms::{
+    add_term, category_posts, edit_term, list_categories, list_tags, remove_term, tag_posts,
+    tag_suggestions,
+};
+use axum::routing::{get, patch, post, Router};
+use utils::state::AppState;
+
+pub fn create_router() -> Router<AppState> {
+    Router::new()
+        .route("/posts", get(list_published_posts))
+        .route("/posts/:slug", get(published_post))
+        .route(
+            "/posts/:slug/comments",
+            get(post_comments).post(add_comment),
+        )
+        .route("/comments/confirm", get(confirm_comment))
+        .route("/pages/:slug", get(published_page))
+        .route("/search", get(search))
+        .route("/menus/:slug", get(published_menu))
+        .route("/types/:slug/entries", get(list_published_entries))
+        .route("/types/:slug/entries/:entry_slug", get(published_entry))
+        .route("/categories", get(list_categories))
+        .route("/categories/:slug/posts", get(category_posts))
+        .route("/tags", get(list_tags))
+        .route("/tags/:slug/posts", get(tag_posts))
+        .route("/manage/posts", get(manage_posts).post(add_post))
+        .route(
+            "/manage/posts/:id",
+            get(manage_post).patch(edit_post).delete(remove_post),
+        )
+        .route("/manage/terms", post(add_term))
+        .route("/manage/terms/:id", patch(edit_term).delete(remove_term))
+        .route("/manage/tags/suggest", get(tag_suggestions))
+        .route(
+            "/manage/types",
+            get(manage_content_types).post(add_content_type),
+        )
+        .route(
+            "/manage/types/:slug",
+            get(manage_content_type)
+                .patch(edit_content_type)
+                .delete(remove_content_type),
+        )
+        .route(
+            "/manage/types/:slug/entries",
+            get(manage_entries).post(add_entry),
+        )
+        .route(
+            "/manage/types/:slug/entries/:id",
+            get(manage_entry).patch(edit_entry).delete(remove_entry),
+        )
+        .route("/manage/menus", get(manage_menus).post(add_menu))
+        .route(
+            "/manage/menus/:slug",
+            get(manage_menu).patch(edit_menu).delete(remove_menu),
+        )
+        .route("/manage/comments", get(moderation_queue))
+        .route(
+            "/manage/comments/:id",
+            patch(moderate_comment).delete(remove_comment),
+        )
+}
+
+// Mounted outside the api so feed readers keep stable urls.
+pub fn create_feed_router() -> Router<AppState> {
+    Router::new()
+        .route("/:file_name", get(site_feed))
+        .route("/category/:slug/:file_name", get(category_feed))
+        .route("/tag/:slug/:file_name", get(tag_feed))
+        .route("/author/:username/:file_name", get(author_feed))
+}
+
+// Crawlers expect these at the site root.
+pub fn create_sitemap_router() -> Router<AppState> {
+    Router::new()
+        .route("/sitemap.xml", get(sitemap))
+        .route("/sitemaps/:file_name", get(sitemap_file))
+        .route("/robots.txt", get(robots))
+}
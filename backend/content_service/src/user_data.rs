@@ -0,0 +1,148 @@
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use sqlx::PgConnection;
+use utils::user_data::{UserDataOwner, UserDataSource};
+
+// Shown in place of the author and body of a comment whose account is gone.
+pub const DELETED_COMMENT: &str = "[deleted]";
+
+// Comments written while signed in, and guest comments left with the account's email address.
+pub struct CommentData;
+
+#[async_trait]
+impl UserDataSource for CommentData {
+    fn name(&self) -> &'static str {
+        "comments"
+    }
+
+    #[tracing::instrument(name = "Exporting comments", skip(self, connection))]
+    async fn export(
+        &self,
+        connection: &mut PgConnection,
+        owner: &UserDataOwner,
+    ) -> Result<Value, sqlx::Error> {
+        let comments = sqlx::query!(
+            r#"SELECT c.id, c.post_id, p.title as post_title, c.parent_id, c.body, c.status,
+                c.created_at
+            FROM comments c JOIN posts p ON p.id = c.post_id
+            WHERE c.user_id = $1 OR lower(c.guest_email) = lower($2)
+            ORDER BY c.created_at"#,
+            owner.id,
+            owner.email
+        )
+        .fetch_all(connection)
+        .await?;
+
+        Ok(comments
+            .into_iter()
+            .map(|c| {
+                json!({
+                    "id": c.id,
+                    "post_id": c.post_id,
+                    "post_title": c.post_title,
+                    "parent_id": c.parent_id,
+                    "body": c.body,
+                    "status": c.status,
+                    "created_at": c.created_at,
+                })
+            })
+            .collect())
+    }
+
+    #[tracing::instrument(name = "Purging comments", skip(self, connection))]
+    async fn purge(
+        &self,
+        connection: &mut PgConnection,
+        owner: &UserDataOwner,
+    ) -> Result<(), sqlx::Error> {
+        // Replies by others hang off the comment, so it stays as a marker without the author.
+        sqlx::query!(
+            r#"UPDATE comments c
+            SET user_id = NULL, guest_name = $3, guest_email = NULL, body = $4, ip_address = '',
+                confirmation_hash = NULL, deleted_at = now(), updated_at = now()
+            WHERE (c.user_id = $1 OR lower(c.guest_email) = lower($2))
+                AND EXISTS (SELECT 1 FROM comments r WHERE r.parent_id = c.id)"#,
+            owner.id,
+            owner.email,
+            DELETED_COMMENT,
+            DELETED_COMMENT
+        )
+        .execute(&mut *connection)
+        .await?;
+        sqlx::query!(
+            r#"DELETE FROM comments WHERE user_id = $1 OR lower(guest_email) = lower($2)"#,
+            owner.id,
+            owner.email
+        )
+        .execute(connection)
+        .await?;
+        Ok(())
+    }
+}
+
+// Posts and pages the account wrote. Published ones are part of the site and stay without an
+// author, unpublished drafts go with the account.
+pub struct PostData;
+
+#[async_trait]
+impl UserDataSource for PostData {
+    fn name(&self) -> &'static str {
+        "posts"
+    }
+
+    #[tracing::instrument(name = "Exporting posts", skip(self, connection))]
+    async fn export(
+        &self,
+        connection: &mut PgConnection,
+        owner: &UserDataOwner,
+    ) -> Result<Value, sqlx::Error> {
+        let posts = sqlx::query!(
+            r#"SELECT id, kind, title, slug, locale, excerpt, body, status, published_at,
+                created_at, updated_at
+            FROM posts WHERE author_id = $1 ORDER BY created_at"#,
+            owner.id
+        )
+        .fetch_all(connection)
+        .await?;
+
+        Ok(posts
+            .into_iter()
+            .map(|p| {
+                json!({
+                    "id": p.id,
+                    "kind": p.kind,
+                    "title": p.title,
+                    "slug": p.slug,
+                    "locale": p.locale,
+                    "excerpt": p.excerpt,
+                    "body": p.body,
+                    "status": p.status,
+                    "published_at": p.published_at,
+                    "created_at": p.created_at,
+                    "updated_at": p.updated_at,
+                })
+            })
+            .collect())
+    }
+
+    #[tracing::instrument(name = "Purging posts", skip(self, connection))]
+    async fn purge(
+        &self,
+        connection: &mut PgConnection,
+        owner: &UserDataOwner,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"DELETE FROM posts WHERE author_id = $1 AND status = 'draft'"#,
+            owner.id
+        )
+        .execute(&mut *connection)
+        .await?;
+        sqlx::query!(
+            r#"UPDATE posts SET author_id = NULL WHERE author_id = $1"#,
+            owner.id
+        )
+        .execute(connection)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,260 @@
+use axum::http::header::RETRY_AFTER;
+use axum::http::{Method, StatusCode};
+use axum::response::Response;
+use axum::Router;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::sync::mpsc::Receiver;
+use tower::ServiceExt;
+use utils::configuration::{RunMode, Settings};
+use utils::email::get_link;
+use utils::state::BackgroundTask;
+use utils::test;
+
+mod common;
+
+#[sqlx::test(migrations = false)]
+async fn verified_users_comment_in_threads(pool: PgPool) {
+    let settings = Settings::get_config(RunMode::Test).unwrap();
+    let (email_rx, task_rx, app) = common::setup_app_with_email(pool.clone(), settings).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let author = common::admin_token_fixture(&mut conn).await;
+    let reader = common::verified_user_fixture(&mut conn).await;
+    let reader_token = common::session_fixture(&mut conn, reader.id).await;
+    post_fixture(&app, &author).await;
+
+    let response = comment(&app, &reader_token, json!({"body": "Lovely write up"})).await;
+    assert_eq!(response["status"], json!("approved"));
+    let parent_id = response["id"].as_i64().unwrap();
+    finish_tasks(&task_rx).await;
+    let notice = email_rx.try_recv().unwrap();
+    assert_eq!(notice.subject, "New comment on Hello world");
+    assert!(notice.plain.contains("Lovely write up"));
+    assert!(notice.plain.contains("/posts/hello-world"));
+
+    // The author replying to their own post is not notified.
+    let reply = comment(
+        &app,
+        &author,
+        json!({"body": "Thank you!", "parent_id": parent_id}),
+    )
+    .await;
+    assert_eq!(reply["status"], json!("approved"));
+    finish_tasks(&task_rx).await;
+    assert!(email_rx.try_recv().is_err());
+
+    let response = common::get(&app, "/api/content/posts/hello-world/comments", None).await;
+    let thread = common::response_json(response).await;
+    assert_eq!(thread["total"], json!(2));
+    assert_eq!(thread["comments"][0]["author_name"], json!(reader.name));
+    assert_eq!(
+        thread["comments"][0]["replies"][0]["body"],
+        json!("Thank you!")
+    );
+
+    let response = common::send(
+        &app,
+        Method::POST,
+        "/api/content/posts/hello-world/comments",
+        &reader_token,
+        &json!({"body": "Hello", "parent_id": parent_id + 100}),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = common::response_json(response).await;
+    assert_eq!(
+        body["details"]["parent_id"][0]["code"],
+        json!("invalid_reply")
+    );
+
+    let response = common::send(
+        &app,
+        Method::POST,
+        "/api/content/posts/hello-world/comments",
+        &reader_token,
+        &json!({"body": "What the fuck"}),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = common::response_json(response).await;
+    assert_eq!(body["details"]["body"][0]["code"], json!("profanity"));
+}
+
+#[sqlx::test(migrations = false)]
+async fn guest_comments_are_confirmed_then_moderated(pool: PgPool) {
+    let settings = Settings::get_config(RunMode::Test).unwrap();
+    let (email_rx, task_rx, app) = common::setup_app_with_email(pool.clone(), settings).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let admin = common::admin_token_fixture(&mut conn).await;
+    post_fixture(&app, &admin).await;
+
+    let response = guest_comment(&app, json!({"body": "Nice", "name": "Guest"})).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = common::response_json(response).await;
+    assert_eq!(body["details"]["email"][0]["code"], json!("required"));
+
+    let data = json!({"body": "Nice post", "name": "Guest", "email": "guest@example.com"});
+    let response = guest_comment(&app, data).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let created = common::response_json(response).await;
+    assert_eq!(created["status"], json!("unconfirmed"));
+    finish_tasks(&task_rx).await;
+    let email = email_rx.try_recv().unwrap();
+    assert_eq!(email.to[0].email, "guest@example.com");
+    let link = get_link(&email.plain);
+    let confirm_url = &link[link.find("/api/").unwrap()..];
+
+    let response = common::get(&app, confirm_url, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = common::get(&app, confirm_url, None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(thread_total(&app).await, 0);
+
+    // Bots fill the hidden website field, their comments skip the queue.
+    let data = json!({"body": "Hi", "name": "Bot", "email": "bot@example.com", "website": "x"});
+    let response = guest_comment(&app, data).await;
+    let spam = common::response_json(response).await;
+    assert_eq!(spam["status"], json!("pending"));
+    finish_tasks(&task_rx).await;
+    assert!(email_rx.try_recv().is_err());
+
+    let response = common::get(&app, "/api/content/manage/comments", Some(&admin)).await;
+    let queue = common::response_json(response).await;
+    assert_eq!(queue["total"], json!(1));
+    assert_eq!(queue["comments"][0]["id"], created["id"]);
+    assert_eq!(
+        queue["comments"][0]["guest_email"],
+        json!("guest@example.com")
+    );
+    let response = common::get(
+        &app,
+        "/api/content/manage/comments?status=spam",
+        Some(&admin),
+    )
+    .await;
+    let queue = common::response_json(response).await;
+    assert_eq!(queue["comments"][0]["id"], spam["id"]);
+
+    let url = format!("/api/content/manage/comments/{}", created["id"]);
+    let response = common::send(
+        &app,
+        Method::PATCH,
+        &url,
+        &admin,
+        &json!({"status": "approved"}),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(thread_total(&app).await, 1);
+    finish_tasks(&task_rx).await;
+    let notice = email_rx.try_recv().unwrap();
+    assert_eq!(notice.subject, "New comment on Hello world");
+
+    let response = common::send(
+        &app,
+        Method::PATCH,
+        &url,
+        &admin,
+        &json!({"status": "spam"}),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(thread_total(&app).await, 0);
+}
+
+#[sqlx::test(migrations = false)]
+async fn comments_are_rate_limited_and_guests_can_be_refused(pool: PgPool) {
+    let mut settings = Settings::get_config(RunMode::Test).unwrap();
+    settings.comments.per_hour = 2;
+    settings.comments.allow_guests = false;
+    let (_, _, app) = common::setup_app_with_email(pool.clone(), settings).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let admin = common::admin_token_fixture(&mut conn).await;
+    post_fixture(&app, &admin).await;
+
+    let data = json!({"body": "Nice post", "name": "Guest", "email": "guest@example.com"});
+    let response = guest_comment(&app, data).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    for body in ["First", "Second"] {
+        comment(&app, &admin, json!({"body": body})).await;
+    }
+    let response = common::send(
+        &app,
+        Method::POST,
+        "/api/content/posts/hello-world/comments",
+        &admin,
+        &json!({"body": "Third"}),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(response.headers().contains_key(RETRY_AFTER));
+}
+
+#[sqlx::test(migrations = false)]
+async fn guests_without_an_address_are_rate_limited_by_email(pool: PgPool) {
+    let mut settings = Settings::get_config(RunMode::Test).unwrap();
+    settings.comments.per_hour = 2;
+    let (email_rx, task_rx, app) = common::setup_app_with_email(pool.clone(), settings).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let admin = common::admin_token_fixture(&mut conn).await;
+    post_fixture(&app, &admin).await;
+
+    for (body, email) in [
+        ("First", "guest@example.com"),
+        ("Second", "Guest@Example.com"),
+    ] {
+        let data = json!({"body": body, "name": "Guest", "email": email});
+        let response = guest_comment(&app, data).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+    let data = json!({"body": "Third", "name": "Guest", "email": "GUEST@example.com"});
+    let response = guest_comment(&app, data).await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    finish_tasks(&task_rx).await;
+    assert_eq!(email_rx.try_iter().count(), 2);
+
+    let data = json!({"body": "Fourth", "name": "Other", "email": "other@example.com"});
+    let response = guest_comment(&app, data).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+}
+
+async fn post_fixture(app: &Router, token: &str) {
+    let data = json!({"title": "Hello world", "status": "published"});
+    common::post_fixture(app, token, data).await;
+}
+
+async fn comment(app: &Router, token: &str, data: Value) -> Value {
+    let response = common::send(
+        app,
+        Method::POST,
+        "/api/content/posts/hello-world/comments",
+        token,
+        &data,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    common::response_json(response).await
+}
+
+async fn guest_comment(app: &Router, data: Value) -> Response {
+    let request = test::build_request(
+        "/api/content/posts/hello-world/comments",
+        Method::POST,
+        &data,
+    );
+    app.clone().oneshot(request).await.unwrap()
+}
+
+async fn thread_total(app: &Router) -> i64 {
+    let response = common::get(app, "/api/content/posts/hello-world/comments", None).await;
+    common::response_json(response).await["total"]
+        .as_i64()
+        .unwrap()
+}
+
+async fn finish_tasks(task_rx: &Receiver<BackgroundTask>) {
+    while let Ok(task) = task_rx.try_recv() {
+        task.handle.await.unwrap();
+    }
+}
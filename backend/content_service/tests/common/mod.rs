@@ -0,0 +1,162 @@
+use auth_service::extractors::user::User;
+use auth_service::helpers::confirmation::mark_user_as_confirmed;
+use auth_service::helpers::sessions::create_new_session;
+use auth_service::helpers::user::insert_user;
+use auth_service::payload::RegisterPayload;
+use axum::body::Body;
+use axum::http::header::AUTHORIZATION;
+use axum::http::{Method, Request};
+use axum::response::Response;
+use axum::Router;
+use content_service::router::{create_feed_router, create_router, create_sitemap_router};
+use email_clients::email::EmailObject;
+use fake::faker::internet::en::SafeEmail;
+use fake::faker::name::en::Name;
+use fake::Fake;
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::{PgConnection, PgPool};
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+use tower::ServiceExt;
+use utils::configuration::{PasswordHashSettings, Settings};
+use utils::state::{AppState, BackgroundTask};
+use utils::test;
+use uuid::Uuid;
+
+pub static STRONG_PASSWORD: &str = "r0sebudmaelstrom11/20/91aaaa";
+
+// Posts reference their authors, search reads the newsletter archive and entries point at
+// media, so those run first.
+pub async fn migrate(pool: &PgPool) {
+    let mut auth = sqlx::migrate!("../auth_service/migrations");
+    auth.set_ignore_missing(true);
+    auth.run(pool).await.expect("Auth migrations failed");
+    let mut subscriptions = sqlx::migrate!("../subscription_service/migrations");
+    subscriptions.set_ignore_missing(true);
+    subscriptions
+        .run(pool)
+        .await
+        .expect("Subscription migrations failed");
+    let mut media = sqlx::migrate!("../media_service/migrations");
+    media.set_ignore_missing(true);
+    media.run(pool).await.expect("Media migrations failed");
+    let mut content = sqlx::migrate!();
+    content.set_ignore_missing(true);
+    content.run(pool).await.expect("Content migrations failed");
+}
+
+#[allow(dead_code)]
+pub async fn setup_app(pool: PgPool) -> Router {
+    setup_app_with_settings(pool, None).await
+}
+
+#[allow(dead_code)]
+pub async fn setup_app_with_settings(pool: PgPool, settings: Option<Settings>) -> Router {
+    migrate(&pool).await;
+    app(AppState::test_state(pool, settings))
+}
+
+// Sent emails and the background tasks sending them are handed to the test.
+#[allow(dead_code)]
+pub async fn setup_app_with_email(
+    pool: PgPool,
+    settings: Settings,
+) -> (Receiver<EmailObject>, Receiver<BackgroundTask>, Router) {
+    migrate(&pool).await;
+    let (email_tx, email_rx) = mpsc::sync_channel(5);
+    let (task_tx, task_rx) = mpsc::sync_channel(5);
+    let mut state = test::test_state_for_email(pool, email_tx);
+    state.settings = settings;
+    state.tasks = Some(task_tx);
+    (email_rx, task_rx, app(state))
+}
+
+fn app(state: AppState) -> Router {
+    Router::new()
+        .nest("/api/content", create_router())
+        .nest("/feeds", create_feed_router())
+        .merge(create_sitemap_router())
+        .with_state(state)
+}
+
+#[allow(dead_code)]
+pub async fn verified_user_fixture(transaction: &mut PgConnection) -> User {
+    let user_payload = RegisterPayload {
+        username: format!("writer{}", &Uuid::new_v4().simple().to_string()[..12]),
+        password: STRONG_PASSWORD.to_string(),
+        email: SafeEmail().fake(),
+        confirm_password: STRONG_PASSWORD.to_string(),
+        name: Name().fake(),
+        invitation: None,
+    };
+    let mut user = User::from_registration(user_payload, &PasswordHashSettings::default())
+        .expect("Cannot form new user");
+    user.id = insert_user(transaction, &user)
+        .await
+        .expect("Cannot insert user");
+    mark_user_as_confirmed(transaction, user.id)
+        .await
+        .expect("Cannot verify user");
+    user.is_confirmed = true;
+    user
+}
+
+// Session token of a new admin.
+#[allow(dead_code)]
+pub async fn admin_token_fixture(transaction: &mut PgConnection) -> String {
+    let user = verified_user_fixture(transaction).await;
+    sqlx::query!("update users set is_admin = true where id = $1", user.id)
+        .execute(&mut *transaction)
+        .await
+        .expect("Cannot promote user");
+    session_fixture(transaction, user.id).await
+}
+
+#[allow(dead_code)]
+pub async fn session_fixture(transaction: &mut PgConnection, user_id: i32) -> String {
+    create_new_session(transaction, user_id, json!({}))
+        .await
+        .unwrap()
+}
+
+#[allow(dead_code)]
+pub async fn get(app: &Router, url: &str, token: Option<&str>) -> Response {
+    let mut request = Request::builder().uri(url).method(Method::GET);
+    if let Some(token) = token {
+        request = request.header(AUTHORIZATION, token);
+    }
+    let request = request.body(Body::empty()).unwrap();
+    app.clone().oneshot(request).await.unwrap()
+}
+
+#[allow(dead_code)]
+pub async fn send(app: &Router, method: Method, url: &str, token: &str, data: &Value) -> Response {
+    let mut request = test::build_request(url, method, data);
+    request
+        .headers_mut()
+        .insert(AUTHORIZATION, token.parse().unwrap());
+    app.clone().oneshot(request).await.unwrap()
+}
+
+#[allow(dead_code)]
+pub async fn response_json(response: Response) -> Value {
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&body).unwrap()
+}
+
+// Creates a term through the api and returns its id.
+#[allow(dead_code)]
+pub async fn term_fixture(app: &Router, token: &str, data: Value) -> i64 {
+    let response = send(app, Method::POST, "/api/content/manage/terms", token, &data).await;
+    assert_eq!(response.status(), 201);
+    response_json(response).await["id"].as_i64().unwrap()
+}
+
+// Creates a post through the api and returns it.
+#[allow(dead_code)]
+pub async fn post_fixture(app: &Router, token: &str, data: Value) -> Value {
+    let response = send(app, Method::POST, "/api/content/manage/posts", token, &data).await;
+    assert_eq!(response.status(), 201);
+    response_json(response).await
+}
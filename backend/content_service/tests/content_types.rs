@@ -0,0 +1,242 @@
+use axum::http::{Method, StatusCode};
+use axum::Router;
+use serde_json::json;
+use sqlx::PgPool;
+use utils::test;
+use uuid::Uuid;
+
+mod common;
+
+const TYPES: &str = "/api/content/manage/types";
+
+#[sqlx::test(migrations = false)]
+async fn content_type_definitions_are_validated(pool: PgPool) {
+    let app = common::setup_app(pool.clone()).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+
+    let data = json!({
+        "name": "Events",
+        "fields": [
+            {"name": "venue", "type": "text"},
+            {"name": "venue", "type": "boolean"},
+            {"name": "title", "type": "text"},
+            {"name": "format", "type": "enum", "choices": []},
+            {"name": "speaker", "type": "relation", "content_type": "people"},
+        ]
+    });
+    let response = common::send(&app, Method::POST, TYPES, &token, &data).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = common::response_json(response).await;
+    assert_eq!(body["message"], json!("Failed to validate input"));
+    let codes: Vec<&str> = body["details"]["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|error| error["code"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        codes,
+        [
+            "duplicate_name",
+            "reserved_name",
+            "empty_choices",
+            "unknown_type"
+        ]
+    );
+    assert_eq!(
+        body["details"]["fields"][3]["params"]["value"],
+        json!("speaker")
+    );
+
+    // Relations to the type itself are allowed before it exists.
+    let data = json!({
+        "name": "Events",
+        "fields": [{"name": "related", "type": "relation", "content_type": "events"}]
+    });
+    let response = common::send(&app, Method::POST, TYPES, &token, &data).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let events = common::response_json(response).await;
+    assert_eq!(events["slug"], json!("events"));
+    assert_eq!(events["fields"][0]["type"], json!("relation"));
+
+    let response = common::send(&app, Method::POST, TYPES, &token, &data).await;
+    test::assert_response(response, StatusCode::BAD_REQUEST, "Slug already used").await;
+
+    let response = common::get(&app, TYPES, Some(&token)).await;
+    let listing = common::response_json(response).await;
+    assert_eq!(listing["content_types"].as_array().unwrap().len(), 1);
+
+    let reader = common::verified_user_fixture(&mut conn).await;
+    let reader_token = common::session_fixture(&mut conn, reader.id).await;
+    let response = common::get(&app, TYPES, Some(&reader_token)).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test(migrations = false)]
+async fn entries_are_validated_against_their_type(pool: PgPool) {
+    let app = common::setup_app(pool.clone()).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+    events_fixture(&app, &token).await;
+
+    let url = "/api/content/manage/types/events/entries";
+    let data = json!({
+        "title": "Rust meetup",
+        "data": {
+            "seats": 2.5,
+            "starts_on": "next friday",
+            "online": "yes",
+            "format": "party",
+            "venue": "x".repeat(41),
+            "speaker": "Amrit"
+        }
+    });
+    let response = common::send(&app, Method::POST, url, &token, &data).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let details = common::response_json(response).await["details"].clone();
+    assert_eq!(details["seats"][0]["code"], json!("integer"));
+    assert_eq!(details["starts_on"][0]["code"], json!("invalid_date"));
+    assert_eq!(details["online"][0]["code"], json!("invalid_type"));
+    assert_eq!(
+        details["format"][0]["message"],
+        json!("Format must be one of talk, workshop")
+    );
+    assert_eq!(details["venue"][0]["code"], json!("length"));
+    assert_eq!(details["speaker"][0]["code"], json!("unknown_field"));
+    assert_eq!(details["speaker"][0]["params"], json!({}));
+
+    let data = json!({"title": "Rust meetup", "data": {"seats": 20}});
+    let response = common::send(&app, Method::POST, url, &token, &data).await;
+    let details = common::response_json(response).await["details"].clone();
+    assert_eq!(details["starts_on"][0]["code"], json!("required"));
+
+    let data = json!({
+        "title": "Rust meetup",
+        "status": "published",
+        "data": {"seats": 20, "starts_on": "2026-11-06", "format": "talk", "venue": ""}
+    });
+    let response = common::send(&app, Method::POST, url, &token, &data).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let entry = common::response_json(response).await;
+    assert_eq!(entry["slug"], json!("rust-meetup"));
+    assert_eq!(
+        entry["data"],
+        json!({"seats": 20, "starts_on": "2026-11-06", "format": "talk"})
+    );
+    let data = json!({"title": "Planning", "data": {"starts_on": "2026-12-01"}});
+    common::send(&app, Method::POST, url, &token, &data).await;
+
+    // Only the sent fields change, `null` clears an optional one.
+    let entry_url = format!("{}/{}", url, entry["id"]);
+    let data = json!({"data": {"online": true, "format": null}});
+    let response = common::send(&app, Method::PATCH, &entry_url, &token, &data).await;
+    let entry = common::response_json(response).await;
+    assert_eq!(
+        entry["data"],
+        json!({"seats": 20, "starts_on": "2026-11-06", "online": true})
+    );
+    let data = json!({"data": {"seats": 0}});
+    let response = common::send(&app, Method::PATCH, &entry_url, &token, &data).await;
+    let details = common::response_json(response).await["details"].clone();
+    assert_eq!(
+        details["seats"][0]["message"],
+        json!("Seats must be at least 1")
+    );
+
+    let response = common::get(&app, "/api/content/types/events/entries", None).await;
+    let listing = common::response_json(response).await;
+    assert_eq!(listing["total"], json!(1));
+    assert_eq!(listing["content_type"]["name"], json!("Events"));
+    assert_eq!(listing["entries"][0]["title"], json!("Rust meetup"));
+    let response = common::get(&app, url, Some(&token)).await;
+    assert_eq!(common::response_json(response).await["total"], json!(2));
+
+    let response = common::get(&app, "/api/content/types/events/entries/planning", None).await;
+    test::assert_response(response, StatusCode::NOT_FOUND, "Entry not found").await;
+    let response = common::get(&app, "/api/content/types/events/entries/rust-meetup", None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = common::get(&app, "/api/content/types/talks/entries", None).await;
+    test::assert_response(response, StatusCode::NOT_FOUND, "Content type not found").await;
+}
+
+#[sqlx::test(migrations = false)]
+async fn entry_references_must_exist(pool: PgPool) {
+    let app = common::setup_app(pool.clone()).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+
+    let data = json!({"name": "People", "fields": [{"name": "photo", "type": "media"}]});
+    let response = common::send(&app, Method::POST, TYPES, &token, &data).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    events_fixture(&app, &token).await;
+    let url = "/api/content/manage/types/events";
+    let data = json!({"fields": [
+        {"name": "starts_on", "type": "date", "required": true},
+        {"name": "speaker", "type": "relation", "content_type": "people"},
+    ]});
+    let response = common::send(&app, Method::PATCH, url, &token, &data).await;
+    let events = common::response_json(response).await;
+    assert_eq!(events["fields"][1]["content_type"], json!("people"));
+
+    let people = "/api/content/manage/types/people/entries";
+    let data = json!({"title": "Amrit", "data": {"photo": Uuid::new_v4()}});
+    let response = common::send(&app, Method::POST, people, &token, &data).await;
+    let details = common::response_json(response).await["details"].clone();
+    assert_eq!(details["photo"][0]["code"], json!("media_not_found"));
+
+    let photo = Uuid::new_v4();
+    sqlx::query!(
+        r#"
+        INSERT INTO media (identifier, file_name, content_type, size_bytes, storage_key,
+            created_at, updated_at)
+        VALUES ($1, 'amrit.png', 'image/png', 10, 'amrit.png', now(), now())
+        "#,
+        photo
+    )
+    .execute(&mut *conn)
+    .await
+    .unwrap();
+    let data = json!({"title": "Amrit", "data": {"photo": photo}});
+    let response = common::send(&app, Method::POST, people, &token, &data).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let speaker = common::response_json(response).await["id"].clone();
+
+    let events = "/api/content/manage/types/events/entries";
+    let data = json!({
+        "title": "Rust meetup",
+        "data": {"starts_on": "2026-11-06", "speaker": speaker.as_i64().unwrap() + 100}
+    });
+    let response = common::send(&app, Method::POST, events, &token, &data).await;
+    let details = common::response_json(response).await["details"].clone();
+    assert_eq!(details["speaker"][0]["code"], json!("relation_not_found"));
+    let data =
+        json!({"title": "Rust meetup", "data": {"starts_on": "2026-11-06", "speaker": speaker}});
+    let response = common::send(&app, Method::POST, events, &token, &data).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let entry = common::response_json(response).await;
+
+    // Entries go away with their type.
+    let url = "/api/content/manage/types/events";
+    let response = common::send(&app, Method::DELETE, url, &token, &json!({})).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let url = format!("{}/entries/{}", url, entry["id"]);
+    let response = common::get(&app, &url, Some(&token)).await;
+    test::assert_response(response, StatusCode::NOT_FOUND, "Content type not found").await;
+}
+
+async fn events_fixture(app: &Router, token: &str) {
+    let data = json!({
+        "name": "Events",
+        "fields": [
+            {"name": "starts_on", "label": "Starts on", "type": "date", "required": true},
+            {"name": "venue", "type": "text", "max_length": 40},
+            {"name": "seats", "label": "Seats", "type": "number", "min": 1, "integer": true},
+            {"name": "online", "type": "boolean"},
+            {"name": "format", "label": "Format", "type": "enum", "choices": ["talk", "workshop"]},
+            {"name": "notes", "type": "rich_text"},
+        ]
+    });
+    let response = common::send(app, Method::POST, TYPES, token, &data).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+}
@@ -0,0 +1,179 @@
+use axum::body::Body;
+use axum::http::header::{CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use axum::http::{Request, StatusCode};
+use axum::response::Response;
+use axum::Router;
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+mod common;
+
+#[sqlx::test(migrations = false)]
+async fn feeds_list_published_posts_in_every_format(pool: PgPool) {
+    let app = common::setup_app(pool.clone()).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+
+    let rust = common::term_fixture(&app, &token, json!({"taxonomy": "tag", "name": "Rust"})).await;
+    let data = json!({
+        "title": "Tom & Jerry",
+        "excerpt": "A chase",
+        "body": "<p>Hi</p>",
+        "status": "published",
+        "term_ids": [rust]
+    });
+    common::post_fixture(&app, &token, data).await;
+    common::post_fixture(&app, &token, json!({"title": "Unfinished"})).await;
+
+    let response = get(&app, "/feeds/rss.xml", &[]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[CONTENT_TYPE],
+        "application/rss+xml; charset=utf-8"
+    );
+    let rss = body_text(response).await;
+    assert!(rss.contains("<title>Tom &amp; Jerry</title>"));
+    assert!(rss.contains("<content:encoded>&lt;p&gt;Hi&lt;/p&gt;</content:encoded>"));
+    assert!(rss.contains("<category>Rust</category>"));
+    assert!(rss.contains("/posts/tom-jerry</link>"));
+    assert!(!rss.contains("Unfinished"));
+
+    let atom = body_text(get(&app, "/feeds/atom.xml", &[]).await).await;
+    assert!(atom.starts_with(
+        r#"<?xml version="1.0" encoding="UTF-8"?><feed xmlns="http://www.w3.org/2005/Atom">"#
+    ));
+    assert!(atom.contains("<summary>A chase</summary>"));
+    assert_eq!(atom.matches("<entry>").count(), 1);
+
+    let response = get(&app, "/feeds/feed.json", &[]).await;
+    assert_eq!(
+        response.headers()[CONTENT_TYPE],
+        "application/feed+json; charset=utf-8"
+    );
+    let feed: Value = serde_json::from_str(&body_text(response).await).unwrap();
+    assert_eq!(feed["version"], json!("https://jsonfeed.org/version/1.1"));
+    assert!(feed["feed_url"]
+        .as_str()
+        .unwrap()
+        .ends_with("/feeds/feed.json"));
+    assert_eq!(feed["items"][0]["content_html"], json!("<p>Hi</p>"));
+    assert_eq!(feed["items"][0]["tags"], json!(["Rust"]));
+
+    let response = get(&app, "/feeds/feed.xml", &[]).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = false)]
+async fn feeds_are_scoped_by_taxonomy_and_author(pool: PgPool) {
+    let app = common::setup_app(pool.clone()).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+    let other_token = common::admin_token_fixture(&mut conn).await;
+
+    let travel = common::term_fixture(
+        &app,
+        &token,
+        json!({"taxonomy": "category", "name": "Travel"}),
+    )
+    .await;
+    let nepal = common::term_fixture(
+        &app,
+        &token,
+        json!({"taxonomy": "category", "name": "Nepal", "parent_id": travel}),
+    )
+    .await;
+    let rust = common::term_fixture(&app, &token, json!({"taxonomy": "tag", "name": "Rust"})).await;
+    let data = json!({"title": "Trekking", "status": "published", "term_ids": [nepal]});
+    let post = common::post_fixture(&app, &token, data).await;
+    let data = json!({"title": "Borrowing", "status": "published", "term_ids": [rust]});
+    common::post_fixture(&app, &other_token, data).await;
+
+    let feed = json_feed(&app, "/feeds/category/travel/feed.json").await;
+    assert_eq!(titles(&feed), vec!["Trekking"]);
+    assert!(feed["feed_url"]
+        .as_str()
+        .unwrap()
+        .ends_with("/feeds/category/travel/feed.json"));
+    let feed = json_feed(&app, "/feeds/tag/rust/feed.json").await;
+    assert_eq!(titles(&feed), vec!["Borrowing"]);
+
+    let username = post["author"]["username"].as_str().unwrap();
+    let feed = json_feed(&app, &format!("/feeds/author/{}/feed.json", username)).await;
+    assert_eq!(titles(&feed), vec!["Trekking"]);
+
+    let response = get(&app, "/feeds/tag/missing/rss.xml", &[]).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = get(&app, "/feeds/author/nobody-here/rss.xml", &[]).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = false)]
+async fn unchanged_feeds_answer_conditional_requests_with_304(pool: PgPool) {
+    let app = common::setup_app(pool.clone()).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+
+    let data = json!({"title": "First", "status": "published"});
+    common::post_fixture(&app, &token, data).await;
+
+    let response = get(&app, "/feeds/atom.xml", &[]).await;
+    let etag = response.headers()[ETAG].to_str().unwrap().to_string();
+    let last_modified = response.headers()[LAST_MODIFIED]
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert!(last_modified.ends_with(" GMT"));
+
+    let response = get(&app, "/feeds/atom.xml", &[(IF_NONE_MATCH.as_str(), &etag)]).await;
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(response.headers()[ETAG], etag.as_str());
+    assert!(body_text(response).await.is_empty());
+    let response = get(
+        &app,
+        "/feeds/atom.xml",
+        &[(IF_MODIFIED_SINCE.as_str(), &last_modified)],
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+    // Each format has its own body and so its own tag.
+    let response = get(&app, "/feeds/rss.xml", &[(IF_NONE_MATCH.as_str(), &etag)]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let data = json!({"title": "Second", "status": "published"});
+    common::post_fixture(&app, &token, data).await;
+    let response = get(&app, "/feeds/atom.xml", &[(IF_NONE_MATCH.as_str(), &etag)]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_ne!(response.headers()[ETAG], etag.as_str());
+}
+
+async fn get(app: &Router, url: &str, headers: &[(&str, &str)]) -> Response {
+    let mut request = Request::builder().uri(url);
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let request = request.body(Body::empty()).unwrap();
+    app.clone().oneshot(request).await.unwrap()
+}
+
+async fn body_text(response: Response) -> String {
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+async fn json_feed(app: &Router, url: &str) -> Value {
+    let response = get(app, url, &[]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    serde_json::from_str(&body_text(response).await).unwrap()
+}
+
+fn titles(feed: &Value) -> Vec<&str> {
+    feed["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["title"].as_str().unwrap())
+        .collect()
+}
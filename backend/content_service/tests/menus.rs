@@ -0,0 +1,128 @@
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+use sqlx::PgPool;
+use utils::test;
+
+mod common;
+
+#[sqlx::test(migrations = false)]
+async fn menus_only_link_to_published_content(pool: PgPool) {
+    let app = common::setup_app(pool.clone()).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+
+    let data = json!({"kind": "page", "title": "About", "status": "published"});
+    let about = common::post_fixture(&app, &token, data).await["id"].clone();
+    let draft =
+        common::post_fixture(&app, &token, json!({"title": "Upcoming"})).await["id"].clone();
+    let rust = common::term_fixture(&app, &token, json!({"taxonomy": "tag", "name": "Rust"})).await;
+
+    let data = json!({
+        "name": "Primary",
+        "items": [
+            {"link_type": "page", "target_id": about, "children": [
+                {"label": "Source", "link_type": "url", "url": "https://github.com", "open_in_new_tab": true},
+                {"link_type": "post", "target_id": draft},
+            ]},
+            {"label": "Rust posts", "link_type": "tag", "target_id": rust},
+            {"label": "Search", "link_type": "url", "url": "/search"},
+        ]
+    });
+    let url = "/api/content/manage/menus";
+    let response = common::send(&app, Method::POST, url, &token, &data).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let menu = common::response_json(response).await;
+    assert_eq!(menu["slug"], json!("primary"));
+    assert_eq!(
+        menu["items"][0]["children"][1]["href"],
+        json!("/posts/upcoming")
+    );
+
+    let response = common::get(&app, "/api/content/menus/primary", None).await;
+    let menu = common::response_json(response).await;
+    let items = menu["items"].as_array().unwrap();
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[0]["label"], json!("About"));
+    assert_eq!(items[0]["href"], json!("/pages/about"));
+    assert_eq!(items[0]["children"].as_array().unwrap().len(), 1);
+    assert_eq!(items[0]["children"][0]["open_in_new_tab"], json!(true));
+    assert_eq!(items[1]["href"], json!("/tags/rust"));
+    assert_eq!(items[2]["href"], json!("/search"));
+
+    // Removing the page takes its submenu out of the navigation.
+    let url = format!("/api/content/manage/posts/{}", about);
+    common::send(&app, Method::DELETE, &url, &token, &json!({})).await;
+    let response = common::get(&app, "/api/content/menus/primary", None).await;
+    let menu = common::response_json(response).await;
+    assert_eq!(menu["items"][0]["label"], json!("Rust posts"));
+    let response = common::get(&app, "/api/content/manage/menus/primary", Some(&token)).await;
+    let menu = common::response_json(response).await;
+    assert_eq!(menu["items"][0]["href"], json!(null));
+
+    let response = common::get(&app, "/api/content/menus/footer", None).await;
+    test::assert_response(response, StatusCode::NOT_FOUND, "Menu not found").await;
+}
+
+#[sqlx::test(migrations = false)]
+async fn menu_items_are_validated_and_replaced(pool: PgPool) {
+    let app = common::setup_app(pool.clone()).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+    let data = json!({"title": "Hello", "status": "published"});
+    let post = common::post_fixture(&app, &token, data).await["id"].clone();
+
+    let url = "/api/content/manage/menus";
+    let data =
+        json!({"name": "Footer", "items": [{"label": "Home", "link_type": "url", "url": "/"}]});
+    let response = common::send(&app, Method::POST, url, &token, &data).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let url = "/api/content/manage/menus/footer";
+    let nested = json!({"label": "Deep", "link_type": "url", "url": "/a", "children": [
+        {"label": "Deeper", "link_type": "url", "url": "/b", "children": [
+            {"label": "Deepest", "link_type": "url", "url": "/c", "children": [
+                {"label": "Too deep", "link_type": "url", "url": "/d"}
+            ]}
+        ]}
+    ]});
+    let data = json!({"items": [
+        {"label": "Script", "link_type": "url", "url": "javascript:alert(1)"},
+        {"link_type": "category"},
+        {"link_type": "page", "target_id": post},
+        nested,
+    ]});
+    let response = common::send(&app, Method::PATCH, url, &token, &data).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = common::response_json(response).await;
+    assert_eq!(body["message"], json!("Failed to validate input"));
+    let details = &body["details"];
+    assert_eq!(details["items[0].url"][0]["code"], json!("invalid_url"));
+    assert_eq!(details["items[1].target_id"][0]["code"], json!("required"));
+    assert_eq!(
+        details["items[2].target_id"][0]["code"],
+        json!("target_not_found")
+    );
+    assert_eq!(
+        details["items[3].children[0].children[0].children"][0]["code"],
+        json!("too_deep")
+    );
+
+    // The failed save left the menu as it was.
+    let response = common::get(&app, url, Some(&token)).await;
+    let menu = common::response_json(response).await;
+    assert_eq!(menu["items"][0]["label"], json!("Home"));
+
+    let data = json!({"name": "Site footer", "items": [
+        {"link_type": "post", "target_id": post},
+        {"label": "Home", "link_type": "url", "url": "/"},
+    ]});
+    let response = common::send(&app, Method::PATCH, url, &token, &data).await;
+    let menu = common::response_json(response).await;
+    assert_eq!(menu["name"], json!("Site footer"));
+    assert_eq!(menu["items"][0]["label"], json!("Hello"));
+    assert_eq!(menu["items"][1]["label"], json!("Home"));
+
+    let response = common::get(&app, "/api/content/manage/menus", Some(&token)).await;
+    let menus = common::response_json(response).await;
+    assert_eq!(menus["menus"][0]["slug"], json!("footer"));
+}
@@ -0,0 +1,108 @@
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+use sqlx::PgPool;
+use utils::test;
+
+mod common;
+
+#[sqlx::test(migrations = false)]
+async fn only_admins_manage_posts(pool: PgPool) {
+    let app = common::setup_app(pool.clone()).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let user = common::verified_user_fixture(&mut conn).await;
+    let token = common::session_fixture(&mut conn, user.id).await;
+
+    let data = json!({"title": "Hello"});
+    let response = common::send(
+        &app,
+        Method::POST,
+        "/api/content/manage/posts",
+        &token,
+        &data,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = common::get(&app, "/api/content/manage/posts", None).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[sqlx::test(migrations = false)]
+async fn publishing_makes_a_post_public(pool: PgPool) {
+    let app = common::setup_app(pool.clone()).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+
+    let post = common::post_fixture(
+        &app,
+        &token,
+        json!({"title": "Café au lait", "body": "Morning notes"}),
+    )
+    .await;
+    assert_eq!(post["slug"], json!("cafe-au-lait"));
+    assert_eq!(post["status"], json!("draft"));
+    assert_eq!(post["published_at"], json!(null));
+    assert!(post["author"]["username"]
+        .as_str()
+        .unwrap()
+        .starts_with("writer"));
+
+    let response = common::get(&app, "/api/content/posts/cafe-au-lait", None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let listing = common::response_json(common::get(&app, "/api/content/posts", None).await).await;
+    assert_eq!(listing["total"], json!(0));
+
+    let url = format!("/api/content/manage/posts/{}", post["id"]);
+    let data = json!({"status": "published"});
+    let response = common::send(&app, Method::PATCH, &url, &token, &data).await;
+    let published = common::response_json(response).await;
+    assert_eq!(published["status"], json!("published"));
+    assert!(published["published_at"].is_string());
+
+    let response = common::get(&app, "/api/content/posts/cafe-au-lait", None).await;
+    let public = common::response_json(response).await;
+    assert_eq!(public["body"], json!("Morning notes"));
+    let listing = common::response_json(common::get(&app, "/api/content/posts", None).await).await;
+    assert_eq!(listing["total"], json!(1));
+    assert_eq!(listing["posts"][0]["title"], json!("Café au lait"));
+
+    // A page with the same slug lives next to the post, a second post does not.
+    let data = json!({"title": "Cafe au lait", "kind": "page", "status": "published"});
+    common::post_fixture(&app, &token, data).await;
+    let response = common::get(&app, "/api/content/pages/cafe-au-lait", None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let data = json!({"title": "Cafe au lait"});
+    let response = common::send(
+        &app,
+        Method::POST,
+        "/api/content/manage/posts",
+        &token,
+        &data,
+    )
+    .await;
+    test::assert_response(response, StatusCode::BAD_REQUEST, "Slug already used").await;
+}
+
+#[sqlx::test(migrations = false)]
+async fn private_and_scheduled_posts_stay_hidden(pool: PgPool) {
+    let app = common::setup_app(pool.clone()).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+
+    let data = json!({"title": "Secret", "status": "private"});
+    common::post_fixture(&app, &token, data).await;
+    let data = json!({
+        "title": "Tomorrow",
+        "status": "published",
+        "published_at": "2999-01-01T00:00:00Z"
+    });
+    common::post_fixture(&app, &token, data).await;
+
+    let listing = common::response_json(common::get(&app, "/api/content/posts", None).await).await;
+    assert_eq!(listing["total"], json!(0));
+    let response = common::get(&app, "/api/content/posts/tomorrow", None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = common::get(&app, "/api/content/manage/posts", Some(&token)).await;
+    let managed = common::response_json(response).await;
+    assert_eq!(managed["total"], json!(2));
+}
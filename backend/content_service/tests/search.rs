@@ -0,0 +1,126 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+mod common;
+
+#[sqlx::test(migrations = false)]
+async fn search_ranks_titles_and_stems_words(pool: PgPool) {
+    let app = common::setup_app(pool.clone()).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+
+    let data = json!({
+        "title": "Morning walks",
+        "body": "Notes on <b>running</b> & walking before work.",
+        "status": "published"
+    });
+    common::post_fixture(&app, &token, data).await;
+    let data =
+        json!({"title": "Running guide", "body": "Shoes and routes.", "status": "published"});
+    common::post_fixture(&app, &token, data).await;
+    let data = json!({"title": "Run draft", "body": "Not ready."});
+    common::post_fixture(&app, &token, data).await;
+
+    let results = search(&app, "q=runs").await;
+    assert_eq!(results["total"], json!(2));
+    assert_eq!(results["results"][0]["title"], json!("Running guide"));
+    assert_eq!(results["results"][1]["kind"], json!("post"));
+    assert_eq!(
+        results["results"][1]["snippet"],
+        json!("Notes on &lt;b&gt;<mark>running</mark>&lt;/b&gt; &amp; walking before work")
+    );
+
+    let results = search(&app, "q=%20%20").await;
+    assert_eq!(results["results"], json!([]));
+
+    // Edits are searchable right away.
+    let post = common::response_json(
+        common::get(&app, "/api/content/manage/posts?per_page=3", Some(&token)).await,
+    )
+    .await;
+    let id = post["posts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|post| post["title"] == json!("Morning walks"))
+        .unwrap()["id"]
+        .clone();
+    let url = format!("/api/content/manage/posts/{}", id);
+    let data = json!({"body": "Only cycling now."});
+    common::send(&app, axum::http::Method::PATCH, &url, &token, &data).await;
+    assert_eq!(search(&app, "q=running").await["total"], json!(1));
+    assert_eq!(search(&app, "q=cycle").await["total"], json!(1));
+}
+
+#[sqlx::test(migrations = false)]
+async fn search_filters_by_type_taxonomy_and_date(pool: PgPool) {
+    let app = common::setup_app(pool.clone()).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+
+    let travel = common::term_fixture(
+        &app,
+        &token,
+        json!({"taxonomy": "category", "name": "Travel"}),
+    )
+    .await;
+    let data = json!({
+        "title": "Mountain trip",
+        "status": "published",
+        "published_at": "2024-03-10T08:00:00Z",
+        "term_ids": [travel]
+    });
+    common::post_fixture(&app, &token, data).await;
+    let data = json!({
+        "title": "Mountain gear",
+        "status": "published",
+        "kind": "page",
+        "published_at": "2024-05-01T08:00:00Z"
+    });
+    common::post_fixture(&app, &token, data).await;
+    sqlx::query!(
+        "INSERT INTO newsletters (title, plain, html, sent_at) VALUES ($1, $2, $3, $4)",
+        "Mountain news",
+        "Snow reports from the mountain.",
+        "<p>Snow reports from the mountain.</p>",
+        "2024-06-01T08:00:00Z"
+            .parse::<chrono::DateTime<chrono::Utc>>()
+            .unwrap()
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    assert_eq!(search(&app, "q=mountain").await["total"], json!(3));
+    let results = search(&app, "q=mountain&kind=newsletter").await;
+    assert_eq!(results["total"], json!(1));
+    assert_eq!(results["results"][0]["slug"], json!(null));
+    let results = search(&app, "q=mountain&category=travel").await;
+    assert_eq!(titles(&results), vec!["Mountain trip"]);
+    let results = search(&app, "q=mountain&from=2024-04-01&to=2024-05-01").await;
+    assert_eq!(titles(&results), vec!["Mountain gear"]);
+
+    let results = search(&app, "q=mountain&per_page=2&page=2").await;
+    assert_eq!(results["results"].as_array().unwrap().len(), 1);
+    assert_eq!(results["total"], json!(3));
+
+    let response = common::get(&app, "/api/content/search?q=mountain&tag=missing", None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+async fn search(app: &axum::Router, query: &str) -> Value {
+    let url = format!("/api/content/search?{}", query);
+    let response = common::get(app, &url, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    common::response_json(response).await
+}
+
+fn titles(results: &Value) -> Vec<&str> {
+    results["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|result| result["title"].as_str().unwrap())
+        .collect()
+}
@@ -0,0 +1,182 @@
+use axum::http::header::CONTENT_TYPE;
+use axum::http::{Method, StatusCode};
+use axum::response::{Html, Response};
+use axum::routing::get;
+use axum::{middleware, Router};
+use content_service::middleware::seo_head;
+use content_service::router::{create_router, create_sitemap_router};
+use http_body_util::BodyExt;
+use serde_json::json;
+use sqlx::PgPool;
+use utils::configuration::{RunMode, Settings};
+use utils::state::AppState;
+
+mod common;
+
+static TEMPLATE: &str =
+    r#"<html><head><title>Hi boss</title></head><body><div id="main"></div></body></html>"#;
+
+#[sqlx::test(migrations = false)]
+async fn posts_get_head_tags_derived_from_content(pool: PgPool) {
+    let app = setup_app(pool.clone(), None).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+    let data = json!({
+        "title": "Tom & Jerry",
+        "body": "<p>A <b>long</b> chase</p><img src=\"/media/cat.png\">",
+        "status": "published"
+    });
+    common::post_fixture(&app, &token, data).await;
+
+    let html = page(&app, "/posts/tom-jerry").await;
+    assert!(html.starts_with("<html><head><title>Tom &amp; Jerry | "));
+    assert_eq!(html.matches("<title>").count(), 1);
+    assert!(html.contains(r#"<meta name="description" content="A long chase">"#));
+    assert!(html.contains(r#"/posts/tom-jerry">"#));
+    assert!(html.contains(r#"<link rel="canonical" href="http"#));
+    assert!(html.contains(r#"<meta property="og:type" content="article">"#));
+    assert!(html.contains(r#"<meta property="og:title" content="Tom &amp; Jerry">"#));
+    assert!(html.contains(r#"/media/cat.png">"#));
+    assert!(html.contains(r#"<meta name="twitter:card" content="summary_large_image">"#));
+    assert!(!html.contains("noindex"));
+    assert!(html.ends_with(r#"</head><body><div id="main"></div></body></html>"#));
+
+    let response = common::get(&app, "/api/content/posts/tom-jerry", None).await;
+    let post = common::response_json(response).await;
+    assert_eq!(post["noindex"], json!(false));
+    assert_eq!(post["meta_title"], json!(null));
+}
+
+#[sqlx::test(migrations = false)]
+async fn overrides_and_noindex_are_respected(pool: PgPool) {
+    let app = setup_app(pool.clone(), None).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+    let data = json!({
+        "title": "About",
+        "kind": "page",
+        "excerpt": "Who we are",
+        "status": "published",
+        "meta_title": "About the team",
+        "canonical_url": "https://example.com/about",
+        "og_image": "https://cdn.example.com/team.png",
+        "noindex": true
+    });
+    let post = common::post_fixture(&app, &token, data).await;
+
+    let html = page(&app, "/pages/about").await;
+    assert!(html.contains("<title>About the team | "));
+    assert!(html.contains(r#"<meta name="description" content="Who we are">"#));
+    assert!(html.contains(r#"<link rel="canonical" href="https://example.com/about">"#));
+    assert!(
+        html.contains(r#"<meta property="og:image" content="https://cdn.example.com/team.png">"#)
+    );
+    assert!(html.contains(r#"<meta name="robots" content="noindex, nofollow">"#));
+    assert!(html.contains(r#"<meta property="og:type" content="website">"#));
+
+    let sitemap = body_text(common::get(&app, "/sitemap.xml", None).await).await;
+    assert!(!sitemap.contains("/pages/about"));
+
+    let url = format!("/api/content/manage/posts/{}", post["id"]);
+    let data = json!({"meta_title": null, "noindex": false});
+    let response = common::send(&app, Method::PATCH, &url, &token, &data).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let html = page(&app, "/pages/about").await;
+    assert!(html.contains("<title>About | "));
+    assert!(!html.contains("noindex"));
+    assert!(html.contains(r#"<link rel="canonical" href="https://example.com/about">"#));
+
+    let response = common::send(
+        &app,
+        Method::PATCH,
+        &url,
+        &token,
+        &json!({"canonical_url": "not a url"}),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test(migrations = false)]
+async fn other_pages_get_site_defaults(pool: PgPool) {
+    let mut settings = Settings::get_config(RunMode::Test).unwrap();
+    settings.seo.description = "Notes on software".to_string();
+    settings.seo.default_image = Some("/static/cover.png".to_string());
+    settings.seo.twitter_site = Some("@example".to_string());
+    let name = settings.application.name.clone();
+    let app = setup_app(pool, Some(settings)).await;
+
+    let html = page(&app, "/search").await;
+    assert!(html.starts_with(&format!("<html><head><title>{}</title>", name)));
+    assert!(html.contains(r#"<meta name="description" content="Notes on software">"#));
+    assert!(html.contains(r#"/static/cover.png">"#));
+    assert!(html.contains(r#"<meta name="twitter:site" content="@example">"#));
+    assert!(html.contains(r#"/search">"#));
+
+    let html = page(&app, "/posts/missing").await;
+    assert!(html.contains(r#"<meta name="robots" content="noindex, nofollow">"#));
+
+    // Api responses pass through untouched.
+    let response = common::get(&app, "/api/content/posts", None).await;
+    assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+}
+
+#[sqlx::test(migrations = false)]
+async fn translations_are_linked_with_hreflang(pool: PgPool) {
+    let mut settings = Settings::get_config(RunMode::Test).unwrap();
+    settings.i18n.locales = vec!["en".into(), "ne".into()];
+    let app = setup_app(pool.clone(), Some(settings)).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+    let data = json!({"title": "Hello world", "status": "published"});
+    let original = common::post_fixture(&app, &token, data).await;
+    let data = json!({
+        "title": "Namaste",
+        "status": "published",
+        "locale": "ne",
+        "translation_of": original["id"]
+    });
+    common::post_fixture(&app, &token, data).await;
+
+    let html = page(&app, "/ne/posts/hello-world").await;
+    assert!(html.starts_with(r#"<html lang="ne"><head><title>Namaste | "#));
+    assert!(html.contains(r#"/ne/posts/namaste">"#));
+    assert!(html.contains(r#"<link rel="alternate" hreflang="ne" href="http"#));
+    assert!(html.contains(r#"<link rel="alternate" hreflang="en" href="http"#));
+    assert!(html.contains(r#"<link rel="alternate" hreflang="x-default" href="http"#));
+    assert!(html.contains(r#"<meta property="og:locale" content="ne">"#));
+
+    // The default locale is served without a prefix and keeps the template language.
+    let html = page(&app, "/posts/hello-world").await;
+    assert!(html.starts_with("<html><head><title>Hello world | "));
+    assert!(html.contains(r#"<link rel="canonical" href="http"#));
+    assert!(html.contains(r#"/posts/hello-world"><link rel="alternate" hreflang="en""#));
+
+    let response = common::get(&app, "/sitemap.xml", None).await;
+    let xml = body_text(response).await;
+    assert!(xml.contains("/ne/posts/namaste</loc>"));
+    assert!(xml.contains("/posts/hello-world</loc>"));
+}
+
+// The api with a stand in for the server rendered Dioxus application.
+async fn setup_app(pool: PgPool, settings: Option<Settings>) -> Router {
+    common::migrate(&pool).await;
+    let state = AppState::test_state(pool, settings);
+    Router::new()
+        .nest("/api/content", create_router())
+        .merge(create_sitemap_router())
+        .fallback(get(|| async { Html(TEMPLATE) }))
+        .layer(middleware::from_fn_with_state(state.clone(), seo_head))
+        .with_state(state)
+}
+
+async fn page(app: &Router, url: &str) -> String {
+    let response = common::get(app, url, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    body_text(response).await
+}
+
+async fn body_text(response: Response) -> String {
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    String::from_utf8(body.to_vec()).unwrap()
+}
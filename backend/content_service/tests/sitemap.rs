@@ -0,0 +1,138 @@
+use axum::http::header::CONTENT_TYPE;
+use axum::http::StatusCode;
+use axum::response::Response;
+use http_body_util::BodyExt;
+use serde_json::json;
+use sqlx::PgPool;
+use utils::configuration::{RunMode, Settings};
+
+mod common;
+
+#[sqlx::test(migrations = false)]
+async fn sitemap_lists_public_content_only(pool: PgPool) {
+    let app = common::setup_app(pool.clone()).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+
+    let rust = common::term_fixture(&app, &token, json!({"taxonomy": "tag", "name": "Rust"})).await;
+    common::term_fixture(&app, &token, json!({"taxonomy": "tag", "name": "Unused"})).await;
+    let travel = common::term_fixture(
+        &app,
+        &token,
+        json!({"taxonomy": "category", "name": "Travel"}),
+    )
+    .await;
+    common::post_fixture(
+        &app,
+        &token,
+        json!({"title": "Hello world", "status": "published", "term_ids": [rust, travel]}),
+    )
+    .await;
+    common::post_fixture(
+        &app,
+        &token,
+        json!({"title": "About", "kind": "page", "status": "published"}),
+    )
+    .await;
+    common::post_fixture(&app, &token, json!({"title": "Unfinished"})).await;
+    common::post_fixture(
+        &app,
+        &token,
+        json!({"title": "Secret", "status": "private"}),
+    )
+    .await;
+    let newsletter: (i32,) = sqlx::query_as(
+        "insert into newsletters (title, plain, html, sent_at) values ('Issue one', 'Hi', '<p>Hi</p>', now()) returning id",
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .unwrap();
+
+    let response = common::get(&app, "/sitemap.xml", None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[CONTENT_TYPE],
+        "application/xml; charset=utf-8"
+    );
+    let xml = body_text(response).await;
+    assert!(xml.contains(r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#));
+    for path in [
+        "/</loc>".to_string(),
+        "/posts/hello-world</loc><lastmod>".to_string(),
+        "/pages/about</loc>".to_string(),
+        "/tags/rust</loc>".to_string(),
+        "/categories/travel</loc>".to_string(),
+        "/newsletters</loc>".to_string(),
+        format!("/newsletters/{}</loc>", newsletter.0),
+    ] {
+        assert!(xml.contains(&path), "{} missing from {}", path, xml);
+    }
+    assert!(!xml.contains("unfinished"));
+    assert!(!xml.contains("secret"));
+    assert!(!xml.contains("/tags/unused"));
+    assert_eq!(xml.matches("<url>").count(), 7);
+}
+
+#[sqlx::test(migrations = false)]
+async fn large_sitemaps_are_split_behind_an_index(pool: PgPool) {
+    let mut settings = Settings::get_config(RunMode::Test).unwrap();
+    settings.seo.sitemap_urls_per_file = 2;
+    let app = common::setup_app_with_settings(pool.clone(), Some(settings)).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+
+    for title in ["First", "Second", "Third", "Fourth"] {
+        common::post_fixture(&app, &token, json!({"title": title, "status": "published"})).await;
+    }
+
+    let xml = body_text(common::get(&app, "/sitemap.xml", None).await).await;
+    assert!(xml.contains(r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#));
+    assert_eq!(xml.matches("<sitemap>").count(), 3);
+    assert!(xml.contains("/sitemaps/3.xml</loc><lastmod>"));
+
+    let second = body_text(common::get(&app, "/sitemaps/2.xml", None).await).await;
+    assert_eq!(second.matches("<url>").count(), 2);
+    assert!(second.contains("/posts/second</loc>"));
+    assert!(second.contains("/posts/third</loc>"));
+
+    let last = body_text(common::get(&app, "/sitemaps/3.xml", None).await).await;
+    assert_eq!(last.matches("<url>").count(), 1);
+
+    let urls = [
+        "/sitemaps/4.xml",
+        "/sitemaps/0.xml",
+        "/sitemaps/one.xml",
+        "/sitemaps/9223372036854775807.xml",
+    ];
+    for url in urls {
+        let response = common::get(&app, url, None).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
+
+#[sqlx::test(migrations = false)]
+async fn robots_follow_configuration(pool: PgPool) {
+    let app = common::setup_app(pool.clone()).await;
+    let response = common::get(&app, "/robots.txt", None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[CONTENT_TYPE],
+        "text/plain; charset=utf-8"
+    );
+    let robots = body_text(response).await;
+    assert!(robots.starts_with("User-agent: *\nDisallow: /api/\n"));
+    assert!(robots.contains("/sitemap.xml\n"));
+
+    let mut settings = Settings::get_config(RunMode::Test).unwrap();
+    settings.seo.robots.allow_indexing = false;
+    settings.seo.robots.extra = "User-agent: GPTBot\nDisallow: /".to_string();
+    let app = common::setup_app_with_settings(pool, Some(settings)).await;
+    let robots = body_text(common::get(&app, "/robots.txt", None).await).await;
+    assert!(robots.starts_with("User-agent: *\nDisallow: /\n\nSitemap: "));
+    assert!(robots.ends_with("\nUser-agent: GPTBot\nDisallow: /\n"));
+}
+
+async fn body_text(response: Response) -> String {
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    String::from_utf8(body.to_vec()).unwrap()
+}
@@ -0,0 +1,156 @@
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+use sqlx::PgPool;
+use utils::test;
+
+mod common;
+
+#[sqlx::test(migrations = false)]
+async fn category_archive_includes_child_categories(pool: PgPool) {
+    let app = common::setup_app(pool.clone()).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+
+    let travel = common::term_fixture(
+        &app,
+        &token,
+        json!({"taxonomy": "category", "name": "Travel", "description": "Trips"}),
+    )
+    .await;
+    let nepal = common::term_fixture(
+        &app,
+        &token,
+        json!({"taxonomy": "category", "name": "Nepal", "parent_id": travel}),
+    )
+    .await;
+    let data = json!({"title": "Trekking", "status": "published", "term_ids": [nepal]});
+    let post = common::post_fixture(&app, &token, data).await;
+    assert_eq!(post["categories"][0]["slug"], json!("nepal"));
+    let data = json!({"title": "Draft trip", "term_ids": [travel]});
+    common::post_fixture(&app, &token, data).await;
+
+    let response = common::get(&app, "/api/content/categories/travel/posts", None).await;
+    let archive = common::response_json(response).await;
+    assert_eq!(archive["category"]["description"], json!("Trips"));
+    assert_eq!(archive["total"], json!(1));
+    assert_eq!(archive["posts"][0]["slug"], json!("trekking"));
+
+    let response = common::get(&app, "/api/content/categories/unknown/posts", None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // Moving a category below its own child would make a loop.
+    let url = format!("/api/content/manage/terms/{}", travel);
+    let data = json!({"parent_id": nepal});
+    let response = common::send(&app, Method::PATCH, &url, &token, &data).await;
+    test::assert_response(
+        response,
+        StatusCode::BAD_REQUEST,
+        "A category cannot be moved below itself",
+    )
+    .await;
+    let url = format!("/api/content/manage/terms/{}", nepal);
+    let response = common::send(
+        &app,
+        Method::PATCH,
+        &url,
+        &token,
+        &json!({"parent_id": null}),
+    )
+    .await;
+    let moved = common::response_json(response).await;
+    assert_eq!(moved["parent_id"], json!(null));
+}
+
+#[sqlx::test(migrations = false)]
+async fn tag_archive_is_paginated(pool: PgPool) {
+    let app = common::setup_app(pool.clone()).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+
+    let rust = common::term_fixture(&app, &token, json!({"taxonomy": "tag", "name": "Rust"})).await;
+    for index in 0..3 {
+        let data =
+            json!({"title": format!("Post {}", index), "status": "published", "term_ids": [rust]});
+        common::post_fixture(&app, &token, data).await;
+    }
+    common::post_fixture(
+        &app,
+        &token,
+        json!({"title": "Untagged", "status": "published"}),
+    )
+    .await;
+
+    let url = "/api/content/tags/rust/posts?page=2&per_page=2";
+    let archive = common::response_json(common::get(&app, url, None).await).await;
+    assert_eq!(archive["tag"]["name"], json!("Rust"));
+    assert_eq!(archive["total"], json!(3));
+    assert_eq!(archive["page"], json!(2));
+    assert_eq!(archive["posts"].as_array().unwrap().len(), 1);
+
+    let data = json!({"taxonomy": "tag", "name": "Nested", "parent_id": rust});
+    let response = common::send(
+        &app,
+        Method::POST,
+        "/api/content/manage/terms",
+        &token,
+        &data,
+    )
+    .await;
+    test::assert_response(
+        response,
+        StatusCode::BAD_REQUEST,
+        "Only categories can have a parent category",
+    )
+    .await;
+    let data = json!({"taxonomy": "tag", "name": "RUST!"});
+    let response = common::send(
+        &app,
+        Method::POST,
+        "/api/content/manage/terms",
+        &token,
+        &data,
+    )
+    .await;
+    test::assert_response(response, StatusCode::BAD_REQUEST, "Slug already used").await;
+}
+
+#[sqlx::test(migrations = false)]
+async fn tag_suggestions_prefer_used_tags(pool: PgPool) {
+    let app = common::setup_app(pool.clone()).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+
+    common::term_fixture(&app, &token, json!({"taxonomy": "tag", "name": "Rust"})).await;
+    let rustic =
+        common::term_fixture(&app, &token, json!({"taxonomy": "tag", "name": "Rustic"})).await;
+    common::term_fixture(&app, &token, json!({"taxonomy": "tag", "name": "Go"})).await;
+    common::term_fixture(
+        &app,
+        &token,
+        json!({"taxonomy": "category", "name": "Rusty"}),
+    )
+    .await;
+    common::post_fixture(
+        &app,
+        &token,
+        json!({"title": "Cabin", "term_ids": [rustic]}),
+    )
+    .await;
+
+    let url = "/api/content/manage/tags/suggest?q=ru";
+    let suggestions = common::response_json(common::get(&app, url, Some(&token)).await).await;
+    let names: Vec<&str> = suggestions["tags"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tag| tag["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["Rustic", "Rust"]);
+
+    let url = "/api/content/manage/tags/suggest?q=%25";
+    let suggestions = common::response_json(common::get(&app, url, Some(&token)).await).await;
+    assert_eq!(suggestions["tags"], json!([]));
+
+    let response = common::get(&app, "/api/content/manage/tags/suggest?q=ru", None).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,188 @@
+use axum::body::Body;
+use axum::http::header::{ACCEPT_LANGUAGE, CONTENT_LANGUAGE, COOKIE};
+use axum::http::{Method, Request, StatusCode};
+use axum::{middleware, Router};
+use content_service::router::create_router;
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+use utils::configuration::{RunMode, Settings};
+use utils::i18n::localize_errors;
+use utils::state::AppState;
+use utils::test;
+
+mod common;
+
+fn settings() -> Settings {
+    let mut settings = Settings::get_config(RunMode::Test).unwrap();
+    settings.i18n.locales = vec!["en".into(), "ne".into(), "hi".into()];
+    settings.i18n.fallbacks = [("ne".into(), "hi".into())].into();
+    settings
+}
+
+#[sqlx::test(migrations = false)]
+async fn translations_are_linked_and_fall_back(pool: PgPool) {
+    let app = common::setup_app_with_settings(pool.clone(), Some(settings())).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+
+    let data = json!({"title": "Hello world", "status": "published"});
+    let original = common::post_fixture(&app, &token, data).await;
+    assert_eq!(original["locale"], json!("en"));
+    let data = json!({
+        "title": "Namaste",
+        "status": "published",
+        "locale": "ne-NP",
+        "translation_of": original["id"]
+    });
+    let translation = common::post_fixture(&app, &token, data).await;
+    assert_eq!(translation["locale"], json!("ne"));
+    assert_eq!(
+        translation["translation_group"],
+        original["translation_group"]
+    );
+    assert_eq!(
+        translation["translations"][0]["path"],
+        json!("/posts/hello-world")
+    );
+    // Drafts are linked for editors but hidden from readers.
+    let data = json!({"title": "Hallo", "locale": "hi", "translation_of": original["id"]});
+    common::post_fixture(&app, &token, data).await;
+
+    let response = common::get(&app, "/api/content/posts/hello-world", None).await;
+    let post = common::response_json(response).await;
+    assert_eq!(post["title"], json!("Hello world"));
+    let translations = post["translations"].as_array().unwrap();
+    assert_eq!(translations.len(), 1);
+    assert_eq!(translations[0]["locale"], json!("ne"));
+    assert_eq!(translations[0]["path"], json!("/ne/posts/namaste"));
+
+    // Any slug of the post leads to the translation in the requested locale.
+    let url = "/api/content/posts/hello-world?locale=ne";
+    let post = common::response_json(common::get(&app, url, None).await).await;
+    assert_eq!(post["title"], json!("Namaste"));
+    let request = Request::builder()
+        .uri("/api/content/posts/hello-world")
+        .header(ACCEPT_LANGUAGE, "ne-NP, en;q=0.5")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let post = common::response_json(response).await;
+    assert_eq!(post["title"], json!("Namaste"));
+    // The draft in `hi` is skipped in favour of the default locale.
+    let url = "/api/content/posts/namaste?locale=hi";
+    let post = common::response_json(common::get(&app, url, None).await).await;
+    assert_eq!(post["title"], json!("Hello world"));
+
+    let url = "/api/content/posts?locale=ne";
+    let page = common::response_json(common::get(&app, url, None).await).await;
+    assert_eq!(page["total"], json!(1));
+    assert_eq!(page["posts"][0]["title"], json!("Namaste"));
+    let page = common::response_json(common::get(&app, "/api/content/posts", None).await).await;
+    assert_eq!(page["total"], json!(1));
+    assert_eq!(page["posts"][0]["title"], json!("Hello world"));
+    let url = "/api/content/manage/posts?locale=hi";
+    let page = common::response_json(common::get(&app, url, Some(&token)).await).await;
+    assert_eq!(page["posts"][0]["title"], json!("Hallo"));
+}
+
+#[sqlx::test(migrations = false)]
+async fn translations_are_validated(pool: PgPool) {
+    let app = common::setup_app_with_settings(pool.clone(), Some(settings())).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+    let original = common::post_fixture(&app, &token, json!({"title": "About"})).await;
+    let url = "/api/content/manage/posts";
+
+    let data = json!({"title": "A propos", "locale": "fr"});
+    let response = common::send(&app, Method::POST, url, &token, &data).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = common::response_json(response).await;
+    assert_eq!(body["message"], json!("This language is not supported"));
+    assert_eq!(
+        body["details"]["locale"][0]["code"],
+        json!("unsupported_locale")
+    );
+
+    let data = json!({"title": "About", "kind": "page", "translation_of": original["id"]});
+    let response = common::send(&app, Method::POST, url, &token, &data).await;
+    assert_eq!(
+        common::response_json(response).await["details"]["translation_of"][0]["code"],
+        json!("invalid_translation")
+    );
+
+    // Translations may share a slug, but a post has one translation per locale.
+    let data = json!({"title": "About", "locale": "ne", "translation_of": original["id"]});
+    let translation = common::post_fixture(&app, &token, data).await;
+    assert_eq!(translation["slug"], json!("about"));
+    let data = json!({"title": "Barema", "locale": "ne", "translation_of": original["id"]});
+    let response = common::send(&app, Method::POST, url, &token, &data).await;
+    test::assert_response(
+        response,
+        StatusCode::BAD_REQUEST,
+        "Translation already exists",
+    )
+    .await;
+
+    let data = json!({"title": "About", "locale": "ne"});
+    let response = common::send(&app, Method::POST, url, &token, &data).await;
+    test::assert_response(response, StatusCode::BAD_REQUEST, "Slug already used").await;
+}
+
+#[sqlx::test(migrations = false)]
+async fn posts_are_stemmed_in_the_language_of_their_locale(pool: PgPool) {
+    let mut settings = settings();
+    settings.i18n.search_languages = [("ne".into(), "nepali".into())].into();
+    let app = common::setup_app_with_settings(pool.clone(), Some(settings)).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+
+    let data = json!({"title": "Namaste", "locale": "ne"});
+    let id = common::post_fixture(&app, &token, data).await["id"]
+        .as_i64()
+        .unwrap() as i32;
+    let language = sqlx::query_scalar!("SELECT search_language::text FROM posts WHERE id = $1", id)
+        .fetch_one(&mut *conn)
+        .await
+        .unwrap();
+    assert_eq!(language.as_deref(), Some("nepali"));
+
+    // Locales without their own configuration use the global one.
+    let url = format!("/api/content/manage/posts/{}", id);
+    let response = common::send(&app, Method::PATCH, &url, &token, &json!({"locale": "hi"})).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let language = sqlx::query_scalar!("SELECT search_language::text FROM posts WHERE id = $1", id)
+        .fetch_one(&mut *conn)
+        .await
+        .unwrap();
+    assert_eq!(language.as_deref(), Some("english"));
+}
+
+#[sqlx::test(migrations = false)]
+async fn errors_are_translated_for_the_client(pool: PgPool) {
+    common::migrate(&pool).await;
+    let state = AppState::test_state(pool, Some(settings()));
+    let app = Router::new()
+        .nest("/api/content", create_router())
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            localize_errors,
+        ))
+        .with_state(state);
+
+    let response = common::get(&app, "/api/content/posts/missing", None).await;
+    test::assert_response(response, StatusCode::NOT_FOUND, "Post not found").await;
+
+    // The cookie set by the frontend wins over the browser languages.
+    let request = Request::builder()
+        .uri("/api/content/posts/missing")
+        .header(ACCEPT_LANGUAGE, "en")
+        .header(COOKIE, "locale=ne")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.headers()[CONTENT_LANGUAGE], "ne");
+    let body = common::response_json(response).await;
+    assert_eq!(body["message"], json!("पोस्ट फेला परेन"));
+}
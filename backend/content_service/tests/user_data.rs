@@ -0,0 +1,82 @@
+use axum::http::Method;
+use content_service::user_data::{CommentData, PostData};
+use serde_json::json;
+use sqlx::PgPool;
+use utils::user_data::{UserDataOwner, UserDataSource};
+
+mod common;
+
+#[sqlx::test(migrations = false)]
+async fn authored_posts_are_exported_and_drafts_purged(pool: PgPool) {
+    let app = common::setup_app(pool.clone()).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let author = common::verified_user_fixture(&mut conn).await;
+    sqlx::query!("update users set is_admin = true where id = $1", author.id)
+        .execute(&mut *conn)
+        .await
+        .unwrap();
+    let token = common::session_fixture(&mut conn, author.id).await;
+    let data = json!({"title": "Hello world", "status": "published"});
+    let published = common::post_fixture(&app, &token, data).await;
+    common::post_fixture(&app, &token, json!({"title": "Unfinished"})).await;
+    let owner = UserDataOwner {
+        id: author.id,
+        email: author.email.clone(),
+    };
+
+    let export = PostData.export(&mut conn, &owner).await.unwrap();
+    let titles: Vec<&str> = export
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|post| post["title"].as_str().unwrap())
+        .collect();
+    assert_eq!(titles, vec!["Hello world", "Unfinished"]);
+
+    PostData.purge(&mut conn, &owner).await.unwrap();
+    assert_eq!(PostData.export(&mut conn, &owner).await.unwrap(), json!([]));
+    let response = common::get(&app, "/api/content/posts/hello-world", None).await;
+    let post = common::response_json(response).await;
+    assert_eq!(post["id"], published["id"]);
+    let remaining = sqlx::query!("SELECT count(*) as \"count!\" FROM posts")
+        .fetch_one(&mut *conn)
+        .await
+        .unwrap();
+    assert_eq!(remaining.count, 1);
+}
+
+#[sqlx::test(migrations = false)]
+async fn purged_comments_keep_the_replies_of_others(pool: PgPool) {
+    let app = common::setup_app(pool.clone()).await;
+    let mut conn = pool.acquire().await.unwrap();
+    let token = common::admin_token_fixture(&mut conn).await;
+    let data = json!({"title": "Hello world", "status": "published"});
+    common::post_fixture(&app, &token, data).await;
+    let writer = common::verified_user_fixture(&mut conn).await;
+    let writer_token = common::session_fixture(&mut conn, writer.id).await;
+    let url = "/api/content/posts/hello-world/comments";
+
+    let data = json!({"body": "Lovely write up"});
+    let response = common::send(&app, Method::POST, url, &writer_token, &data).await;
+    let parent = common::response_json(response).await;
+    let data = json!({"body": "Thank you!", "parent_id": parent["id"]});
+    common::send(&app, Method::POST, url, &token, &data).await;
+    let data = json!({"body": "Another thought"});
+    common::send(&app, Method::POST, url, &writer_token, &data).await;
+    let owner = UserDataOwner {
+        id: writer.id,
+        email: writer.email.clone(),
+    };
+
+    CommentData.purge(&mut conn, &owner).await.unwrap();
+    assert_eq!(
+        CommentData.export(&mut conn, &owner).await.unwrap(),
+        json!([])
+    );
+    let thread = common::response_json(common::get(&app, url, None).await).await;
+    let comments = thread["comments"].as_array().unwrap();
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0]["author_name"], json!("[deleted]"));
+    assert_eq!(comments[0]["body"], json!("[deleted]"));
+    assert_eq!(comments[0]["replies"][0]["body"], json!("Thank you!"));
+}
@@ -0,0 +1,23 @@
+create table media
+(
+    identifier   uuid primary key,
+    user_id      integer
+        constraint media_user_fk
+            references users on delete set null,
+    file_name    varchar(255) not null,
+    content_type varchar(100) not null,
+    size_bytes   bigint       not null,
+    width        integer,
+    height       integer,
+    storage_key  varchar(255) not null,
+    alt_text     text         not null default '',
+    caption      text         not null default '',
+    -- Resized copies, each with its name, size and storage key.
+    variants     jsonb        not null default '[]'::jsonb,
+    created_at   timestamptz  not null,
+    updated_at   timestamptz  not null
+);
+
+create index media_user_id_index on media (user_id, created_at);
+
+comment on table media is 'Uploaded images and attachments, the files themselves live in the storage backend';
@@ -0,0 +1,70 @@
+use serde_json::{json, Value};
+use util_macros::ErrorPayloadMacro;
+use utils::errors::{ErrorPayload, ErrorReport};
+use utils::storage::StorageError;
+use validator::ValidationErrors;
+
+#[derive(Debug, thiserror::Error, ErrorPayloadMacro)]
+pub enum MediaError {
+    #[error("Failed to acquire a Postgres connection from the pool")]
+    Pool(#[source] sqlx::Error),
+    #[error("Media database failed")]
+    DatabaseError(#[source] sqlx::Error),
+    #[error("Media storage failed")]
+    Storage(#[from] StorageError),
+    #[error("Invalid upload: {0}")]
+    InvalidUpload(String),
+    #[error("Failed to validate input")]
+    InvalidInput(#[source] ValidationErrors),
+    #[error("No file was uploaded")]
+    MissingFile,
+    #[error("File is larger than {max} bytes")]
+    FileTooLarge { max: usize },
+    #[error("File type is not allowed: {0}")]
+    UnsupportedType(String),
+    #[error("Image could not be processed: {0}")]
+    ImageProcessing(String),
+    #[error("Media not found")]
+    NotFound,
+    #[error("Only the uploader or an admin can change this media")]
+    Forbidden,
+}
+
+impl ErrorReport for MediaError {
+    fn message(&self) -> String {
+        self.to_string()
+    }
+
+    fn status(&self) -> u16 {
+        match self {
+            MediaError::Pool(_) => 500,
+            MediaError::DatabaseError(_) => 500,
+            MediaError::Storage(_) => 500,
+            MediaError::FileTooLarge { .. } => 413,
+            MediaError::UnsupportedType(_) => 415,
+            MediaError::ImageProcessing(_) => 422,
+            MediaError::NotFound => 404,
+            MediaError::Forbidden => 403,
+            _ => 400,
+        }
+    }
+
+    fn details(&self) -> Value {
+        match self {
+            MediaError::InvalidInput(e) => json!(e.errors()),
+            MediaError::MissingFile => {
+                ErrorPayload::form_details("file", "missing_file", &self.to_string(), None)
+            }
+            MediaError::FileTooLarge { .. } => {
+                ErrorPayload::form_details("file", "file_too_large", &self.to_string(), None)
+            }
+            MediaError::UnsupportedType(content_type) => ErrorPayload::form_details(
+                "file",
+                "unsupported_type",
+                &self.to_string(),
+                Some(content_type),
+            ),
+            _ => json!({}),
+        }
+    }
+}
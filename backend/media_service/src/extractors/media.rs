@@ -0,0 +1,135 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub static ORIGINAL_VARIANT: &str = "original";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MediaVariant {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub storage_key: String,
+}
+
+#[derive(Debug, FromRow, Clone)]
+pub struct Media {
+    pub identifier: Uuid,
+    pub user_id: Option<i32>,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub storage_key: String,
+    pub alt_text: String,
+    pub caption: String,
+    pub variants: Json<Vec<MediaVariant>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Media {
+    /// Public address of a stored file, served next to the frontend assets.
+    ///
+    /// ```
+    /// use media_service::extractors::media::Media;
+    /// use uuid::Uuid;
+    ///
+    /// let identifier = Uuid::nil();
+    /// assert_eq!(
+    ///     Media::url(identifier, "thumbnail"),
+    ///     "/media/00000000-0000-0000-0000-000000000000/thumbnail"
+    /// );
+    /// ```
+    pub fn url(identifier: Uuid, variant: &str) -> String {
+        format!("/media/{}/{}", identifier, variant)
+    }
+
+    pub fn storage_key(identifier: Uuid, variant: &str, extension: &str) -> String {
+        format!("{}/{}.{}", identifier, variant, extension)
+    }
+
+    // Storage key and content type of the original or one of the variants.
+    pub fn stored_file(&self, variant: &str) -> Option<(&str, &str)> {
+        if variant == ORIGINAL_VARIANT {
+            return Some((&self.storage_key, &self.content_type));
+        }
+        self.variants
+            .iter()
+            .find(|stored| stored.name == variant)
+            .map(|stored| (stored.storage_key.as_str(), stored.content_type.as_str()))
+    }
+
+    pub fn storage_keys(&self) -> Vec<String> {
+        std::iter::once(self.storage_key.clone())
+            .chain(
+                self.variants
+                    .iter()
+                    .map(|variant| variant.storage_key.clone()),
+            )
+            .collect()
+    }
+
+    pub fn is_owned_by(&self, user_id: i32) -> bool {
+        self.user_id == Some(user_id)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VariantDetail {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub url: String,
+}
+
+// What clients see of a media item, storage keys stay on the server.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MediaDetail {
+    pub identifier: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub alt_text: String,
+    pub caption: String,
+    pub url: String,
+    pub variants: Vec<VariantDetail>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Media> for MediaDetail {
+    fn from(media: Media) -> Self {
+        let variants = media
+            .variants
+            .iter()
+            .map(|variant| VariantDetail {
+                name: variant.name.clone(),
+                width: variant.width,
+                height: variant.height,
+                url: Media::url(media.identifier, &variant.name),
+            })
+            .collect();
+        Self {
+            identifier: media.identifier,
+            url: Media::url(media.identifier, ORIGINAL_VARIANT),
+            file_name: media.file_name,
+            content_type: media.content_type,
+            size_bytes: media.size_bytes,
+            width: media.width,
+            height: media.height,
+            alt_text: media.alt_text,
+            caption: media.caption,
+            variants,
+            created_at: media.created_at,
+            updated_at: media.updated_at,
+        }
+    }
+}
@@ -0,0 +1,291 @@
+use crate::errors::media::MediaError;
+use crate::extractors::media::{Media, MediaDetail, MediaVariant, ORIGINAL_VARIANT};
+use crate::helpers::media::{
+    count_media, delete_media, fetch_media, insert_media, list_media, remove_files,
+    update_media_text,
+};
+use crate::helpers::processing::{is_processable_image, process_image, sniff_content_type};
+use crate::payload::{MediaListQuery, MediaTextPayload};
+use crate::storage::Storage;
+use auth_service::extractors::authentication::AuthenticatedUser;
+use axum::extract::multipart::Field;
+use axum::extract::{Multipart, Path, Query, State};
+use axum::http::header::{
+    CACHE_CONTROL, CONTENT_DISPOSITION, CONTENT_TYPE, X_CONTENT_TYPE_OPTIONS,
+};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::Utc;
+use serde_json::json;
+use sqlx::types::Json as JsonColumn;
+use utils::errors::ErrorPayload;
+use utils::state::AppState;
+use utils::validation::ValidatedForm;
+use uuid::Uuid;
+use validator::Validate;
+
+// Alt text and captions are validated afterwards, this only bounds what is read.
+const TEXT_FIELD_LIMIT: usize = 16 * 1024;
+
+#[tracing::instrument(name = "Uploading media", skip(user, storage, state, multipart), fields(username = % user.user.username))]
+pub async fn upload_media(
+    user: AuthenticatedUser,
+    Storage(storage): Storage,
+    State(state): State<AppState>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let settings = &state.settings.media;
+    let mut upload = None;
+    let mut text = MediaTextPayload::default();
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|err| MediaError::InvalidUpload(err.body_text()))?
+    {
+        match field.name() {
+            Some("file") => {
+                let file_name = sanitize_file_name(field.file_name().unwrap_or_default());
+                let data = read_field(field, settings.max_upload_bytes).await?;
+                upload = Some((file_name, data));
+            }
+            Some("alt_text") => text.alt_text = Some(read_text_field(field).await?),
+            Some("caption") => text.caption = Some(read_text_field(field).await?),
+            _ => {}
+        }
+    }
+    text.validate().map_err(MediaError::InvalidInput)?;
+    let (file_name, data) = upload
+        .filter(|(_, data)| !data.is_empty())
+        .ok_or(MediaError::MissingFile)?;
+
+    let sniffed = sniff_content_type(&data)
+        .ok_or_else(|| MediaError::UnsupportedType("unknown".to_string()))?;
+    if !settings.allowed_types.contains(&sniffed.content_type) {
+        return Err(MediaError::UnsupportedType(sniffed.content_type).into());
+    }
+
+    let identifier = Uuid::new_v4();
+    let mut media = Media {
+        identifier,
+        user_id: Some(user.user.id),
+        file_name,
+        content_type: sniffed.content_type.clone(),
+        size_bytes: data.len() as i64,
+        width: None,
+        height: None,
+        storage_key: Media::storage_key(identifier, ORIGINAL_VARIANT, &sniffed.extension),
+        alt_text: text.alt_text.unwrap_or_default(),
+        caption: text.caption.unwrap_or_default(),
+        variants: JsonColumn(vec![]),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    };
+
+    let mut rendered = vec![];
+    if is_processable_image(&sniffed.content_type) {
+        let settings = settings.clone();
+        let (source, content_type) = (data.clone(), sniffed.content_type.clone());
+        let processed =
+            tokio::task::spawn_blocking(move || process_image(&source, &content_type, &settings))
+                .await
+                .map_err(|err| MediaError::ImageProcessing(err.to_string()))??;
+        media.width = Some(processed.width as i32);
+        media.height = Some(processed.height as i32);
+        rendered = processed.variants;
+    }
+
+    storage
+        .put(&media.storage_key, &media.content_type, data)
+        .await
+        .map_err(MediaError::Storage)?;
+    for variant in rendered {
+        let storage_key = Media::storage_key(identifier, &variant.name, &variant.extension);
+        media.variants.push(MediaVariant {
+            name: variant.name,
+            width: variant.width,
+            height: variant.height,
+            content_type: variant.content_type.clone(),
+            size_bytes: variant.data.len() as i64,
+            storage_key: storage_key.clone(),
+        });
+        if let Err(err) = storage
+            .put(&storage_key, &variant.content_type, variant.data)
+            .await
+        {
+            remove_files(storage.as_ref(), &media).await;
+            return Err(MediaError::Storage(err).into());
+        }
+    }
+
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(MediaError::Pool)?;
+    if let Err(err) = insert_media(&mut connection, &media).await {
+        remove_files(storage.as_ref(), &media).await;
+        return Err(err.into());
+    }
+    Ok((StatusCode::CREATED, Json(MediaDetail::from(media))))
+}
+
+// Admins see the whole library, everyone else their own uploads.
+#[tracing::instrument(name = "Listing media", skip(user, state), fields(username = % user.user.username))]
+pub async fn list_media_library(
+    user: AuthenticatedUser,
+    State(state): State<AppState>,
+    Query(query): Query<MediaListQuery>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let owner = (!user.user.is_admin).then_some(user.user.id);
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(MediaError::Pool)?;
+    let media = list_media(&mut connection, owner, query.per_page(), query.offset()).await?;
+    let total = count_media(&mut connection, owner).await?;
+    let media: Vec<MediaDetail> = media.into_iter().map(MediaDetail::from).collect();
+
+    Ok(Json(json!({
+        "media": media,
+        "total": total,
+        "page": query.page(),
+        "per_page": query.per_page(),
+    })))
+}
+
+#[tracing::instrument(name = "Viewing media", skip(user, state), fields(username = % user.user.username))]
+pub async fn media_detail(
+    user: AuthenticatedUser,
+    State(state): State<AppState>,
+    Path(identifier): Path<Uuid>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(MediaError::Pool)?;
+    let media = fetch_media(&mut connection, identifier).await?;
+    check_owner_or_admin(&user, &media)?;
+    Ok(Json(MediaDetail::from(media)))
+}
+
+#[tracing::instrument(name = "Updating media", skip(user, state, payload), fields(username = % user.user.username))]
+pub async fn update_media(
+    user: AuthenticatedUser,
+    State(state): State<AppState>,
+    Path(identifier): Path<Uuid>,
+    ValidatedForm(payload): ValidatedForm<MediaTextPayload>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(MediaError::Pool)?;
+    let media = fetch_media(&mut transaction, identifier).await?;
+    check_owner_or_admin(&user, &media)?;
+    let media = update_media_text(
+        &mut transaction,
+        identifier,
+        payload.alt_text,
+        payload.caption,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(MediaError::DatabaseError)?;
+    Ok(Json(MediaDetail::from(media)))
+}
+
+#[tracing::instrument(name = "Deleting media", skip(user, storage, state), fields(username = % user.user.username))]
+pub async fn remove_media(
+    user: AuthenticatedUser,
+    Storage(storage): Storage,
+    State(state): State<AppState>,
+    Path(identifier): Path<Uuid>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut transaction = pool.begin().await.map_err(MediaError::Pool)?;
+    let media = fetch_media(&mut transaction, identifier).await?;
+    check_owner_or_admin(&user, &media)?;
+    delete_media(&mut transaction, identifier).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(MediaError::DatabaseError)?;
+    remove_files(storage.as_ref(), &media).await;
+    Ok(Json(json!({"ok": true})))
+}
+
+// Public, media is embedded in published content. Files never change once stored.
+#[tracing::instrument(name = "Serving media", skip(storage, state))]
+pub async fn serve_media(
+    Storage(storage): Storage,
+    State(state): State<AppState>,
+    Path((identifier, variant)): Path<(Uuid, String)>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let pool = &state.connection;
+    let mut connection = pool.acquire().await.map_err(MediaError::Pool)?;
+    let media = fetch_media(&mut connection, identifier).await?;
+    let (storage_key, content_type) = media.stored_file(&variant).ok_or(MediaError::NotFound)?;
+    let data = storage
+        .get(storage_key)
+        .await
+        .map_err(MediaError::Storage)?
+        .ok_or(MediaError::NotFound)?;
+
+    // Only images are shown inline, anything else is downloaded.
+    let disposition = match content_type.starts_with("image/") {
+        true => "inline".to_string(),
+        false => format!(
+            "attachment; filename=\"{}\"",
+            media.file_name.replace('"', "")
+        ),
+    };
+    Ok((
+        [
+            (CONTENT_TYPE, content_type.to_string()),
+            (CONTENT_DISPOSITION, disposition),
+            (
+                CACHE_CONTROL,
+                "public, max-age=31536000, immutable".to_string(),
+            ),
+            (X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+        ],
+        data,
+    ))
+}
+
+fn check_owner_or_admin(user: &AuthenticatedUser, media: &Media) -> Result<(), MediaError> {
+    if user.user.is_admin || media.is_owned_by(user.user.id) {
+        return Ok(());
+    }
+    Err(MediaError::Forbidden)
+}
+
+async fn read_field(mut field: Field<'_>, limit: usize) -> Result<Vec<u8>, MediaError> {
+    let mut data = vec![];
+    while let Some(chunk) = field
+        .chunk()
+        .await
+        .map_err(|err| MediaError::InvalidUpload(err.body_text()))?
+    {
+        if data.len() + chunk.len() > limit {
+            return Err(MediaError::FileTooLarge { max: limit });
+        }
+        data.extend_from_slice(&chunk);
+    }
+    Ok(data)
+}
+
+async fn read_text_field(field: Field<'_>) -> Result<String, MediaError> {
+    let data = read_field(field, TEXT_FIELD_LIMIT).await?;
+    String::from_utf8(data).map_err(|_| MediaError::InvalidUpload("text is not utf-8".to_string()))
+}
+
+// Keeps only the last path segment of the client supplied name, `C:\photos\cat.png` becomes `cat.png`.
+fn sanitize_file_name(name: &str) -> String {
+    let name = name
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(255)
+        .collect::<String>();
+    match name.is_empty() {
+        true => "upload".to_string(),
+        false => name,
+    }
+}
@@ -0,0 +1,147 @@
+use crate::errors::media::MediaError;
+use crate::extractors::media::{Media, MediaVariant};
+use crate::storage::MediaStorage;
+use chrono::Utc;
+use sqlx::types::Json;
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+#[tracing::instrument(name = "Inserting media", skip(transaction, media))]
+pub async fn insert_media(transaction: &mut PgConnection, media: &Media) -> Result<(), MediaError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO media (identifier, user_id, file_name, content_type, size_bytes, width, height,
+            storage_key, alt_text, caption, variants, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+        "#,
+        media.identifier,
+        media.user_id,
+        media.file_name,
+        media.content_type,
+        media.size_bytes,
+        media.width,
+        media.height,
+        media.storage_key,
+        media.alt_text,
+        media.caption,
+        media.variants as _,
+        media.created_at,
+        media.updated_at
+    )
+    .execute(transaction)
+    .await
+    .map_err(MediaError::DatabaseError)?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "Fetching media", skip(transaction))]
+pub async fn fetch_media(
+    transaction: &mut PgConnection,
+    identifier: Uuid,
+) -> Result<Media, MediaError> {
+    let media = sqlx::query_as!(
+        Media,
+        r#"
+        SELECT identifier, user_id, file_name, content_type, size_bytes, width, height,
+            storage_key, alt_text, caption, variants as "variants: Json<Vec<MediaVariant>>",
+            created_at, updated_at
+        FROM media WHERE identifier = $1
+        "#,
+        identifier
+    )
+    .fetch_optional(transaction)
+    .await
+    .map_err(MediaError::DatabaseError)?;
+    media.ok_or(MediaError::NotFound)
+}
+
+// Without a user every upload is listed, newest first.
+#[tracing::instrument(name = "Listing media", skip(transaction))]
+pub async fn list_media(
+    transaction: &mut PgConnection,
+    user_id: Option<i32>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<Media>, MediaError> {
+    let media = sqlx::query_as!(
+        Media,
+        r#"
+        SELECT identifier, user_id, file_name, content_type, size_bytes, width, height,
+            storage_key, alt_text, caption, variants as "variants: Json<Vec<MediaVariant>>",
+            created_at, updated_at
+        FROM media WHERE $1::int IS NULL OR user_id = $1
+        ORDER BY created_at DESC LIMIT $2 OFFSET $3
+        "#,
+        user_id,
+        limit,
+        offset
+    )
+    .fetch_all(transaction)
+    .await
+    .map_err(MediaError::DatabaseError)?;
+    Ok(media)
+}
+
+#[tracing::instrument(name = "Counting media", skip(transaction))]
+pub async fn count_media(
+    transaction: &mut PgConnection,
+    user_id: Option<i32>,
+) -> Result<i64, MediaError> {
+    let result = sqlx::query!(
+        r#"SELECT COUNT(*) as "count!" FROM media WHERE $1::int IS NULL OR user_id = $1"#,
+        user_id
+    )
+    .fetch_one(transaction)
+    .await
+    .map_err(MediaError::DatabaseError)?;
+    Ok(result.count)
+}
+
+#[tracing::instrument(name = "Updating media text", skip(transaction, alt_text, caption))]
+pub async fn update_media_text(
+    transaction: &mut PgConnection,
+    identifier: Uuid,
+    alt_text: Option<String>,
+    caption: Option<String>,
+) -> Result<Media, MediaError> {
+    let media = sqlx::query_as!(
+        Media,
+        r#"
+        UPDATE media SET alt_text = COALESCE($2, alt_text), caption = COALESCE($3, caption),
+            updated_at = $4
+        WHERE identifier = $1
+        RETURNING identifier, user_id, file_name, content_type, size_bytes, width, height,
+            storage_key, alt_text, caption, variants as "variants: Json<Vec<MediaVariant>>",
+            created_at, updated_at
+        "#,
+        identifier,
+        alt_text,
+        caption,
+        Utc::now()
+    )
+    .fetch_optional(transaction)
+    .await
+    .map_err(MediaError::DatabaseError)?;
+    media.ok_or(MediaError::NotFound)
+}
+
+#[tracing::instrument(name = "Deleting media", skip(transaction))]
+pub async fn delete_media(
+    transaction: &mut PgConnection,
+    identifier: Uuid,
+) -> Result<(), MediaError> {
+    sqlx::query!(r#"DELETE FROM media WHERE identifier = $1"#, identifier)
+        .execute(transaction)
+        .await
+        .map_err(MediaError::DatabaseError)?;
+    Ok(())
+}
+
+// Best effort, a leftover file is harmless once its row is gone.
+pub async fn remove_files(storage: &dyn MediaStorage, media: &Media) {
+    for key in media.storage_keys() {
+        if let Err(err) = storage.delete(&key).await {
+            tracing::warn!("Failed to delete media file {}: {:?}", key, err);
+        }
+    }
+}
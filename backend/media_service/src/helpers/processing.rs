@@ -0,0 +1,124 @@
+use crate::errors::media::MediaError;
+use image::{DynamicImage, GenericImageView, ImageFormat, ImageReader, Limits};
+use std::io::Cursor;
+use utils::configuration::MediaSettings;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SniffedType {
+    pub content_type: String,
+    pub extension: String,
+}
+
+/// Detects the type from the leading bytes, so a renamed file can't pass as an image.
+///
+/// ```
+/// use media_service::helpers::processing::sniff_content_type;
+///
+/// let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+/// assert_eq!(sniff_content_type(png).unwrap().content_type, "image/png");
+/// assert_eq!(sniff_content_type(b"%PDF-1.7\n").unwrap().extension, "pdf");
+/// assert_eq!(sniff_content_type(b"<html><script>").unwrap().content_type, "text/html");
+/// assert!(sniff_content_type(b"plain words").is_none());
+/// ```
+pub fn sniff_content_type(data: &[u8]) -> Option<SniffedType> {
+    infer::get(data).map(|kind| SniffedType {
+        content_type: kind.mime_type().to_string(),
+        extension: kind.extension().to_string(),
+    })
+}
+
+pub fn is_processable_image(content_type: &str) -> bool {
+    image_format(content_type).is_some()
+}
+
+fn image_format(content_type: &str) -> Option<ImageFormat> {
+    match content_type {
+        "image/png" => Some(ImageFormat::Png),
+        "image/jpeg" => Some(ImageFormat::Jpeg),
+        "image/gif" => Some(ImageFormat::Gif),
+        "image/webp" => Some(ImageFormat::WebP),
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+pub struct RenderedVariant {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub content_type: String,
+    pub extension: String,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub struct ProcessedImage {
+    pub width: u32,
+    pub height: u32,
+    pub variants: Vec<RenderedVariant>,
+}
+
+// Decoding and resizing is CPU bound, call it from a blocking task. Variants larger than the
+// original are skipped instead of upscaled.
+pub fn process_image(
+    data: &[u8],
+    content_type: &str,
+    settings: &MediaSettings,
+) -> Result<ProcessedImage, MediaError> {
+    let format = image_format(content_type)
+        .ok_or_else(|| MediaError::UnsupportedType(content_type.to_string()))?;
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(settings.max_image_dimension);
+    limits.max_image_height = Some(settings.max_image_dimension);
+    limits.max_alloc = Some(settings.max_decode_bytes);
+    let mut reader = ImageReader::with_format(Cursor::new(data), format);
+    reader.limits(limits);
+    let image = reader
+        .decode()
+        .map_err(|err| MediaError::ImageProcessing(err.to_string()))?;
+    let (width, height) = image.dimensions();
+
+    let mut rendered = vec![];
+    for variant in &settings.variants {
+        if width <= variant.width && height <= variant.height {
+            continue;
+        }
+        let resized = image.thumbnail(variant.width, variant.height);
+        rendered.push(encode_variant(&variant.name, resized, format)?);
+    }
+    Ok(ProcessedImage {
+        width,
+        height,
+        variants: rendered,
+    })
+}
+
+// Photos stay jpeg, everything else becomes png to keep transparency.
+fn encode_variant(
+    name: &str,
+    image: DynamicImage,
+    source: ImageFormat,
+) -> Result<RenderedVariant, MediaError> {
+    let (width, height) = image.dimensions();
+    let (image, format, content_type, extension) = match source {
+        ImageFormat::Jpeg => (
+            DynamicImage::ImageRgb8(image.to_rgb8()),
+            ImageFormat::Jpeg,
+            "image/jpeg",
+            "jpg",
+        ),
+        _ => (image, ImageFormat::Png, "image/png", "png"),
+    };
+    let mut data = Cursor::new(vec![]);
+    image
+        .write_to(&mut data, format)
+        .map_err(|err| MediaError::ImageProcessing(err.to_string()))?;
+    Ok(RenderedVariant {
+        name: name.to_string(),
+        width,
+        height,
+        content_type: content_type.to_string(),
+        extension: extension.to_string(),
+        data: data.into_inner(),
+    })
+}
@@ -0,0 +1,30 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate, Default)]
+pub struct MediaTextPayload {
+    #[validate(length(max = 1000, message = "Alt text must contain at most 1000 characters"))]
+    pub alt_text: Option<String>,
+    #[validate(length(max = 5000, message = "Caption must contain at most 5000 characters"))]
+    pub caption: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct MediaListQuery {
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+impl MediaListQuery {
+    pub fn per_page(&self) -> i64 {
+        self.per_page.unwrap_or(25).clamp(1, 100)
+    }
+
+    pub fn page(&self) -> i64 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn offset(&self) -> i64 {
+        (self.page() - 1) * self.per_page()
+    }
+}
@@ -0,0 +1,26 @@
+use crate::handlers::media::{
+    list_media_library, media_detail, remove_media, serve_media, update_media, upload_media,
+};
+use axum::extract::DefaultBodyLimit;
+use axum::routing::{get, Router};
+use utils::state::AppState;
+
+pub fn create_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/",
+            get(list_media_library)
+                // The configured upload limit is enforced while reading the file.
+                .post(upload_media)
+                .layer(DefaultBodyLimit::disable()),
+        )
+        .route(
+            "/:identifier",
+            get(media_detail).patch(update_media).delete(remove_media),
+        )
+}
+
+// Mounted outside the api, next to the frontend assets.
+pub fn create_serve_router() -> Router<AppState> {
+    Router::new().route("/:identifier/:variant", get(serve_media))
+}
@@ -0,0 +1,190 @@
+use crate::errors::media::MediaError;
+use async_trait::async_trait;
+use axum::extract::FromRequestParts;
+use axum::http::header::CONTENT_TYPE;
+use axum::http::request::Parts;
+use rusty_s3::{Bucket, Credentials, S3Action, UrlStyle};
+use secrecy::ExposeSecret;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+use utils::configuration::{MediaBackend, MediaSettings, S3Settings};
+use utils::errors::ErrorPayload;
+use utils::state::AppState;
+pub use utils::storage::{MediaStorage, StorageError};
+
+// Presigned requests are sent right away, so a short window is enough.
+const SIGNATURE_LIFETIME: Duration = Duration::from_secs(60);
+
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// ```
+    /// use media_service::storage::LocalStorage;
+    ///
+    /// let storage = LocalStorage::new("/srv/media");
+    /// assert!(storage.path("abc/original.png").is_ok());
+    /// assert!(storage.path("../etc/passwd").is_err());
+    /// assert!(storage.path("/etc/passwd").is_err());
+    /// ```
+    pub fn path(&self, key: &str) -> Result<PathBuf, StorageError> {
+        let relative = Path::new(key);
+        if !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err(StorageError::InvalidKey(key.to_string()));
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+#[async_trait]
+impl MediaStorage for LocalStorage {
+    async fn put(&self, key: &str, _content_type: &str, data: Vec<u8>) -> Result<(), StorageError> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(path, data).await?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        match tokio::fs::read(self.path(key)?).await {
+            Ok(data) => Ok(Some(data)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+pub struct S3Storage {
+    bucket: Bucket,
+    credentials: Credentials,
+    client: reqwest::Client,
+}
+
+impl S3Storage {
+    pub fn new(settings: &S3Settings) -> Result<Self, StorageError> {
+        let endpoint = Url::parse(&settings.endpoint)
+            .map_err(|err| StorageError::Configuration(err.to_string()))?;
+        let style = match settings.path_style {
+            true => UrlStyle::Path,
+            false => UrlStyle::VirtualHost,
+        };
+        let bucket = Bucket::new(
+            endpoint,
+            style,
+            settings.bucket.clone(),
+            settings.region.clone(),
+        )
+        .map_err(|err| StorageError::Configuration(err.to_string()))?;
+        let credentials = Credentials::new(
+            settings.access_key.clone(),
+            settings.secret_key.expose_secret().clone(),
+        );
+        Ok(Self {
+            bucket,
+            credentials,
+            client: reqwest::Client::new(),
+        })
+    }
+}
+
+#[async_trait]
+impl MediaStorage for S3Storage {
+    async fn put(&self, key: &str, content_type: &str, data: Vec<u8>) -> Result<(), StorageError> {
+        let url = self
+            .bucket
+            .put_object(Some(&self.credentials), key)
+            .sign(SIGNATURE_LIFETIME);
+        let response = self
+            .client
+            .put(url)
+            .header(CONTENT_TYPE, content_type)
+            .body(data)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(StorageError::Status(response.status().as_u16()));
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        let url = self
+            .bucket
+            .get_object(Some(&self.credentials), key)
+            .sign(SIGNATURE_LIFETIME);
+        let response = self.client.get(url).send().await?;
+        match response.status().as_u16() {
+            404 => Ok(None),
+            status if !response.status().is_success() => Err(StorageError::Status(status)),
+            _ => Ok(Some(response.bytes().await?.to_vec())),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        let url = self
+            .bucket
+            .delete_object(Some(&self.credentials), key)
+            .sign(SIGNATURE_LIFETIME);
+        let response = self.client.delete(url).send().await?;
+        // S3 answers 204 whether or not the object existed.
+        if !response.status().is_success() && response.status().as_u16() != 404 {
+            return Err(StorageError::Status(response.status().as_u16()));
+        }
+        Ok(())
+    }
+}
+
+pub fn storage_from_settings(
+    settings: &MediaSettings,
+) -> Result<Arc<dyn MediaStorage>, StorageError> {
+    match settings.backend {
+        MediaBackend::Local => Ok(Arc::new(LocalStorage::new(&settings.local_root))),
+        MediaBackend::S3 => {
+            let s3 = settings.s3.as_ref().ok_or(StorageError::Configuration(
+                "s3 settings missing".to_string(),
+            ))?;
+            Ok(Arc::new(S3Storage::new(s3)?))
+        }
+    }
+}
+
+// The backend built from the media settings at startup.
+pub struct Storage(pub Arc<dyn MediaStorage>);
+
+#[async_trait]
+impl FromRequestParts<AppState> for Storage {
+    type Rejection = ErrorPayload;
+
+    async fn from_request_parts(
+        _parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let storage =
+            state
+                .media_storage
+                .clone()
+                .ok_or(MediaError::Storage(StorageError::Configuration(
+                    "storage was not set up".to_string(),
+                )))?;
+        Ok(Storage(storage))
+    }
+}
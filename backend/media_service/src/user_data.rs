@@ -0,0 +1,86 @@
+use crate::extractors::media::{Media, MediaVariant, ORIGINAL_VARIANT};
+use crate::helpers::media::remove_files;
+use crate::storage::MediaStorage;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use sqlx::types::Json;
+use sqlx::PgConnection;
+use std::sync::Arc;
+use utils::user_data::{UserDataOwner, UserDataSource};
+
+// Files the account uploaded, purged together with their stored originals and variants.
+pub struct MediaData {
+    storage: Arc<dyn MediaStorage>,
+}
+
+impl MediaData {
+    pub fn new(storage: Arc<dyn MediaStorage>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl UserDataSource for MediaData {
+    fn name(&self) -> &'static str {
+        "media"
+    }
+
+    #[tracing::instrument(name = "Exporting media", skip(self, connection))]
+    async fn export(
+        &self,
+        connection: &mut PgConnection,
+        owner: &UserDataOwner,
+    ) -> Result<Value, sqlx::Error> {
+        let media = sqlx::query!(
+            r#"SELECT identifier, file_name, content_type, size_bytes, width, height, alt_text,
+                caption, created_at
+            FROM media WHERE user_id = $1 ORDER BY created_at"#,
+            owner.id
+        )
+        .fetch_all(connection)
+        .await?;
+
+        Ok(media
+            .into_iter()
+            .map(|m| {
+                json!({
+                    "identifier": m.identifier,
+                    "url": Media::url(m.identifier, ORIGINAL_VARIANT),
+                    "file_name": m.file_name,
+                    "content_type": m.content_type,
+                    "size_bytes": m.size_bytes,
+                    "width": m.width,
+                    "height": m.height,
+                    "alt_text": m.alt_text,
+                    "caption": m.caption,
+                    "created_at": m.created_at,
+                })
+            })
+            .collect())
+    }
+
+    #[tracing::instrument(name = "Purging media", skip(self, connection))]
+    async fn purge(
+        &self,
+        connection: &mut PgConnection,
+        owner: &UserDataOwner,
+    ) -> Result<(), sqlx::Error> {
+        let media = sqlx::query_as!(
+            Media,
+            r#"
+            DELETE FROM media WHERE user_id = $1
+            RETURNING identifier, user_id, file_name, content_type, size_bytes, width, height,
+                storage_key, alt_text, caption, variants as "variants: Json<Vec<MediaVariant>>",
+                created_at, updated_at
+            "#,
+            owner.id
+        )
+        .fetch_all(connection)
+        .await?;
+        // Storage is not transactional, the files go even if the rest of the purge fails.
+        for media in media {
+            remove_files(self.storage.as_ref(), &media).await;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+use auth_service::extractors::user::User;
+use auth_service::helpers::confirmation::mark_user_as_confirmed;
+use auth_service::helpers::sessions::create_new_session;
+use auth_service::helpers::user::insert_user;
+use auth_service::payload::RegisterPayload;
+use axum::body::Body;
+use axum::http::header::{AUTHORIZATION, CONTENT_TYPE};
+use axum::http::{Method, Request};
+use axum::response::Response;
+use axum::Router;
+use fake::faker::internet::en::SafeEmail;
+use fake::faker::name::en::Name;
+use fake::Fake;
+use http_body_util::BodyExt;
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+use media_service::router::{create_router, create_serve_router};
+use media_service::storage::storage_from_settings;
+use serde_json::{json, Value};
+use sqlx::{PgConnection, PgPool};
+use std::io::Cursor;
+use tempfile::TempDir;
+use tower::ServiceExt;
+use utils::configuration::{MediaBackend, PasswordHashSettings};
+use utils::state::AppState;
+use uuid::Uuid;
+
+pub static STRONG_PASSWORD: &str = "r0sebudmaelstrom11/20/91aaaa";
+pub static BOUNDARY: &str = "media-test-boundary";
+
+// Media rows reference users, so the auth migrations run first.
+pub async fn migrate(pool: &PgPool) {
+    let mut auth = sqlx::migrate!("../auth_service/migrations");
+    auth.set_ignore_missing(true);
+    auth.run(pool).await.expect("Auth migrations failed");
+    let mut media = sqlx::migrate!();
+    media.set_ignore_missing(true);
+    media.run(pool).await.expect("Media migrations failed");
+}
+
+// Files are kept in a temporary directory that lives as long as the returned guard.
+#[allow(dead_code)]
+pub async fn setup_state(pool: PgPool) -> (AppState, TempDir) {
+    migrate(&pool).await;
+    let root = TempDir::new().expect("Unable to create media directory");
+    let mut state = AppState::test_state(pool, None);
+    state.settings.media.backend = MediaBackend::Local;
+    state.settings.media.local_root = root.path().to_string_lossy().to_string();
+    let storage = storage_from_settings(&state.settings.media).expect("Invalid media storage");
+    (state.with_media_storage(storage), root)
+}
+
+#[allow(dead_code)]
+pub fn setup_app(state: AppState) -> Router {
+    Router::new()
+        .nest("/api/media", create_router())
+        .nest("/media", create_serve_router())
+        .with_state(state)
+}
+
+#[allow(dead_code)]
+pub async fn verified_user_fixture(transaction: &mut PgConnection) -> User {
+    let user_payload = RegisterPayload {
+        username: format!("media{}", &Uuid::new_v4().simple().to_string()[..12]),
+        password: STRONG_PASSWORD.to_string(),
+        email: SafeEmail().fake(),
+        confirm_password: STRONG_PASSWORD.to_string(),
+        name: Name().fake(),
+        invitation: None,
+    };
+    let mut user = User::from_registration(user_payload, &PasswordHashSettings::default())
+        .expect("Cannot form new user");
+    user.id = insert_user(transaction, &user)
+        .await
+        .expect("Cannot insert user");
+    mark_user_as_confirmed(transaction, user.id)
+        .await
+        .expect("Cannot verify user");
+    user.is_confirmed = true;
+    user
+}
+
+#[allow(dead_code)]
+pub async fn session_fixture(transaction: &mut PgConnection, user_id: i32) -> String {
+    create_new_session(transaction, user_id, json!({}))
+        .await
+        .unwrap()
+}
+
+#[allow(dead_code)]
+pub fn png_fixture(width: u32, height: u32) -> Vec<u8> {
+    let image = RgbaImage::from_pixel(width, height, Rgba([200, 40, 40, 255]));
+    let mut data = Cursor::new(vec![]);
+    DynamicImage::ImageRgba8(image)
+        .write_to(&mut data, ImageFormat::Png)
+        .unwrap();
+    data.into_inner()
+}
+
+// Multipart body with the file part last, as browsers send forms.
+#[allow(dead_code)]
+pub fn multipart_body(fields: &[(&str, &str)], file: Option<(&str, &[u8])>) -> Vec<u8> {
+    let mut body = vec![];
+    for (name, value) in fields {
+        body.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                BOUNDARY, name, value
+            )
+            .as_bytes(),
+        );
+    }
+    if let Some((file_name, data)) = file {
+        body.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+                BOUNDARY, file_name
+            )
+            .as_bytes(),
+        );
+        body.extend_from_slice(data);
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(format!("--{}--\r\n", BOUNDARY).as_bytes());
+    body
+}
+
+#[allow(dead_code)]
+pub async fn upload(app: &Router, token: &str, body: Vec<u8>) -> Response {
+    let request = Request::builder()
+        .uri("/api/media")
+        .method(Method::POST)
+        .header(
+            CONTENT_TYPE,
+            format!("multipart/form-data; boundary={}", BOUNDARY),
+        )
+        .header(AUTHORIZATION, token)
+        .body(Body::from(body))
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap()
+}
+
+#[allow(dead_code)]
+pub async fn response_json(response: Response) -> Value {
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&body).unwrap()
+}
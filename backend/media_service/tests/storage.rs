@@ -0,0 +1,110 @@
+use axum::body::Bytes;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::put;
+use axum::Router;
+use media_service::storage::{LocalStorage, MediaStorage, S3Storage};
+use secrecy::Secret;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+use tokio::net::TcpListener;
+use utils::configuration::S3Settings;
+
+type Objects = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+#[tokio::test]
+async fn local_storage_round_trip() {
+    let root = TempDir::new().unwrap();
+    let storage = LocalStorage::new(root.path());
+    check_round_trip(&storage).await;
+    assert!(storage
+        .put("../escape.txt", "text/plain", vec![1])
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn s3_storage_round_trip() {
+    let objects = Objects::default();
+    let endpoint = s3_stand_in(objects.clone()).await;
+    let storage = S3Storage::new(&S3Settings {
+        endpoint,
+        bucket: "media".to_string(),
+        region: "us-east-1".to_string(),
+        access_key: "access".to_string(),
+        secret_key: Secret::from("secret".to_string()),
+        path_style: true,
+    })
+    .unwrap();
+    check_round_trip(&storage).await;
+    assert!(objects.lock().unwrap().is_empty());
+}
+
+async fn check_round_trip(storage: &dyn MediaStorage) {
+    let key = "abc/original.png";
+    assert_eq!(storage.get(key).await.unwrap(), None);
+    storage
+        .put(key, "image/png", b"stored".to_vec())
+        .await
+        .unwrap();
+    assert_eq!(storage.get(key).await.unwrap(), Some(b"stored".to_vec()));
+    storage.delete(key).await.unwrap();
+    assert_eq!(storage.get(key).await.unwrap(), None);
+    storage.delete(key).await.unwrap();
+}
+
+// Keeps objects in memory and answers like S3 for the three operations the storage uses.
+// Signatures aren't checked, only that one was sent.
+async fn s3_stand_in(objects: Objects) -> String {
+    async fn put_object(
+        State(objects): State<Objects>,
+        Path((bucket, key)): Path<(String, String)>,
+        body: Bytes,
+    ) -> StatusCode {
+        let mut objects = objects.lock().unwrap();
+        objects.insert(format!("{}/{}", bucket, key), body.to_vec());
+        StatusCode::OK
+    }
+    async fn get_object(
+        State(objects): State<Objects>,
+        Path((bucket, key)): Path<(String, String)>,
+    ) -> Result<Vec<u8>, StatusCode> {
+        let objects = objects.lock().unwrap();
+        objects
+            .get(&format!("{}/{}", bucket, key))
+            .cloned()
+            .ok_or(StatusCode::NOT_FOUND)
+    }
+    async fn delete_object(
+        State(objects): State<Objects>,
+        Path((bucket, key)): Path<(String, String)>,
+    ) -> StatusCode {
+        let mut objects = objects.lock().unwrap();
+        objects.remove(&format!("{}/{}", bucket, key));
+        StatusCode::NO_CONTENT
+    }
+
+    let app = Router::new()
+        .route(
+            "/:bucket/*key",
+            put(put_object).get(get_object).delete(delete_object),
+        )
+        .layer(axum::middleware::from_fn(
+            |request: axum::extract::Request, next: axum::middleware::Next| async move {
+                let signed = request
+                    .uri()
+                    .query()
+                    .is_some_and(|query| query.contains("X-Amz-Signature="));
+                match signed {
+                    true => next.run(request).await,
+                    false => axum::response::IntoResponse::into_response(StatusCode::FORBIDDEN),
+                }
+            },
+        ))
+        .with_state(objects);
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", address)
+}
@@ -0,0 +1,177 @@
+use axum::body::Body;
+use axum::http::header::{AUTHORIZATION, CACHE_CONTROL, CONTENT_TYPE};
+use axum::http::{Request, StatusCode};
+use axum::response::Response;
+use axum::{http, Router};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tower::ServiceExt;
+use utils::test;
+
+mod common;
+
+#[sqlx::test(migrations = false)]
+async fn upload_image_creates_variants_and_serves_them(pool: PgPool) {
+    let (state, _root) = common::setup_state(pool.clone()).await;
+    let app = common::setup_app(state);
+    let mut conn = pool.acquire().await.unwrap();
+    let user = common::verified_user_fixture(&mut conn).await;
+    let token = common::session_fixture(&mut conn, user.id).await;
+
+    let png = common::png_fixture(1000, 500);
+    let body = common::multipart_body(
+        &[("alt_text", "A red square"), ("caption", "Sunset")],
+        Some(("../../photos/red.png", &png)),
+    );
+    let response = common::upload(&app, &token, body).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let media = common::response_json(response).await;
+    assert_eq!(media["file_name"], json!("red.png"));
+    assert_eq!(media["content_type"], json!("image/png"));
+    assert_eq!(media["width"], json!(1000));
+    assert_eq!(media["height"], json!(500));
+    assert_eq!(media["alt_text"], json!("A red square"));
+    assert!(media.get("storage_key").is_none());
+
+    let variants = media["variants"].as_array().unwrap();
+    let names: Vec<&str> = variants
+        .iter()
+        .map(|variant| variant["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["thumbnail", "medium"]);
+    assert_eq!(variants[0]["width"], json!(200));
+    assert_eq!(variants[0]["height"], json!(100));
+
+    let response = get(&app, variants[0]["url"].as_str().unwrap(), None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[CONTENT_TYPE], "image/png");
+    assert!(response.headers()[CACHE_CONTROL]
+        .to_str()
+        .unwrap()
+        .contains("immutable"));
+    let thumbnail = response.into_body().collect().await.unwrap().to_bytes();
+    let thumbnail = image::load_from_memory(&thumbnail).unwrap();
+    assert_eq!((thumbnail.width(), thumbnail.height()), (200, 100));
+
+    let response = get(&app, media["url"].as_str().unwrap(), None).await;
+    let original = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(original.to_vec(), png);
+
+    let response = get(&app, "/api/media", Some(&token)).await;
+    let library = common::response_json(response).await;
+    assert_eq!(library["total"], json!(1));
+}
+
+#[sqlx::test(migrations = false)]
+async fn upload_rejects_spoofed_oversized_and_missing_files(pool: PgPool) {
+    let (mut state, _root) = common::setup_state(pool.clone()).await;
+    state.settings.media.max_upload_bytes = 1024;
+    let app = common::setup_app(state);
+    let mut conn = pool.acquire().await.unwrap();
+    let user = common::verified_user_fixture(&mut conn).await;
+    let token = common::session_fixture(&mut conn, user.id).await;
+
+    let body = common::multipart_body(&[], Some(("evil.png", b"<script>alert(1)</script>")));
+    let response = common::upload(&app, &token, body).await;
+    test::assert_response(
+        response,
+        StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        "File type is not allowed: text/html",
+    )
+    .await;
+
+    let png = common::png_fixture(20, 20);
+    assert!(png.len() < 1024);
+    let mut large = png.clone();
+    large.resize(2048, 0);
+    let body = common::multipart_body(&[], Some(("large.png", &large)));
+    let response = common::upload(&app, &token, body).await;
+    test::assert_response(
+        response,
+        StatusCode::PAYLOAD_TOO_LARGE,
+        "File is larger than 1024 bytes",
+    )
+    .await;
+
+    let (mut state, _root) = common::setup_state(pool.clone()).await;
+    state.settings.media.max_image_dimension = 100;
+    let limited = common::setup_app(state);
+    let body = common::multipart_body(&[], Some(("wide.png", &common::png_fixture(200, 20))));
+    let response = common::upload(&limited, &token, body).await;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let body = common::multipart_body(&[("alt_text", "nothing")], None);
+    let response = common::upload(&app, &token, body).await;
+    test::assert_response(response, StatusCode::BAD_REQUEST, "No file was uploaded").await;
+
+    let body = common::multipart_body(&[], Some(("small.png", &png)));
+    let response = common::upload(&app, "", body).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[sqlx::test(migrations = false)]
+async fn only_the_uploader_changes_or_deletes_media(pool: PgPool) {
+    let (state, root) = common::setup_state(pool.clone()).await;
+    let app = common::setup_app(state);
+    let mut conn = pool.acquire().await.unwrap();
+    let owner = common::verified_user_fixture(&mut conn).await;
+    let owner_token = common::session_fixture(&mut conn, owner.id).await;
+    let other = common::verified_user_fixture(&mut conn).await;
+    let other_token = common::session_fixture(&mut conn, other.id).await;
+
+    let body = common::multipart_body(&[], Some(("tiny.png", &common::png_fixture(10, 10))));
+    let media = common::response_json(common::upload(&app, &owner_token, body).await).await;
+    assert_eq!(media["variants"], json!([]));
+    let url = format!("/api/media/{}", media["identifier"].as_str().unwrap());
+
+    let response = get(&app, &url, Some(&other_token)).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = get(&app, &url, Some(&owner_token)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let data = json!({"alt_text": "Stolen"});
+    let response = send(&app, http::Method::PATCH, &url, &other_token, &data).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = send(&app, http::Method::DELETE, &url, &other_token, &json!({})).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let data = json!({"alt_text": "A tiny square"});
+    let response = send(&app, http::Method::PATCH, &url, &owner_token, &data).await;
+    let updated = common::response_json(response).await;
+    assert_eq!(updated["alt_text"], json!("A tiny square"));
+    assert_eq!(updated["caption"], json!(""));
+
+    let response = send(&app, http::Method::DELETE, &url, &owner_token, &json!({})).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = get(&app, media["url"].as_str().unwrap(), None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let files = std::fs::read_dir(root.path())
+        .unwrap()
+        .flat_map(|dir| std::fs::read_dir(dir.unwrap().path()).unwrap())
+        .count();
+    assert_eq!(files, 0);
+}
+
+async fn get(app: &Router, url: &str, token: Option<&str>) -> Response {
+    let mut request = Request::builder().uri(url).method(http::Method::GET);
+    if let Some(token) = token {
+        request = request.header(AUTHORIZATION, token);
+    }
+    let request = request.body(Body::empty()).unwrap();
+    app.clone().oneshot(request).await.unwrap()
+}
+
+async fn send(
+    app: &Router,
+    method: http::Method,
+    url: &str,
+    token: &str,
+    data: &Value,
+) -> Response {
+    let mut request = test::build_request(url, method, data);
+    request
+        .headers_mut()
+        .insert(AUTHORIZATION, token.parse().unwrap());
+    app.clone().oneshot(request).await.unwrap()
+}
@@ -0,0 +1,44 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use media_service::storage::storage_from_settings;
+use media_service::user_data::MediaData;
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+use utils::user_data::{UserDataOwner, UserDataSource};
+
+mod common;
+
+#[sqlx::test(migrations = false)]
+async fn uploads_are_exported_and_purged_with_their_files(pool: PgPool) {
+    let (state, root) = common::setup_state(pool.clone()).await;
+    let source = MediaData::new(storage_from_settings(&state.settings.media).unwrap());
+    let app = common::setup_app(state);
+    let mut conn = pool.acquire().await.unwrap();
+    let user = common::verified_user_fixture(&mut conn).await;
+    let token = common::session_fixture(&mut conn, user.id).await;
+    let body = common::multipart_body(&[], Some(("photo.png", &common::png_fixture(800, 600))));
+    let media = common::response_json(common::upload(&app, &token, body).await).await;
+    let owner = UserDataOwner {
+        id: user.id,
+        email: user.email.clone(),
+    };
+
+    let export = source.export(&mut conn, &owner).await.unwrap();
+    assert_eq!(export[0]["identifier"], media["identifier"]);
+    assert_eq!(export[0]["file_name"], json!("photo.png"));
+
+    source.purge(&mut conn, &owner).await.unwrap();
+    assert_eq!(source.export(&mut conn, &owner).await.unwrap(), json!([]));
+    let request = Request::builder()
+        .uri(media["url"].as_str().unwrap())
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let files = std::fs::read_dir(root.path())
+        .unwrap()
+        .flat_map(|dir| std::fs::read_dir(dir.unwrap().path()).unwrap())
+        .count();
+    assert_eq!(files, 0);
+}
@@ -0,0 +1,16 @@
+-- Sent issues are kept for the public archive and search.
+create table newsletters
+(
+    id              serial primary key,
+    title           text        not null,
+    plain           text        not null,
+    html            text        not null,
+    search_language regconfig   not null default 'english',
+    search_vector   tsvector generated always as (
+        setweight(to_tsvector(search_language, title), 'A') ||
+        setweight(to_tsvector(search_language, plain), 'C')
+    ) stored,
+    sent_at         timestamptz not null
+);
+
+create index newsletters_search_vector_index on newsletters using gin (search_vector);
@@ -7,6 +7,12 @@ pub enum NewsletterError {
     PoolError(#[source] sqlx::Error),
     #[error("Failed to add subscriber: {0}")]
     ConfirmedSubscribersError(#[source] sqlx::Error),
+    #[error("Failed to archive newsletter: {0}")]
+    ArchiveError(#[source] sqlx::Error),
+    #[error("Failed to read the newsletter archive: {0}")]
+    ArchiveReadError(#[source] sqlx::Error),
+    #[error("Newsletter not found")]
+    NotFound,
 }
 
 impl ErrorReport for NewsletterError {
@@ -15,6 +21,9 @@ impl ErrorReport for NewsletterError {
     }
 
     fn status(&self) -> u16 {
-        500
+        match self {
+            NewsletterError::NotFound => 404,
+            _ => 500,
+        }
     }
 }
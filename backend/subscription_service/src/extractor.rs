@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use email_clients::email::{EmailAddress, EmailObject};
 use unicode_segmentation::UnicodeSegmentation;
 use validator::{Validate, ValidationError};
@@ -30,6 +31,42 @@ pub struct NewsletterPayload {
     pub content: NewsletterContent,
 }
 
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct ArchivedNewsletter {
+    pub id: i32,
+    pub title: String,
+    pub plain: String,
+    pub html: String,
+    pub sent_at: DateTime<Utc>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct NewsletterSummary {
+    pub id: i32,
+    pub title: String,
+    pub sent_at: DateTime<Utc>,
+}
+
+#[derive(serde::Deserialize, Default)]
+pub struct ArchiveQuery {
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+impl ArchiveQuery {
+    pub fn per_page(&self) -> i64 {
+        self.per_page.unwrap_or(20).clamp(1, 100)
+    }
+
+    pub fn page(&self) -> i64 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn offset(&self) -> i64 {
+        (self.page() - 1) * self.per_page()
+    }
+}
+
 #[derive(serde::Serialize, serde::Deserialize)]
 pub struct ConfirmedSubscriber {
     pub name: String,
@@ -1,11 +1,12 @@
 use crate::errors::subscribe::SubscribeError;
-use crate::extractor::{NewsletterPayload, SubscriptionPayload};
+use crate::extractor::{ArchiveQuery, NewsletterPayload, SubscriptionPayload};
 use crate::helper;
 use crate::helper::{
-    confirm_subscription, generate_subscription_token, get_confirmed_subscribers,
-    get_subscriber_id_from_token, send_newsletter_email, store_token,
+    archive_newsletter, confirm_subscription, fetch_archived_newsletter,
+    generate_subscription_token, get_confirmed_subscribers, get_subscriber_id_from_token,
+    list_archived_newsletters, send_newsletter_email, store_token,
 };
-use axum::extract::{Query, State};
+use axum::extract::{Path, Query, State};
 use axum::response::{IntoResponse, Result};
 use axum::Json;
 use serde::Deserialize;
@@ -82,7 +83,32 @@ pub async fn publish_newsletter(
         ));
     }
     let confirmed_users = get_confirmed_subscribers(pool).await?;
+    archive_newsletter(pool, &payload, &state.settings.search.language).await?;
 
     let count = send_newsletter_email(&state, payload, confirmed_users).await;
     Ok(format!("Sent email to {} subscribers", count))
 }
+
+#[tracing::instrument(name = "Newsletter archive", skip(state, query))]
+pub async fn newsletter_archive(
+    State(state): State<AppState>,
+    Query(query): Query<ArchiveQuery>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let (newsletters, total) =
+        list_archived_newsletters(&state.connection, query.per_page(), query.offset()).await?;
+    Ok(Json(json!({
+        "newsletters": newsletters,
+        "total": total,
+        "page": query.page(),
+        "per_page": query.per_page(),
+    })))
+}
+
+#[tracing::instrument(name = "Archived newsletter", skip(state))]
+pub async fn archived_newsletter(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorPayload> {
+    let newsletter = fetch_archived_newsletter(&state.connection, id).await?;
+    Ok(Json(newsletter))
+}
@@ -1,6 +1,9 @@
 use crate::errors::confirmation::ConfirmationError;
 use crate::errors::subscribe::SubscribeError;
-use crate::extractor::{ConfirmedSubscriber, NewsletterPayload, SubscriptionPayload};
+use crate::extractor::{
+    ArchivedNewsletter, ConfirmedSubscriber, NewsletterPayload, NewsletterSummary,
+    SubscriptionPayload,
+};
 use chrono::Utc;
 use email_clients::email::{EmailAddress, EmailObject};
 use rand::distributions::Alphanumeric;
@@ -155,6 +158,73 @@ pub async fn get_confirmed_subscribers(
     Ok(result)
 }
 
+// Keeps the issue for the public archive, the search index is a generated column.
+#[tracing::instrument(name = "archive the newsletter", skip(pool, payload))]
+pub async fn archive_newsletter(
+    pool: &PgPool,
+    payload: &NewsletterPayload,
+    search_language: &str,
+) -> Result<i32, NewsletterError> {
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO newsletters (title, plain, html, search_language, sent_at)
+        VALUES ($1, $2, $3, $4::text::regconfig, $5)
+        RETURNING id
+        "#,
+        payload.title,
+        payload.content.plain,
+        payload.content.html,
+        search_language,
+        Utc::now()
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(NewsletterError::ArchiveError)?;
+    Ok(row.id)
+}
+
+#[tracing::instrument(name = "list the newsletter archive", skip(pool))]
+pub async fn list_archived_newsletters(
+    pool: &PgPool,
+    limit: i64,
+    offset: i64,
+) -> Result<(Vec<NewsletterSummary>, i64), NewsletterError> {
+    let newsletters = sqlx::query_as!(
+        NewsletterSummary,
+        r#"
+        SELECT id, title, sent_at FROM newsletters
+        ORDER BY sent_at DESC, id DESC LIMIT $1 OFFSET $2
+        "#,
+        limit,
+        offset
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(NewsletterError::ArchiveReadError)?;
+    let total = sqlx::query!(r#"SELECT count(*) as "count!" FROM newsletters"#)
+        .fetch_one(pool)
+        .await
+        .map_err(NewsletterError::ArchiveReadError)?
+        .count;
+    Ok((newsletters, total))
+}
+
+#[tracing::instrument(name = "fetch an archived newsletter", skip(pool))]
+pub async fn fetch_archived_newsletter(
+    pool: &PgPool,
+    id: i32,
+) -> Result<ArchivedNewsletter, NewsletterError> {
+    sqlx::query_as!(
+        ArchivedNewsletter,
+        "SELECT id, title, plain, html, sent_at FROM newsletters WHERE id = $1",
+        id
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(NewsletterError::ArchiveReadError)?
+    .ok_or(NewsletterError::NotFound)
+}
+
 #[tracing::instrument(
     name = "send the confirmation email",
     skip(state, payload, confirmed_users)
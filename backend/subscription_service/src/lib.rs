@@ -5,3 +5,4 @@ mod extractor;
 mod handler;
 pub mod helper;
 pub mod router;
+pub mod user_data;
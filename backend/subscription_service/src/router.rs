@@ -2,11 +2,15 @@ use axum::routing::method_routing::post;
 use axum::routing::{get, Router};
 use utils::state::AppState;
 
-use crate::handler::{confirm, publish_newsletter, subscribe};
+use crate::handler::{
+    archived_newsletter, confirm, newsletter_archive, publish_newsletter, subscribe,
+};
 
 pub fn create_router() -> Router<AppState> {
     Router::new()
         .route("/newsletter", post(publish_newsletter))
+        .route("/newsletters", get(newsletter_archive))
+        .route("/newsletters/:id", get(archived_newsletter))
         .route("/confirm", get(confirm))
         .route("/", post(subscribe))
 }
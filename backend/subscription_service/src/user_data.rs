@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use sqlx::PgConnection;
+use utils::user_data::{UserDataOwner, UserDataSource};
+
+// Newsletter subscriptions are matched to accounts by email address.
+pub struct SubscriptionData;
+
+#[async_trait]
+impl UserDataSource for SubscriptionData {
+    fn name(&self) -> &'static str {
+        "subscriptions"
+    }
+
+    #[tracing::instrument(name = "Exporting subscriptions", skip(self, connection))]
+    async fn export(
+        &self,
+        connection: &mut PgConnection,
+        owner: &UserDataOwner,
+    ) -> Result<Value, sqlx::Error> {
+        let subscriptions = sqlx::query!(
+            r#"SELECT id, email, name, subscribed_at, status FROM subscriptions
+            WHERE lower(email) = lower($1)"#,
+            owner.email
+        )
+        .fetch_all(connection)
+        .await?;
+
+        Ok(subscriptions
+            .into_iter()
+            .map(|s| {
+                json!({
+                    "id": s.id,
+                    "email": s.email,
+                    "name": s.name,
+                    "subscribed_at": s.subscribed_at,
+                    "status": s.status,
+                })
+            })
+            .collect())
+    }
+
+    #[tracing::instrument(name = "Purging subscriptions", skip(self, connection))]
+    async fn purge(
+        &self,
+        connection: &mut PgConnection,
+        owner: &UserDataOwner,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"DELETE FROM subscription_tokens WHERE subscription_id IN
+            (SELECT id FROM subscriptions WHERE lower(email) = lower($1))"#,
+            owner.email
+        )
+        .execute(&mut *connection)
+        .await?;
+        sqlx::query!(
+            r#"DELETE FROM subscriptions WHERE lower(email) = lower($1)"#,
+            owner.email
+        )
+        .execute(&mut *connection)
+        .await?;
+        Ok(())
+    }
+}
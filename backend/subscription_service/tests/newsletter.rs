@@ -5,7 +5,8 @@ use crate::helper::{create_confirmed_subscriber, get_confirmation_link};
 use axum::http;
 use axum::http::StatusCode;
 use claims::assert_err;
-use serde_json::json;
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
 use sqlx::PgPool;
 use std::sync::mpsc;
 use subscription_service::router::create_router;
@@ -18,7 +19,7 @@ async fn newsletter_are_not_delivered_to_unconfirmed_subscriber(pool: PgPool) {
     let (email_tx, email_rx) = mpsc::sync_channel(5);
     let (task_tx, task_rx) = mpsc::sync_channel(5);
 
-    let mut state = test::test_state_for_email(pool, email_tx);
+    let mut state = test::test_state_for_email(pool.clone(), email_tx);
     state.tasks = Some(task_tx);
 
     let app = create_router().with_state(state);
@@ -44,6 +45,13 @@ async fn newsletter_are_not_delivered_to_unconfirmed_subscriber(pool: PgPool) {
         .unwrap();
     assert_eq!(response.status(), StatusCode::OK);
     assert_eq!(email_rx.try_iter().count(), 1); // Assert only one email is sent.
+
+    let archived = sqlx::query!("SELECT title, plain FROM newsletters")
+        .fetch_one(&pool)
+        .await
+        .expect("Newsletter was not archived");
+    assert_eq!(archived.title, "Newsletter title");
+    assert_eq!(archived.plain, "Newsletter body as plain text");
 }
 
 #[sqlx::test]
@@ -99,3 +107,38 @@ async fn newsletter_returns_a_400_for_invalid_form_data(pool: PgPool) {
         );
     }
 }
+
+#[sqlx::test]
+async fn newsletter_archive_lists_sent_issues(pool: PgPool) {
+    for title in ["First issue", "Second issue"] {
+        sqlx::query!(
+            "INSERT INTO newsletters (title, plain, html, sent_at) VALUES ($1, 'Plain body', '<p>Html body</p>', now())",
+            title
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+    let state = AppState::test_state(pool, None);
+    let app = create_router().with_state(state);
+
+    let request = test::build_request("/newsletters?per_page=1", http::Method::GET, &json!({}));
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let archive: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(archive["total"], json!(2));
+    assert_eq!(archive["newsletters"][0]["title"], json!("Second issue"));
+    assert!(archive["newsletters"][0].get("html").is_none());
+
+    let url = format!("/newsletters/{}", archive["newsletters"][0]["id"]);
+    let request = test::build_request(&url, http::Method::GET, &json!({}));
+    let response = app.clone().oneshot(request).await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let newsletter: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(newsletter["html"], json!("<p>Html body</p>"));
+
+    let request = test::build_request("/newsletters/0", http::Method::GET, &json!({}));
+    let response = app.oneshot(request).await.unwrap();
+    test::assert_response(response, StatusCode::NOT_FOUND, "Newsletter not found").await;
+}
@@ -0,0 +1,36 @@
+use serde_json::json;
+use sqlx::PgPool;
+use subscription_service::helper::store_token;
+use subscription_service::user_data::SubscriptionData;
+use utils::user_data::{UserDataOwner, UserDataSource};
+use uuid::Uuid;
+
+#[sqlx::test]
+async fn subscriptions_are_exported_and_purged_by_email(pool: PgPool) {
+    let mut conn = pool.acquire().await.expect("Unable to acquire connection");
+    let owner = UserDataOwner {
+        id: 1,
+        email: "Owner@example.com".to_string(),
+    };
+    let subscriber_id = Uuid::new_v4();
+    sqlx::query!(
+        "insert into subscriptions (id, email, name, subscribed_at) values ($1, $2, $3, now())",
+        subscriber_id,
+        "owner@example.com",
+        "Owner"
+    )
+    .execute(&mut *conn)
+    .await
+    .expect("Cannot insert subscription");
+    store_token(&mut conn, subscriber_id, "token")
+        .await
+        .unwrap();
+
+    let exported = SubscriptionData.export(&mut conn, &owner).await.unwrap();
+    assert_eq!(exported.as_array().unwrap().len(), 1);
+    assert_eq!(exported[0]["status"], json!("pending"));
+
+    SubscriptionData.purge(&mut conn, &owner).await.unwrap();
+    let exported = SubscriptionData.export(&mut conn, &owner).await.unwrap();
+    assert!(exported.as_array().unwrap().is_empty());
+}
@@ -1,3 +1,5 @@
+use crate::rate_limit::RateLimitPolicy;
+use chrono::Duration;
 use config::{Config, ConfigError, Environment, File};
 use email_clients::clients::mailersend::MailerSendConfig;
 use email_clients::clients::{memory::MemoryConfig, smtp::SmtpConfig, terminal::TerminalConfig};
@@ -6,6 +8,7 @@ use email_clients::errors::EmailError;
 use secrecy::{ExposeSecret, Secret};
 use std::env;
 use std::path::PathBuf;
+use translations::LocaleConfig;
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum RunMode {
@@ -57,6 +60,34 @@ pub struct Settings {
     pub application: ApplicationSettings,
     pub email: EmailSettings,
     pub frontend: FrontendSettings,
+    #[serde(default)]
+    pub rate_limit: RateLimitSettings,
+    #[serde(default)]
+    pub account: AccountSettings,
+    #[serde(default)]
+    pub registration: RegistrationSettings,
+    #[serde(default)]
+    pub security: SecuritySettings,
+    #[serde(default)]
+    pub password_policy: PasswordPolicy,
+    #[serde(default)]
+    pub password_hash: PasswordHashSettings,
+    #[serde(default)]
+    pub tokens: ClientTokenSettings,
+    #[serde(default)]
+    pub cookie: CookieSettings,
+    #[serde(default)]
+    pub csrf: CsrfSettings,
+    #[serde(default)]
+    pub media: MediaSettings,
+    #[serde(default)]
+    pub search: SearchSettings,
+    #[serde(default)]
+    pub seo: SeoSettings,
+    #[serde(default)]
+    pub comments: CommentSettings,
+    #[serde(default)]
+    pub i18n: LocaleConfig,
 }
 
 #[derive(Clone, Debug, serde::Deserialize)]
@@ -113,6 +144,461 @@ impl ApplicationSettings {
     }
 }
 
+#[derive(Clone, Debug, serde::Deserialize)]
+pub enum RateLimitBackend {
+    Postgres,
+    Memory,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct RateLimitSettings {
+    pub backend: RateLimitBackend,
+    // Only enable behind a proxy that overwrites the X-Forwarded-For header.
+    pub trust_forwarded_for: bool,
+    pub free_attempts: u32,
+    pub base_delay_seconds: i64,
+    pub max_delay_seconds: i64,
+    pub lockout_after: u32,
+    pub lockout_minutes: i64,
+    pub ip_free_attempts: u32,
+    pub reset_requests_per_hour: u32,
+}
+
+impl Default for RateLimitSettings {
+    fn default() -> Self {
+        Self {
+            backend: RateLimitBackend::Postgres,
+            trust_forwarded_for: false,
+            free_attempts: 3,
+            base_delay_seconds: 1,
+            max_delay_seconds: 60,
+            lockout_after: 10,
+            lockout_minutes: 15,
+            ip_free_attempts: 20,
+            reset_requests_per_hour: 3,
+        }
+    }
+}
+
+impl RateLimitSettings {
+    pub fn login_policy(&self) -> RateLimitPolicy {
+        RateLimitPolicy {
+            free_attempts: self.free_attempts,
+            base_delay: Duration::try_seconds(self.base_delay_seconds).unwrap(),
+            max_delay: Duration::try_seconds(self.max_delay_seconds).unwrap(),
+            lockout_after: Some(self.lockout_after),
+            lockout: Duration::try_minutes(self.lockout_minutes).unwrap(),
+            window: Duration::try_minutes(self.lockout_minutes).unwrap(),
+        }
+    }
+
+    pub fn ip_policy(&self) -> RateLimitPolicy {
+        RateLimitPolicy {
+            free_attempts: self.ip_free_attempts,
+            lockout_after: None,
+            ..self.login_policy()
+        }
+    }
+
+    pub fn reset_policy(&self) -> RateLimitPolicy {
+        let hour = Duration::try_hours(1).unwrap();
+        RateLimitPolicy {
+            free_attempts: self.reset_requests_per_hour,
+            base_delay: hour,
+            max_delay: hour,
+            lockout_after: None,
+            lockout: hour,
+            window: hour,
+        }
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct AccountSettings {
+    // Deleted accounts can be restored until the grace period runs out.
+    pub deletion_grace_days: i64,
+    pub purge_interval_minutes: u64,
+    pub export_expiry_days: i64,
+}
+
+impl Default for AccountSettings {
+    fn default() -> Self {
+        Self {
+            deletion_grace_days: 30,
+            purge_interval_minutes: 60,
+            export_expiry_days: 7,
+        }
+    }
+}
+
+impl AccountSettings {
+    pub fn deletion_grace(&self) -> Duration {
+        Duration::try_days(self.deletion_grace_days).unwrap()
+    }
+
+    pub fn export_expiry(&self) -> Duration {
+        Duration::try_days(self.export_expiry_days).unwrap()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RegistrationMode {
+    Open,
+    // Sign-up only works with a valid invitation token.
+    InviteOnly,
+    Closed,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct RegistrationSettings {
+    pub mode: RegistrationMode,
+    pub invite_expiry_days: i64,
+}
+
+impl Default for RegistrationSettings {
+    fn default() -> Self {
+        Self {
+            mode: RegistrationMode::Open,
+            invite_expiry_days: 7,
+        }
+    }
+}
+
+impl RegistrationSettings {
+    pub fn invite_expiry(&self) -> Duration {
+        Duration::try_days(self.invite_expiry_days)
+            .expect("invite expiry is validated when the settings are loaded")
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        match Duration::try_days(self.invite_expiry_days) {
+            Some(_) if self.invite_expiry_days > 0 => Ok(()),
+            _ => Err(ConfigError::Message(format!(
+                "registration.invite_expiry_days must be a positive number of days, got {}",
+                self.invite_expiry_days
+            ))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub max_length: usize,
+    // zxcvbn score between 0 and 4.
+    pub min_score: u8,
+    // Words such as the site name that shouldn't carry a password on their own.
+    pub forbidden_inputs: Vec<String>,
+    // Range files named after the first five hex characters of the SHA-1 hash, with
+    // `SUFFIX:COUNT` lines, as served by the Pwned Passwords range API.
+    pub breached_hashes_dir: Option<String>,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 8,
+            max_length: 72,
+            min_score: 3,
+            forbidden_inputs: vec![],
+            breached_hashes_dir: None,
+        }
+    }
+}
+
+// Argon2id cost. Stored hashes made with other values are upgraded on the next sign in.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct PasswordHashSettings {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+    // Server side secret mixed into every hash. Hashes made under a different pepper stop verifying.
+    pub pepper: Option<Secret<String>>,
+}
+
+impl Default for PasswordHashSettings {
+    fn default() -> Self {
+        Self {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+            pepper: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CookieSameSite {
+    Strict,
+    Lax,
+    None,
+}
+
+// Attributes of the session cookie used by the web frontend.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct CookieSettings {
+    // Turn off only when serving over plain http outside localhost.
+    pub secure: bool,
+    pub http_only: bool,
+    pub same_site: CookieSameSite,
+    pub domain: Option<String>,
+    pub path: String,
+}
+
+impl Default for CookieSettings {
+    fn default() -> Self {
+        Self {
+            secure: true,
+            http_only: true,
+            same_site: CookieSameSite::Lax,
+            domain: None,
+            path: "/".to_string(),
+        }
+    }
+}
+
+// Cookie authenticated requests that change state must come from one of these origins. The
+// application url and the request's own host are always trusted.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct CsrfSettings {
+    pub enabled: bool,
+    pub trusted_origins: Vec<String>,
+}
+
+impl Default for CsrfSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            trusted_origins: vec![],
+        }
+    }
+}
+
+// Lifetimes for the access and refresh tokens handed to api clients, the web session is unaffected.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct ClientTokenSettings {
+    pub access_token_minutes: i64,
+    pub refresh_token_days: i64,
+}
+
+impl Default for ClientTokenSettings {
+    fn default() -> Self {
+        Self {
+            access_token_minutes: 15,
+            refresh_token_days: 30,
+        }
+    }
+}
+
+impl ClientTokenSettings {
+    pub fn access_token_lifetime(&self) -> Duration {
+        Duration::try_minutes(self.access_token_minutes).unwrap()
+    }
+
+    pub fn refresh_token_lifetime(&self) -> Duration {
+        Duration::try_days(self.refresh_token_days).unwrap()
+    }
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct SecuritySettings {
+    // Registration answers the same way for new and taken emails, and doesn't sign in until verified.
+    // Login reports unknown accounts the same way as wrong passwords.
+    pub hardened: bool,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub enum MediaBackend {
+    Local,
+    S3,
+}
+
+// Any S3 compatible service, such as MinIO or R2 with `path_style` set.
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct S3Settings {
+    pub endpoint: String,
+    pub bucket: String,
+    pub region: String,
+    pub access_key: String,
+    pub secret_key: Secret<String>,
+    #[serde(default)]
+    pub path_style: bool,
+}
+
+// Resized copy generated for uploaded images, fitting within the bounds while keeping the ratio.
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct ImageVariantSettings {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct MediaSettings {
+    pub backend: MediaBackend,
+    pub local_root: String,
+    pub s3: Option<S3Settings>,
+    pub max_upload_bytes: usize,
+    // Checked against the type sniffed from the content, the client supplied one is ignored.
+    pub allowed_types: Vec<String>,
+    pub variants: Vec<ImageVariantSettings>,
+    // Bounds on decoding, a small file can still claim huge dimensions.
+    pub max_image_dimension: u32,
+    pub max_decode_bytes: u64,
+}
+
+impl Default for MediaSettings {
+    fn default() -> Self {
+        let variant = |name: &str, size: u32| ImageVariantSettings {
+            name: name.to_string(),
+            width: size,
+            height: size,
+        };
+        Self {
+            backend: MediaBackend::Local,
+            local_root: "media".to_string(),
+            s3: None,
+            max_upload_bytes: 10 * 1024 * 1024,
+            allowed_types: [
+                "image/png",
+                "image/jpeg",
+                "image/gif",
+                "image/webp",
+                "application/pdf",
+            ]
+            .map(String::from)
+            .to_vec(),
+            variants: vec![
+                variant("thumbnail", 200),
+                variant("medium", 800),
+                variant("large", 1600),
+            ],
+            max_image_dimension: 10_000,
+            max_decode_bytes: 512 * 1024 * 1024,
+        }
+    }
+}
+
+// Postgres text search configuration new content is stemmed with, such as `english` or `simple`.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct SearchSettings {
+    pub language: String,
+}
+
+impl Default for SearchSettings {
+    fn default() -> Self {
+        Self {
+            language: "english".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct CommentSettings {
+    pub enabled: bool,
+    // Guests confirm their email address before a comment reaches the moderation queue.
+    pub allow_guests: bool,
+    // Hold comments of verified users for moderation too, admins are never held.
+    pub moderate_users: bool,
+    // Top level comments have depth zero.
+    pub max_depth: i32,
+    // Links allowed before a comment counts towards spam.
+    pub max_links: usize,
+    // Comments allowed per hour for every user or client address.
+    pub per_hour: u32,
+    pub notify_authors: bool,
+}
+
+impl Default for CommentSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            allow_guests: true,
+            moderate_users: false,
+            max_depth: 4,
+            max_links: 2,
+            per_hour: 10,
+            notify_authors: true,
+        }
+    }
+}
+
+impl CommentSettings {
+    pub fn rate_limit_policy(&self) -> RateLimitPolicy {
+        let hour = Duration::try_hours(1).unwrap();
+        RateLimitPolicy {
+            free_attempts: self.per_hour,
+            base_delay: hour,
+            max_delay: hour,
+            lockout_after: None,
+            lockout: hour,
+            window: hour,
+        }
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct SeoSettings {
+    // Larger sitemaps are split behind a sitemap index, 50,000 is the protocol limit.
+    pub sitemap_urls_per_file: i64,
+    pub robots: RobotsSettings,
+    // Used for pages that are not content entries.
+    pub description: String,
+    // Shared when an entry has no image of its own, relative urls are resolved against the site.
+    pub default_image: Option<String>,
+    // Twitter handle of the site, such as `@example`.
+    pub twitter_site: Option<String>,
+}
+
+impl Default for SeoSettings {
+    fn default() -> Self {
+        Self {
+            sitemap_urls_per_file: 50_000,
+            robots: RobotsSettings::default(),
+            description: String::new(),
+            default_image: None,
+            twitter_site: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct RobotsSettings {
+    // Turn off on staging sites to keep every crawler out.
+    pub allow_indexing: bool,
+    pub disallow: Vec<String>,
+    // Appended as is, such as rules for a single crawler.
+    pub extra: String,
+}
+
+impl Default for RobotsSettings {
+    fn default() -> Self {
+        Self {
+            allow_indexing: true,
+            disallow: ["/api/", "/admin/", "/auth/", "/settings/"]
+                .map(String::from)
+                .to_vec(),
+            extra: String::new(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, serde::Deserialize)]
 pub enum EmailMode {
     Terminal,
@@ -192,7 +678,9 @@ impl Settings {
             // You may also programmatically change settings
             .build()?;
         // You can deserialize (and thus freeze) the entire configuration as
-        s.try_deserialize()
+        let settings: Self = s.try_deserialize()?;
+        settings.registration.validate()?;
+        Ok(settings)
     }
 
     fn get_root_dir() -> PathBuf {
@@ -1,5 +1,6 @@
 use axum::extract::rejection::{ExtensionRejection, JsonRejection};
-use axum::http::StatusCode;
+use axum::http::header::RETRY_AFTER;
+use axum::http::{HeaderValue, StatusCode};
 use axum::response::{IntoResponse, Response};
 use axum::Json;
 use phf::phf_map;
@@ -12,6 +13,12 @@ static DATABASE_ERRORS: phf::Map<&'static str, (&'static str, u16)> = phf_map! {
     "duplicate key value violates unique constraint \"users_email_key\"" => ("Email already registered", 400),
     "duplicate key value violates unique constraint \"users_username_key\"" => ("Username not available", 400),
     "duplicate key value violates unique constraint \"users_normalized_username_key\"" => ("Username not available", 400),
+    "duplicate key value violates unique constraint \"posts_kind_locale_slug_key\"" => ("Slug already used", 400),
+    "duplicate key value violates unique constraint \"posts_translation_group_locale_key\"" => ("Translation already exists", 400),
+    "duplicate key value violates unique constraint \"terms_taxonomy_slug_key\"" => ("Slug already used", 400),
+    "duplicate key value violates unique constraint \"content_types_slug_key\"" => ("Slug already used", 400),
+    "duplicate key value violates unique constraint \"content_entries_type_slug_key\"" => ("Slug already used", 400),
+    "duplicate key value violates unique constraint \"menus_slug_key\"" => ("Slug already used", 400),
 };
 
 pub trait ErrorReport {
@@ -30,6 +37,11 @@ pub trait ErrorReport {
     fn details(&self) -> serde_json::Value {
         json!({})
     }
+
+    // Seconds the client should wait before retrying, sent as `Retry-After`.
+    fn retry_after(&self) -> Option<u64> {
+        None
+    }
 }
 
 #[derive(serde::Deserialize, Default, Debug)]
@@ -38,6 +50,8 @@ pub struct ErrorPayload {
     message: String,
     status: u16,
     details: Value,
+    #[serde(default)]
+    retry_after: Option<u64>,
 }
 
 impl ErrorPayload {
@@ -47,6 +61,7 @@ impl ErrorPayload {
             level: level.unwrap_or("error").to_string(),
             status: status.unwrap_or(400),
             details: json!({}),
+            retry_after: None,
         }
     }
 
@@ -71,6 +86,7 @@ impl ErrorPayload {
             level: error.level().to_string(),
             status,
             details: error.details(),
+            retry_after: error.retry_after(),
         }
     }
 
@@ -135,11 +151,17 @@ impl IntoResponse for ErrorPayload {
             "status": self.status,
             "details": self.details
         });
-        (
+        let mut response = (
             StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
             Json(response),
         )
-            .into_response()
+            .into_response();
+        if let Some(retry_after) = self.retry_after {
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from(retry_after));
+        }
+        response
     }
 }
 
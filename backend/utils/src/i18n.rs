@@ -0,0 +1,127 @@
+use crate::state::AppState;
+use axum::async_trait;
+use axum::body::Body;
+use axum::extract::{FromRequestParts, Request, State};
+use axum::http::header::{ACCEPT_LANGUAGE, CONTENT_LANGUAGE, CONTENT_LENGTH, CONTENT_TYPE};
+use axum::http::request::Parts;
+use axum::http::{HeaderMap, HeaderValue};
+use axum::middleware::Next;
+use axum::response::Response;
+use axum_extra::extract::CookieJar;
+use http_body_util::BodyExt;
+use serde_json::Value;
+use std::convert::Infallible;
+use translations::catalog::SOURCE_LOCALE;
+use translations::locale::{language, LOCALE_COOKIE};
+use translations::{translate, LocaleConfig};
+
+// Locale the client asked for, the cookie set by the frontend wins over `Accept-Language`.
+pub fn request_locale(config: &LocaleConfig, headers: &HeaderMap) -> String {
+    let cookie = CookieJar::from_headers(headers)
+        .get(LOCALE_COOKIE)
+        .and_then(|cookie| config.resolve(cookie.value()));
+    cookie.unwrap_or_else(|| {
+        let accept_language = headers
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        config.negotiate(accept_language)
+    })
+}
+
+#[derive(Debug)]
+pub struct RequestLocale(pub String);
+
+#[async_trait]
+impl FromRequestParts<AppState> for RequestLocale {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(RequestLocale(request_locale(
+            &state.settings.i18n,
+            &parts.headers,
+        )))
+    }
+}
+
+// Error messages are written in English, clients asking for another language get the message
+// and every field error translated through the shared catalog.
+pub async fn localize_errors(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let locale = request_locale(&state.settings.i18n, request.headers());
+    let response = next.run(request).await;
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.starts_with("application/json"))
+        .unwrap_or(false);
+    let is_error = response.status().is_client_error() || response.status().is_server_error();
+    if !is_json || !is_error || language(&locale) == SOURCE_LOCALE {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match body.collect().await {
+        Ok(collected) => collected.to_bytes(),
+        Err(err) => {
+            tracing::error!("Unable to read error response: {:?}", err);
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+    let mut payload: Value = match serde_json::from_slice(&bytes) {
+        Ok(payload) => payload,
+        Err(_) => return Response::from_parts(parts, Body::from(bytes)),
+    };
+    translate_payload(&locale, &mut payload);
+    parts.headers.remove(CONTENT_LENGTH);
+    if let Ok(value) = HeaderValue::from_str(&locale) {
+        parts.headers.insert(CONTENT_LANGUAGE, value);
+    }
+    Response::from_parts(parts, Body::from(payload.to_string()))
+}
+
+/// Translates the message of an error payload and of each of its field errors.
+///
+/// ```
+/// use serde_json::json;
+/// use utils::i18n::translate_payload;
+///
+/// let mut payload = json!({
+///     "message": "Failed to validate input",
+///     "details": {"title": [{
+///         "code": "length",
+///         "message": "Title must contain between 1 and 255 characters"
+///     }]}
+/// });
+/// translate_payload("ne", &mut payload);
+/// assert_eq!(payload["message"], "इनपुट प्रमाणीकरण असफल भयो");
+/// assert_eq!(payload["details"]["title"][0]["message"], "शीर्षकमा १ देखि २५५ अक्षरसम्म हुनुपर्छ");
+/// assert_eq!(payload["details"]["title"][0]["code"], "length");
+/// ```
+pub fn translate_payload(locale: &str, payload: &mut Value) {
+    if let Some(Value::String(message)) = payload.get_mut("message") {
+        *message = translate(locale, message);
+    }
+    let details = match payload.get_mut("details") {
+        Some(Value::Object(details)) => details,
+        _ => return,
+    };
+    for errors in details.values_mut() {
+        let errors = match errors {
+            Value::Array(errors) => errors,
+            _ => continue,
+        };
+        for error in errors {
+            if let Some(Value::String(message)) = error.get_mut("message") {
+                *message = translate(locale, message);
+            }
+        }
+    }
+}
@@ -1,6 +1,11 @@
+pub mod client_ip;
 pub mod configuration;
 pub mod email;
 pub mod errors;
+pub mod i18n;
+pub mod rate_limit;
 pub mod state;
+pub mod storage;
 pub mod test;
+pub mod user_data;
 pub mod validation;
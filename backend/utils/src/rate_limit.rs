@@ -0,0 +1,274 @@
+use crate::configuration::{RateLimitBackend, RateLimitSettings};
+use crate::errors::{ErrorPayload, ErrorReport};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, thiserror::Error)]
+pub enum RateLimitError {
+    #[error("Too many attempts, please try again later")]
+    TooManyAttempts(i64),
+    #[error("Too many failed attempts, please try again later")]
+    Locked(i64),
+    #[error("Rate limit storage failed")]
+    Storage(#[source] sqlx::Error),
+}
+
+impl ErrorReport for RateLimitError {
+    fn message(&self) -> String {
+        self.to_string()
+    }
+
+    fn status(&self) -> u16 {
+        match self {
+            RateLimitError::TooManyAttempts(_) => 429,
+            RateLimitError::Locked(_) => 429,
+            RateLimitError::Storage(_) => 500,
+        }
+    }
+
+    fn retry_after(&self) -> Option<u64> {
+        match self {
+            RateLimitError::TooManyAttempts(seconds) => Some(*seconds as u64),
+            RateLimitError::Locked(seconds) => Some(*seconds as u64),
+            RateLimitError::Storage(_) => None,
+        }
+    }
+}
+
+impl From<RateLimitError> for ErrorPayload {
+    fn from(value: RateLimitError) -> Self {
+        ErrorPayload::from_error(value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitEntry {
+    pub attempts: i32,
+    pub last_attempt_at: DateTime<Utc>,
+    pub locked_until: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimitPolicy {
+    // Attempts allowed before any delay is enforced.
+    pub free_attempts: u32,
+    // Delay after the first throttled attempt, doubled for every further one.
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    // Lock the key entirely once this many attempts are recorded.
+    pub lockout_after: Option<u32>,
+    pub lockout: Duration,
+    // Attempts are forgotten when the key stays quiet for this long.
+    pub window: Duration,
+}
+
+impl RateLimitPolicy {
+    pub fn check(
+        &self,
+        entry: Option<&RateLimitEntry>,
+        now: DateTime<Utc>,
+    ) -> Result<(), RateLimitError> {
+        let entry = match entry {
+            Some(entry) => entry,
+            None => return Ok(()),
+        };
+        if let Some(locked_until) = entry.locked_until {
+            if locked_until > now {
+                return Err(RateLimitError::Locked(seconds_until(locked_until, now)));
+            }
+        }
+        if self.is_stale(entry, now) {
+            return Ok(());
+        }
+
+        let allowed_at = entry.last_attempt_at + self.delay(entry.attempts);
+        if allowed_at > now {
+            return Err(RateLimitError::TooManyAttempts(seconds_until(
+                allowed_at, now,
+            )));
+        }
+        Ok(())
+    }
+
+    /// The entry after recording one more attempt on top of `entry`.
+    pub fn next_entry(&self, entry: Option<RateLimitEntry>, now: DateTime<Utc>) -> RateLimitEntry {
+        let attempts = match entry {
+            Some(entry) if !self.is_stale(&entry, now) => entry.attempts + 1,
+            _ => 1,
+        };
+        let locked_until = self
+            .lockout_after
+            .filter(|lockout_after| attempts >= *lockout_after as i32)
+            .map(|_| now + self.lockout);
+
+        RateLimitEntry {
+            attempts,
+            last_attempt_at: now,
+            locked_until,
+        }
+    }
+
+    fn delay(&self, attempts: i32) -> Duration {
+        let throttled = attempts - self.free_attempts as i32;
+        if throttled < 0 {
+            return Duration::zero();
+        }
+        let factor = 2i32.pow(throttled.min(16) as u32);
+        (self.base_delay * factor).min(self.max_delay)
+    }
+
+    fn is_stale(&self, entry: &RateLimitEntry, now: DateTime<Utc>) -> bool {
+        if let Some(locked_until) = entry.locked_until {
+            return locked_until <= now;
+        }
+        now - entry.last_attempt_at > self.window
+    }
+}
+
+fn seconds_until(time: DateTime<Utc>, now: DateTime<Utc>) -> i64 {
+    // Round up so that clients never retry a moment too early.
+    let milliseconds = (time - now).num_milliseconds();
+    ((milliseconds + 999) / 1000).max(1)
+}
+
+#[async_trait]
+pub trait RateLimiter: Send + Sync {
+    async fn get(&self, key: &str) -> Result<Option<RateLimitEntry>, RateLimitError>;
+
+    /// Records an attempt for the key and returns the updated entry.
+    async fn hit(
+        &self,
+        key: &str,
+        policy: &RateLimitPolicy,
+    ) -> Result<RateLimitEntry, RateLimitError>;
+
+    async fn clear(&self, key: &str) -> Result<(), RateLimitError>;
+
+    async fn check(&self, key: &str, policy: &RateLimitPolicy) -> Result<(), RateLimitError> {
+        let entry = self.get(key).await?;
+        policy.check(entry.as_ref(), Utc::now())
+    }
+}
+
+pub fn rate_limiter(settings: &RateLimitSettings, connection: PgPool) -> Arc<dyn RateLimiter> {
+    match settings.backend {
+        RateLimitBackend::Postgres => Arc::new(PostgresRateLimiter::new(connection)),
+        RateLimitBackend::Memory => Arc::new(MemoryRateLimiter::default()),
+    }
+}
+
+// Only suitable for a single server process, counters are lost on restart.
+#[derive(Default)]
+pub struct MemoryRateLimiter {
+    entries: Mutex<HashMap<String, RateLimitEntry>>,
+}
+
+#[async_trait]
+impl RateLimiter for MemoryRateLimiter {
+    async fn get(&self, key: &str) -> Result<Option<RateLimitEntry>, RateLimitError> {
+        let entries = self.entries.lock().unwrap();
+        Ok(entries.get(key).cloned())
+    }
+
+    async fn hit(
+        &self,
+        key: &str,
+        policy: &RateLimitPolicy,
+    ) -> Result<RateLimitEntry, RateLimitError> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = policy.next_entry(entries.remove(key), Utc::now());
+        entries.insert(key.to_string(), entry.clone());
+        Ok(entry)
+    }
+
+    async fn clear(&self, key: &str) -> Result<(), RateLimitError> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.remove(key);
+        Ok(())
+    }
+}
+
+// Backed by the `rate_limits` table created by the auth service migrations.
+pub struct PostgresRateLimiter {
+    connection: PgPool,
+}
+
+impl PostgresRateLimiter {
+    pub fn new(connection: PgPool) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl RateLimiter for PostgresRateLimiter {
+    async fn get(&self, key: &str) -> Result<Option<RateLimitEntry>, RateLimitError> {
+        let entry = sqlx::query_as!(
+            RateLimitEntry,
+            r#"
+            SELECT attempts, last_attempt_at, locked_until FROM rate_limits WHERE key = $1
+            "#,
+            key
+        )
+        .fetch_optional(&self.connection)
+        .await
+        .map_err(RateLimitError::Storage)?;
+        Ok(entry)
+    }
+
+    async fn hit(
+        &self,
+        key: &str,
+        policy: &RateLimitPolicy,
+    ) -> Result<RateLimitEntry, RateLimitError> {
+        let mut transaction = self
+            .connection
+            .begin()
+            .await
+            .map_err(RateLimitError::Storage)?;
+
+        // Lock the row so concurrent attempts cannot overwrite each other's count.
+        let entry = sqlx::query_as!(
+            RateLimitEntry,
+            r#"
+            SELECT attempts, last_attempt_at, locked_until FROM rate_limits WHERE key = $1 FOR UPDATE
+            "#,
+            key
+        )
+        .fetch_optional(&mut *transaction)
+        .await
+        .map_err(RateLimitError::Storage)?;
+
+        let entry = policy.next_entry(entry, Utc::now());
+        sqlx::query!(
+            r#"
+            INSERT INTO rate_limits (key, attempts, last_attempt_at, locked_until)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (key) DO UPDATE SET attempts = $2, last_attempt_at = $3, locked_until = $4
+            "#,
+            key,
+            entry.attempts,
+            entry.last_attempt_at,
+            entry.locked_until
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(RateLimitError::Storage)?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(RateLimitError::Storage)?;
+        Ok(entry)
+    }
+
+    async fn clear(&self, key: &str) -> Result<(), RateLimitError> {
+        sqlx::query!("DELETE FROM rate_limits WHERE key = $1", key)
+            .execute(&self.connection)
+            .await
+            .map_err(RateLimitError::Storage)?;
+        Ok(())
+    }
+}
@@ -1,4 +1,7 @@
 use crate::configuration::{RunMode, Settings};
+use crate::rate_limit::{rate_limiter, RateLimiter};
+use crate::storage::MediaStorage;
+use crate::user_data::UserDataSource;
 use axum::extract::FromRef;
 use axum_extra::extract::cookie::Key;
 use email_clients::clients::{get_email_client, EmailClient};
@@ -6,6 +9,7 @@ use email_clients::configuration::EmailConfiguration;
 use sqlx::postgres::PgPoolOptions;
 use sqlx::PgPool;
 use std::sync::mpsc::SyncSender;
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::task::JoinHandle;
 use uuid::Uuid;
@@ -32,6 +36,10 @@ pub struct AppState {
     pub connection: PgPool,
     pub email_client: EmailClient,
     pub tasks: Option<SyncSender<BackgroundTask>>,
+    pub rate_limiter: Arc<dyn RateLimiter>,
+    pub user_data: Vec<Arc<dyn UserDataSource>>,
+    // Built once by the media app, the S3 backend keeps its http client between requests.
+    pub media_storage: Option<Arc<dyn MediaStorage>>,
 }
 
 impl AppState {
@@ -48,11 +56,16 @@ impl AppState {
             .expect("Invalid email configuration");
         let email_client = get_email_client(email_configuration);
 
+        let rate_limiter = rate_limiter(&settings.rate_limit, connection.clone());
+
         Self {
             settings,
             connection,
             email_client,
             tasks: None,
+            rate_limiter,
+            user_data: vec![],
+            media_storage: None,
         }
     }
 
@@ -65,11 +78,16 @@ impl AppState {
             .expect("Invalid email configuration");
         let email_client = get_email_client(email_configuration);
 
+        let rate_limiter = rate_limiter(&settings.rate_limit, connection.clone());
+
         Self {
             settings,
             connection,
             email_client,
             tasks: None,
+            rate_limiter,
+            user_data: vec![],
+            media_storage: None,
         }
     }
 
@@ -84,26 +102,48 @@ impl AppState {
             .expect("Invalid email configuration");
         let email_client = get_email_client(email_configuration);
 
+        let rate_limiter = rate_limiter(&settings.rate_limit, connection.clone());
+
         Self {
             settings,
             connection,
             email_client,
             tasks: None,
+            rate_limiter,
+            user_data: vec![],
+            media_storage: None,
         }
     }
 
     pub fn test_email_state(connection: PgPool, email_client: EmailClient) -> Self {
         let settings = Settings::get_config(RunMode::Test).expect("Unable to fetch test config");
 
+        let rate_limiter = rate_limiter(&settings.rate_limit, connection.clone());
+
         Self {
             settings,
             connection,
             email_client,
             tasks: None,
+            rate_limiter,
+            user_data: vec![],
+            media_storage: None,
         }
     }
 }
 
+impl AppState {
+    pub fn with_user_data(mut self, source: impl UserDataSource + 'static) -> Self {
+        self.user_data.push(Arc::new(source));
+        self
+    }
+
+    pub fn with_media_storage(mut self, storage: Arc<dyn MediaStorage>) -> Self {
+        self.media_storage = Some(storage);
+        self
+    }
+}
+
 impl FromRef<AppState> for PgPool {
     fn from_ref(app_state: &AppState) -> PgPool {
         app_state.connection.clone()
@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+
+#[derive(Debug, thiserror::Error)]
+pub enum StorageError {
+    #[error("Storage file operation failed")]
+    Io(#[from] std::io::Error),
+    #[error("Storage request failed")]
+    Request(#[from] reqwest::Error),
+    #[error("Storage responded with status {0}")]
+    Status(u16),
+    #[error("Storage is misconfigured: {0}")]
+    Configuration(String),
+    #[error("Invalid storage key: {0}")]
+    InvalidKey(String),
+}
+
+/// Where uploaded files and their variants are kept, addressed by keys like `{identifier}/original.png`.
+#[async_trait]
+pub trait MediaStorage: Send + Sync {
+    async fn put(&self, key: &str, content_type: &str, data: Vec<u8>) -> Result<(), StorageError>;
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError>;
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError>;
+}
@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+use serde_json::Value;
+use sqlx::PgConnection;
+
+// The account whose data is being exported or purged.
+#[derive(Debug, Clone)]
+pub struct UserDataOwner {
+    pub id: i32,
+    pub email: String,
+}
+
+// Implemented by apps that keep records tied to a user outside the users table,
+// so that data exports and account purges can include them.
+#[async_trait]
+pub trait UserDataSource: Send + Sync {
+    // Key the exported records are stored under in the archive.
+    fn name(&self) -> &'static str;
+
+    async fn export(
+        &self,
+        connection: &mut PgConnection,
+        owner: &UserDataOwner,
+    ) -> Result<Value, sqlx::Error>;
+
+    async fn purge(
+        &self,
+        connection: &mut PgConnection,
+        owner: &UserDataOwner,
+    ) -> Result<(), sqlx::Error>;
+}
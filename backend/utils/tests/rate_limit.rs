@@ -0,0 +1,87 @@
+use chrono::{Duration, Utc};
+use utils::rate_limit::{MemoryRateLimiter, RateLimitError, RateLimitPolicy, RateLimiter};
+
+fn policy() -> RateLimitPolicy {
+    RateLimitPolicy {
+        free_attempts: 2,
+        base_delay: Duration::try_seconds(10).unwrap(),
+        max_delay: Duration::try_seconds(30).unwrap(),
+        lockout_after: Some(5),
+        lockout: Duration::try_minutes(15).unwrap(),
+        window: Duration::try_minutes(15).unwrap(),
+    }
+}
+
+#[test]
+fn delay_grows_after_free_attempts() {
+    let policy = policy();
+    let now = Utc::now();
+
+    let entry = policy.next_entry(None, now);
+    assert!(policy.check(Some(&entry), now).is_ok());
+
+    let entry = policy.next_entry(Some(entry), now);
+    assert!(matches!(
+        policy.check(Some(&entry), now),
+        Err(RateLimitError::TooManyAttempts(10))
+    ));
+
+    let entry = policy.next_entry(Some(entry), now);
+    assert!(matches!(
+        policy.check(Some(&entry), now),
+        Err(RateLimitError::TooManyAttempts(20))
+    ));
+
+    let entry = policy.next_entry(Some(entry), now);
+    assert!(matches!(
+        policy.check(Some(&entry), now),
+        Err(RateLimitError::TooManyAttempts(30))
+    ));
+}
+
+#[test]
+fn lockout_expires_and_resets_attempts() {
+    let policy = policy();
+    let now = Utc::now();
+
+    let mut entry = None;
+    for _ in 0..5 {
+        entry = Some(policy.next_entry(entry, now));
+    }
+    let entry = entry.unwrap();
+    assert!(entry.locked_until.is_some());
+    assert!(matches!(
+        policy.check(Some(&entry), now),
+        Err(RateLimitError::Locked(900))
+    ));
+
+    let later = now + Duration::try_minutes(16).unwrap();
+    assert!(policy.check(Some(&entry), later).is_ok());
+    assert_eq!(policy.next_entry(Some(entry), later).attempts, 1);
+}
+
+#[test]
+fn stale_attempts_are_forgotten() {
+    let policy = policy();
+    let now = Utc::now();
+
+    let entry = policy.next_entry(None, now);
+    let entry = policy.next_entry(Some(entry), now);
+    let later = now + Duration::try_minutes(20).unwrap();
+    assert!(policy.check(Some(&entry), later).is_ok());
+    assert_eq!(policy.next_entry(Some(entry), later).attempts, 1);
+}
+
+#[tokio::test]
+async fn memory_limiter_tracks_and_clears_keys() {
+    let limiter = MemoryRateLimiter::default();
+    let policy = policy();
+
+    limiter.hit("login:user:one", &policy).await.unwrap();
+    limiter.hit("login:user:one", &policy).await.unwrap();
+    assert!(limiter.check("login:user:one", &policy).await.is_err());
+    assert!(limiter.check("login:user:two", &policy).await.is_ok());
+
+    limiter.clear("login:user:one").await.unwrap();
+    assert!(limiter.check("login:user:one", &policy).await.is_ok());
+}
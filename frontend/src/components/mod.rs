@@ -2,4 +2,5 @@ pub(crate) mod button;
 pub(crate) mod error_line;
 pub(crate) mod input;
 pub(crate) mod navbar;
+pub(crate) mod pager;
 pub(crate) mod toast;
@@ -1,11 +1,31 @@
+use crate::entities::menu::MenuItem;
+use crate::routes::Route;
 use crate::state::AppState;
 use crate::utils;
 use crate::utils::api::logout::logout;
+use crate::utils::api::menus::{menu, PRIMARY_MENU};
 use dioxus::prelude::*;
 
 #[component]
 pub fn AuthenticatedNavbar() -> Element {
     let mut app_context = consume_context::<Signal<AppState>>();
+    let (is_admin, username) = app_context
+        .read()
+        .user
+        .as_ref()
+        .map(|user| (user.is_admin, user.username.clone()))
+        .unwrap_or_default();
+    let t = move |message: &str| app_context.read().t(message);
+    let home = format!("/{}", app_context.read().locale());
+
+    // A missing menu leaves the navigation empty rather than interrupting the page.
+    let primary_menu = use_resource(|| async { menu(PRIMARY_MENU).await.ok() });
+    let items = primary_menu
+        .read()
+        .clone()
+        .flatten()
+        .map(|menu| menu.items)
+        .unwrap_or_default();
 
     let onclick = move |_| async move {
         let response = logout().await;
@@ -27,7 +47,7 @@ pub fn AuthenticatedNavbar() -> Element {
                 div { class: "flex h-full flex-nowrap items-center gap-20 2xl:gap-32",
                     a {
                         title: "AmritCMS",
-                        href: "/en",
+                        href: "{home}",
                         translate: "no",
                         class: "text-heading border-heading whitespace-nowrap border-[3px] px-3 py-1 text-lg font-bold uppercase tracking-widest sm:text-xl",
                         " AmritCMS "
@@ -35,30 +55,26 @@ pub fn AuthenticatedNavbar() -> Element {
                     nav {
                         "aria-label": "Navigation",
                         class: "hidden tracking-wide xl:flex",
-                        h2 { class: "sr-only", id: "navigation", "Navigation" }
+                        h2 { class: "sr-only", id: "navigation", {t("Navigation")} }
                         menu { class: "text-heading flex space-x-10 tracking-wider",
-                            li {
-                                a {
-                                    href: "/en/blog",
-                                    class: "inline-flex whitespace-nowrap py-4 text-base font-semibold",
-                                    "Link 1"
-                                }
-                            }
-                            li {
-                                a {
-                                    href: "/en/blog",
-                                    class: "inline-flex whitespace-nowrap py-4 text-base font-semibold",
-                                    "Link 2"
-                                }
+                            for item in items.iter() {
+                                DesktopMenuItem { key: "{item.id}", item: item.clone() }
                             }
                         }
                     }
                 }
                 div { class: "text-heading hidden items-center gap-4 font-semibold xl:flex",
+                    if is_admin {
+                        a {
+                            href: "/admin/users",
+                            class: "hover:text-heading flex items-center gap-2",
+                            span { {t("Admin")} }
+                        }
+                    }
                     a {
-                        href: "/en/contact",
+                        href: "/settings/profile",
                         class: "hover:text-heading flex items-center gap-2",
-                        span { "Username" }
+                        span { "{username}" }
                     }
                     svg {
                         "xmlns": "http://www.w3.org/2000/svg",
@@ -73,12 +89,12 @@ pub fn AuthenticatedNavbar() -> Element {
                     }
                     div {
                         button { onclick, class: "flex items-center justify-center gap-2",
-                            div { " Logout" }
+                            div { {t("Logout")} }
                         }
                     }
                 }
                 aside { class: "flex h-full w-1/2 flex-1 justify-end xl:hidden",
-                    h2 { class: "sr-only", id: "mobile-navigation", "Mobile navigation" }
+                    h2 { class: "sr-only", id: "mobile-navigation", {t("Mobile navigation")} }
                     div { class: "flex items-center",
                         label {
                             r#for: "ss-mobile-menu",
@@ -95,30 +111,19 @@ pub fn AuthenticatedNavbar() -> Element {
                             div { class: "peer-checked:shadow-heading fixed right-0 top-0 z-[9999] h-full w-full translate-x-full overflow-y-auto overscroll-y-none transition duration-500 peer-checked:translate-x-0",
                                 div { class: "float-right min-h-full w-[85%] bg-white px-6 pt-12 shadow-2xl",
                                     menu { class: "text-heading mb-8 mt-8 flex flex-col space-y-4",
-                                        li {
-                                            a {
-                                                href: "/en/blog",
-                                                class: "whitespace-nowrap pb-1 font-semibold",
-                                                "Link 1"
-                                            }
+                                        for item in items.iter() {
+                                            MobileMenuItem { key: "{item.id}", item: item.clone() }
                                         }
                                         li {
                                             a {
-                                                href: "/en/blog",
+                                                href: "/settings/profile",
                                                 class: "whitespace-nowrap pb-1 font-semibold",
-                                                "Link 2"
-                                            }
-                                        }
-                                        li {
-                                            a {
-                                                href: "/en/blog",
-                                                class: "whitespace-nowrap pb-1 font-semibold",
-                                                "Username"
+                                                "{username}"
                                             }
                                         }
                                         li {
                                             button { onclick, class: "flex items-center justify-center gap-2",
-                                                div { " Logout" }
+                                                div { {t("Logout")} }
                                             }
                                         }
                                     }
@@ -131,3 +136,57 @@ pub fn AuthenticatedNavbar() -> Element {
         }
     }
 }
+
+// Submenus open on hover.
+#[component]
+fn DesktopMenuItem(item: MenuItem) -> Element {
+    let target = menu_target(item.href.as_deref().unwrap_or_default());
+    rsx! {
+        li { class: "group relative",
+            Link {
+                to: target,
+                new_tab: item.open_in_new_tab,
+                rel: "noopener",
+                class: "inline-flex whitespace-nowrap py-4 text-base font-semibold",
+                "{item.label}"
+            }
+            if !item.children.is_empty() {
+                menu { class: "absolute left-0 top-full hidden min-w-48 flex-col space-y-2 bg-white px-4 py-3 shadow-lg group-hover:flex",
+                    for child in item.children.iter() {
+                        DesktopMenuItem { key: "{child.id}", item: child.clone() }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Submenus are listed indented below their parent.
+#[component]
+fn MobileMenuItem(item: MenuItem) -> Element {
+    let target = menu_target(item.href.as_deref().unwrap_or_default());
+    rsx! {
+        li {
+            Link {
+                to: target,
+                new_tab: item.open_in_new_tab,
+                rel: "noopener",
+                class: "whitespace-nowrap pb-1 font-semibold",
+                "{item.label}"
+            }
+            if !item.children.is_empty() {
+                menu { class: "ml-4 mt-2 flex flex-col space-y-2",
+                    for child in item.children.iter() {
+                        MobileMenuItem { key: "{child.id}", item: child.clone() }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Paths on the site are routed in place, anything else is followed as a plain link.
+fn menu_target(href: &str) -> NavigationTarget<Route> {
+    href.parse()
+        .unwrap_or_else(|_| NavigationTarget::External(href.to_string()))
+}
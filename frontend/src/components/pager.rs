@@ -0,0 +1,28 @@
+use crate::state::AppState;
+use dioxus::prelude::*;
+
+// Previous and next links for a paginated listing, `page` is moved by them.
+#[component]
+pub fn Pager(page: Signal<i64>, current: i64, per_page: i64, total: i64) -> Element {
+    let app_context = consume_context::<Signal<AppState>>();
+    let t = move |message: &str| app_context.read().t(message);
+
+    rsx! {
+        div { class: "mt-6 flex gap-x-4 text-sm",
+            if current > 1 {
+                button {
+                    class: "font-semibold text-indigo-600 hover:text-indigo-500",
+                    onclick: move |_| page -= 1,
+                    {t("Previous")}
+                }
+            }
+            if current * per_page < total {
+                button {
+                    class: "font-semibold text-indigo-600 hover:text-indigo-500",
+                    onclick: move |_| page += 1,
+                    {t("Next")}
+                }
+            }
+        }
+    }
+}
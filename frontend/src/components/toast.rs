@@ -42,7 +42,7 @@ fn ClearToastMessage(id: u16) -> Element {
                 r#type: "button",
                 onclick,
                 class: "inline-flex size-5 flex-shrink-0 items-center justify-center rounded-lg text-white opacity-50 hover:text-white hover:opacity-100 focus:opacity-100 focus:outline-none",
-                span { class: "sr-only", "Close" }
+                span { class: "sr-only", {app_context.read().t("Close")} }
                 svg {
                     "stroke-width": "2",
                     width: "24",
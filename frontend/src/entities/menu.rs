@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct MenuItem {
+    pub id: i32,
+    pub label: String,
+    // Public menus only contain items that link somewhere.
+    #[serde(default)]
+    pub href: Option<String>,
+    #[serde(default)]
+    pub open_in_new_tab: bool,
+    #[serde(default)]
+    pub children: Vec<MenuItem>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Menu {
+    pub name: String,
+    pub slug: String,
+    pub items: Vec<MenuItem>,
+}
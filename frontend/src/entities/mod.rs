@@ -1,3 +1,6 @@
+pub(crate) mod api_token;
 pub(crate) mod input;
+pub(crate) mod menu;
+pub(crate) mod post;
 pub(crate) mod toast;
 pub(crate) mod user;
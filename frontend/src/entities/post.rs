@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct PostAuthor {
+    pub username: String,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Term {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Post {
+    pub id: i32,
+    pub kind: String,
+    pub title: String,
+    pub slug: String,
+    pub locale: String,
+    pub excerpt: String,
+    // Written by the editors, rendered as is.
+    pub body: String,
+    pub author: Option<PostAuthor>,
+    #[serde(default)]
+    pub categories: Vec<Term>,
+    #[serde(default)]
+    pub tags: Vec<Term>,
+    pub published_at: Option<String>,
+}
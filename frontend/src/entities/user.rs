@@ -1,10 +1,20 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct User {
     pub id: i32,
     pub name: String,
     pub email: String,
     pub username: String,
     pub is_confirmed: bool,
+    #[serde(default)]
+    pub is_active: bool,
+    #[serde(default)]
+    pub is_admin: bool,
+    #[serde(default)]
+    pub suspended_at: Option<String>,
+    #[serde(default)]
+    pub suspension_reason: Option<String>,
+    #[serde(default)]
+    pub suspended_until: Option<String>,
 }
@@ -19,6 +19,8 @@ pub enum ApplicationError {
     SerializeError(#[from] serde_json::Error),
     #[error("Bad request ")]
     BadRequestError(ErrorPayload),
+    #[error("Account suspended")]
+    AccountSuspended(ErrorPayload),
 }
 
 #[derive(serde::Deserialize, serde::Serialize, Default, Debug, Clone, PartialEq)]
@@ -48,4 +50,21 @@ impl ErrorPayload {
         }
         "".to_string()
     }
+
+    pub fn has_code(&self, field: &str, code: &str) -> bool {
+        self.details
+            .get(field)
+            .and_then(|detail| detail.as_array())
+            .is_some_and(|detail| detail.iter().any(|d| d.get("code") == Some(&code.into())))
+    }
+
+    pub fn param_for_field(&self, field: &str) -> Option<String> {
+        self.details
+            .get(field)?
+            .get(0)?
+            .get("params")?
+            .get("value")?
+            .as_str()
+            .map(|value| value.to_string())
+    }
 }
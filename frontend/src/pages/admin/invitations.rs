@@ -0,0 +1,201 @@
+use crate::components::button::Button;
+use crate::components::error_line::OverallErrorLine;
+use crate::components::input::InputField;
+use crate::entities::input::UserInput;
+use crate::entities::toast::ToastType;
+use crate::errors::{ApplicationError, ErrorPayload};
+use crate::routes::Route;
+use crate::state::AppState;
+use crate::utils;
+use crate::utils::api::admin::{
+    create_invitation, list_invitations, resend_invitation, revoke_invitation, Invitation,
+};
+use dioxus::prelude::*;
+
+#[component]
+pub fn AdminInvitationsPage() -> Element {
+    let mut error_message: Signal<Option<ErrorPayload>> = use_signal(|| None);
+    let mut in_progress = use_signal(|| false);
+    let mut user_input = use_signal(UserInput::new);
+    let mut admin_role = use_signal(|| false);
+    let mut app_context = consume_context::<Signal<AppState>>();
+
+    let mut invitations = use_resource(move || async move {
+        match list_invitations().await {
+            Ok(invitations) => invitations,
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+                vec![]
+            }
+        }
+    });
+
+    let onsubmit = move |_: FormEvent| async move {
+        error_message.set(None);
+        in_progress.set(true);
+        let role = if *admin_role.read() { "admin" } else { "user" };
+        let email = user_input.read().get("email");
+
+        match create_invitation(email, role).await {
+            Ok(_) => {
+                user_input.write().set("email", String::new());
+                app_context
+                    .write()
+                    .add_toast(ToastType::Success, "Invitation sent");
+                invitations.restart();
+            }
+            Err(ApplicationError::BadRequestError(payload)) => {
+                error_message.set(Some(payload));
+            }
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+            }
+        }
+        in_progress.set(false);
+    };
+
+    rsx! {
+        div { class: "mx-auto max-w-5xl px-6 py-12 lg:px-8",
+            Link {
+                to: Route::AdminUsersPage {},
+                class: "text-sm font-semibold text-indigo-600 hover:text-indigo-500",
+                "Back to users"
+            }
+            h2 { class: "mt-6 text-2xl font-bold leading-9 tracking-tight text-gray-900",
+                "Invitations"
+            }
+            form { onsubmit, class: "mt-6 space-y-6",
+                OverallErrorLine {
+                    error_payload: error_message
+                }
+                div { class: "grid grid-cols-1 gap-x-6 gap-y-6 sm:grid-cols-6",
+                    div { class: "sm:col-span-4",
+                        label {
+                            r#for: "email",
+                            class: "block text-sm font-medium leading-6 text-gray-900",
+                            "Email address"
+                        }
+                        div { class: "mt-2",
+                            InputField {
+                                required: "true",
+                                autocomplete: "off",
+                                error_payload: error_message,
+                                identifier: "email",
+                                typ: "email",
+                                value: user_input.read().get("email"),
+                                oninput: move |event: Event<FormData>| user_input.write().set("email", event.value())
+                            }
+                        }
+                    }
+                    div { class: "sm:col-span-2 relative flex items-end gap-x-3",
+                        div { class: "flex h-9 items-center",
+                            input {
+                                r#type: "checkbox",
+                                id: "admin_role",
+                                class: "h-4 w-4 rounded border-gray-300 text-indigo-600 focus:ring-indigo-600",
+                                checked: *admin_role.read(),
+                                oninput: move |event: Event<FormData>| admin_role.set(event.value() == "true")
+                            }
+                        }
+                        label {
+                            r#for: "admin_role",
+                            class: "pb-2 text-sm font-medium leading-6 text-gray-900",
+                            "Invite as admin"
+                        }
+                    }
+                }
+                div { class: "flex items-center justify-end",
+                    Button {
+                        r#type: "submit",
+                        progress: *in_progress.read(),
+                        class: "rounded-md bg-indigo-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-indigo-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-600",
+                        "Send invitation"
+                    }
+                }
+            }
+            table { class: "mt-10 min-w-full divide-y divide-gray-300 text-left text-sm",
+                thead {
+                    tr {
+                        th { class: "py-3 font-semibold text-gray-900", "Email" }
+                        th { class: "py-3 font-semibold text-gray-900", "Role" }
+                        th { class: "py-3 font-semibold text-gray-900", "Expires" }
+                        th { class: "py-3" }
+                    }
+                }
+                tbody { class: "divide-y divide-gray-200",
+                    if let Some(list) = &*invitations.read() {
+                        for invitation in list.iter() {
+                            InvitationRow {
+                                key: "{invitation.identifier}",
+                                invitation: invitation.clone(),
+                                onchange: move |_| invitations.restart()
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn InvitationRow(invitation: Invitation, onchange: EventHandler<()>) -> Element {
+    let mut app_context = consume_context::<Signal<AppState>>();
+    let identifier = use_signal(|| invitation.identifier.clone());
+
+    let onresend = move |_: MouseEvent| async move {
+        match resend_invitation(&identifier.read()).await {
+            Ok(_) => {
+                app_context
+                    .write()
+                    .add_toast(ToastType::Success, "Invitation sent again");
+                onchange.call(());
+            }
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+            }
+        }
+    };
+
+    let onrevoke = move |_: MouseEvent| async move {
+        match revoke_invitation(&identifier.read()).await {
+            Ok(_) => {
+                app_context
+                    .write()
+                    .add_toast(ToastType::Success, "Invitation revoked");
+                onchange.call(());
+            }
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+            }
+        }
+    };
+
+    rsx! {
+        tr {
+            td { class: "py-3 text-gray-900", "{invitation.email}" }
+            td { class: "py-3 text-gray-600", "{invitation.role}" }
+            td { class: "py-3 text-gray-600", "{invitation.expires_at}" }
+            td { class: "py-3",
+                div { class: "flex justify-end gap-x-3",
+                    div { onclick: onresend,
+                        Button {
+                            r#type: "button",
+                            progress: false,
+                            class: "rounded-md bg-white px-3 py-2 text-sm font-semibold text-gray-900 shadow-sm ring-1 ring-inset ring-gray-300 hover:bg-gray-50",
+                            "Resend"
+                        }
+                    }
+                    div { onclick: onrevoke,
+                        Button {
+                            r#type: "button",
+                            progress: false,
+                            class: "rounded-md bg-red-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-red-500",
+                            "Revoke"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,7 @@
+mod invitations;
+mod user_detail;
+mod users;
+
+pub(crate) use {
+    invitations::AdminInvitationsPage, user_detail::AdminUserDetailPage, users::AdminUsersPage,
+};
@@ -0,0 +1,253 @@
+use crate::components::button::Button;
+use crate::components::error_line::OverallErrorLine;
+use crate::components::input::InputField;
+use crate::entities::input::UserInput;
+use crate::entities::toast::ToastType;
+use crate::errors::{ApplicationError, ErrorPayload};
+use crate::routes::Route;
+use crate::state::AppState;
+use crate::utils;
+use crate::utils::api::admin::{impersonate, suspend_user, user_action, user_detail};
+use dioxus::prelude::*;
+
+#[component]
+pub fn AdminUserDetailPage(user_id: i32) -> Element {
+    let mut app_context = consume_context::<Signal<AppState>>();
+    let user_id = use_signal(|| user_id);
+
+    let mut detail = use_resource(move || async move {
+        match user_detail(user_id()).await {
+            Ok(detail) => Some(detail),
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+                None
+            }
+        }
+    });
+
+    let onimpersonate = move |_: MouseEvent| async move {
+        match impersonate(user_id()).await {
+            Ok(user) => {
+                app_context.write().user = Some(user);
+                navigator().replace(Route::Home {});
+            }
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+            }
+        }
+    };
+
+    rsx! {
+        div { class: "mx-auto max-w-4xl px-6 py-12 lg:px-8",
+            Link {
+                to: Route::AdminUsersPage {},
+                class: "text-sm font-semibold text-indigo-600 hover:text-indigo-500",
+                "Back to users"
+            }
+            if let Some(Some(info)) = &*detail.read() {
+                h2 { class: "mt-6 text-2xl font-bold leading-9 tracking-tight text-gray-900",
+                    "{info.user.name}"
+                }
+                dl { class: "mt-6 grid grid-cols-1 gap-x-6 gap-y-4 text-sm sm:grid-cols-2",
+                    div {
+                        dt { class: "font-medium text-gray-900", "Username" }
+                        dd { class: "text-gray-600", "{info.user.username}" }
+                    }
+                    div {
+                        dt { class: "font-medium text-gray-900", "Email" }
+                        dd { class: "text-gray-600", "{info.user.email}" }
+                    }
+                    div {
+                        dt { class: "font-medium text-gray-900", "Verified" }
+                        dd { class: "text-gray-600", "{info.user.is_confirmed}" }
+                    }
+                    div {
+                        dt { class: "font-medium text-gray-900", "Active" }
+                        dd { class: "text-gray-600", "{info.user.is_active}" }
+                    }
+                    div {
+                        dt { class: "font-medium text-gray-900", "Sessions" }
+                        dd { class: "text-gray-600", "{info.sessions}" }
+                    }
+                    if let Some(suspended_at) = &info.user.suspended_at {
+                        div {
+                            dt { class: "font-medium text-gray-900", "Suspended" }
+                            dd { class: "text-gray-600",
+                                "Since {suspended_at}"
+                                if let Some(until) = &info.user.suspended_until {
+                                    " until {until}"
+                                }
+                                if let Some(reason) = &info.user.suspension_reason {
+                                    ": {reason}"
+                                }
+                            }
+                        }
+                    }
+                }
+                div { class: "mt-8 flex flex-wrap gap-3",
+                    if info.user.is_active {
+                        AdminActionButton { user_id: user_id(), action: "deactivate", label: "Deactivate", onsuccess: move |_| detail.restart() }
+                    } else {
+                        AdminActionButton { user_id: user_id(), action: "activate", label: "Activate", onsuccess: move |_| detail.restart() }
+                    }
+                    if !info.user.is_confirmed {
+                        AdminActionButton { user_id: user_id(), action: "verify", label: "Mark verified", onsuccess: move |_| detail.restart() }
+                    }
+                    AdminActionButton { user_id: user_id(), action: "reset-password", label: "Force password reset", onsuccess: move |_| detail.restart() }
+                    AdminActionButton { user_id: user_id(), action: "revoke-sessions", label: "Revoke sessions", onsuccess: move |_| detail.restart() }
+                    if info.user.suspended_at.is_some() {
+                        AdminActionButton { user_id: user_id(), action: "unsuspend", label: "Lift suspension", onsuccess: move |_| detail.restart() }
+                    }
+                    div { onclick: onimpersonate,
+                        Button {
+                            r#type: "button",
+                            progress: false,
+                            class: "rounded-md bg-red-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-red-500",
+                            "Impersonate"
+                        }
+                    }
+                }
+                if info.user.suspended_at.is_none() {
+                    SuspendUserForm { user_id: user_id(), onsuccess: move |_| detail.restart() }
+                }
+                h3 { class: "mt-10 text-lg font-semibold text-gray-900", "Audit trail" }
+                ul { class: "mt-4 divide-y divide-gray-200 text-sm",
+                    for entry in info.audit.iter() {
+                        li { key: "{entry.id}", class: "py-2 text-gray-600",
+                            "{entry.created_at}: {entry.action}"
+                            if let Some(actor_id) = entry.actor_id {
+                                " by user #{actor_id}"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn AdminActionButton(
+    user_id: i32,
+    action: &'static str,
+    label: &'static str,
+    onsuccess: EventHandler<()>,
+) -> Element {
+    let mut app_context = consume_context::<Signal<AppState>>();
+    let mut in_progress = use_signal(|| false);
+
+    let onclick = move |_: MouseEvent| async move {
+        in_progress.set(true);
+        match user_action(user_id, action).await {
+            Ok(_) => {
+                app_context
+                    .write()
+                    .add_toast(ToastType::Success, format!("{} done", label));
+                onsuccess.call(());
+            }
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+            }
+        }
+        in_progress.set(false);
+    };
+
+    rsx! {
+        div { onclick,
+            Button {
+                r#type: "button",
+                progress: *in_progress.read(),
+                class: "rounded-md bg-white px-3 py-2 text-sm font-semibold text-gray-900 shadow-sm ring-1 ring-inset ring-gray-300 hover:bg-gray-50",
+                "{label}"
+            }
+        }
+    }
+}
+
+#[component]
+fn SuspendUserForm(user_id: i32, onsuccess: EventHandler<()>) -> Element {
+    let mut error_message: Signal<Option<ErrorPayload>> = use_signal(|| None);
+    let mut in_progress = use_signal(|| false);
+    let mut user_input = use_signal(UserInput::new);
+    let mut app_context = consume_context::<Signal<AppState>>();
+
+    let onsubmit = move |_: FormEvent| async move {
+        error_message.set(None);
+        in_progress.set(true);
+        let entry = user_input.read();
+        // The date input has no time zone, suspensions end at midnight UTC.
+        let until = Some(entry.get("until"))
+            .filter(|until| !until.is_empty())
+            .map(|until| format!("{}T00:00:00Z", until));
+
+        match suspend_user(user_id, entry.get("reason"), until).await {
+            Ok(_) => {
+                app_context
+                    .write()
+                    .add_toast(ToastType::Success, "User suspended");
+                onsuccess.call(());
+            }
+            Err(ApplicationError::BadRequestError(payload)) => {
+                error_message.set(Some(payload));
+            }
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+            }
+        }
+        in_progress.set(false);
+    };
+
+    rsx! {
+        h3 { class: "mt-10 text-lg font-semibold text-gray-900", "Suspend account" }
+        form { onsubmit, class: "mt-4 space-y-6",
+            OverallErrorLine {
+                error_payload: error_message
+            }
+            div { class: "grid grid-cols-1 gap-x-6 gap-y-6 sm:grid-cols-6",
+                div { class: "sm:col-span-4",
+                    label {
+                        r#for: "reason",
+                        class: "block text-sm font-medium leading-6 text-gray-900",
+                        "Reason shown to the user"
+                    }
+                    div { class: "mt-2",
+                        InputField {
+                            required: "true",
+                            autocomplete: "off",
+                            error_payload: error_message,
+                            identifier: "reason",
+                            typ: "text",
+                            value: user_input.read().get("reason"),
+                            oninput: move |event: Event<FormData>| user_input.write().set("reason", event.value())
+                        }
+                    }
+                }
+                div { class: "sm:col-span-2",
+                    label {
+                        r#for: "until",
+                        class: "block text-sm font-medium leading-6 text-gray-900",
+                        "Until (empty for indefinite)"
+                    }
+                    div { class: "mt-2",
+                        InputField {
+                            autocomplete: "off",
+                            error_payload: error_message,
+                            identifier: "until",
+                            typ: "date",
+                            value: user_input.read().get("until"),
+                            oninput: move |event: Event<FormData>| user_input.write().set("until", event.value())
+                        }
+                    }
+                }
+            }
+            div { class: "flex items-center justify-end",
+                Button {
+                    r#type: "submit",
+                    progress: *in_progress.read(),
+                    class: "rounded-md bg-red-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-red-500",
+                    "Suspend"
+                }
+            }
+        }
+    }
+}
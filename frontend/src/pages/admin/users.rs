@@ -0,0 +1,107 @@
+use crate::entities::user::User;
+use crate::routes::Route;
+use crate::state::AppState;
+use crate::utils;
+use crate::utils::api::admin::list_users;
+use dioxus::prelude::*;
+
+#[component]
+pub fn AdminUsersPage() -> Element {
+    let mut query = use_signal(String::new);
+    let mut page = use_signal(|| 1i64);
+    let mut app_context = consume_context::<Signal<AppState>>();
+
+    let users = use_resource(move || async move {
+        match list_users(query(), page()).await {
+            Ok(users) => Some(users),
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+                None
+            }
+        }
+    });
+
+    rsx! {
+        div { class: "mx-auto max-w-5xl px-6 py-12 lg:px-8",
+            div { class: "flex items-center justify-between",
+                h2 { class: "text-2xl font-bold leading-9 tracking-tight text-gray-900",
+                    "Users"
+                }
+                Link {
+                    to: Route::AdminInvitationsPage {},
+                    class: "text-sm font-semibold text-indigo-600 hover:text-indigo-500",
+                    "Invitations"
+                }
+            }
+            input {
+                r#type: "search",
+                placeholder: "Search by username, email or name",
+                class: "mt-6 block w-full rounded-md border-0 py-1.5 text-gray-900 shadow-sm ring-1 ring-inset ring-gray-300 placeholder:text-gray-400 focus:ring-2 focus:ring-inset focus:ring-indigo-600 sm:text-sm sm:leading-6",
+                value: "{query}",
+                oninput: move |event: Event<FormData>| {
+                    query.set(event.value());
+                    page.set(1);
+                }
+            }
+            if let Some(Some(list)) = &*users.read() {
+                table { class: "mt-6 min-w-full divide-y divide-gray-300 text-left text-sm",
+                    thead {
+                        tr {
+                            th { class: "py-3 font-semibold text-gray-900", "Username" }
+                            th { class: "py-3 font-semibold text-gray-900", "Email" }
+                            th { class: "py-3 font-semibold text-gray-900", "Status" }
+                        }
+                    }
+                    tbody { class: "divide-y divide-gray-200",
+                        for user in list.users.iter() {
+                            AdminUserRow { key: "{user.id}", user: user.clone() }
+                        }
+                    }
+                }
+                div { class: "mt-6 flex items-center justify-between text-sm text-gray-600",
+                    span { "{list.total} users" }
+                    div { class: "flex gap-x-4",
+                        if list.page > 1 {
+                            button {
+                                class: "font-semibold text-indigo-600 hover:text-indigo-500",
+                                onclick: move |_| page -= 1,
+                                "Previous"
+                            }
+                        }
+                        if list.page * list.per_page < list.total {
+                            button {
+                                class: "font-semibold text-indigo-600 hover:text-indigo-500",
+                                onclick: move |_| page += 1,
+                                "Next"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn AdminUserRow(user: User) -> Element {
+    let status = match (user.is_active, user.is_confirmed) {
+        (false, _) => "Inactive",
+        _ if user.suspended_at.is_some() => "Suspended",
+        (true, false) => "Unverified",
+        (true, true) => "Active",
+    };
+
+    rsx! {
+        tr {
+            td { class: "py-3 text-gray-900",
+                Link {
+                    to: Route::AdminUserDetailPage { user_id: user.id },
+                    class: "font-semibold text-indigo-600 hover:text-indigo-500",
+                    "{user.username}"
+                }
+            }
+            td { class: "py-3 text-gray-600", "{user.email}" }
+            td { class: "py-3 text-gray-600", "{status}" }
+        }
+    }
+}
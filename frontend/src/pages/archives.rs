@@ -0,0 +1,88 @@
+use crate::components::pager::Pager;
+use crate::entities::post::Post;
+use crate::errors::ApplicationError;
+use crate::pages::PageNotFound;
+use crate::routes::Route;
+use crate::state::AppState;
+use crate::utils;
+use crate::utils::api::posts::archive;
+use dioxus::prelude::*;
+
+#[component]
+pub fn CategoryPage(slug: String) -> Element {
+    rsx! { Archive { section: "categories", slug, locale: None } }
+}
+
+#[component]
+pub fn TagPage(slug: String) -> Element {
+    rsx! { Archive { section: "tags", slug, locale: None } }
+}
+
+#[component]
+pub fn Archive(section: &'static str, slug: String, locale: Option<String>) -> Element {
+    let mut app_context = consume_context::<Signal<AppState>>();
+    let page = use_signal(|| 1i64);
+
+    let listing = use_resource(use_reactive(
+        (&slug, &locale),
+        move |(slug, locale)| async move {
+            match archive(section, &slug, page(), locale).await {
+                Ok(listing) => Some(listing),
+                Err(ApplicationError::RequestAPIFailed) => None,
+                Err(e) => {
+                    utils::handle_application_error(&mut app_context, e);
+                    None
+                }
+            }
+        },
+    ));
+
+    let view = match &*listing.read() {
+        Some(Some(listing)) => rsx! {
+            div { class: "mx-auto max-w-3xl px-6 py-12 lg:px-8",
+                h2 { class: "text-2xl font-bold leading-9 tracking-tight text-gray-900",
+                    "{listing.term.name}"
+                }
+                if !listing.term.description.is_empty() {
+                    p { class: "mt-2 text-sm text-gray-600", "{listing.term.description}" }
+                }
+                ul { class: "mt-6 divide-y divide-gray-200",
+                    for post in listing.posts.iter() {
+                        PostRow { key: "{post.id}", post: post.clone(), locale: locale.clone() }
+                    }
+                }
+                Pager {
+                    page,
+                    current: listing.page,
+                    per_page: listing.per_page,
+                    total: listing.total
+                }
+            }
+        },
+        Some(None) => rsx! { PageNotFound { route: vec![section.to_string(), slug] } },
+        None => rsx! {},
+    };
+    view
+}
+
+#[component]
+fn PostRow(post: Post, locale: Option<String>) -> Element {
+    let date = post
+        .published_at
+        .as_deref()
+        .and_then(|date| date.get(..10))
+        .unwrap_or_default()
+        .to_string();
+
+    rsx! {
+        li { class: "py-4",
+            span { class: "text-xs text-gray-500", "{date}" }
+            Link {
+                to: Route::post(post.slug.clone(), locale),
+                class: "mt-1 block font-semibold text-gray-900 hover:text-indigo-600",
+                "{post.title}"
+            }
+            p { class: "mt-1 text-sm leading-6 text-gray-600", "{post.excerpt}" }
+        }
+    }
+}
@@ -1,7 +1,10 @@
 mod confirm;
 mod layout;
+mod restore;
 mod signin;
 mod signup;
+mod suspended;
+mod unlock;
 
 mod not_verified;
 mod reset;
@@ -9,5 +12,7 @@ mod reset;
 pub(crate) use {
     confirm::ConfirmationPage, layout::AuthenticatedLayout, layout::VerifiedLayout,
     not_verified::UserNotVerified, reset::initiate::InitiateResetPasswordPage,
-    reset::reset_link::ProcessResetLinkPage, signin::SignInPage, signup::SignUpPage,
+    reset::reset_link::ProcessResetLinkPage, restore::RestoreAccountPage, signin::SignInPage,
+    signup::InviteSignUpPage, signup::SignUpPage, suspended::AccountSuspendedPage,
+    unlock::UnlockAccountPage,
 };
@@ -0,0 +1,77 @@
+use crate::errors::ApplicationError;
+use crate::routes::Route;
+use crate::state::AppState;
+use crate::utils;
+use crate::utils::api::restore::restore_account;
+use dioxus::prelude::*;
+
+#[component]
+pub fn RestoreAccountPage(token: String) -> Element {
+    let token_signal = use_signal(|| token);
+    let mut app_context = consume_context::<Signal<AppState>>();
+
+    let future = use_resource(move || async move {
+        let response = restore_account(&token_signal.read()).await;
+        match response {
+            Ok(_) => None,
+            Err(ApplicationError::BadRequestError(payload)) => Some(payload.message.clone()),
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+                Some("Failed to restore the account.".to_string())
+            }
+        }
+    });
+
+    rsx! {
+        main { class: "grid min-h-full place-items-center bg-white px-6 py-24 sm:py-32 lg:px-8",
+            div { class: "text-center",
+                p { class: "text-base font-semibold text-indigo-600", "..." }
+                match &*future.read_unchecked() {
+                    Some(Some(response)) => {
+                        rsx! {
+                            h1 {
+                                class: "mt-4 text-3xl font-bold tracking-tight text-gray-900 sm:text-5xl text-red-500",
+                                "Restore failed."
+                            }
+                            p {
+                                class: "mt-6 text-base leading-7 text-gray-600",
+                                "{response}"
+                            }
+                        }
+                    }
+                    Some(None) => {
+                        rsx! {
+                            h1 {
+                                class: "mt-4 text-3xl font-bold tracking-tight text-gray-900 sm:text-5xl",
+                                "Account restored."
+                            }
+                            p {
+                                class: "mt-6 text-base leading-7 text-gray-600",
+                                "You can sign in again now."
+                            }
+                        }
+                    }
+                    None => {
+                        rsx! {
+                            h1 {
+                                class: "mt-4 text-3xl font-bold tracking-tight text-gray-900 sm:text-5xl",
+                                "Restoring your account."
+                            }
+                            p {
+                                class: "mt-6 text-base leading-7 text-gray-600",
+                                "Please wait while we validate your request."
+                            }
+                        }
+                    }
+                }
+                div { class: "mt-10 flex items-center justify-center gap-x-6",
+                    Link {
+                        to: Route::SignInPage {},
+                        class: "rounded-md bg-indigo-600 px-3.5 py-2.5 text-sm font-semibold text-white shadow-sm hover:bg-indigo-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-600",
+                        "Go to sign in"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -59,7 +59,7 @@ pub fn SignInPage() -> Element {
                     label {
                         r#for: "username",
                         class: "block text-sm font-medium leading-6 text-gray-900",
-                        "Username"
+                        "Username or email"
                     }
                     div { class: "mt-2",
                         InputField {
@@ -1,29 +1,127 @@
 use crate::components::button::Button;
 use crate::errors::{ApplicationError, ErrorPayload};
 use crate::routes::Route;
-use crate::utils::api::sign_up::{signup, RegistrationPayload};
+use crate::utils::api::sign_up::{
+    invitation_detail, registration_mode, signup, RegistrationPayload, SignUpResponse,
+};
 use dioxus::prelude::*;
 
 use crate::components::error_line::OverallErrorLine;
 use crate::components::input::InputField;
 use crate::entities::input::UserInput;
+use crate::entities::toast::ToastType;
 use crate::state::AppState;
 use crate::utils;
 
 #[component]
 pub fn SignUpPage() -> Element {
+    let mut app_context = consume_context::<Signal<AppState>>();
+    let mode = use_resource(move || async move {
+        match registration_mode().await {
+            Ok(response) => response.mode,
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+                "open".to_string()
+            }
+        }
+    });
+
+    rsx! {
+        match mode.read().as_deref() {
+            Some("open") => rsx! { SignUpForm {} },
+            Some("invite-only") => rsx! {
+                RegistrationUnavailable { message: "Registration is by invitation only. Please use the link from your invitation email." }
+            },
+            Some(_) => rsx! {
+                RegistrationUnavailable { message: "Registration is currently closed." }
+            },
+            None => rsx! {},
+        }
+    }
+}
+
+#[component]
+pub fn InviteSignUpPage(token: String) -> Element {
+    let token = use_signal(|| token);
+    let mut app_context = consume_context::<Signal<AppState>>();
+
+    let invitation = use_resource(move || async move {
+        match invitation_detail(&token.read()).await {
+            Ok(invitation) => Ok(invitation),
+            Err(ApplicationError::BadRequestError(payload)) => Err(payload.message),
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+                Err("Failed to load the invitation.".to_string())
+            }
+        }
+    });
+
+    rsx! {
+        match &*invitation.read_unchecked() {
+            Some(Ok(invitation)) => rsx! {
+                SignUpForm { invitation: token(), email: invitation.email.clone() }
+            },
+            Some(Err(message)) => rsx! {
+                RegistrationUnavailable { message: message.clone() }
+            },
+            None => rsx! {},
+        }
+    }
+}
+
+#[component]
+fn RegistrationUnavailable(message: String) -> Element {
+    rsx! {
+        main { class: "grid min-h-full place-items-center bg-white px-6 py-24 sm:py-32 lg:px-8",
+            div { class: "text-center",
+                p { class: "text-base font-semibold text-indigo-600", "Sign up" }
+                h1 { class: "mt-4 text-3xl font-bold tracking-tight text-gray-900 sm:text-5xl",
+                    "Registration unavailable"
+                }
+                p { class: "mt-6 text-base leading-7 text-gray-600", "{message}" }
+                div { class: "mt-10 flex items-center justify-center gap-x-6",
+                    Link {
+                        to: Route::SignInPage {},
+                        class: "rounded-md bg-indigo-600 px-3.5 py-2.5 text-sm font-semibold text-white shadow-sm hover:bg-indigo-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-600",
+                        "Go to sign in"
+                    }
+                }
+            }
+        }
+    }
+}
+
+// With an invitation the email is fixed to the invited address.
+#[component]
+fn SignUpForm(invitation: Option<String>, email: Option<String>) -> Element {
     let mut error_message: Signal<Option<ErrorPayload>> = use_signal(|| None);
-    let mut user_input = use_signal(UserInput::new);
+    let email_locked = email.is_some();
+    let mut user_input = use_signal(move || {
+        let mut input = UserInput::new();
+        if let Some(email) = email {
+            input.set("email", email);
+        }
+        input
+    });
+    let invitation = use_signal(move || invitation);
     let mut app_context = consume_context::<Signal<AppState>>();
     let mut in_progress = use_signal(|| false);
 
     let onsubmit = move |_: FormEvent| async move {
         in_progress.set(true);
-        let payload = RegistrationPayload::from(user_input);
+        let mut payload = RegistrationPayload::from(user_input);
+        payload.invitation = invitation();
         error_message.set(None);
 
         let response = signup(payload).await;
         match response {
+            Ok(SignUpResponse { pending: true }) => {
+                app_context.write().add_toast(
+                    ToastType::Info,
+                    "Check your email to finish creating your account.",
+                );
+                navigator().replace(Route::SignInPage {});
+            }
             Ok(_) => {
                 let nav = navigator();
                 nav.replace(Route::Home {});
@@ -129,6 +227,7 @@ pub fn SignUpPage() -> Element {
                                     InputField {
                                         required: "true",
                                         autocomplete: "email",
+                                        readonly: if email_locked { "true" } else { "false" },
                                         error_payload: error_message,
                                         identifier: "email",
                                         typ: "email",
@@ -0,0 +1,44 @@
+use crate::routes::Route;
+use crate::state::AppState;
+use dioxus::prelude::*;
+
+#[component]
+pub fn AccountSuspendedPage() -> Element {
+    let app_context = consume_context::<Signal<AppState>>();
+    let suspension = app_context.read().suspension.clone();
+    let reason = suspension
+        .as_ref()
+        .map(|payload| payload.error_for_field("auth"))
+        .unwrap_or_default();
+    let until = suspension
+        .as_ref()
+        .and_then(|payload| payload.param_for_field("auth"));
+
+    rsx! {
+        main { class: "grid min-h-full place-items-center bg-white px-6 py-24 sm:py-32 lg:px-8",
+            div { class: "text-center",
+                p { class: "text-base font-semibold text-red-600", "Account suspended" }
+                h1 { class: "mt-4 text-3xl font-bold tracking-tight text-gray-900 sm:text-5xl",
+                    "Your account has been suspended"
+                }
+                if !reason.is_empty() {
+                    p { class: "mt-6 text-base leading-7 text-gray-600", "Reason: {reason}" }
+                }
+                p { class: "mt-2 text-base leading-7 text-gray-600",
+                    if let Some(until) = until {
+                        "The suspension ends on {until}."
+                    } else {
+                        "The suspension stays in place until it is lifted by an administrator."
+                    }
+                }
+                div { class: "mt-10 flex items-center justify-center gap-x-6",
+                    Link {
+                        to: Route::SignInPage {},
+                        class: "rounded-md bg-indigo-600 px-3.5 py-2.5 text-sm font-semibold text-white shadow-sm hover:bg-indigo-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-600",
+                        "Go to sign in"
+                    }
+                }
+            }
+        }
+    }
+}
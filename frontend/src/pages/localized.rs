@@ -0,0 +1,91 @@
+use crate::pages::archives::Archive;
+use crate::pages::newsletters::{NewsletterPage, NewslettersPage};
+use crate::pages::posts::Published;
+use crate::pages::{Home, PageNotFound};
+use crate::state::AppState;
+use dioxus::prelude::*;
+use translations::catalog::catalog_locales;
+use translations::locale::LOCALE_COOKIE;
+use translations::normalize_locale;
+
+// Locale of a path prefix, switching the interface to it. `None` when it is not a known locale.
+fn use_path_locale(locale: &str) -> Option<String> {
+    let mut app_context = consume_context::<Signal<AppState>>();
+    let supported = normalize_locale(locale)
+        .filter(|normalized| catalog_locales().contains(&normalized.as_str()));
+
+    // The cookie makes the api answer in the same locale, effects only run in the browser.
+    use_effect(use_reactive((&supported,), move |(selected,)| {
+        if let Some(locale) = selected {
+            let cookie = format!(
+                "document.cookie = '{}={}; path=/; max-age=31536000; samesite=lax'",
+                LOCALE_COOKIE, locale
+            );
+            let _ = eval(&cookie);
+            if app_context.peek().locale.as_ref() != Some(&locale) {
+                app_context.write().locale = Some(locale);
+            }
+        }
+    }));
+    supported
+}
+
+// Anything that is not a known locale is shown as not found.
+#[component]
+pub fn LocalizedHome(locale: String) -> Element {
+    match use_path_locale(&locale) {
+        Some(_) => rsx! { Home {} },
+        None => rsx! { PageNotFound { route: vec![locale] } },
+    }
+}
+
+#[component]
+pub fn LocalizedPostPage(locale: String, slug: String) -> Element {
+    match use_path_locale(&locale) {
+        Some(locale) => rsx! { Published { section: "posts", slug, locale } },
+        None => rsx! { PageNotFound { route: vec![locale, "posts".to_string(), slug] } },
+    }
+}
+
+#[component]
+pub fn LocalizedStaticPage(locale: String, slug: String) -> Element {
+    match use_path_locale(&locale) {
+        Some(locale) => rsx! { Published { section: "pages", slug, locale } },
+        None => rsx! { PageNotFound { route: vec![locale, "pages".to_string(), slug] } },
+    }
+}
+
+#[component]
+pub fn LocalizedCategoryPage(locale: String, slug: String) -> Element {
+    match use_path_locale(&locale) {
+        Some(locale) => rsx! { Archive { section: "categories", slug, locale } },
+        None => rsx! { PageNotFound { route: vec![locale, "categories".to_string(), slug] } },
+    }
+}
+
+#[component]
+pub fn LocalizedTagPage(locale: String, slug: String) -> Element {
+    match use_path_locale(&locale) {
+        Some(locale) => rsx! { Archive { section: "tags", slug, locale } },
+        None => rsx! { PageNotFound { route: vec![locale, "tags".to_string(), slug] } },
+    }
+}
+
+// Newsletters are not translated, the prefix only switches the interface.
+#[component]
+pub fn LocalizedNewslettersPage(locale: String) -> Element {
+    match use_path_locale(&locale) {
+        Some(_) => rsx! { NewslettersPage {} },
+        None => rsx! { PageNotFound { route: vec![locale, "newsletters".to_string()] } },
+    }
+}
+
+#[component]
+pub fn LocalizedNewsletterPage(locale: String, id: i32) -> Element {
+    match use_path_locale(&locale) {
+        Some(_) => rsx! { NewsletterPage { id } },
+        None => rsx! {
+            PageNotFound { route: vec![locale, "newsletters".to_string(), id.to_string()] }
+        },
+    }
+}
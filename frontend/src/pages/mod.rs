@@ -1,9 +1,24 @@
+mod admin;
+mod archives;
 mod auth;
 mod home;
+mod localized;
+mod newsletters;
 mod not_found;
+mod posts;
+mod search;
+mod settings;
 
 pub(crate) use {
+    admin::AdminInvitationsPage, admin::AdminUserDetailPage, admin::AdminUsersPage,
+    archives::CategoryPage, archives::TagPage, auth::AccountSuspendedPage,
     auth::AuthenticatedLayout, auth::ConfirmationPage, auth::InitiateResetPasswordPage,
-    auth::ProcessResetLinkPage, auth::SignInPage, auth::SignUpPage, auth::VerifiedLayout,
-    home::Home, not_found::PageNotFound,
+    auth::InviteSignUpPage, auth::ProcessResetLinkPage, auth::RestoreAccountPage, auth::SignInPage,
+    auth::SignUpPage, auth::UnlockAccountPage, auth::VerifiedLayout, home::Home,
+    localized::LocalizedCategoryPage, localized::LocalizedHome, localized::LocalizedNewsletterPage,
+    localized::LocalizedNewslettersPage, localized::LocalizedPostPage,
+    localized::LocalizedStaticPage, localized::LocalizedTagPage, newsletters::NewsletterPage,
+    newsletters::NewslettersPage, not_found::PageNotFound, posts::PostPage, posts::StaticPage,
+    search::SearchPage, settings::ApiTokensPage, settings::ProfileSettingsPage,
+    settings::SecuritySettingsPage,
 };
@@ -0,0 +1,93 @@
+use crate::components::pager::Pager;
+use crate::errors::ApplicationError;
+use crate::pages::PageNotFound;
+use crate::routes::Route;
+use crate::state::AppState;
+use crate::utils;
+use crate::utils::api::newsletters::{newsletter, newsletters};
+use dioxus::prelude::*;
+
+#[component]
+pub fn NewslettersPage() -> Element {
+    let mut app_context = consume_context::<Signal<AppState>>();
+    let t = move |message: &str| app_context.read().t(message);
+    let page = use_signal(|| 1i64);
+
+    let archive = use_resource(move || async move {
+        match newsletters(page()).await {
+            Ok(archive) => Some(archive),
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+                None
+            }
+        }
+    });
+
+    rsx! {
+        div { class: "mx-auto max-w-3xl px-6 py-12 lg:px-8",
+            h2 { class: "text-2xl font-bold leading-9 tracking-tight text-gray-900",
+                {t("Newsletters")}
+            }
+            if let Some(Some(archive)) = &*archive.read() {
+                ul { class: "mt-6 divide-y divide-gray-200",
+                    for summary in archive.newsletters.iter() {
+                        li { key: "{summary.id}", class: "py-4",
+                            span { class: "text-xs text-gray-500",
+                                {summary.sent_at.get(..10).unwrap_or_default()}
+                            }
+                            Link {
+                                to: Route::NewsletterPage { id: summary.id },
+                                class: "mt-1 block font-semibold text-gray-900 hover:text-indigo-600",
+                                "{summary.title}"
+                            }
+                        }
+                    }
+                }
+                Pager {
+                    page,
+                    current: archive.page,
+                    per_page: archive.per_page,
+                    total: archive.total
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn NewsletterPage(id: i32) -> Element {
+    let mut app_context = consume_context::<Signal<AppState>>();
+
+    let detail = use_resource(use_reactive((&id,), move |(id,)| async move {
+        match newsletter(id).await {
+            Ok(newsletter) => Some(newsletter),
+            Err(ApplicationError::RequestAPIFailed) => None,
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+                None
+            }
+        }
+    }));
+
+    let view = match &*detail.read() {
+        Some(Some(newsletter)) => rsx! {
+            article { class: "mx-auto max-w-3xl px-6 py-12 lg:px-8",
+                h1 { class: "text-3xl font-bold leading-9 tracking-tight text-gray-900",
+                    "{newsletter.title}"
+                }
+                p { class: "mt-2 text-sm text-gray-500",
+                    {newsletter.sent_at.get(..10).unwrap_or_default()}
+                }
+                div {
+                    class: "mt-8 leading-7 text-gray-700",
+                    dangerous_inner_html: "{newsletter.html}"
+                }
+            }
+        },
+        Some(None) => rsx! {
+            PageNotFound { route: vec!["newsletters".to_string(), id.to_string()] }
+        },
+        None => rsx! {},
+    };
+    view
+}
@@ -1,9 +1,11 @@
+use crate::state::AppState;
 use dioxus::prelude::*;
 
 #[component]
 pub fn PageNotFound(route: Vec<String>) -> Element {
+    let app_context = consume_context::<Signal<AppState>>();
     rsx! {
-        h1 { "Page not found" }
+        h1 { {app_context.read().t("Page not found")} }
         p { "We are terribly sorry, but the page you requested doesn't exist." }
         pre { color: "red", "log:\nattemped to navigate to: {route:?}" }
     }
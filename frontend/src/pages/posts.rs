@@ -0,0 +1,96 @@
+use crate::entities::post::Post;
+use crate::errors::ApplicationError;
+use crate::pages::PageNotFound;
+use crate::routes::Route;
+use crate::state::AppState;
+use crate::utils;
+use crate::utils::api::posts::published;
+use dioxus::prelude::*;
+
+#[component]
+pub fn PostPage(slug: String) -> Element {
+    rsx! { Published { section: "posts", slug, locale: None } }
+}
+
+#[component]
+pub fn StaticPage(slug: String) -> Element {
+    rsx! { Published { section: "pages", slug, locale: None } }
+}
+
+#[component]
+pub fn Published(section: &'static str, slug: String, locale: Option<String>) -> Element {
+    let mut app_context = consume_context::<Signal<AppState>>();
+
+    let detail = use_resource(use_reactive(
+        (&slug, &locale),
+        move |(slug, locale)| async move {
+            match published(section, &slug, locale).await {
+                Ok(post) => Some(post),
+                // Unknown and unpublished posts answer with not found.
+                Err(ApplicationError::RequestAPIFailed) => None,
+                Err(e) => {
+                    utils::handle_application_error(&mut app_context, e);
+                    None
+                }
+            }
+        },
+    ));
+
+    let view = match &*detail.read() {
+        Some(Some(post)) => rsx! { Article { post: post.clone(), locale } },
+        Some(None) => rsx! { PageNotFound { route: vec![section.to_string(), slug] } },
+        None => rsx! {},
+    };
+    view
+}
+
+#[component]
+fn Article(post: Post, locale: Option<String>) -> Element {
+    let date = post
+        .published_at
+        .as_deref()
+        .and_then(|date| date.get(..10))
+        .unwrap_or_default()
+        .to_string();
+
+    rsx! {
+        article { class: "mx-auto max-w-3xl px-6 py-12 lg:px-8",
+            h1 { class: "text-3xl font-bold leading-9 tracking-tight text-gray-900",
+                "{post.title}"
+            }
+            div { class: "mt-2 flex items-center gap-x-2 text-sm text-gray-500",
+                if let Some(author) = &post.author {
+                    span { "{author.name}" }
+                }
+                // Pages are not dated.
+                if post.kind == "post" {
+                    span { "{date}" }
+                }
+            }
+            if !post.categories.is_empty() || !post.tags.is_empty() {
+                div { class: "mt-4 flex flex-wrap gap-2 text-xs text-gray-600",
+                    for term in post.categories.iter() {
+                        Link {
+                            key: "category-{term.id}",
+                            to: Route::category(term.slug.clone(), locale.clone()),
+                            class: "rounded bg-gray-100 px-2 py-0.5 hover:bg-gray-200",
+                            "{term.name}"
+                        }
+                    }
+                    for term in post.tags.iter() {
+                        Link {
+                            key: "tag-{term.id}",
+                            to: Route::tag(term.slug.clone(), locale.clone()),
+                            class: "rounded bg-gray-100 px-2 py-0.5 hover:bg-gray-200",
+                            "#{term.name}"
+                        }
+                    }
+                }
+            }
+            div {
+                class: "prose mt-8 leading-7 text-gray-700",
+                dangerous_inner_html: "{post.body}"
+            }
+        }
+    }
+}
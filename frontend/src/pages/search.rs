@@ -0,0 +1,136 @@
+use crate::routes::Route;
+use crate::state::AppState;
+use crate::utils;
+use crate::utils::api::search::{search, SearchResult};
+use dioxus::prelude::*;
+use translations::translate_with;
+
+#[component]
+pub fn SearchPage() -> Element {
+    let mut query = use_signal(String::new);
+    let mut kind = use_signal(String::new);
+    let mut page = use_signal(|| 1i64);
+    let mut app_context = consume_context::<Signal<AppState>>();
+    let t = move |message: &str| app_context.read().t(message);
+
+    // Runs again on every keystroke, a newer search replaces the one in flight.
+    let results = use_resource(move || async move {
+        if query().trim().is_empty() {
+            return None;
+        }
+        match search(query(), kind(), page()).await {
+            Ok(results) => Some(results),
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+                None
+            }
+        }
+    });
+
+    rsx! {
+        div { class: "mx-auto max-w-3xl px-6 py-12 lg:px-8",
+            h2 { class: "text-2xl font-bold leading-9 tracking-tight text-gray-900",
+                {t("Search")}
+            }
+            div { class: "mt-6 flex gap-x-4",
+                input {
+                    r#type: "search",
+                    placeholder: t("Search posts, pages and newsletters"),
+                    class: "block w-full rounded-md border-0 py-1.5 text-gray-900 shadow-sm ring-1 ring-inset ring-gray-300 placeholder:text-gray-400 focus:ring-2 focus:ring-inset focus:ring-indigo-600 sm:text-sm sm:leading-6",
+                    value: "{query}",
+                    oninput: move |event: Event<FormData>| {
+                        query.set(event.value());
+                        page.set(1);
+                    }
+                }
+                select {
+                    class: "rounded-md border-0 py-1.5 text-gray-900 shadow-sm ring-1 ring-inset ring-gray-300 focus:ring-2 focus:ring-inset focus:ring-indigo-600 sm:text-sm sm:leading-6",
+                    value: "{kind}",
+                    onchange: move |event: Event<FormData>| {
+                        kind.set(event.value());
+                        page.set(1);
+                    },
+                    option { value: "", {t("Everything")} }
+                    option { value: "post", {t("Posts")} }
+                    option { value: "page", {t("Pages")} }
+                    option { value: "newsletter", {t("Newsletters")} }
+                }
+            }
+            if let Some(Some(list)) = &*results.read() {
+                if list.results.is_empty() {
+                    p { class: "mt-6 text-sm text-gray-600", {t("Nothing matched your search.")} }
+                }
+                ul { class: "mt-6 divide-y divide-gray-200",
+                    for result in list.results.iter() {
+                        SearchResultRow {
+                            key: "{result.kind}-{result.id}",
+                            result: result.clone()
+                        }
+                    }
+                }
+                div { class: "mt-6 flex items-center justify-between text-sm text-gray-600",
+                    span {
+                        {translate_with(app_context.read().locale(), "{count} results", &[("count", &list.total.to_string())])}
+                    }
+                    div { class: "flex gap-x-4",
+                        if list.page > 1 {
+                            button {
+                                class: "font-semibold text-indigo-600 hover:text-indigo-500",
+                                onclick: move |_| page -= 1,
+                                {t("Previous")}
+                            }
+                        }
+                        if list.page * list.per_page < list.total {
+                            button {
+                                class: "font-semibold text-indigo-600 hover:text-indigo-500",
+                                onclick: move |_| page += 1,
+                                {t("Next")}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn SearchResultRow(result: SearchResult) -> Element {
+    let date = result
+        .published_at
+        .get(..10)
+        .unwrap_or_default()
+        .to_string();
+
+    rsx! {
+        li { class: "py-4",
+            div { class: "flex items-center gap-x-2 text-xs text-gray-500",
+                span { class: "rounded bg-gray-100 px-2 py-0.5 capitalize", "{result.kind}" }
+                span { "{date}" }
+            }
+            if let Some(route) = result_route(&result) {
+                Link {
+                    to: route,
+                    class: "mt-1 block font-semibold text-gray-900 hover:text-indigo-600",
+                    "{result.title}"
+                }
+            } else {
+                p { class: "mt-1 font-semibold text-gray-900", "{result.title}" }
+            }
+            p {
+                class: "mt-1 text-sm leading-6 text-gray-600 [&_mark]:bg-yellow-100",
+                dangerous_inner_html: "{result.snippet}"
+            }
+        }
+    }
+}
+
+// Posts and pages are found by slug, newsletters only have an id.
+fn result_route(result: &SearchResult) -> Option<Route> {
+    match (result.kind.as_str(), result.slug.clone()) {
+        ("post", Some(slug)) => Some(Route::PostPage { slug }),
+        ("page", Some(slug)) => Some(Route::StaticPage { slug }),
+        ("newsletter", _) => Some(Route::NewsletterPage { id: result.id }),
+        _ => None,
+    }
+}
@@ -0,0 +1,8 @@
+mod nav;
+mod profile;
+mod security;
+mod tokens;
+
+pub(crate) use {
+    profile::ProfileSettingsPage, security::SecuritySettingsPage, tokens::ApiTokensPage,
+};
@@ -0,0 +1,16 @@
+use crate::routes::Route;
+use dioxus::prelude::*;
+
+#[component]
+pub fn SettingsNav() -> Element {
+    let link_class =
+        "whitespace-nowrap py-2 text-sm font-semibold text-gray-600 hover:text-indigo-600";
+
+    rsx! {
+        nav { class: "mb-8 flex gap-x-8 border-b border-gray-200",
+            Link { to: Route::ProfileSettingsPage {}, class: link_class, "Profile" }
+            Link { to: Route::SecuritySettingsPage {}, class: link_class, "Security" }
+            Link { to: Route::ApiTokensPage {}, class: link_class, "API tokens" }
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use crate::components::button::Button;
+use crate::components::error_line::OverallErrorLine;
+use crate::components::input::InputField;
+use crate::entities::input::UserInput;
+use crate::entities::toast::ToastType;
+use crate::errors::{ApplicationError, ErrorPayload};
+use crate::pages::settings::nav::SettingsNav;
+use crate::state::AppState;
+use crate::utils;
+use crate::utils::api::account::update_profile;
+use dioxus::prelude::*;
+
+#[component]
+pub fn ProfileSettingsPage() -> Element {
+    let app_context = consume_context::<Signal<AppState>>();
+
+    rsx! {
+        div { class: "mx-auto max-w-4xl px-6 py-12 lg:px-8",
+            SettingsNav {}
+            h2 { class: "text-2xl font-bold leading-9 tracking-tight text-gray-900",
+                "Profile"
+            }
+            if let Some(user) = app_context().user {
+                ProfileForm { name: user.name, username: user.username }
+            }
+        }
+    }
+}
+
+#[component]
+fn ProfileForm(name: String, username: String) -> Element {
+    let mut error_message: Signal<Option<ErrorPayload>> = use_signal(|| None);
+    let mut in_progress = use_signal(|| false);
+    let mut user_input = use_signal(|| {
+        let mut input = UserInput::new();
+        input.set("name", name);
+        input.set("username", username);
+        input
+    });
+    let mut app_context = consume_context::<Signal<AppState>>();
+
+    let onsubmit = move |_: FormEvent| async move {
+        error_message.set(None);
+        in_progress.set(true);
+        let entry = user_input.read();
+
+        let response = update_profile(entry.get("name"), entry.get("username")).await;
+        match response {
+            Ok(user) => {
+                app_context.write().user = Some(user);
+                app_context
+                    .write()
+                    .add_toast(ToastType::Success, "Profile updated");
+            }
+            Err(ApplicationError::BadRequestError(payload)) => {
+                error_message.set(Some(payload));
+            }
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+            }
+        }
+        in_progress.set(false);
+    };
+
+    rsx! {
+        form { onsubmit, class: "mt-6 space-y-6",
+            OverallErrorLine {
+                error_payload: error_message
+            }
+            div { class: "grid grid-cols-1 gap-x-6 gap-y-6 sm:grid-cols-6",
+                div { class: "sm:col-span-3",
+                    label {
+                        r#for: "name",
+                        class: "block text-sm font-medium leading-6 text-gray-900",
+                        "Name"
+                    }
+                    div { class: "mt-2",
+                        InputField {
+                            required: "true",
+                            autocomplete: "name",
+                            error_payload: error_message,
+                            identifier: "name",
+                            typ: "text",
+                            value: user_input.read().get("name"),
+                            oninput: move |event: Event<FormData>| user_input.write().set("name", event.value())
+                        }
+                    }
+                }
+                div { class: "sm:col-span-3",
+                    label {
+                        r#for: "username",
+                        class: "block text-sm font-medium leading-6 text-gray-900",
+                        "Username"
+                    }
+                    div { class: "mt-2",
+                        InputField {
+                            required: "true",
+                            autocomplete: "username",
+                            error_payload: error_message,
+                            identifier: "username",
+                            typ: "text",
+                            value: user_input.read().get("username"),
+                            oninput: move |event: Event<FormData>| user_input.write().set("username", event.value())
+                        }
+                    }
+                }
+            }
+            div { class: "flex items-center justify-end",
+                Button {
+                    r#type: "submit",
+                    progress: *in_progress.read(),
+                    class: "rounded-md bg-indigo-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-indigo-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-600",
+                    "Save profile"
+                }
+            }
+        }
+    }
+}
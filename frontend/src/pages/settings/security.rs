@@ -0,0 +1,424 @@
+use crate::components::button::Button;
+use crate::components::error_line::OverallErrorLine;
+use crate::components::input::InputField;
+use crate::entities::input::UserInput;
+use crate::entities::toast::ToastType;
+use crate::errors::{ApplicationError, ErrorPayload};
+use crate::pages::settings::nav::SettingsNav;
+use crate::state::AppState;
+use crate::utils;
+use crate::utils::api::account::{
+    change_email, change_password, delete_account, request_export, security_activity, SecurityEvent,
+};
+use dioxus::prelude::*;
+
+#[component]
+pub fn SecuritySettingsPage() -> Element {
+    rsx! {
+        div { class: "mx-auto max-w-4xl px-6 py-12 lg:px-8",
+            SettingsNav {}
+            ChangePasswordForm {}
+            ChangeEmailForm {}
+            SecurityActivity {}
+            DataExportForm {}
+            DeleteAccountForm {}
+        }
+    }
+}
+
+#[component]
+fn ChangePasswordForm() -> Element {
+    let mut error_message: Signal<Option<ErrorPayload>> = use_signal(|| None);
+    let mut in_progress = use_signal(|| false);
+    let mut user_input = use_signal(UserInput::new);
+    let mut revoke_other_sessions = use_signal(|| false);
+    let mut app_context = consume_context::<Signal<AppState>>();
+
+    let onsubmit = move |_: FormEvent| async move {
+        error_message.set(None);
+        in_progress.set(true);
+        let response = {
+            let entry = user_input.read();
+            change_password(
+                entry.get("current_password"),
+                entry.get("password"),
+                entry.get("confirm_password"),
+                *revoke_other_sessions.read(),
+            )
+            .await
+        };
+
+        match response {
+            Ok(_) => {
+                user_input.set(UserInput::new());
+                app_context
+                    .write()
+                    .add_toast(ToastType::Success, "Password changed");
+            }
+            Err(ApplicationError::BadRequestError(payload)) => {
+                error_message.set(Some(payload));
+            }
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+            }
+        }
+        in_progress.set(false);
+    };
+
+    rsx! {
+        h2 { class: "text-2xl font-bold leading-9 tracking-tight text-gray-900",
+            "Change password"
+        }
+        form { onsubmit, class: "mt-6 space-y-6",
+            OverallErrorLine {
+                error_payload: error_message
+            }
+            div { class: "grid grid-cols-1 gap-x-6 gap-y-6 sm:grid-cols-6",
+                div { class: "col-span-full",
+                    label {
+                        r#for: "current_password",
+                        class: "block text-sm font-medium leading-6 text-gray-900",
+                        "Current password"
+                    }
+                    div { class: "mt-2",
+                        InputField {
+                            required: "true",
+                            autocomplete: "current-password",
+                            error_payload: error_message,
+                            identifier: "current_password",
+                            typ: "password",
+                            value: user_input.read().get("current_password"),
+                            oninput: move |event: Event<FormData>| user_input.write().set("current_password", event.value())
+                        }
+                    }
+                }
+                div { class: "sm:col-span-3",
+                    label {
+                        r#for: "password",
+                        class: "block text-sm font-medium leading-6 text-gray-900",
+                        "New password"
+                    }
+                    div { class: "mt-2",
+                        InputField {
+                            required: "true",
+                            autocomplete: "new-password",
+                            error_payload: error_message,
+                            identifier: "password",
+                            typ: "password",
+                            value: user_input.read().get("password"),
+                            oninput: move |event: Event<FormData>| user_input.write().set("password", event.value())
+                        }
+                    }
+                }
+                div { class: "sm:col-span-3",
+                    label {
+                        r#for: "confirm_password",
+                        class: "block text-sm font-medium leading-6 text-gray-900",
+                        "Confirm new password"
+                    }
+                    div { class: "mt-2",
+                        InputField {
+                            required: "true",
+                            autocomplete: "new-password",
+                            error_payload: error_message,
+                            identifier: "confirm_password",
+                            typ: "password",
+                            value: user_input.read().get("confirm_password"),
+                            oninput: move |event: Event<FormData>| user_input.write().set("confirm_password", event.value())
+                        }
+                    }
+                }
+                div { class: "col-span-full relative flex gap-x-3",
+                    div { class: "flex h-6 items-center",
+                        input {
+                            r#type: "checkbox",
+                            id: "revoke_other_sessions",
+                            class: "h-4 w-4 rounded border-gray-300 text-indigo-600 focus:ring-indigo-600",
+                            checked: *revoke_other_sessions.read(),
+                            oninput: move |event: Event<FormData>| revoke_other_sessions.set(event.value() == "true")
+                        }
+                    }
+                    label {
+                        r#for: "revoke_other_sessions",
+                        class: "text-sm font-medium leading-6 text-gray-900",
+                        "Sign out of all other sessions"
+                    }
+                }
+            }
+            div { class: "flex items-center justify-end",
+                Button {
+                    r#type: "submit",
+                    progress: *in_progress.read(),
+                    class: "rounded-md bg-indigo-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-indigo-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-600",
+                    "Change password"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ChangeEmailForm() -> Element {
+    let mut error_message: Signal<Option<ErrorPayload>> = use_signal(|| None);
+    let mut in_progress = use_signal(|| false);
+    let mut user_input = use_signal(UserInput::new);
+    let mut app_context = consume_context::<Signal<AppState>>();
+
+    let onsubmit = move |_: FormEvent| async move {
+        error_message.set(None);
+        in_progress.set(true);
+        let response = {
+            let entry = user_input.read();
+            change_email(entry.get("email"), entry.get("current_password")).await
+        };
+
+        match response {
+            Ok(_) => {
+                user_input.set(UserInput::new());
+                app_context.write().add_toast(
+                    ToastType::Info,
+                    "Please check your new email address to confirm the change",
+                );
+            }
+            Err(ApplicationError::BadRequestError(payload)) => {
+                error_message.set(Some(payload));
+            }
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+            }
+        }
+        in_progress.set(false);
+    };
+
+    rsx! {
+        h2 { class: "mt-12 text-2xl font-bold leading-9 tracking-tight text-gray-900",
+            "Change email address"
+        }
+        if let Some(user) = app_context().user {
+            p { class: "mt-1 text-sm leading-6 text-gray-600",
+                "Your current address is {user.email}. The change happens once the new address is confirmed."
+            }
+        }
+        form { onsubmit, class: "mt-6 space-y-6",
+            OverallErrorLine {
+                error_payload: error_message
+            }
+            div { class: "grid grid-cols-1 gap-x-6 gap-y-6 sm:grid-cols-6",
+                div { class: "sm:col-span-3",
+                    label {
+                        r#for: "email",
+                        class: "block text-sm font-medium leading-6 text-gray-900",
+                        "New email address"
+                    }
+                    div { class: "mt-2",
+                        InputField {
+                            required: "true",
+                            autocomplete: "email",
+                            error_payload: error_message,
+                            identifier: "email",
+                            typ: "email",
+                            value: user_input.read().get("email"),
+                            oninput: move |event: Event<FormData>| user_input.write().set("email", event.value())
+                        }
+                    }
+                }
+                div { class: "sm:col-span-3",
+                    label {
+                        r#for: "current_password",
+                        class: "block text-sm font-medium leading-6 text-gray-900",
+                        "Current password"
+                    }
+                    div { class: "mt-2",
+                        InputField {
+                            required: "true",
+                            autocomplete: "current-password",
+                            error_payload: error_message,
+                            identifier: "current_password",
+                            typ: "password",
+                            value: user_input.read().get("current_password"),
+                            oninput: move |event: Event<FormData>| user_input.write().set("current_password", event.value())
+                        }
+                    }
+                }
+            }
+            div { class: "flex items-center justify-end",
+                Button {
+                    r#type: "submit",
+                    progress: *in_progress.read(),
+                    class: "rounded-md bg-indigo-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-indigo-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-600",
+                    "Change email"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn SecurityActivity() -> Element {
+    let mut app_context = consume_context::<Signal<AppState>>();
+    let events = use_resource(move || async move {
+        match security_activity().await {
+            Ok(events) => events,
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+                vec![]
+            }
+        }
+    });
+
+    rsx! {
+        h2 { class: "mt-12 text-2xl font-bold leading-9 tracking-tight text-gray-900",
+            "Recent security activity"
+        }
+        p { class: "mt-1 text-sm leading-6 text-gray-600",
+            "Sign ins, password changes and other sensitive events on your account. If you don't recognise one, change your password."
+        }
+        table { class: "mt-6 min-w-full divide-y divide-gray-300 text-left text-sm",
+            thead {
+                tr {
+                    th { class: "py-3 font-semibold text-gray-900", "Event" }
+                    th { class: "py-3 font-semibold text-gray-900", "IP address" }
+                    th { class: "py-3 font-semibold text-gray-900", "When" }
+                }
+            }
+            tbody { class: "divide-y divide-gray-200",
+                if let Some(list) = &*events.read() {
+                    for event in list.iter() {
+                        SecurityEventRow { key: "{event.id}", event: event.clone() }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn SecurityEventRow(event: SecurityEvent) -> Element {
+    let label = event.event_type.replace('_', " ");
+    let ip = event.ip.clone().unwrap_or_default();
+    let user_agent = event.user_agent.clone().unwrap_or_default();
+
+    rsx! {
+        tr {
+            td { class: "py-3 text-gray-900 capitalize", "{label}" }
+            td { class: "py-3 text-gray-600", title: "{user_agent}", "{ip}" }
+            td { class: "py-3 text-gray-600", "{event.created_at}" }
+        }
+    }
+}
+
+#[component]
+fn DataExportForm() -> Element {
+    let mut in_progress = use_signal(|| false);
+    let mut app_context = consume_context::<Signal<AppState>>();
+
+    let onsubmit = move |_: FormEvent| async move {
+        in_progress.set(true);
+        match request_export().await {
+            Ok(_) => {
+                app_context.write().add_toast(
+                    ToastType::Info,
+                    "We will email you a download link once your export is ready",
+                );
+            }
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+            }
+        }
+        in_progress.set(false);
+    };
+
+    rsx! {
+        h2 { class: "mt-12 text-2xl font-bold leading-9 tracking-tight text-gray-900",
+            "Export your data"
+        }
+        p { class: "mt-1 text-sm leading-6 text-gray-600",
+            "Download a copy of your profile, sessions and everything else stored for your account."
+        }
+        form { onsubmit, class: "mt-6 flex items-center justify-end",
+            Button {
+                r#type: "submit",
+                progress: *in_progress.read(),
+                class: "rounded-md bg-indigo-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-indigo-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-600",
+                "Request export"
+            }
+        }
+    }
+}
+
+#[component]
+fn DeleteAccountForm() -> Element {
+    let mut error_message: Signal<Option<ErrorPayload>> = use_signal(|| None);
+    let mut in_progress = use_signal(|| false);
+    let mut user_input = use_signal(UserInput::new);
+    let mut app_context = consume_context::<Signal<AppState>>();
+
+    let onsubmit = move |_: FormEvent| async move {
+        error_message.set(None);
+        in_progress.set(true);
+        let response = {
+            let entry = user_input.read();
+            delete_account(entry.get("current_password")).await
+        };
+
+        match response {
+            Ok(_) => {
+                app_context.write().user = None;
+                app_context.write().add_toast(
+                    ToastType::Info,
+                    "Your account was deleted, check your email to restore it",
+                );
+                utils::redirect_to_login();
+            }
+            Err(ApplicationError::BadRequestError(payload)) => {
+                error_message.set(Some(payload));
+            }
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+            }
+        }
+        in_progress.set(false);
+    };
+
+    rsx! {
+        h2 { class: "mt-12 text-2xl font-bold leading-9 tracking-tight text-red-600",
+            "Delete account"
+        }
+        p { class: "mt-1 text-sm leading-6 text-gray-600",
+            "Your account is deactivated right away and removed permanently after 30 days. Until then it can be restored from the link we email you."
+        }
+        form { onsubmit, class: "mt-6 space-y-6",
+            OverallErrorLine {
+                error_payload: error_message
+            }
+            div { class: "grid grid-cols-1 gap-x-6 gap-y-6 sm:grid-cols-6",
+                div { class: "sm:col-span-3",
+                    label {
+                        r#for: "current_password",
+                        class: "block text-sm font-medium leading-6 text-gray-900",
+                        "Current password"
+                    }
+                    div { class: "mt-2",
+                        InputField {
+                            required: "true",
+                            autocomplete: "current-password",
+                            error_payload: error_message,
+                            identifier: "current_password",
+                            typ: "password",
+                            value: user_input.read().get("current_password"),
+                            oninput: move |event: Event<FormData>| user_input.write().set("current_password", event.value())
+                        }
+                    }
+                }
+            }
+            div { class: "flex items-center justify-end",
+                Button {
+                    r#type: "submit",
+                    progress: *in_progress.read(),
+                    class: "rounded-md bg-red-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-red-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-red-600",
+                    "Delete account"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use crate::components::button::Button;
+use crate::components::error_line::OverallErrorLine;
+use crate::components::input::InputField;
+use crate::entities::input::UserInput;
+use crate::entities::toast::ToastType;
+use crate::errors::{ApplicationError, ErrorPayload};
+use crate::pages::settings::nav::SettingsNav;
+use crate::state::AppState;
+use crate::utils;
+use crate::utils::api::tokens::{create_token, list_tokens, revoke_token};
+use dioxus::prelude::*;
+
+#[component]
+pub fn ApiTokensPage() -> Element {
+    let mut error_message: Signal<Option<ErrorPayload>> = use_signal(|| None);
+    let mut in_progress = use_signal(|| false);
+    let mut user_input = use_signal(UserInput::new);
+    let mut write_scope = use_signal(|| false);
+    let mut created_token: Signal<Option<String>> = use_signal(|| None);
+    let mut app_context = consume_context::<Signal<AppState>>();
+
+    let mut tokens = use_resource(move || async move {
+        match list_tokens().await {
+            Ok(tokens) => tokens,
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+                vec![]
+            }
+        }
+    });
+
+    let onsubmit = move |_: FormEvent| async move {
+        error_message.set(None);
+        in_progress.set(true);
+        let entry = user_input.read();
+        let mut scopes = vec!["read".to_string()];
+        if *write_scope.read() {
+            scopes.push("write".to_string());
+        }
+        let expires_in_days = entry.get("expires_in_days").parse::<i64>().ok();
+
+        let response = create_token(entry.get("name"), scopes, expires_in_days).await;
+        match response {
+            Ok(response) => {
+                created_token.set(Some(response.token));
+                tokens.restart();
+            }
+            Err(ApplicationError::BadRequestError(payload)) => {
+                error_message.set(Some(payload));
+            }
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+            }
+        }
+        in_progress.set(false);
+    };
+
+    rsx! {
+        div { class: "mx-auto max-w-4xl px-6 py-12 lg:px-8",
+            SettingsNav {}
+            h2 { class: "text-2xl font-bold leading-9 tracking-tight text-gray-900",
+                "Personal access tokens"
+            }
+            p { class: "mt-1 text-sm leading-6 text-gray-600",
+                "Tokens let scripts and CI call the API with "
+                code { "Authorization: Bearer <token>" }
+                ". Read tokens can only fetch data, write tokens can also make changes."
+            }
+            if let Some(token) = created_token() {
+                div { class: "mt-6 rounded-md bg-teal-50 p-4",
+                    p { class: "text-sm font-semibold text-teal-800",
+                        "Copy your new token now. You won't be able to see it again."
+                    }
+                    pre { class: "mt-2 overflow-x-auto rounded bg-white p-2 text-sm text-gray-900",
+                        "{token}"
+                    }
+                }
+            }
+            form { onsubmit, class: "mt-6 space-y-6",
+                OverallErrorLine {
+                    error_payload: error_message
+                }
+                div { class: "grid grid-cols-1 gap-x-6 gap-y-6 sm:grid-cols-6",
+                    div { class: "sm:col-span-3",
+                        label {
+                            r#for: "name",
+                            class: "block text-sm font-medium leading-6 text-gray-900",
+                            "Token name"
+                        }
+                        div { class: "mt-2",
+                            InputField {
+                                required: "true",
+                                autocomplete: "off",
+                                error_payload: error_message,
+                                identifier: "name",
+                                typ: "text",
+                                value: user_input.read().get("name"),
+                                oninput: move |event: Event<FormData>| user_input.write().set("name", event.value())
+                            }
+                        }
+                    }
+                    div { class: "sm:col-span-3",
+                        label {
+                            r#for: "expires_in_days",
+                            class: "block text-sm font-medium leading-6 text-gray-900",
+                            "Expires in (days, empty for never)"
+                        }
+                        div { class: "mt-2",
+                            InputField {
+                                autocomplete: "off",
+                                error_payload: error_message,
+                                identifier: "expires_in_days",
+                                typ: "number",
+                                value: user_input.read().get("expires_in_days"),
+                                oninput: move |event: Event<FormData>| user_input.write().set("expires_in_days", event.value())
+                            }
+                        }
+                    }
+                    div { class: "col-span-full relative flex gap-x-3",
+                        div { class: "flex h-6 items-center",
+                            input {
+                                r#type: "checkbox",
+                                id: "write_scope",
+                                class: "h-4 w-4 rounded border-gray-300 text-indigo-600 focus:ring-indigo-600",
+                                checked: *write_scope.read(),
+                                oninput: move |event: Event<FormData>| write_scope.set(event.value() == "true")
+                            }
+                        }
+                        label {
+                            r#for: "write_scope",
+                            class: "text-sm font-medium leading-6 text-gray-900",
+                            "Allow changes (write scope)"
+                        }
+                    }
+                }
+                div { class: "flex items-center justify-end",
+                    Button {
+                        r#type: "submit",
+                        progress: *in_progress.read(),
+                        class: "rounded-md bg-indigo-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-indigo-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-600",
+                        "Generate token"
+                    }
+                }
+            }
+            table { class: "mt-10 min-w-full divide-y divide-gray-300 text-left text-sm",
+                thead {
+                    tr {
+                        th { class: "py-3 font-semibold text-gray-900", "Name" }
+                        th { class: "py-3 font-semibold text-gray-900", "Scopes" }
+                        th { class: "py-3 font-semibold text-gray-900", "Last used" }
+                        th { class: "py-3 font-semibold text-gray-900", "Expires" }
+                        th { class: "py-3" }
+                    }
+                }
+                tbody { class: "divide-y divide-gray-200",
+                    if let Some(token_list) = &*tokens.read() {
+                        for token in token_list.iter() {
+                            ApiTokenRow {
+                                key: "{token.identifier}",
+                                identifier: token.identifier.clone(),
+                                name: token.name.clone(),
+                                scopes: token.scopes.join(", "),
+                                last_used_at: token.last_used_at.clone().unwrap_or("Never".to_string()),
+                                expires_at: token.expires_at.clone().unwrap_or("Never".to_string()),
+                                onrevoke: move |_| tokens.restart()
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ApiTokenRow(
+    identifier: String,
+    name: String,
+    scopes: String,
+    last_used_at: String,
+    expires_at: String,
+    onrevoke: EventHandler<()>,
+) -> Element {
+    let mut app_context = consume_context::<Signal<AppState>>();
+    let mut in_progress = use_signal(|| false);
+    let identifier = use_signal(|| identifier);
+
+    let onclick = move |_: MouseEvent| async move {
+        in_progress.set(true);
+        match revoke_token(&identifier.read()).await {
+            Ok(_) => {
+                app_context
+                    .write()
+                    .add_toast(ToastType::Success, "Token revoked");
+                onrevoke.call(());
+            }
+            Err(e) => {
+                utils::handle_application_error(&mut app_context, e);
+            }
+        }
+        in_progress.set(false);
+    };
+
+    rsx! {
+        tr {
+            td { class: "py-3 text-gray-900", "{name}" }
+            td { class: "py-3 text-gray-600", "{scopes}" }
+            td { class: "py-3 text-gray-600", "{last_used_at}" }
+            td { class: "py-3 text-gray-600", "{expires_at}" }
+            td { class: "py-3",
+                div { onclick, class: "flex justify-end",
+                    Button {
+                        r#type: "button",
+                        progress: *in_progress.read(),
+                        class: "rounded-md bg-red-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-red-500",
+                        "Revoke"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,8 +1,13 @@
 use dioxus::prelude::*;
 
 use crate::pages::{
-    AuthenticatedLayout, ConfirmationPage, Home, InitiateResetPasswordPage, PageNotFound,
-    ProcessResetLinkPage, SignInPage, SignUpPage, VerifiedLayout,
+    AccountSuspendedPage, AdminInvitationsPage, AdminUserDetailPage, AdminUsersPage, ApiTokensPage,
+    AuthenticatedLayout, CategoryPage, ConfirmationPage, Home, InitiateResetPasswordPage,
+    InviteSignUpPage, LocalizedCategoryPage, LocalizedHome, LocalizedNewsletterPage,
+    LocalizedNewslettersPage, LocalizedPostPage, LocalizedStaticPage, LocalizedTagPage,
+    NewsletterPage, NewslettersPage, PageNotFound, PostPage, ProcessResetLinkPage,
+    ProfileSettingsPage, RestoreAccountPage, SearchPage, SecuritySettingsPage, SignInPage,
+    SignUpPage, StaticPage, TagPage, UnlockAccountPage, VerifiedLayout,
 };
 
 #[derive(Clone, Routable, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -11,11 +16,23 @@ pub enum Route {
     #[layout(AuthenticatedLayout)]
     #[route("/auth/confirm/:token")]
     ConfirmationPage { token: String },
+    #[route("/settings/security")]
+    SecuritySettingsPage {},
     #[end_layout]
     // Verification required
     #[layout(VerifiedLayout)]
     #[route("/")]
     Home {},
+    #[route("/settings/profile")]
+    ProfileSettingsPage {},
+    #[route("/settings/tokens")]
+    ApiTokensPage {},
+    #[route("/admin/users")]
+    AdminUsersPage {},
+    #[route("/admin/users/:user_id")]
+    AdminUserDetailPage { user_id: i32 },
+    #[route("/admin/invitations")]
+    AdminInvitationsPage {},
     #[end_layout]
     // Out of authentication boundary
     #[route("/auth/login")]
@@ -26,6 +43,67 @@ pub enum Route {
     ProcessResetLinkPage { token: String },
     #[route("/auth/signup")]
     SignUpPage {},
+    #[route("/auth/invite/:token")]
+    InviteSignUpPage { token: String },
+    #[route("/auth/suspended")]
+    AccountSuspendedPage {},
+    #[route("/auth/unlock/:token")]
+    UnlockAccountPage { token: String },
+    #[route("/auth/restore/:token")]
+    RestoreAccountPage { token: String },
+    #[route("/search")]
+    SearchPage {},
+    #[route("/posts/:slug")]
+    PostPage { slug: String },
+    #[route("/pages/:slug")]
+    StaticPage { slug: String },
+    #[route("/categories/:slug")]
+    CategoryPage { slug: String },
+    #[route("/tags/:slug")]
+    TagPage { slug: String },
+    #[route("/newsletters")]
+    NewslettersPage {},
+    #[route("/newsletters/:id")]
+    NewsletterPage { id: i32 },
+    // Content behind a locale prefix, anything that is not a known locale is shown as not found.
+    #[route("/:locale")]
+    LocalizedHome { locale: String },
+    #[route("/:locale/posts/:slug")]
+    LocalizedPostPage { locale: String, slug: String },
+    #[route("/:locale/pages/:slug")]
+    LocalizedStaticPage { locale: String, slug: String },
+    #[route("/:locale/categories/:slug")]
+    LocalizedCategoryPage { locale: String, slug: String },
+    #[route("/:locale/tags/:slug")]
+    LocalizedTagPage { locale: String, slug: String },
+    #[route("/:locale/newsletters")]
+    LocalizedNewslettersPage { locale: String },
+    #[route("/:locale/newsletters/:id")]
+    LocalizedNewsletterPage { locale: String, id: i32 },
     #[route("/:..route")]
     PageNotFound { route: Vec<String> },
 }
+
+// Links between published content keep the locale prefix the visitor came in with.
+impl Route {
+    pub fn post(slug: String, locale: Option<String>) -> Self {
+        match locale {
+            Some(locale) => Route::LocalizedPostPage { locale, slug },
+            None => Route::PostPage { slug },
+        }
+    }
+
+    pub fn category(slug: String, locale: Option<String>) -> Self {
+        match locale {
+            Some(locale) => Route::LocalizedCategoryPage { locale, slug },
+            None => Route::CategoryPage { slug },
+        }
+    }
+
+    pub fn tag(slug: String, locale: Option<String>) -> Self {
+        match locale {
+            Some(locale) => Route::LocalizedTagPage { locale, slug },
+            None => Route::TagPage { slug },
+        }
+    }
+}
@@ -1,12 +1,19 @@
 use crate::entities::toast::{ToastMessage, ToastType};
 use crate::entities::user::User;
+use crate::errors::ErrorPayload;
+use translations::catalog::SOURCE_LOCALE;
+use translations::translate;
 
 #[derive(Clone, Default)]
 pub struct AppState {
     pub toast_index: u16,
+    #[allow(dead_code)]
     pub dark_mode: bool,
     pub user: Option<User>,
     pub toast_messages: Vec<ToastMessage>,
+    pub suspension: Option<ErrorPayload>,
+    // Picked from the url prefix, the source locale until a localized page is visited.
+    pub locale: Option<String>,
 }
 
 impl AppState {
@@ -15,4 +22,13 @@ impl AppState {
             .push(ToastMessage::new(self.toast_index, message, typ));
         self.toast_index += 1;
     }
+
+    pub fn locale(&self) -> &str {
+        self.locale.as_deref().unwrap_or(SOURCE_LOCALE)
+    }
+
+    // UI text in the current locale.
+    pub fn t(&self, message: &str) -> String {
+        translate(self.locale(), message)
+    }
 }
@@ -0,0 +1,75 @@
+use crate::entities::user::User;
+use crate::utils::api::{delete_request_with_data, get_request, patch_request, post_request};
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ChangeResponse {}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DeleteAccountResponse {
+    pub purge_at: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DataExportResponse {
+    pub identifier: String,
+    pub expires_at: String,
+}
+
+pub async fn update_profile(name: String, username: String) -> Result<User> {
+    let data = json!({
+        "name": name,
+        "username": username,
+    });
+    patch_request("/auth/me", &data).await
+}
+
+pub async fn change_password(
+    current_password: String,
+    password: String,
+    confirm_password: String,
+    revoke_other_sessions: bool,
+) -> Result<ChangeResponse> {
+    let data = json!({
+        "current_password": current_password,
+        "password": password,
+        "confirm_password": confirm_password,
+        "revoke_other_sessions": revoke_other_sessions,
+    });
+    post_request("/auth/change-password", &data).await
+}
+
+pub async fn change_email(email: String, current_password: String) -> Result<ChangeResponse> {
+    let data = json!({
+        "email": email,
+        "password": current_password,
+    });
+    post_request("/auth/change-email", &data).await
+}
+
+pub async fn request_export() -> Result<DataExportResponse> {
+    let data = json!({});
+    post_request("/auth/export", &data).await
+}
+
+pub async fn delete_account(current_password: String) -> Result<DeleteAccountResponse> {
+    let data = json!({
+        "current_password": current_password,
+    });
+    delete_request_with_data("/auth/me", &data).await
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct SecurityEvent {
+    pub id: i64,
+    pub event_type: String,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: String,
+}
+
+pub async fn security_activity() -> Result<Vec<SecurityEvent>> {
+    get_request("/auth/security-activity").await
+}
@@ -0,0 +1,89 @@
+use crate::entities::user::User;
+use crate::utils::api::{delete_request, get_request, get_request_with_query, post_request};
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct UserList {
+    pub users: Vec<User>,
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ActionResponse {
+    ok: bool,
+}
+
+pub async fn list_users(q: String, page: i64) -> Result<UserList> {
+    get_request_with_query("/admin/users", &[("q", q), ("page", page.to_string())]).await
+}
+
+// One of activate, deactivate, verify, reset-password, revoke-sessions or unsuspend.
+pub async fn user_action(user_id: i32, action: &str) -> Result<ActionResponse> {
+    let data = json!({});
+    post_request(&format!("/admin/users/{}/{}", user_id, action), &data).await
+}
+
+pub async fn suspend_user(
+    user_id: i32,
+    reason: String,
+    until: Option<String>,
+) -> Result<ActionResponse> {
+    let data = json!({"reason": reason, "until": until});
+    post_request(&format!("/admin/users/{}/suspend", user_id), &data).await
+}
+
+pub async fn impersonate(user_id: i32) -> Result<User> {
+    let data = json!({});
+    post_request(&format!("/admin/users/{}/impersonate", user_id), &data).await
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct AuditEntry {
+    pub id: i32,
+    pub actor_id: Option<i32>,
+    pub action: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct UserDetail {
+    pub user: User,
+    pub sessions: i64,
+    pub audit: Vec<AuditEntry>,
+}
+
+pub async fn user_detail(user_id: i32) -> Result<UserDetail> {
+    get_request(&format!("/admin/users/{}", user_id)).await
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Invitation {
+    pub identifier: String,
+    pub email: String,
+    pub role: String,
+    pub inviter_id: Option<i32>,
+    pub created_at: String,
+    pub expires_at: String,
+}
+
+pub async fn list_invitations() -> Result<Vec<Invitation>> {
+    get_request("/admin/invitations").await
+}
+
+pub async fn create_invitation(email: String, role: &str) -> Result<Invitation> {
+    let data = json!({"email": email, "role": role});
+    post_request("/admin/invitations", &data).await
+}
+
+pub async fn resend_invitation(identifier: &str) -> Result<Invitation> {
+    let data = json!({});
+    post_request(&format!("/admin/invitations/{}/resend", identifier), &data).await
+}
+
+pub async fn revoke_invitation(identifier: &str) -> Result<ActionResponse> {
+    delete_request(&format!("/admin/invitations/{}", identifier)).await
+}
@@ -0,0 +1,10 @@
+use crate::entities::menu::Menu;
+use crate::utils::api::get_request;
+use crate::Result;
+
+// The navbar renders this menu.
+pub const PRIMARY_MENU: &str = "primary";
+
+pub async fn menu(slug: &str) -> Result<Menu> {
+    get_request(&format!("/content/menus/{}", slug)).await
+}
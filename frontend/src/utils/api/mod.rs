@@ -4,12 +4,21 @@ use reqwest::{Response, StatusCode};
 use serde::de::DeserializeOwned;
 use serde_json::Value;
 
+pub(crate) mod account;
+pub(crate) mod admin;
 pub(crate) mod confirm;
 pub(crate) mod logout;
 pub(crate) mod me;
+pub(crate) mod menus;
+pub(crate) mod newsletters;
+pub(crate) mod posts;
 pub(crate) mod reset;
+pub(crate) mod restore;
+pub(crate) mod search;
 pub(crate) mod sign_in;
 pub(crate) mod sign_up;
+pub(crate) mod tokens;
+pub(crate) mod unlock;
 
 pub fn form_url(path: &str) -> String {
     let base_url = web_sys::window().unwrap().location().origin().unwrap();
@@ -24,14 +33,63 @@ pub async fn post_request<T: DeserializeOwned>(path: &str, data: &Value) -> Resu
     Ok(value)
 }
 
+pub async fn patch_request<T: DeserializeOwned>(path: &str, data: &Value) -> Result<T> {
+    let url = form_url(path);
+    let client = reqwest::Client::new();
+    let response = client.patch(url).json(data).send().await?;
+    let value = process_response(response).await?;
+    Ok(value)
+}
+
+pub async fn get_request<T: DeserializeOwned>(path: &str) -> Result<T> {
+    let url = form_url(path);
+    let response = reqwest::get(url).await?;
+    let value = process_response(response).await?;
+    Ok(value)
+}
+
+pub async fn get_request_with_query<T: DeserializeOwned>(
+    path: &str,
+    query: &[(&str, String)],
+) -> Result<T> {
+    let url = reqwest::Url::parse_with_params(&form_url(path), query)
+        .map_err(|_| ApplicationError::RequestAPIFailed)?;
+    let response = reqwest::get(url).await?;
+    let value = process_response(response).await?;
+    Ok(value)
+}
+
+pub async fn delete_request<T: DeserializeOwned>(path: &str) -> Result<T> {
+    let url = form_url(path);
+    let client = reqwest::Client::new();
+    let response = client.delete(url).send().await?;
+    let value = process_response(response).await?;
+    Ok(value)
+}
+
+pub async fn delete_request_with_data<T: DeserializeOwned>(path: &str, data: &Value) -> Result<T> {
+    let url = form_url(path);
+    let client = reqwest::Client::new();
+    let response = client.delete(url).json(data).send().await?;
+    let value = process_response(response).await?;
+    Ok(value)
+}
+
 pub async fn process_response<T: DeserializeOwned>(response: Response) -> Result<T> {
     if response.status() == StatusCode::UNAUTHORIZED {
         Err(ApplicationError::Unauthorized)?
     }
     if response.status() == StatusCode::FORBIDDEN {
-        Err(ApplicationError::Forbidden)?
+        return match response.json::<ErrorPayload>().await {
+            Ok(value) if value.has_code("auth", "account_suspended") => {
+                Err(ApplicationError::AccountSuspended(value))
+            }
+            _ => Err(ApplicationError::Forbidden),
+        };
     }
-    if response.status() == StatusCode::BAD_REQUEST {
+    if response.status() == StatusCode::BAD_REQUEST
+        || response.status() == StatusCode::TOO_MANY_REQUESTS
+    {
         let value: ErrorPayload = response.json().await?;
         return Err(ApplicationError::BadRequestError(value));
     }
@@ -0,0 +1,35 @@
+use crate::utils::api::{get_request, get_request_with_query};
+use crate::Result;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct NewsletterSummary {
+    pub id: i32,
+    pub title: String,
+    pub sent_at: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct NewsletterArchive {
+    pub newsletters: Vec<NewsletterSummary>,
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Newsletter {
+    pub id: i32,
+    pub title: String,
+    // The body that was mailed, rendered as is.
+    pub html: String,
+    pub sent_at: String,
+}
+
+pub async fn newsletters(page: i64) -> Result<NewsletterArchive> {
+    get_request_with_query("/subscriptions/newsletters", &[("page", page.to_string())]).await
+}
+
+pub async fn newsletter(id: i32) -> Result<Newsletter> {
+    get_request(&format!("/subscriptions/newsletters/{}", id)).await
+}
@@ -0,0 +1,39 @@
+use crate::entities::post::{Post, Term};
+use crate::utils::api::get_request_with_query;
+use crate::Result;
+use serde::{Deserialize, Serialize};
+
+// `section` is where the kind is served from, `posts` or `pages`.
+// Without a locale the api picks the translation from the locale cookie.
+pub async fn published(section: &str, slug: &str, locale: Option<String>) -> Result<Post> {
+    let mut query = vec![];
+    if let Some(locale) = locale {
+        query.push(("locale", locale));
+    }
+    get_request_with_query(&format!("/content/{}/{}", section, slug), &query).await
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct PostArchive {
+    // The category or tag the posts are filed under.
+    #[serde(alias = "category", alias = "tag")]
+    pub term: Term,
+    pub posts: Vec<Post>,
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+}
+
+// `section` is the taxonomy the archive belongs to, `categories` or `tags`.
+pub async fn archive(
+    section: &str,
+    slug: &str,
+    page: i64,
+    locale: Option<String>,
+) -> Result<PostArchive> {
+    let mut query = vec![("page", page.to_string())];
+    if let Some(locale) = locale {
+        query.push(("locale", locale));
+    }
+    get_request_with_query(&format!("/content/{}/{}/posts", section, slug), &query).await
+}
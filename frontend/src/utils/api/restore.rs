@@ -0,0 +1,14 @@
+use crate::utils::api::post_request;
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct RestoreResponse {
+    pub username: String,
+}
+
+pub async fn restore_account(token: &str) -> Result<RestoreResponse> {
+    let data = json!({});
+    post_request(&format!("/auth/restore/{}", token), &data).await
+}
@@ -0,0 +1,30 @@
+use crate::utils::api::get_request_with_query;
+use crate::Result;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct SearchResult {
+    pub kind: String,
+    pub id: i32,
+    pub slug: Option<String>,
+    pub title: String,
+    // Escaped by the server, only the `<mark>` highlights are markup.
+    pub snippet: String,
+    pub published_at: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct SearchResults {
+    pub results: Vec<SearchResult>,
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+}
+
+pub async fn search(q: String, kind: String, page: i64) -> Result<SearchResults> {
+    let mut query = vec![("q", q), ("page", page.to_string())];
+    if !kind.is_empty() {
+        query.push(("kind", kind));
+    }
+    get_request_with_query("/content/search", &query).await
+}
@@ -1,9 +1,8 @@
 use crate::entities::input::UserInput;
-use crate::entities::user::User;
 use dioxus::prelude::{Readable, Signal};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::utils::api::post_request;
+use crate::utils::api::{get_request, post_request};
 use crate::Result;
 
 #[derive(Debug, Serialize, Clone)]
@@ -13,6 +12,7 @@ pub struct RegistrationPayload {
     pub email: String,
     pub confirm_password: String,
     pub name: String,
+    pub invitation: Option<String>,
 }
 
 impl From<Signal<UserInput>> for RegistrationPayload {
@@ -24,11 +24,39 @@ impl From<Signal<UserInput>> for RegistrationPayload {
             confirm_password: value.get("confirm_password"),
             email: value.get("email"),
             name: value.get("name"),
+            invitation: None,
         }
     }
 }
 
-pub async fn signup(payload: RegistrationPayload) -> Result<User> {
+// Hardened servers only confirm that an email is on its way instead of returning the user.
+#[derive(Debug, Deserialize, Clone)]
+pub struct SignUpResponse {
+    #[serde(default)]
+    pub pending: bool,
+}
+
+pub async fn signup(payload: RegistrationPayload) -> Result<SignUpResponse> {
     let data = serde_json::to_value(payload)?;
     post_request("/auth/register", &data).await
 }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct RegistrationMode {
+    pub mode: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct InvitationDetail {
+    pub email: String,
+    pub role: String,
+    pub expires_at: String,
+}
+
+pub async fn registration_mode() -> Result<RegistrationMode> {
+    get_request("/auth/registration").await
+}
+
+pub async fn invitation_detail(token: &str) -> Result<InvitationDetail> {
+    get_request(&format!("/auth/invitations/{}", token)).await
+}
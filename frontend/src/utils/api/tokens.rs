@@ -0,0 +1,37 @@
+use crate::entities::api_token::ApiToken;
+use crate::utils::api::{delete_request, get_request, post_request};
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CreatedApiToken {
+    pub token: String,
+    pub api_token: ApiToken,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct RevokeResponse {
+    ok: bool,
+}
+
+pub async fn list_tokens() -> Result<Vec<ApiToken>> {
+    get_request("/auth/tokens").await
+}
+
+pub async fn create_token(
+    name: String,
+    scopes: Vec<String>,
+    expires_in_days: Option<i64>,
+) -> Result<CreatedApiToken> {
+    let data = json!({
+        "name": name,
+        "scopes": scopes,
+        "expires_in_days": expires_in_days,
+    });
+    post_request("/auth/tokens", &data).await
+}
+
+pub async fn revoke_token(identifier: &str) -> Result<RevokeResponse> {
+    delete_request(&format!("/auth/tokens/{}", identifier)).await
+}
@@ -0,0 +1,14 @@
+use crate::utils::api::post_request;
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct UnlockResponse {
+    pub username: String,
+}
+
+pub async fn unlock_account(token: &str) -> Result<UnlockResponse> {
+    let data = json!({});
+    post_request(&format!("/auth/unlock/{}", token), &data).await
+}
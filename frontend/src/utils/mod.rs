@@ -2,7 +2,7 @@ use crate::entities::toast::ToastType;
 use crate::errors::ApplicationError;
 use crate::routes::Route;
 use crate::state::AppState;
-use dioxus::prelude::{navigator, Signal, Writable};
+use dioxus::prelude::{navigator, Readable, Signal, Writable};
 use log::info;
 
 pub(crate) mod api;
@@ -23,11 +23,15 @@ pub fn navigate_back_or_home() {
 }
 
 pub fn handle_application_error(app_context: &mut Signal<AppState>, error: ApplicationError) {
-    if let ApplicationError::Unauthorized = error {
-        redirect_to_login();
-    } else {
-        app_context
-            .write()
-            .add_toast(ToastType::Error, error.to_string());
+    match error {
+        ApplicationError::Unauthorized => redirect_to_login(),
+        ApplicationError::AccountSuspended(payload) => {
+            app_context.write().suspension = Some(payload);
+            navigator().replace(Route::AccountSuspendedPage {});
+        }
+        error => {
+            let message = app_context.read().t(&error.to_string());
+            app_context.write().add_toast(ToastType::Error, message);
+        }
     }
 }
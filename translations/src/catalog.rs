@@ -0,0 +1,90 @@
+use crate::locale::language;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+// Messages are written in English and looked up by their English text, so English needs no
+// catalog and anything not translated yet stays readable.
+pub const SOURCE_LOCALE: &str = "en";
+
+const CATALOGS: [(&str, &str); 1] = [("ne", include_str!("../locales/ne.json"))];
+
+type Catalog = HashMap<String, String>;
+
+fn catalogs() -> &'static HashMap<&'static str, Catalog> {
+    static PARSED: OnceLock<HashMap<&'static str, Catalog>> = OnceLock::new();
+    PARSED.get_or_init(|| {
+        CATALOGS
+            .iter()
+            .map(|(locale, source)| {
+                let catalog = serde_json::from_str(source).expect("Invalid message catalog");
+                (*locale, catalog)
+            })
+            .collect()
+    })
+}
+
+// Locales with translated messages, including the source locale.
+pub fn catalog_locales() -> Vec<&'static str> {
+    let mut locales: Vec<&str> = CATALOGS.iter().map(|(locale, _)| *locale).collect();
+    locales.insert(0, SOURCE_LOCALE);
+    locales
+}
+
+/// Message in the given locale, a regional locale uses its language and missing messages are
+/// returned as they are.
+///
+/// ```
+/// use translations::translate;
+///
+/// assert_eq!(translate("ne", "Post not found"), "पोस्ट फेला परेन");
+/// assert_eq!(translate("ne-NP", "Post not found"), "पोस्ट फेला परेन");
+/// assert_eq!(translate("fr", "Post not found"), "Post not found");
+/// assert_eq!(translate("ne", "Not translated yet"), "Not translated yet");
+/// ```
+pub fn translate(locale: &str, message: &str) -> String {
+    let catalogs = catalogs();
+    [locale, language(locale)]
+        .iter()
+        .filter_map(|locale| catalogs.get(locale)?.get(message))
+        .next()
+        .cloned()
+        .unwrap_or_else(|| message.to_string())
+}
+
+/// Translated message with `{name}` placeholders filled in.
+///
+/// ```
+/// use translations::translate_with;
+///
+/// assert_eq!(translate_with("en", "{count} results", &[("count", "3")]), "3 results");
+/// assert_eq!(translate_with("ne", "{count} results", &[("count", "3")]), "3 नतिजाहरू");
+/// ```
+pub fn translate_with(locale: &str, message: &str, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(translate(locale, message), |message, (name, value)| {
+            message.replace(&format!("{{{}}}", name), value)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalogs_keep_placeholders() {
+        for (locale, catalog) in catalogs() {
+            for (source, translated) in catalog {
+                for placeholder in source.split('{').skip(1) {
+                    let name = placeholder.split('}').next().unwrap();
+                    assert!(
+                        translated.contains(&format!("{{{}}}", name)),
+                        "{} translation of {:?} lost {{{}}}",
+                        locale,
+                        source,
+                        name
+                    );
+                }
+            }
+        }
+    }
+}
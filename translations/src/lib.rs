@@ -1,14 +1,6 @@
-pub fn add(left: usize, right: usize) -> usize {
-    left + right
-}
+//! Locales and the message catalog shared by the api and the frontend.
+pub mod catalog;
+pub mod locale;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+pub use catalog::{translate, translate_with};
+pub use locale::{normalize_locale, LocaleConfig};
@@ -0,0 +1,220 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+// Sent by the frontend so the api answers in the language of the page.
+pub const LOCALE_COOKIE: &str = "locale";
+
+/// Lowercase language with an uppercase region, `None` for anything that is not a language tag.
+///
+/// ```
+/// use translations::normalize_locale;
+///
+/// assert_eq!(normalize_locale("EN_gb"), Some("en-GB".to_string()));
+/// assert_eq!(normalize_locale(" ne "), Some("ne".to_string()));
+/// assert_eq!(normalize_locale("posts"), None);
+/// assert_eq!(normalize_locale("en-GB-x"), None);
+/// ```
+pub fn normalize_locale(tag: &str) -> Option<String> {
+    let mut parts = tag.trim().split(['-', '_']);
+    let language = parts.next()?;
+    if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let mut locale = language.to_ascii_lowercase();
+    if let Some(region) = parts.next() {
+        if !(2..=3).contains(&region.len()) || !region.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return None;
+        }
+        locale.push('-');
+        locale.push_str(&region.to_ascii_uppercase());
+    }
+    match parts.next() {
+        Some(_) => None,
+        None => Some(locale),
+    }
+}
+
+// Language part of a normalized locale, `en` for `en-GB`.
+pub fn language(locale: &str) -> &str {
+    locale.split('-').next().unwrap_or(locale)
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct LocaleConfig {
+    // Used when nothing else matches, its pages are served without a locale prefix.
+    pub default_locale: String,
+    // Every locale content can be written in, the default is always included.
+    pub locales: Vec<String>,
+    // Tried before the default locale, such as `hi` for content missing in `ne`.
+    pub fallbacks: HashMap<String, String>,
+    // Postgres text search configuration per locale, such as `nepali` for `ne`.
+    pub search_languages: HashMap<String, String>,
+}
+
+impl Default for LocaleConfig {
+    fn default() -> Self {
+        Self {
+            default_locale: "en".to_string(),
+            locales: vec!["en".to_string()],
+            fallbacks: HashMap::new(),
+            search_languages: HashMap::new(),
+        }
+    }
+}
+
+impl LocaleConfig {
+    pub fn is_supported(&self, locale: &str) -> bool {
+        locale == self.default_locale || self.locales.iter().any(|known| known == locale)
+    }
+
+    /// Supported locale for a requested tag, a regional tag falls back to its language.
+    ///
+    /// ```
+    /// use translations::LocaleConfig;
+    ///
+    /// let config = LocaleConfig {
+    ///     locales: vec!["en".into(), "ne".into()],
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(config.resolve("ne"), Some("ne".to_string()));
+    /// assert_eq!(config.resolve("ne-np"), Some("ne".to_string()));
+    /// assert_eq!(config.resolve("fr"), None);
+    /// ```
+    pub fn resolve(&self, tag: &str) -> Option<String> {
+        let locale = normalize_locale(tag)?;
+        if self.is_supported(&locale) {
+            return Some(locale);
+        }
+        let language = language(&locale);
+        self.is_supported(language).then(|| language.to_string())
+    }
+
+    /// Locales to look in for content, most preferred first and ending with the default.
+    ///
+    /// ```
+    /// use translations::LocaleConfig;
+    ///
+    /// let config = LocaleConfig {
+    ///     locales: vec!["en".into(), "ne".into(), "hi".into(), "en-GB".into()],
+    ///     fallbacks: [("ne".into(), "hi".into())].into(),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(config.fallback_chain("ne"), ["ne", "hi", "en"]);
+    /// assert_eq!(config.fallback_chain("en-GB"), ["en-GB", "en"]);
+    /// assert_eq!(config.fallback_chain("en"), ["en"]);
+    /// ```
+    pub fn fallback_chain(&self, locale: &str) -> Vec<String> {
+        let mut chain: Vec<String> = vec![];
+        let mut next = Some(locale.to_string());
+        // Configured fallbacks may loop, every locale is only tried once.
+        while let Some(current) = next.filter(|current| !chain.contains(current)) {
+            next = self.fallbacks.get(&current).cloned();
+            chain.push(current);
+        }
+        for locale in [language(locale), &self.default_locale] {
+            if !chain.iter().any(|known| known == locale) {
+                chain.push(locale.to_string());
+            }
+        }
+        chain
+    }
+
+    /// Text search configuration for content in a locale, a regional locale falls back to its
+    /// language and a locale without one to `fallback`.
+    ///
+    /// ```
+    /// use translations::LocaleConfig;
+    ///
+    /// let config = LocaleConfig {
+    ///     search_languages: [("ne".into(), "nepali".into())].into(),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(config.search_language("ne", "english"), "nepali");
+    /// assert_eq!(config.search_language("ne-NP", "english"), "nepali");
+    /// assert_eq!(config.search_language("en", "english"), "english");
+    /// ```
+    pub fn search_language<'a>(&'a self, locale: &str, fallback: &'a str) -> &'a str {
+        self.search_languages
+            .get(locale)
+            .or_else(|| self.search_languages.get(language(locale)))
+            .map_or(fallback, String::as_str)
+    }
+
+    /// Best supported locale for an `Accept-Language` header.
+    ///
+    /// ```
+    /// use translations::LocaleConfig;
+    ///
+    /// let config = LocaleConfig {
+    ///     locales: vec!["en".into(), "ne".into()],
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(config.negotiate("fr-FR, ne-NP;q=0.8, en;q=0.5"), "ne");
+    /// assert_eq!(config.negotiate("en;q=0.2, ne;q=0.9"), "ne");
+    /// assert_eq!(config.negotiate("fr, *;q=0.1"), "en");
+    /// ```
+    pub fn negotiate(&self, header: &str) -> String {
+        let mut ranges: Vec<(&str, f32)> = header
+            .split(',')
+            .filter_map(|range| {
+                let mut parts = range.split(';');
+                let tag = parts.next()?.trim();
+                let quality = parts
+                    .filter_map(|param| param.trim().strip_prefix("q="))
+                    .find_map(|quality| quality.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (quality > 0.0).then_some((tag, quality))
+            })
+            .collect();
+        ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranges
+            .into_iter()
+            .find_map(|(tag, _)| self.resolve(tag))
+            .unwrap_or_else(|| self.default_locale.clone())
+    }
+
+    /// Address of a page in a locale, the default locale keeps the unprefixed address.
+    ///
+    /// ```
+    /// use translations::LocaleConfig;
+    ///
+    /// let config = LocaleConfig::default();
+    /// assert_eq!(config.localized_path("en", "/posts/hello"), "/posts/hello");
+    /// assert_eq!(config.localized_path("ne", "/posts/hello"), "/ne/posts/hello");
+    /// assert_eq!(config.localized_path("ne", "/"), "/ne");
+    /// ```
+    pub fn localized_path(&self, locale: &str, path: &str) -> String {
+        match (locale == self.default_locale, path) {
+            (true, _) => path.to_string(),
+            (false, "/") => format!("/{}", locale),
+            (false, _) => format!("/{}{}", locale, path),
+        }
+    }
+
+    /// Splits a supported locale prefix from a path.
+    ///
+    /// ```
+    /// use translations::LocaleConfig;
+    ///
+    /// let config = LocaleConfig {
+    ///     locales: vec!["en".into(), "ne".into()],
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(config.strip_locale("/ne/posts/hello"), (Some("ne".to_string()), "/posts/hello"));
+    /// assert_eq!(config.strip_locale("/en"), (Some("en".to_string()), "/"));
+    /// assert_eq!(config.strip_locale("/posts/hello"), (None, "/posts/hello"));
+    /// assert_eq!(config.strip_locale("/fr/posts/hello"), (None, "/fr/posts/hello"));
+    /// ```
+    pub fn strip_locale<'a>(&self, path: &'a str) -> (Option<String>, &'a str) {
+        let trimmed = path.strip_prefix('/').unwrap_or(path);
+        let (prefix, rest) = match trimmed.find('/') {
+            Some(index) => (&trimmed[..index], &trimmed[index..]),
+            None => (trimmed, "/"),
+        };
+        match normalize_locale(prefix).filter(|locale| self.is_supported(locale)) {
+            Some(locale) => (Some(locale), rest),
+            None => (None, path),
+        }
+    }
+}